@@ -0,0 +1,402 @@
+use crate::ai::engine;
+use crate::ai::error::AIError;
+use crate::ai::formatting::play_pdn;
+use crate::ai::genai_client::{exec_chat_cached, store_reply};
+use crate::config::{active_settings, gemini_model};
+use crate::core::board::Board;
+use crate::core::fen::to_fen;
+use crate::core::game::CheckersGame;
+use crate::core::move_history::MoveHistory;
+use crate::core::notation::turn_to_algebraic;
+use crate::core::pdn::{parse_pdn, result_string, PdnGame};
+use crate::core::piece::Color as PieceColor;
+use crate::core::Turn;
+use crate::utils::prompts::get_review_prompt;
+use genai::chat::{ChatMessage, ChatOptions};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Search depth used to score each move of a finished game
+pub const ANALYSIS_DEPTH: u32 = 4;
+
+// Score drops (in hundredths of a man) that earn an annotation
+const BLUNDER_LOSS: i32 = 150;
+const MISTAKE_LOSS: i32 = 60;
+const INACCURACY_LOSS: i32 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Annotation {
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+    /// A capture was made, but a longer jump sequence was available
+    MissedCapture,
+}
+
+impl Annotation {
+    /// Move suffix used in the annotated list and the PDN export
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Annotation::Good => "",
+            Annotation::Inaccuracy => "?!",
+            Annotation::Mistake | Annotation::MissedCapture => "?",
+            Annotation::Blunder => "??",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Annotation::Good => "Good",
+            Annotation::Inaccuracy => "Inaccuracy",
+            Annotation::Mistake => "Mistake",
+            Annotation::Blunder => "Blunder",
+            Annotation::MissedCapture => "Missed capture",
+        }
+    }
+
+    fn from_loss(loss: i32) -> Self {
+        if loss >= BLUNDER_LOSS {
+            Annotation::Blunder
+        } else if loss >= MISTAKE_LOSS {
+            Annotation::Mistake
+        } else if loss >= INACCURACY_LOSS {
+            Annotation::Inaccuracy
+        } else {
+            Annotation::Good
+        }
+    }
+
+    fn from_verdict(verdict: &str) -> Option<Self> {
+        match verdict.trim().to_lowercase().as_str() {
+            "good" => Some(Annotation::Good),
+            "inaccuracy" => Some(Annotation::Inaccuracy),
+            "mistake" => Some(Annotation::Mistake),
+            "blunder" => Some(Annotation::Blunder),
+            _ => None,
+        }
+    }
+}
+
+/// Where the annotations of a review came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoreSource {
+    Engine,
+    Llm,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnnotatedMove {
+    pub player: PieceColor,
+    pub turn: Turn,
+    pub notation: String,
+    pub board_after: Board,
+    /// The engine's preferred turn in the position before this move
+    pub best: Option<Turn>,
+    /// How much worse the move scored than the engine's choice
+    pub loss: i32,
+    pub annotation: Annotation,
+    pub comment: Option<String>,
+}
+
+/// A finished game replayed from its history with every turn scored
+#[derive(Clone, Debug)]
+pub struct GameAnalysis {
+    pub moves: Vec<AnnotatedMove>,
+    pub source: ScoreSource,
+    pub winner: Option<PieceColor>,
+    /// PDN result, `*` for a game read unfinished
+    pub result: String,
+    /// Tags written ahead of the moves in the PDN export
+    pub tags: Vec<(String, String)>,
+    /// FEN of the starting position, unless it was the standard one
+    pub start: Option<String>,
+}
+
+impl GameAnalysis {
+    /// Replays the history from the starting position and scores each turn
+    /// with the built-in engine.
+    pub fn from_history(history: &MoveHistory, winner: Option<PieceColor>) -> Self {
+        Self::from_position(&CheckersGame::new(), history, winner)
+    }
+
+    /// Same as `from_history`, for games that did not begin from the standard
+    /// setup.
+    pub fn from_position(
+        start: &CheckersGame,
+        history: &MoveHistory,
+        winner: Option<PieceColor>,
+    ) -> Self {
+        let steps = history.moves();
+        let mut game = start.clone();
+        game.move_history = MoveHistory::new();
+        let mut analysis = Self {
+            moves: Vec::new(),
+            source: ScoreSource::Engine,
+            winner,
+            result: result_string(winner).to_string(),
+            tags: vec![
+                ("Event".to_string(), "checkers-rs game".to_string()),
+                ("White".to_string(), "?".to_string()),
+                ("Black".to_string(), "?".to_string()),
+            ],
+            start: Some(to_fen(start)).filter(|fen| *fen != to_fen(&CheckersGame::new())),
+        };
+        let mut i = 0;
+
+        while i < steps.len() {
+            // A failed AI move passes the turn without recording anything
+            if steps[i].player != game.current_player {
+                game = game.with_switched_player();
+            }
+
+            let before = game.clone();
+            let mut path = vec![steps[i].from];
+            loop {
+                let step = &steps[i];
+                let Ok((next, continue_capture)) =
+                    game.make_move_coords(step.from.0, step.from.1, step.to.0, step.to.1)
+                else {
+                    // History no longer matches the rules; keep what was scored
+                    return analysis;
+                };
+                game = next;
+                path.push(step.to);
+                i += 1;
+                if !continue_capture || i >= steps.len() {
+                    break;
+                }
+            }
+
+            analysis
+                .moves
+                .push(annotate_turn(&before, &game, Turn::new(path)));
+        }
+
+        analysis
+    }
+
+    /// Reads a PDN game and scores its moves, from the position its FEN tag
+    /// gives or the standard one. The game's own tags are kept for export.
+    pub fn from_pdn(text: &str) -> Result<Self, String> {
+        let pdn = parse_pdn(text).map_err(|e| e.to_string())?;
+        let (start, game) = play_pdn(&pdn)?;
+
+        let winner = match pdn.result.as_str() {
+            "1-0" => Some(PieceColor::White),
+            "0-1" => Some(PieceColor::Black),
+            _ => None,
+        };
+        let mut analysis = Self::from_position(&start, &game.move_history, winner);
+        analysis.result = pdn.result.clone();
+        // The file's own comments come first
+        for (annotated, pdn_move) in analysis.moves.iter_mut().zip(&pdn.moves) {
+            if let Some(comment) = &pdn_move.comment {
+                annotated.comment = Some(match annotated.comment.take() {
+                    Some(engine) => format!("{comment}; {engine}"),
+                    None => comment.clone(),
+                });
+            }
+        }
+        analysis.tags = pdn
+            .tags
+            .into_iter()
+            .filter(|(name, _)| !["FEN", "SetUp", "Annotator"].contains(&name.as_str()))
+            .collect();
+        Ok(analysis)
+    }
+
+    /// Names the players in the White and Black tags
+    pub fn with_players(mut self, white: &str, black: &str) -> Self {
+        for (name, value) in self.tags.iter_mut() {
+            match name.as_str() {
+                "White" => *value = white.to_string(),
+                "Black" => *value = black.to_string(),
+                _ => {}
+            }
+        }
+        self
+    }
+
+    /// Takes verdicts and comments from an LLM reply in `N: verdict - comment`
+    /// lines. Moves the reply skips keep their engine annotation.
+    pub fn apply_review(&self, reply: &str) -> Self {
+        let mut analysis = self.clone();
+        analysis.source = ScoreSource::Llm;
+
+        for line in reply.lines() {
+            let Some((number, rest)) = line.trim().split_once(':') else {
+                continue;
+            };
+            let Ok(number) = number.trim().trim_end_matches('.').parse::<usize>() else {
+                continue;
+            };
+            let Some(annotated) = number
+                .checked_sub(1)
+                .and_then(|index| analysis.moves.get_mut(index))
+            else {
+                continue;
+            };
+
+            let (verdict, comment) = match rest.split_once(" - ") {
+                Some((verdict, comment)) => (verdict, Some(comment.trim())),
+                None => (rest, None),
+            };
+            let Some(annotation) = Annotation::from_verdict(verdict) else {
+                continue;
+            };
+
+            // A shorter jump than the board allowed is a fact, not an opinion
+            if annotated.annotation != Annotation::MissedCapture {
+                annotated.annotation = annotation;
+            }
+            annotated.comment = comment.filter(|c| !c.is_empty()).map(str::to_string);
+        }
+
+        analysis
+    }
+
+    pub fn to_pdn(&self) -> String {
+        let mut game = PdnGame::new(&self.result);
+        for (name, value) in &self.tags {
+            game = game.with_tag(name, value);
+        }
+        if let Some(fen) = &self.start {
+            game = game.with_tag("SetUp", "1").with_tag("FEN", fen);
+        }
+        game = game.with_tag(
+            "Annotator",
+            match self.source {
+                ScoreSource::Engine => "checkers-rs engine",
+                ScoreSource::Llm => "AI review",
+            },
+        );
+
+        for annotated in &self.moves {
+            let comment = match (annotated.annotation, &annotated.comment) {
+                (Annotation::Good, comment) => comment.clone(),
+                (annotation, Some(comment)) => Some(format!("{}: {}", annotation.label(), comment)),
+                (annotation, None) => Some(annotation.label().to_string()),
+            };
+            game = game.with_move(
+                annotated.player,
+                format!("{}{}", annotated.notation, annotated.annotation.symbol()),
+                comment,
+            );
+        }
+
+        game.to_pdn()
+    }
+
+    /// Writes the PDN export into `dir` under a timestamped file name
+    pub fn export_pdn(&self, dir: &Path) -> io::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let path = dir.join(format!("checkers-game-{timestamp}.pdn"));
+        std::fs::write(&path, self.to_pdn())?;
+        Ok(path)
+    }
+}
+
+fn annotate_turn(before: &CheckersGame, after: &CheckersGame, turn: Turn) -> AnnotatedMove {
+    let size = before.board.size;
+    let legal_turns = before.legal_turns();
+    let best = engine::search(before, ANALYSIS_DEPTH);
+
+    let loss = match &best {
+        Some(best) if best.turn != turn => {
+            let played = -engine::score_position(after, ANALYSIS_DEPTH - 1);
+            (best.score - played).max(0)
+        }
+        _ => 0,
+    };
+
+    let longest_capture = legal_turns.iter().max_by_key(|t| t.capture_count());
+    let mut annotation = Annotation::from_loss(loss);
+    let mut comment = match &best {
+        Some(best) if annotation != Annotation::Good => Some(format!(
+            "Better was {}",
+            turn_to_algebraic(&best.turn, size)
+        )),
+        _ => None,
+    };
+
+    // A shorter jump is the clearest explanation, so it wins over the score
+    if let Some(longest) = longest_capture {
+        if turn.capture_count() < longest.capture_count() {
+            annotation = Annotation::MissedCapture;
+            comment = Some(format!(
+                "{} captured {} pieces",
+                turn_to_algebraic(longest, size),
+                longest.capture_count()
+            ));
+        }
+    }
+
+    AnnotatedMove {
+        player: before.current_player,
+        notation: turn_to_algebraic(&turn, size),
+        turn,
+        board_after: after.board.clone(),
+        best: best.map(|b| b.turn),
+        loss,
+        annotation,
+        comment,
+    }
+}
+
+/// Asks the configured LLM to judge every move of an engine-scored game
+pub async fn review_with_llm(analysis: &GameAnalysis) -> Result<GameAnalysis, AIError> {
+    dotenv::dotenv().ok();
+    let api_key = env::var("GEMINI_API_KEY").map_err(|_| AIError::NoApiKey)?;
+    let model = gemini_model().ok_or(AIError::NoModel)?;
+
+    let moves = analysis
+        .moves
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let player = if m.player == PieceColor::White {
+                "White"
+            } else {
+                "Black"
+            };
+            format!("{}. {} {}", i + 1, player, m.notation)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let result = match analysis.winner {
+        Some(PieceColor::White) => "White won",
+        Some(PieceColor::Black) => "Black won",
+        None => "Draw",
+    };
+
+    let prompt = get_review_prompt()
+        .replace("{result}", result)
+        .replace("{moves}", &moves);
+
+    // Set API key in environment for genai client
+    env::set_var("GEMINI_API_KEY", api_key);
+
+    let chat_options = ChatOptions::default()
+        .with_temperature(active_settings().review_temperature as f64)
+        .with_max_tokens(40 * analysis.moves.len().max(1) as u32);
+
+    let messages = [ChatMessage::user(prompt)];
+    let text = exec_chat_cached(&model, &messages, &chat_options).await?;
+    let reviewed = analysis.apply_review(&text);
+    // A reply that judged no move is not worth replaying
+    let judged = reviewed
+        .moves
+        .iter()
+        .zip(&analysis.moves)
+        .any(|(new, old)| new.annotation != old.annotation || new.comment != old.comment);
+    if judged {
+        store_reply(&model, &messages, &chat_options, &text);
+    }
+    Ok(reviewed)
+}
@@ -0,0 +1,169 @@
+use crate::utils::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Oldest responses are dropped beyond this many entries
+const MAX_ENTRIES: usize = 500;
+const CACHE_FILE: &str = "responses.json";
+/// New responses are written to the file in batches of this many, and the
+/// rest when the program ends
+const SAVE_EVERY: usize = 10;
+/// Answers sampled hotter than this are meant to vary, like the welcome
+/// story, so they are neither looked up nor stored
+pub const MAX_CACHED_TEMPERATURE: f64 = 0.5;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    response: String,
+    stored_at: u64,
+}
+
+/// LLM responses keyed by model, temperature and prompt. Prompts embed the
+/// board and move history, so the key changes with the position.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ResponseCache {
+    entries: HashMap<String, CachedResponse>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Inserts since the file was last written
+    #[serde(skip)]
+    unsaved: usize,
+}
+
+impl ResponseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the cache stored at `path`. A missing or unreadable file gives
+    /// an empty cache that will be written there on the next insert.
+    pub fn load(path: &Path) -> Self {
+        let mut cache = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<ResponseCache>(&json).ok())
+            .unwrap_or_default();
+        cache.path = Some(path.to_path_buf());
+        cache
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts not yet written to the file
+    pub fn unsaved(&self) -> usize {
+        self.unsaved
+    }
+
+    pub fn get(&self, model: &str, temperature: f64, prompt: &str) -> Option<&str> {
+        self.entries
+            .get(&cache_key(model, temperature, prompt))
+            .map(|entry| entry.response.as_str())
+    }
+
+    pub fn insert(&mut self, model: &str, temperature: f64, prompt: &str, response: &str) {
+        let stored_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.unsaved += 1;
+        self.entries.insert(
+            cache_key(model, temperature, prompt),
+            CachedResponse {
+                response: response.to_string(),
+                stored_at,
+            },
+        );
+
+        while self.entries.len() > MAX_ENTRIES {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    /// Writes the cache back to the file it was loaded from, if any
+    pub fn save(&mut self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        std::fs::write(path, json)?;
+        self.unsaved = 0;
+        Ok(())
+    }
+}
+
+/// FNV-1a hash of the model, temperature and prompt; stable across builds,
+/// unlike the standard library's hasher
+fn cache_key(model: &str, temperature: f64, prompt: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = model
+        .bytes()
+        .chain([0])
+        .chain(temperature.to_bits().to_le_bytes())
+        .chain(prompt.bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+fn shared_cache() -> &'static Mutex<ResponseCache> {
+    static CACHE: OnceLock<Mutex<ResponseCache>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        let cache = match paths::cache_dir() {
+            Some(dir) => ResponseCache::load(&dir.join(CACHE_FILE)),
+            None => ResponseCache::new(),
+        };
+        Mutex::new(cache)
+    })
+}
+
+/// Looks up a response in the on-disk cache shared by all LLM calls
+pub fn cached_response(model: &str, temperature: f64, prompt: &str) -> Option<String> {
+    if temperature > MAX_CACHED_TEMPERATURE {
+        return None;
+    }
+    let cache = shared_cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.get(model, temperature, prompt).map(str::to_string)
+}
+
+/// Stores a response, persisting the cache once a batch has built up; a
+/// failed write only costs the cache entries, never the response itself
+pub fn store_response(model: &str, temperature: f64, prompt: &str, response: &str) {
+    if temperature > MAX_CACHED_TEMPERATURE {
+        return;
+    }
+    let mut cache = shared_cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache.insert(model, temperature, prompt, response);
+    if cache.unsaved() >= SAVE_EVERY {
+        let _ = cache.save();
+    }
+}
+
+/// Writes any responses still waiting for the next batch
+pub fn flush_responses() {
+    let mut cache = shared_cache().lock().unwrap_or_else(|e| e.into_inner());
+    if cache.unsaved() > 0 {
+        let _ = cache.save();
+    }
+}
@@ -0,0 +1,271 @@
+use crate::ai::error::AIError;
+use crate::ai::formatting::format_board;
+use crate::ai::genai_client::record_usage;
+use crate::ai::usage::check_budget;
+use crate::config::{active_settings, gemini_model};
+use crate::core::{board::Board, move_history::MoveHistory, piece::Color as PieceColor};
+use crate::utils::log;
+use crate::utils::prompts::get_coach_prompt;
+use futures::StreamExt;
+use genai::{
+    chat::{ChatMessage, ChatOptions, ChatRequest, ChatStreamEvent},
+    Client,
+};
+use serde_json::json;
+use std::env;
+use std::time::Instant;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoachRole {
+    /// Board snapshot sent to the model whenever the position changes
+    Context,
+    Player,
+    Coach,
+}
+
+#[derive(Clone, Debug)]
+pub struct CoachMessage {
+    pub role: CoachRole,
+    pub text: String,
+}
+
+/// Events produced while a coach answer streams in
+#[derive(Debug, PartialEq)]
+pub enum CoachEvent {
+    Chunk(String),
+    Done,
+    Failed(String),
+}
+
+/// Conversation history between the player and the AI coach
+#[derive(Clone, Default)]
+pub struct CoachConversation {
+    messages: Vec<CoachMessage>,
+    grounded_position: Option<String>,
+    awaiting_reply: bool,
+}
+
+impl CoachConversation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn messages(&self) -> &[CoachMessage] {
+        &self.messages
+    }
+
+    pub fn is_awaiting_reply(&self) -> bool {
+        self.awaiting_reply
+    }
+
+    /// Records a question, re-sending the board first if the position changed
+    /// since the coach last saw it.
+    pub fn ask(
+        &self,
+        question: &str,
+        board: &Board,
+        current_player: PieceColor,
+        history: &MoveHistory,
+    ) -> Self {
+        let mut new_conversation = self.clone();
+
+        let position = position_context(board, current_player, history);
+        if new_conversation.grounded_position.as_deref() != Some(position.as_str()) {
+            new_conversation.messages.push(CoachMessage {
+                role: CoachRole::Context,
+                text: position.clone(),
+            });
+            new_conversation.grounded_position = Some(position);
+        }
+
+        new_conversation.messages.push(CoachMessage {
+            role: CoachRole::Player,
+            text: question.trim().to_string(),
+        });
+        new_conversation.messages.push(CoachMessage {
+            role: CoachRole::Coach,
+            text: String::new(),
+        });
+        new_conversation.awaiting_reply = true;
+        new_conversation
+    }
+
+    pub fn append_reply(&self, chunk: &str) -> Self {
+        let mut new_conversation = self.clone();
+        if let Some(last) = new_conversation.messages.last_mut() {
+            if last.role == CoachRole::Coach {
+                last.text.push_str(chunk);
+            }
+        }
+        new_conversation
+    }
+
+    pub fn finish_reply(&self) -> Self {
+        let mut new_conversation = self.clone();
+        new_conversation.awaiting_reply = false;
+        new_conversation
+    }
+
+    pub fn fail_reply(&self, error: &str) -> Self {
+        let mut new_conversation = self.append_reply(&format!("[{error}]"));
+        new_conversation.awaiting_reply = false;
+        new_conversation
+    }
+
+    /// Builds the chat transcript for the model, leaving out the pending
+    /// (still empty) coach answer.
+    pub fn to_chat_messages(&self) -> Vec<ChatMessage> {
+        let mut chat_messages = vec![ChatMessage::system(get_coach_prompt())];
+
+        let pending = usize::from(self.awaiting_reply);
+        for message in &self.messages[..self.messages.len() - pending] {
+            let chat_message = match message.role {
+                CoachRole::Context | CoachRole::Player => ChatMessage::user(message.text.clone()),
+                CoachRole::Coach => ChatMessage::assistant(message.text.clone()),
+            };
+            chat_messages.push(chat_message);
+        }
+
+        chat_messages
+    }
+}
+
+fn position_context(board: &Board, current_player: PieceColor, history: &MoveHistory) -> String {
+    let move_history = history.to_notation();
+    format!(
+        "Current position ({} to move):\n{}\nMove history: {}",
+        if current_player == PieceColor::White {
+            "White"
+        } else {
+            "Black"
+        },
+        format_board(board),
+        if move_history.is_empty() {
+            "No moves yet"
+        } else {
+            &move_history
+        }
+    )
+}
+
+#[derive(Clone)]
+pub struct Coach {
+    api_key: String,
+    model: String,
+}
+
+impl Coach {
+    pub fn new(api_key: String) -> Result<Self, String> {
+        let model = gemini_model().ok_or("A model is required: set model in config.txt")?;
+        Ok(Self { api_key, model })
+    }
+
+    /// Streams the answer to the conversation's pending question on a
+    /// background task. Chunks arrive on the returned channel as they come in.
+    pub fn spawn_reply(&self, conversation: &CoachConversation) -> UnboundedReceiver<CoachEvent> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        if let Err(error) = check_budget() {
+            log::warn("ai_budget", json!({ "message": error.to_string() }));
+            let _ = sender.send(CoachEvent::Failed(error.to_string()));
+            return receiver;
+        }
+
+        // Set API key in environment for genai client
+        env::set_var("GEMINI_API_KEY", &self.api_key);
+        let model = self.model.clone();
+        let temperature = active_settings().temperature as f64;
+        let chat_req = ChatRequest::new(conversation.to_chat_messages());
+        // The whole exchange is only kept for a debug log
+        let question = log::enabled(log::Level::Debug)
+            .then(|| {
+                conversation
+                    .messages
+                    .iter()
+                    .rfind(|message| message.role == CoachRole::Player)
+                    .map(|message| message.text.clone())
+            })
+            .flatten();
+
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let mut reply = String::new();
+            let failed = |error: &AIError| {
+                log::error(
+                    "ai_request_failed",
+                    json!({
+                        "model": model,
+                        "ms": started.elapsed().as_millis() as u64,
+                        "message": error.to_string(),
+                    }),
+                );
+            };
+            let client = Client::default();
+            let chat_options = ChatOptions::default()
+                .with_temperature(temperature)
+                .with_max_tokens(400)
+                .with_capture_usage(true);
+
+            let response = match client
+                .exec_chat_stream(&model, chat_req, Some(&chat_options))
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    let error = AIError::RequestFailed(e.to_string());
+                    failed(&error);
+                    let _ = sender.send(CoachEvent::Failed(error.to_string()));
+                    return;
+                }
+            };
+
+            let mut stream = response.stream;
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(ChatStreamEvent::Chunk(chunk)) => {
+                        if question.is_some() {
+                            reply.push_str(&chunk.content);
+                        }
+                        if sender.send(CoachEvent::Chunk(chunk.content)).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(ChatStreamEvent::End(end)) => {
+                        let usage = end.captured_usage.unwrap_or_default();
+                        record_usage(&usage);
+                        log::info(
+                            "ai_request",
+                            json!({
+                                "model": model,
+                                "cached": false,
+                                "streamed": true,
+                                "ms": started.elapsed().as_millis() as u64,
+                                "prompt_tokens": usage.prompt_tokens,
+                                "completion_tokens": usage.completion_tokens,
+                            }),
+                        );
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        let error = AIError::RequestFailed(e.to_string());
+                        failed(&error);
+                        let _ = sender.send(CoachEvent::Failed(error.to_string()));
+                        return;
+                    }
+                }
+            }
+
+            if let Some(question) = question {
+                log::debug(
+                    "ai_exchange",
+                    json!({ "model": model, "prompt": question, "response": reply }),
+                );
+            }
+            let _ = sender.send(CoachEvent::Done);
+        });
+
+        receiver
+    }
+}
@@ -0,0 +1,187 @@
+use std::time::{Duration, Instant};
+
+use crate::core::board::Board;
+use crate::core::game::CheckersGame;
+use crate::core::move_history::MoveHistory;
+use crate::core::piece::Color as PieceColor;
+use crate::core::Turn;
+
+const MAN_VALUE: i32 = 100;
+const KING_VALUE: i32 = 160;
+const ADVANCE_BONUS: i32 = 3;
+const CENTER_BONUS: i32 = 4;
+
+/// Score for a won position; faster wins score slightly higher
+pub const WIN_SCORE: i32 = 10_000;
+
+/// Games between engines still going after this many turns are drawn, since
+/// the rules have no draw of their own
+pub const DRAW_AFTER_TURNS: usize = 200;
+
+/// Deepest iteration `search_within` will try, however much time is left
+pub const MAX_DEPTH: u32 = 12;
+/// Roughly how much longer each ply makes a search
+const BRANCHING: u32 = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub turn: Turn,
+    /// Score from the point of view of the player to move
+    pub score: i32,
+}
+
+/// Static evaluation of the board from `color`'s point of view. Material
+/// dominates; men earn a little for advancing and kings for staying central.
+pub fn evaluate(board: &Board, color: PieceColor) -> i32 {
+    let mut score = 0;
+    let center = (board.size as i32 - 1) as f32 / 2.0;
+
+    for row in 0..board.size {
+        for col in 0..board.size {
+            let Some(piece) = board.get_piece(row, col) else {
+                continue;
+            };
+
+            let value = if piece.is_king {
+                let distance = (row as f32 - center).abs().max((col as f32 - center).abs()) as i32;
+                KING_VALUE + CENTER_BONUS * (board.size as i32 / 2 - distance)
+            } else {
+                // White moves up the board, Black moves down
+                let advanced = match piece.color {
+                    PieceColor::White => board.size - 1 - row,
+                    PieceColor::Black => row,
+                };
+                MAN_VALUE + ADVANCE_BONUS * advanced as i32
+            };
+
+            if piece.color == color {
+                score += value;
+            } else {
+                score -= value;
+            }
+        }
+    }
+
+    score
+}
+
+/// Finds the best turn for the player to move with a fixed-depth alpha-beta
+/// search. Returns `None` when the player has no legal turn.
+pub fn search(game: &CheckersGame, depth: u32) -> Option<SearchResult> {
+    // History is irrelevant to the search and only slows down every clone
+    let mut root = game.clone();
+    root.move_history = MoveHistory::new();
+
+    let mut best: Option<SearchResult> = None;
+    let mut alpha = -WIN_SCORE - 1;
+    let beta = WIN_SCORE + 1;
+
+    for turn in root.legal_turns() {
+        let Ok(next) = root.apply_turn(&turn) else {
+            continue;
+        };
+        let score = -negamax(&next, depth.saturating_sub(1), -beta, -alpha);
+        if best.as_ref().is_none_or(|b| score > b.score) {
+            best = Some(SearchResult { turn, score });
+        }
+        alpha = alpha.max(score);
+    }
+
+    best
+}
+
+/// Searches one ply deeper at a time for as long as `budget` allows. An
+/// iteration is only started when it should finish in time, judging by the
+/// previous one, so the budget is a target rather than a hard limit.
+pub fn search_within(game: &CheckersGame, budget: Duration) -> Option<SearchResult> {
+    search_within_depth(game, budget, MAX_DEPTH)
+}
+
+/// Same as `search_within`, never looking further ahead than `max_depth`
+pub fn search_within_depth(
+    game: &CheckersGame,
+    budget: Duration,
+    max_depth: u32,
+) -> Option<SearchResult> {
+    let started = Instant::now();
+    let mut best = None;
+
+    for depth in 1..=max_depth.clamp(1, MAX_DEPTH) {
+        let result = search(game, depth)?;
+        // Nothing deeper changes a forced result
+        let decided = result.score.abs() >= WIN_SCORE;
+        best = Some(result);
+        if decided || started.elapsed() * BRANCHING >= budget {
+            break;
+        }
+    }
+
+    best
+}
+
+/// Scores the position from the point of view of the player to move
+pub fn score_position(game: &CheckersGame, depth: u32) -> i32 {
+    let mut root = game.clone();
+    root.move_history = MoveHistory::new();
+    negamax(&root, depth, -WIN_SCORE - 1, WIN_SCORE + 1)
+}
+
+/// Counts the complete turns `depth` plies deep, for checking move
+/// generation against known totals
+pub fn perft(game: &CheckersGame, depth: u32) -> u64 {
+    let mut root = game.clone();
+    root.move_history = MoveHistory::new();
+    count_turns(&root, depth)
+}
+
+/// `perft` split by the legal turns of the position, in the order
+/// `legal_turns` gives them
+pub fn perft_divide(game: &CheckersGame, depth: u32) -> Vec<(Turn, u64)> {
+    let mut root = game.clone();
+    root.move_history = MoveHistory::new();
+    root.legal_turns()
+        .into_iter()
+        .filter_map(|turn| {
+            let next = root.apply_turn(&turn).ok()?;
+            let nodes = count_turns(&next, depth.saturating_sub(1));
+            Some((turn, nodes))
+        })
+        .collect()
+}
+
+fn count_turns(game: &CheckersGame, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    game.legal_turns()
+        .iter()
+        .filter_map(|turn| game.apply_turn(turn).ok())
+        .map(|next| count_turns(&next, depth - 1))
+        .sum()
+}
+
+fn negamax(game: &CheckersGame, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+    if depth == 0 {
+        return evaluate(&game.board, game.current_player);
+    }
+    let turns = game.legal_turns();
+    if turns.is_empty() {
+        // No pieces or no moves left: the player to move has lost
+        return -WIN_SCORE - depth as i32;
+    }
+
+    let mut best = -WIN_SCORE - 1;
+    for turn in turns {
+        let Ok(next) = game.apply_turn(&turn) else {
+            continue;
+        };
+        let score = -negamax(&next, depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    best
+}
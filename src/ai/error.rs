@@ -1,4 +1,5 @@
 use std::io;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,10 +10,14 @@ pub enum AIError {
     ParseError(String),
     #[error("API key not found - add GEMINI_API_KEY to your .env file to enable AI features")]
     NoApiKey,
-    #[error("Model not specified - add GEMINI_MODEL to your .env file")]
+    #[error("Model not specified - set model in config.txt or GEMINI_MODEL in your .env file")]
     NoModel,
     #[error("AI response format is invalid: {0}")]
     InvalidResponseFormat(String),
+    #[error("AI budget for this session is used up ({0}) - raise LLM_MAX_REQUESTS or LLM_MAX_TOKENS in your .env file")]
+    BudgetExceeded(String),
+    #[error("AI ran out of thinking time after {0:.1?}")]
+    OutOfTime(Duration),
     #[error("No possible moves available for the AI.")]
     NoPossibleMoves,
     #[error("IO error: {0}")]
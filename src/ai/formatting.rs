@@ -1,4 +1,9 @@
 use crate::core::board::Board;
+use crate::core::fen::parse_fen;
+use crate::core::game::CheckersGame;
+use crate::core::notation::number_to_square;
+use crate::core::pdn::PdnGame;
+use crate::core::Turn;
 
 pub fn format_square(row: usize, col: usize) -> String {
     // Convert internal row (0=top, 7=bottom) to display row (8=top, 1=bottom)
@@ -23,3 +28,82 @@ pub fn format_board(board: &Board) -> String {
     }
     board_str
 }
+
+/// Reads a square name such as "D4" or "d4" back into coordinates on a
+/// board of `board_size`
+pub fn parse_square(name: &str, board_size: usize) -> Option<(usize, usize)> {
+    let mut chars = name.chars();
+    let file = chars.next()?.to_ascii_uppercase();
+    let rank_text = chars.as_str();
+    if !file.is_ascii_uppercase() || !rank_text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let col = (file as u8 - b'A') as usize;
+    let rank = rank_text.parse::<usize>().ok()?;
+    if col >= board_size || !(1..=board_size).contains(&rank) {
+        return None;
+    }
+    Some((board_size - rank, col))
+}
+
+/// Reads a typed move such as "c3-d4", "b6xd4xf2" or numeric "22-18" into
+/// the squares it passes through. Square names and numbers may be mixed;
+/// whether the separator is `-` or `x` is left for the rules to judge.
+pub fn parse_move(text: &str, board_size: usize) -> Option<Vec<(usize, usize)>> {
+    let path = text
+        .trim()
+        .split(['-', 'x', 'X'])
+        .map(|token| {
+            let token = token.trim();
+            match token.parse::<usize>() {
+                Ok(number) => number_to_square(number, board_size),
+                Err(_) => parse_square(token, board_size),
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (path.len() >= 2).then_some(path)
+}
+
+/// The turn a parsed path stands for. A jump may name only its first and
+/// last square when just one legal turn joins them; anything else is taken
+/// as written and left for the rules to judge.
+pub fn resolve_turn(game: &CheckersGame, path: Vec<(usize, usize)>) -> Turn {
+    let legal = game.legal_turns();
+    let mut matching = legal
+        .iter()
+        .filter(|turn| turn.from() == path[0] && turn.to() == path[path.len() - 1]);
+    match (path.len(), matching.next(), matching.next()) {
+        (2, Some(turn), None) => turn.clone(),
+        _ => Turn::new(path),
+    }
+}
+
+/// Plays out the moves of a PDN game from its FEN tag, or the standard
+/// start, returning the starting position and the one reached
+pub fn play_pdn(pdn: &PdnGame) -> Result<(CheckersGame, CheckersGame), String> {
+    let start = match pdn.tag("FEN") {
+        Some(fen) => parse_fen(fen).map_err(|e| e.to_string())?,
+        None => CheckersGame::new(),
+    };
+
+    let mut game = start.clone();
+    for (index, pdn_move) in pdn.moves.iter().enumerate() {
+        let number = index / 2 + 1;
+        let path = parse_move(&pdn_move.notation, game.board.size)
+            .ok_or_else(|| format!("Move {number} ({}) can't be read", pdn_move.notation))?;
+        let turn = resolve_turn(&game, path);
+        game = game
+            .apply_turn(&turn)
+            .map_err(|e| format!("Move {number} ({}) is not legal: {e}", pdn_move.notation))?;
+    }
+    Ok((start, game))
+}
+
+/// Finds every square name on a board of `board_size` mentioned in free
+/// text, in order of appearance. Only standalone tokens count, so words like
+/// "a1b2", or "h10" on an 8x8 board, are skipped.
+pub fn find_squares(text: &str, board_size: usize) -> Vec<(usize, usize)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter_map(|token| parse_square(token, board_size))
+        .collect()
+}
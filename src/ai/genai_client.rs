@@ -1,69 +1,171 @@
 use genai::{
-    chat::{ChatMessage, ChatOptions, ChatRequest},
+    chat::{ChatMessage, ChatOptions, ChatRequest, Usage},
     Client,
 };
+use serde_json::json;
 use std::env;
+use std::time::Instant;
 
+use crate::ai::cache::{cached_response, store_response};
 use crate::ai::error::AIError;
 use crate::ai::formatting::{format_board, format_square};
+use crate::ai::persona::Persona;
 use crate::ai::ui::{start_loading_animation, stop_loading_animation};
+use crate::ai::usage::{check_budget, record_cache_hit, record_request};
+use crate::config::{active_settings, gemini_model};
 use crate::core::game::CheckersGame;
-use crate::core::game_logic::get_all_valid_moves_for_player;
+use crate::core::game_logic::{get_all_valid_moves_for_player, Move};
 use crate::core::piece::Color as PieceColor;
-use crate::interface::messages;
-use crate::utils::prompts::get_ai_move_prompt;
+use crate::utils::log;
+use crate::utils::prompts::{get_ai_move_prompt, get_story_prompt};
+
+/// The conversation as one string, which keys it in the response cache
+pub fn prompt_key(messages: &[ChatMessage]) -> String {
+    messages
+        .iter()
+        .map(|m| {
+            format!(
+                "{:?}: {}",
+                m.role,
+                m.content.text_as_str().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs a chat request through the response cache and the session budget.
+/// Cached answers cost nothing; otherwise the budget is checked before the
+/// call and the tokens it used are recorded afterwards. Requests sampled
+/// hotter than [`crate::ai::cache::MAX_CACHED_TEMPERATURE`] always go to
+/// the model. Every request is logged with its timing, and at debug level
+/// with the whole exchange.
+///
+/// New replies are not cached here: the caller stores one with
+/// [`store_reply`] once it has read it, so a reply it can't use is asked
+/// for again next time.
+pub async fn exec_chat_cached(
+    model: &str,
+    messages: &[ChatMessage],
+    options: &ChatOptions,
+) -> Result<String, AIError> {
+    let prompt = prompt_key(messages);
+    let temperature = options.temperature.unwrap_or_default();
+    if let Some(response) = cached_response(model, temperature, &prompt) {
+        record_cache_hit();
+        log::info("ai_request", json!({ "model": model, "cached": true }));
+        log_exchange(model, &prompt, &response);
+        return Ok(response);
+    }
+
+    check_budget().inspect_err(|e| {
+        log::warn("ai_budget", json!({ "message": e.to_string() }));
+    })?;
+
+    let started = Instant::now();
+    let client = Client::default();
+    let result = client
+        .exec_chat(model, ChatRequest::new(messages.to_vec()), Some(options))
+        .await
+        .map_err(|e| {
+            log::error(
+                "ai_request_failed",
+                json!({
+                    "model": model,
+                    "ms": started.elapsed().as_millis() as u64,
+                    "message": e.to_string(),
+                }),
+            );
+            AIError::RequestFailed(e.to_string())
+        })?;
+    record_usage(&result.usage);
+    log::info(
+        "ai_request",
+        json!({
+            "model": model,
+            "cached": false,
+            "ms": started.elapsed().as_millis() as u64,
+            "prompt_tokens": result.usage.prompt_tokens,
+            "completion_tokens": result.usage.completion_tokens,
+        }),
+    );
+
+    let text = result
+        .content_text_as_str()
+        .ok_or_else(|| AIError::ParseError("No text content in response".to_string()))?
+        .to_string();
+    log_exchange(model, &prompt, &text);
+    Ok(text)
+}
+
+/// Keeps a reply to the request `exec_chat_cached` was given, for the
+/// caller to use once the reply has been read and checked
+pub fn store_reply(model: &str, messages: &[ChatMessage], options: &ChatOptions, reply: &str) {
+    let temperature = options.temperature.unwrap_or_default();
+    store_response(model, temperature, &prompt_key(messages), reply);
+}
+
+fn log_exchange(model: &str, prompt: &str, response: &str) {
+    if log::enabled(log::Level::Debug) {
+        log::debug(
+            "ai_exchange",
+            json!({ "model": model, "prompt": prompt, "response": response }),
+        );
+    }
+}
+
+/// Adds the tokens reported for one request to the session usage
+pub fn record_usage(usage: &Usage) {
+    record_request(
+        usage.prompt_tokens.unwrap_or_default().max(0) as u64,
+        usage.completion_tokens.unwrap_or_default().max(0) as u64,
+    );
+}
 
 pub async fn explain_rules() -> Result<String, AIError> {
     dotenv::dotenv().ok();
 
     let api_key = env::var("GEMINI_API_KEY").map_err(|_| AIError::NoApiKey)?;
-    let model = env::var("GEMINI_MODEL").map_err(|_| AIError::NoModel)?;
+    let model = gemini_model().ok_or(AIError::NoModel)?;
 
     let (running, loading_thread) = start_loading_animation()?;
 
     // Create client with the API key set in environment
     env::set_var("GEMINI_API_KEY", api_key);
-    let client = Client::default();
-
-    let chat_req = ChatRequest::new(vec![ChatMessage::user(messages::STORY_PROMPT)]);
 
     let chat_options = ChatOptions::default()
-        .with_temperature(0.7)
+        .with_temperature(active_settings().temperature as f64)
         .with_max_tokens(512);
 
-    let result = client
-        .exec_chat(&model, chat_req, Some(&chat_options))
-        .await;
+    let messages = [ChatMessage::user(get_story_prompt())];
+    let result = exec_chat_cached(&model, &messages, &chat_options).await;
 
     stop_loading_animation(running, loading_thread)?;
 
-    let result = result.map_err(|e| AIError::RequestFailed(e.to_string()))?;
-
-    match result.content_text_as_str() {
-        Some(text) => {
-            // Remove HTML tags from the response
-            let cleaned_text = text
-                .replace("<br>", "\n")
-                .replace("<br/>", "\n")
-                .replace("<br />", "\n");
-            Ok(cleaned_text)
-        }
-        None => Err(AIError::ParseError(
-            "No text content in response".to_string(),
-        )),
+    let text = result?;
+    if !text.trim().is_empty() {
+        store_reply(&model, &messages, &chat_options, &text);
     }
+    // Remove HTML tags from the response
+    let cleaned_text = text
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+    Ok(cleaned_text)
+}
+
+/// The LLM opponent's choice, with a remark if its persona makes them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AiMove {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+    pub remark: Option<String>,
 }
 
-pub async fn get_ai_move(game: &CheckersGame) -> Result<((usize, usize), (usize, usize)), AIError> {
+pub async fn get_ai_move(game: &CheckersGame, persona: &Persona) -> Result<AiMove, AIError> {
     dotenv::dotenv().ok();
     let api_key = env::var("GEMINI_API_KEY").map_err(|_| AIError::NoApiKey)?;
 
-    if game.current_player != PieceColor::Black {
-        return Err(AIError::InvalidResponseFormat(
-            "AI can only play as Black.".to_string(),
-        ));
-    }
-
     let possible_moves = get_all_valid_moves_for_player(&game.board, game.current_player);
     if possible_moves.is_empty() {
         return Err(AIError::NoPossibleMoves);
@@ -88,59 +190,113 @@ pub async fn get_ai_move(game: &CheckersGame) -> Result<((usize, usize), (usize,
         moves_str.push('\n');
     }
 
+    let move_history = game.move_history.to_notation();
     let prompt_template = get_ai_move_prompt();
-    let prompt = prompt_template
+    let mut prompt = prompt_template
         .replace("{board_state}", &board_representation)
-        .replace("{available_moves}", moves_str.trim());
+        .replace("{available_moves}", moves_str.trim())
+        .replace(
+            "{player_color}",
+            match game.current_player {
+                PieceColor::White => "White (w/W pieces)",
+                PieceColor::Black => "Black (b/B pieces)",
+            },
+        )
+        .replace(
+            "{move_history}",
+            if move_history.is_empty() {
+                "No moves yet"
+            } else {
+                &move_history
+            },
+        );
+    // Custom templates may leave the persona out; it still has to be heard
+    if prompt.contains("{persona}") {
+        prompt = prompt.replace("{persona}", &persona.prompt_section());
+    } else {
+        prompt = format!("{}\n\n{prompt}", persona.prompt_section());
+    }
 
     // Create client with the API key set in environment
     env::set_var("GEMINI_API_KEY", api_key);
-    let client = Client::default();
-    let model = env::var("GEMINI_MODEL").map_err(|_| AIError::NoModel)?;
+    let model = gemini_model().ok_or(AIError::NoModel)?;
 
-    let chat_req = ChatRequest::new(vec![ChatMessage::user(prompt)]);
-
-    let chat_options = ChatOptions::default()
-        .with_temperature(0.1) // Lower temperature for more deterministic responses
-        .with_max_tokens(5); // We only need a single digit
+    let settings = active_settings();
+    let chat_options = if persona.remarks {
+        ChatOptions::default()
+            .with_temperature(settings.temperature as f64)
+            .with_max_tokens(60) // Move number plus a one-line remark
+    } else {
+        ChatOptions::default()
+            .with_temperature(settings.move_temperature as f64) // Low by default, for steadier choices
+            .with_max_tokens(5) // We only need a single digit
+    };
 
     let (running, loading_thread) = start_loading_animation()?;
 
-    let result = client
-        .exec_chat(&model, chat_req, Some(&chat_options))
-        .await;
+    let messages = [ChatMessage::user(prompt)];
+    let result = exec_chat_cached(&model, &messages, &chat_options).await;
 
     stop_loading_animation(running, loading_thread)?;
 
-    let result = result.map_err(|e| AIError::RequestFailed(e.to_string()))?;
-
-    match result.content_text_as_str() {
-        Some(text_response) => {
-            let text_response = text_response.trim();
-
-            let cleaned_response = text_response
-                .chars()
-                .filter(|c| c.is_ascii_digit())
-                .collect::<String>();
-
-            match cleaned_response.parse::<usize>() {
-                Ok(move_number) if move_number > 0 && move_number <= possible_moves.len() => {
-                    let chosen_move_data = &possible_moves[move_number - 1];
-                    Ok((chosen_move_data.0, chosen_move_data.1))
-                }
-                Ok(_) => Err(AIError::InvalidResponseFormat(format!(
-                    "Move index {} is out of bounds. Valid range: 1-{}. Original response: '{}'",
-                    cleaned_response,
-                    possible_moves.len(),
-                    text_response
-                ))),
-                Err(_) => Err(AIError::InvalidResponseFormat(format!(
-                    "AI returned non-numeric or invalid response: '{text_response}'. Cleaned: '{cleaned_response}'"
-                ))),
-            }
+    accept_ai_move(
+        &model,
+        &messages,
+        &chat_options,
+        &result?,
+        &possible_moves,
+        persona,
+    )
+}
+
+/// Reads the move a reply to `messages` picks, caching the reply only
+/// when it names one of `moves`
+pub fn accept_ai_move(
+    model: &str,
+    messages: &[ChatMessage],
+    options: &ChatOptions,
+    reply: &str,
+    moves: &[Move],
+    persona: &Persona,
+) -> Result<AiMove, AIError> {
+    let ai_move = read_ai_move(reply, moves, persona)?;
+    store_reply(model, messages, options, reply);
+    Ok(ai_move)
+}
+
+/// The move a reply to the move prompt picks from the numbered `moves`,
+/// with the remark after a `|` when the persona makes them
+fn read_ai_move(reply: &str, moves: &[Move], persona: &Persona) -> Result<AiMove, AIError> {
+    let text_response = reply.trim();
+    let (choice, remark) = match text_response.split_once('|') {
+        Some((choice, remark)) => (choice, Some(remark.trim().trim_matches('"'))),
+        None => (text_response, None),
+    };
+
+    let cleaned_response = choice
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>();
+
+    match cleaned_response.parse::<usize>() {
+        Ok(move_number) if move_number > 0 && move_number <= moves.len() => {
+            let chosen_move_data = &moves[move_number - 1];
+            Ok(AiMove {
+                from: chosen_move_data.0,
+                to: chosen_move_data.1,
+                remark: remark
+                    .filter(|r| persona.remarks && !r.is_empty())
+                    .map(str::to_string),
+            })
         }
-        None => Err(AIError::ParseError(
-            "No text content in response".to_string(),
-        )),
+        Ok(_) => Err(AIError::InvalidResponseFormat(format!(
+            "Move index {} is out of bounds. Valid range: 1-{}. Original response: '{}'",
+            cleaned_response,
+            moves.len(),
+            text_response
+        ))),
+        Err(_) => Err(AIError::InvalidResponseFormat(format!(
+            "AI returned non-numeric or invalid response: '{text_response}'. Cleaned: '{cleaned_response}'"
+        ))),
     }
 }
@@ -1,14 +1,16 @@
-use crate::ai::formatting::{format_board, format_square};
+use crate::ai::formatting::{find_squares, format_board, format_square};
+use crate::ai::genai_client::{exec_chat_cached, store_reply};
 use crate::ai::ui::{start_loading_animation, stop_loading_animation};
+use crate::ai::usage::check_budget;
+use crate::ai::Hint;
+use crate::config::{active_settings, gemini_model};
 use crate::core::{
-    board::Board, game_logic::get_all_valid_moves_for_player, move_history::MoveHistory,
-    piece::Color as PieceColor,
+    board::Board, game::CheckersGame, move_history::MoveHistory, piece::Color as PieceColor, Turn,
 };
+use crate::utils::log;
 use crate::utils::prompts::get_hint_prompt;
-use genai::{
-    chat::{ChatMessage, ChatOptions, ChatRequest},
-    Client,
-};
+use genai::chat::{ChatMessage, ChatOptions};
+use serde_json::json;
 use std::env;
 
 #[derive(Clone)]
@@ -19,29 +21,37 @@ pub struct HintProvider {
 
 impl HintProvider {
     pub fn new(api_key: String) -> Result<Self, String> {
-        let model = env::var("GEMINI_MODEL")
-            .map_err(|_| "GEMINI_MODEL environment variable is required")?;
+        let model = gemini_model().ok_or("A model is required: set model in config.txt")?;
         Ok(Self { api_key, model })
     }
 
+    /// Asks for a hint and checks the move it names against the rules. An
+    /// illegal suggestion is sent back once for correction; if the second
+    /// answer is still wrong the hint is returned unverified.
     pub async fn get_hint(
         &self,
         board: &Board,
         current_player: PieceColor,
         history: &MoveHistory,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<Hint, Box<dyn std::error::Error>> {
+        // Don't start a hint that the session budget can no longer pay for
+        check_budget()?;
+
         let board_state = format_board(board);
         let move_history = history.to_notation();
 
-        // Get all valid moves for the current player
-        let possible_moves = get_all_valid_moves_for_player(board, current_player);
+        // Get all valid moves for the current player, multi-jumps spelled out
         let mut moves_str = String::new();
 
-        for ((from_row, from_col), (to_row, to_col), is_capture) in possible_moves.iter() {
-            let from_sq = format_square(*from_row, *from_col);
-            let to_sq = format_square(*to_row, *to_col);
-            let move_type = if *is_capture { "capture" } else { "move" };
-            moves_str.push_str(&format!("- {from_sq} to {to_sq} ({move_type})\n"));
+        for turn in legal_turns(board, current_player) {
+            let squares = turn
+                .path
+                .iter()
+                .map(|&(row, col)| format_square(row, col))
+                .collect::<Vec<_>>()
+                .join(" to ");
+            let move_type = if turn.is_capture() { "capture" } else { "move" };
+            moves_str.push_str(&format!("- {squares} ({move_type})\n"));
         }
 
         let prompt_template = get_hint_prompt();
@@ -72,42 +82,93 @@ impl HintProvider {
                 },
             );
 
-        // Set API key in environment for genai client
-        env::set_var("GEMINI_API_KEY", &self.api_key);
-        let client = Client::default();
+        let mut messages = vec![ChatMessage::user(prompt)];
+        let text = self.request(&messages).await?;
+        if let Some(suggested_move) = verify_hint(&text, board, current_player) {
+            store_reply(&self.model, &messages, &chat_options(), &text);
+            return Ok(Hint {
+                hint: text,
+                suggested_move: Some(suggested_move),
+            });
+        }
 
-        let chat_req = ChatRequest::new(vec![ChatMessage::user(prompt)]);
+        messages.push(ChatMessage::assistant(text.clone()));
+        messages.push(ChatMessage::user(format!(
+            "That move is not legal in this position. Reply again in the same format, \
+             suggesting one of these moves exactly as written:\n{moves_str}"
+        )));
+        let retry = self.request(&messages).await?;
+        let suggested_move = verify_hint(&retry, board, current_player);
+        if suggested_move.is_some() {
+            store_reply(&self.model, &messages, &chat_options(), &retry);
+        }
+
+        Ok(Hint {
+            hint: retry,
+            suggested_move,
+        })
+    }
 
-        let chat_options = ChatOptions::default()
-            .with_temperature(0.7)
-            .with_max_tokens(150);
+    async fn request(
+        &self,
+        messages: &[ChatMessage],
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        // Set API key in environment for genai client
+        env::set_var("GEMINI_API_KEY", &self.api_key);
 
         // Start loading animation
         let (running, loading_thread) =
             start_loading_animation().map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
-        let result = client
-            .exec_chat(&self.model, chat_req, Some(&chat_options))
-            .await;
+        let result = exec_chat_cached(&self.model, messages, &chat_options()).await;
 
         // Stop loading animation
         stop_loading_animation(running, loading_thread)
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
-        let result = result.map_err(|e| {
-            #[cfg(debug_assertions)]
-            eprintln!("Hint API error: {e:?}");
-            Box::new(std::io::Error::other(format!("API request failed: {e}")))
-                as Box<dyn std::error::Error>
+        let text = result.map_err(|e| {
+            log::error("hint_error", json!({ "message": e.to_string() }));
+            Box::new(e) as Box<dyn std::error::Error>
         })?;
-
-        match result.content_text_as_str() {
-            Some(text) => Ok(text.trim().to_string()),
-            None => {
-                #[cfg(debug_assertions)]
-                eprintln!("Hint response has no text content");
-                Err("Failed to parse hint from API response".into())
-            }
-        }
+        Ok(text.trim().to_string())
     }
 }
+
+fn chat_options() -> ChatOptions {
+    ChatOptions::default()
+        .with_temperature(active_settings().temperature as f64)
+        .with_max_tokens(150)
+}
+
+fn legal_turns(board: &Board, current_player: PieceColor) -> Vec<Turn> {
+    let game = CheckersGame {
+        board: board.clone(),
+        current_player,
+        is_game_over: false,
+        move_history: MoveHistory::new(),
+    };
+    game.legal_turns()
+}
+
+/// Finds the move a hint recommends and checks it with the rules engine.
+/// Squares named one after the other are read as a move's start and
+/// destination, and the first such pair that is legal wins, so prose like
+/// "don't play D6, move C3 to D4" still finds C3-D4. For a multi-jump any of
+/// its landing squares is accepted.
+pub fn verify_hint(
+    text: &str,
+    board: &Board,
+    current_player: PieceColor,
+) -> Option<((usize, usize), (usize, usize))> {
+    let squares = find_squares(text, board.size);
+    let legal = legal_turns(board, current_player);
+
+    squares
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|&(from, to)| {
+            legal
+                .iter()
+                .any(|turn| turn.from() == from && turn.path[1..].contains(&to))
+        })
+}
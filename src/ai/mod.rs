@@ -1,16 +1,31 @@
+pub mod analysis;
+pub mod cache;
+pub mod coach;
+pub mod engine;
 pub mod error;
 pub mod formatting;
 pub mod genai_client;
 pub mod hint;
+pub mod persona;
+pub mod tournament;
 pub mod ui;
+pub mod usage;
 
 pub use error::AIError;
 pub use genai_client::explain_rules;
 #[allow(unused_imports)]
-pub use genai_client::get_ai_move;
+pub use genai_client::{get_ai_move, AiMove};
 
 // Simple hint structure for UI display
 #[derive(Clone)]
 pub struct Hint {
     pub hint: String,
+    /// The move the hint recommends, set only when the rules engine accepts it
+    pub suggested_move: Option<((usize, usize), (usize, usize))>,
+}
+
+impl Hint {
+    pub fn is_verified(&self) -> bool {
+        self.suggested_move.is_some()
+    }
 }
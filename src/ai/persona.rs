@@ -0,0 +1,143 @@
+use crate::utils::paths;
+use std::path::Path;
+
+/// An opponent style for the LLM player. Built-in personas can be extended
+/// with `<config dir>/personas/<name>.txt` files:
+///
+/// ```text
+/// description: Plays for the draw at all costs
+/// remarks: yes
+///
+/// Instructions added to the move prompt...
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Persona {
+    pub name: String,
+    pub description: String,
+    /// Playing style added to the move prompt; empty for the plain opponent
+    pub instructions: String,
+    /// Whether the opponent comments on its own moves
+    pub remarks: bool,
+}
+
+impl Default for Persona {
+    fn default() -> Self {
+        Self::new("classic", "Balanced play, no commentary", "", false)
+    }
+}
+
+impl Persona {
+    pub fn new(name: &str, description: &str, instructions: &str, remarks: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            instructions: instructions.to_string(),
+            remarks,
+        }
+    }
+
+    pub fn builtins() -> Vec<Self> {
+        vec![
+            Self::default(),
+            Self::new(
+                "aggressive",
+                "Hunts captures and races for kings",
+                "Play aggressively. Prefer captures and moves that advance toward the king row, \
+                 and accept some risk to keep the initiative.",
+                false,
+            ),
+            Self::new(
+                "defensive",
+                "Guards the back row and trades carefully",
+                "Play defensively. Keep your back row intact, avoid leaving pieces where they can \
+                 be jumped, and only trade when it is clearly safe.",
+                false,
+            ),
+            Self::new(
+                "trash-talker",
+                "Plays to win and tells you about it",
+                "Play confidently and tease your opponent with playful, good-natured trash talk. \
+                 Never be rude or offensive.",
+                true,
+            ),
+        ]
+    }
+
+    /// Reads a persona file: optional `key: value` header lines, a blank line,
+    /// then the instructions. Without a header the whole text is instructions.
+    pub fn parse(name: &str, text: &str) -> Self {
+        let mut persona = Self::new(name, "Custom opponent", "", false);
+        let mut lines = text.lines().peekable();
+
+        while let Some(line) = lines.peek() {
+            let Some((key, value)) = line.split_once(':') else {
+                break;
+            };
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "description" => persona.description = value.to_string(),
+                "remarks" => {
+                    persona.remarks = matches!(value.to_lowercase().as_str(), "yes" | "true")
+                }
+                _ => break,
+            }
+            lines.next();
+        }
+
+        persona.instructions = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+        persona
+    }
+
+    /// Built-ins followed by the `.txt` files in `dir`, sorted by name. A file
+    /// named after a built-in replaces it.
+    pub fn load_all(dir: Option<&Path>) -> Vec<Self> {
+        let mut personas = Self::builtins();
+        let Some(entries) = dir.and_then(|dir| std::fs::read_dir(dir).ok()) else {
+            return personas;
+        };
+
+        let mut custom = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_str()?.to_string();
+                let text = std::fs::read_to_string(&path).ok()?;
+                Some(Self::parse(&name, &text))
+            })
+            .collect::<Vec<_>>();
+        custom.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for persona in custom {
+            match personas.iter_mut().find(|p| p.name == persona.name) {
+                Some(existing) => *existing = persona,
+                None => personas.push(persona),
+            }
+        }
+
+        personas
+    }
+
+    /// Text for the `{persona}` placeholder of the move prompt
+    pub fn prompt_section(&self) -> String {
+        let mut section = String::new();
+        if !self.instructions.is_empty() {
+            section.push_str(&format!("Playing style: {}\n\n", self.instructions));
+        }
+        if self.remarks {
+            section.push_str(
+                "After the move number, write \" | \" and one short remark to your opponent \
+                 in character (at most 15 words). Example: 3 | Say goodbye to that piece.",
+            );
+        } else {
+            section.push_str("Respond with ONLY the move number - no text or punctuation.");
+        }
+        section
+    }
+}
+
+/// Personas available in this run, including the user's own
+pub fn available_personas() -> Vec<Persona> {
+    let dir = paths::config_dir().map(|dir| dir.join("personas"));
+    Persona::load_all(dir.as_deref())
+}
@@ -0,0 +1,535 @@
+//! Headless matches between two engine settings, for telling whether a
+//! change to the AI made it stronger. Each opening is played twice with the
+//! colors swapped, and the result is reported as W/D/L, an Elo difference
+//! with its 95% error bars and a sequential probability ratio test.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::ai::engine::{search, search_within, DRAW_AFTER_TURNS};
+use crate::ai::formatting::{parse_move, resolve_turn};
+use crate::core::fen::to_fen;
+use crate::core::game::CheckersGame;
+use crate::core::notation::turn_to_numeric;
+use crate::core::pdn::{result_string, PdnGame};
+use crate::core::piece::Color;
+use crate::core::Turn;
+
+/// Three-move openings the engine scores as level, spread over every first
+/// move so matches do not keep replaying the same game
+pub const OPENINGS: [&str; 24] = [
+    "21-17 11-15 24-19",
+    "21-17 10-15 25-21",
+    "21-17 12-16 17-13",
+    "22-17 11-15 23-18",
+    "22-17 9-13 24-20",
+    "22-17 12-16 24-20",
+    "22-17 10-15 26-22",
+    "22-18 10-15 23-19",
+    "22-18 10-14 23-19",
+    "22-18 10-15 24-20",
+    "23-18 10-15 22-17",
+    "23-18 11-16 24-19",
+    "23-18 9-13 22-17",
+    "23-19 11-15 24-20",
+    "23-19 9-14 22-18",
+    "23-19 11-16 22-17",
+    "23-19 10-14 26-23",
+    "24-19 11-15 22-17",
+    "24-19 9-13 28-24",
+    "24-19 11-16 23-18",
+    "24-20 11-15 23-19",
+    "24-20 12-16 28-24",
+    "24-20 9-13 22-17",
+    "24-20 10-15 27-24",
+];
+
+/// Games in a match unless `--games` says otherwise
+pub const DEFAULT_GAMES: usize = 100;
+/// Where the games are written unless `--pdn` says otherwise
+pub const DEFAULT_PDN: &str = "selfplay.pdn";
+
+/// Times a position may come up before the game is drawn
+const REPETITIONS: usize = 3;
+/// How far apart the normal distribution's 95% interval lies from the mean
+const Z_95: f64 = 1.96;
+
+/// How one side of a match picks its moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerSpec {
+    /// A fixed-depth search
+    Depth(u32),
+    /// A search that deepens for as long as the time allows
+    MoveTime(Duration),
+}
+
+impl PlayerSpec {
+    /// Reads "depth:N" or "time:MS"
+    pub fn parse(text: &str) -> Option<Self> {
+        let (kind, value) = text.trim().split_once(':')?;
+        let value: u64 = value.trim().parse().ok().filter(|&value| value > 0)?;
+        match kind.trim() {
+            "depth" => Some(Self::Depth(u32::try_from(value).ok()?)),
+            "time" => Some(Self::MoveTime(Duration::from_millis(value))),
+            _ => None,
+        }
+    }
+
+    /// The turn this player makes, or `None` when it has none
+    pub fn choose(&self, game: &CheckersGame) -> Option<Turn> {
+        let best = match *self {
+            Self::Depth(depth) => search(game, depth),
+            Self::MoveTime(budget) => search_within(game, budget),
+        };
+        best.map(|best| best.turn)
+    }
+}
+
+impl fmt::Display for PlayerSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Depth(depth) => write!(f, "depth:{depth}"),
+            Self::MoveTime(budget) => write!(f, "time:{}", budget.as_millis()),
+        }
+    }
+}
+
+/// A sequential probability ratio test of "the first player is `elo0`
+/// stronger" against "it is `elo1` stronger"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    /// Chance of accepting `elo1` when `elo0` is true
+    pub alpha: f64,
+    /// Chance of accepting `elo0` when `elo1` is true
+    pub beta: f64,
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Self {
+            elo0: 0.0,
+            elo1: 10.0,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    AcceptH0,
+    AcceptH1,
+    Continue,
+}
+
+impl Sprt {
+    /// The log-likelihood ratio below and above which the test stops
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Log-likelihood ratio of the results so far, with the game scores
+    /// taken as normally distributed around their mean
+    pub fn llr(&self, tally: &Tally) -> f64 {
+        let games = tally.games() as f64;
+        let variance = tally.variance();
+        if games == 0.0 || variance == 0.0 {
+            return 0.0;
+        }
+        let (s0, s1) = (expected_score(self.elo0), expected_score(self.elo1));
+        games * (s1 - s0) * (2.0 * tally.score() - s0 - s1) / (2.0 * variance)
+    }
+
+    pub fn decision(&self, tally: &Tally) -> SprtDecision {
+        let llr = self.llr(tally);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            SprtDecision::AcceptH1
+        } else if llr <= lower {
+            SprtDecision::AcceptH0
+        } else {
+            SprtDecision::Continue
+        }
+    }
+}
+
+/// Share of the points a player `elo` stronger expects to score
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// The Elo difference a share of the points stands for; infinite for a
+/// clean sweep either way
+fn elo_from_score(score: f64) -> f64 {
+    if score <= 0.0 {
+        f64::NEG_INFINITY
+    } else if score >= 1.0 {
+        f64::INFINITY
+    } else {
+        -400.0 * (1.0 / score - 1.0).log10()
+    }
+}
+
+/// Results from the first player's side
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Tally {
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    pub fn add(&mut self, points: Option<bool>) {
+        match points {
+            Some(true) => self.wins += 1,
+            Some(false) => self.losses += 1,
+            None => self.draws += 1,
+        }
+    }
+
+    /// Share of the points scored, counting a draw as half
+    pub fn score(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games() as f64
+    }
+
+    /// Variance of a single game's score
+    fn variance(&self) -> f64 {
+        let games = self.games() as f64;
+        if games == 0.0 {
+            return 0.0;
+        }
+        let score = self.score();
+        (self.wins as f64 * (1.0 - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2))
+            / games
+    }
+
+    pub fn elo(&self) -> f64 {
+        elo_from_score(self.score())
+    }
+
+    /// The 95% confidence interval of the Elo difference
+    pub fn elo_interval(&self) -> (f64, f64) {
+        let games = self.games().max(1) as f64;
+        let margin = Z_95 * (self.variance() / games).sqrt();
+        (
+            elo_from_score(self.score() - margin),
+            elo_from_score(self.score() + margin),
+        )
+    }
+}
+
+/// Why a game stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// A side had no pieces or no moves left
+    Board,
+    Repetition,
+    MoveLimit,
+}
+
+impl Termination {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Board => "normal",
+            Self::Repetition => "repetition",
+            Self::MoveLimit => "move limit",
+        }
+    }
+}
+
+/// How a game went, move by move
+#[derive(Debug, Clone)]
+pub struct PlayedGame {
+    pub winner: Option<Color>,
+    pub termination: Termination,
+    pub turns: Vec<(Color, Turn)>,
+}
+
+/// A finished game of the match
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    pub round: usize,
+    pub opening: String,
+    pub white: PlayerSpec,
+    pub black: PlayerSpec,
+    /// Whether the first player of the match had White
+    pub first_is_white: bool,
+    pub winner: Option<Color>,
+    pub termination: Termination,
+    pub turns: Vec<(Color, Turn)>,
+}
+
+impl GameRecord {
+    /// The game's result for the first player: a win, a loss or a draw
+    pub fn first_player_won(&self) -> Option<bool> {
+        let first = if self.first_is_white {
+            Color::White
+        } else {
+            Color::Black
+        };
+        self.winner.map(|winner| winner == first)
+    }
+
+    pub fn to_pdn(&self) -> String {
+        let mut pdn = PdnGame::new(result_string(self.winner))
+            .with_tag("Event", "checkers-rs self-play")
+            .with_tag("Round", &self.round.to_string())
+            .with_tag("White", &self.white.to_string())
+            .with_tag("Black", &self.black.to_string())
+            .with_tag("Opening", &self.opening)
+            .with_tag("Termination", self.termination.label());
+        for (player, turn) in &self.turns {
+            pdn = pdn.with_move(*player, turn_to_numeric(turn, 8), None);
+        }
+        pdn.to_pdn()
+    }
+}
+
+/// Plays out `opening` and then lets the players take turns until the board
+/// decides the game, a position comes up a third time or the game runs past
+/// `DRAW_AFTER_TURNS`
+pub fn play_game(
+    white: PlayerSpec,
+    black: PlayerSpec,
+    opening: &[String],
+) -> Result<PlayedGame, String> {
+    let mut game = CheckersGame::new();
+    let mut turns = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let finish = |winner, termination, turns| PlayedGame {
+        winner,
+        termination,
+        turns,
+    };
+
+    for text in opening {
+        let path = parse_move(text, game.board.size)
+            .ok_or_else(|| format!("can't read opening move {text}"))?;
+        let turn = resolve_turn(&game, path);
+        let player = game.current_player;
+        game = game
+            .apply_turn(&turn)
+            .map_err(|e| format!("opening move {text}: {e}"))?;
+        turns.push((player, turn));
+    }
+
+    loop {
+        let player = game.current_player;
+        let spec = match player {
+            Color::White => white,
+            Color::Black => black,
+        };
+        // Losing every piece or every move loses the game
+        let Some(turn) = spec.choose(&game) else {
+            return Ok(finish(Some(player.opposite()), Termination::Board, turns));
+        };
+        game = game.apply_turn(&turn).map_err(|e| e.to_string())?;
+        turns.push((player, turn));
+
+        if let Some(winner) = game.check_winner() {
+            return Ok(finish(Some(winner), Termination::Board, turns));
+        }
+        let seen_before = seen.entry(to_fen(&game)).or_default();
+        *seen_before += 1;
+        if *seen_before >= REPETITIONS {
+            return Ok(finish(None, Termination::Repetition, turns));
+        }
+        if turns.len() >= DRAW_AFTER_TURNS {
+            return Ok(finish(None, Termination::MoveLimit, turns));
+        }
+    }
+}
+
+/// What a match plays and where it keeps the games
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchConfig {
+    /// The player measured, then its opponent
+    pub players: [PlayerSpec; 2],
+    pub games: usize,
+    pub openings: Vec<String>,
+    pub pdn: PathBuf,
+    pub sprt: Sprt,
+}
+
+impl MatchConfig {
+    /// Reads `--player SPEC --player SPEC [--games N] [--pdn FILE]
+    /// [--openings FILE] [--sprt ELO0,ELO1]`
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let mut players = Vec::new();
+        let mut games = DEFAULT_GAMES;
+        let mut openings: Vec<String> = OPENINGS.iter().map(|o| o.to_string()).collect();
+        let mut pdn = PathBuf::from(DEFAULT_PDN);
+        let mut sprt = Sprt::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--player" => {
+                    let spec = value()?;
+                    players.push(PlayerSpec::parse(&spec).ok_or(format!(
+                        "Unknown player \"{spec}\" - use depth:N or time:MS"
+                    ))?);
+                }
+                "--games" => {
+                    games = value()?
+                        .parse()
+                        .ok()
+                        .filter(|&games| games > 0)
+                        .ok_or("--games takes a positive number")?;
+                }
+                "--pdn" => pdn = PathBuf::from(value()?),
+                "--openings" => openings = load_openings(Path::new(&value()?))?,
+                "--sprt" => {
+                    let bounds = value()?;
+                    let (elo0, elo1) = bounds
+                        .split_once(',')
+                        .and_then(|(a, b)| Some((a.trim().parse().ok()?, b.trim().parse().ok()?)))
+                        .filter(|(elo0, elo1)| elo0 < elo1)
+                        .ok_or("--sprt takes ELO0,ELO1 with ELO0 below ELO1")?;
+                    sprt.elo0 = elo0;
+                    sprt.elo1 = elo1;
+                }
+                other => return Err(format!("Unknown argument {other}")),
+            }
+        }
+
+        let [first, second] = players[..] else {
+            return Err("A match needs two --player settings".to_string());
+        };
+        Ok(Self {
+            players: [first, second],
+            games,
+            openings,
+            pdn,
+            sprt,
+        })
+    }
+}
+
+/// Reads openings one per line, moves separated by spaces; blank lines and
+/// lines starting with `#` are skipped
+pub fn load_openings(path: &Path) -> Result<Vec<String>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let openings: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    if openings.is_empty() {
+        return Err(format!("{} has no openings", path.display()));
+    }
+    Ok(openings)
+}
+
+/// Plays the whole match, appending each game to the PDN file as it ends
+/// and handing it to `on_game`
+pub fn run_match(
+    config: &MatchConfig,
+    mut on_game: impl FnMut(&GameRecord, &Tally),
+) -> io::Result<Tally> {
+    let mut pdn = File::create(&config.pdn)?;
+    let mut tally = Tally::default();
+    let [first, second] = config.players;
+
+    for round in 1..=config.games {
+        // Both colors of an opening are played before moving on
+        let pair = (round - 1) / 2;
+        let first_is_white = (round - 1) % 2 == 0;
+        let opening = &config.openings[pair % config.openings.len()];
+        let (white, black) = if first_is_white {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let moves: Vec<String> = opening.split_whitespace().map(str::to_string).collect();
+        let played = play_game(white, black, &moves)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let record = GameRecord {
+            round,
+            opening: opening.clone(),
+            white,
+            black,
+            first_is_white,
+            winner: played.winner,
+            termination: played.termination,
+            turns: played.turns,
+        };
+
+        writeln!(pdn, "{}", record.to_pdn())?;
+        tally.add(record.first_player_won());
+        on_game(&record, &tally);
+    }
+
+    Ok(tally)
+}
+
+/// The closing summary of a match
+pub struct MatchReport<'a> {
+    pub config: &'a MatchConfig,
+    pub tally: Tally,
+}
+
+impl fmt::Display for MatchReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tally = &self.tally;
+        let [first, second] = self.config.players;
+        writeln!(
+            f,
+            "Score of {first} vs {second}: {} - {} - {} [{:.3}] {}",
+            tally.wins,
+            tally.losses,
+            tally.draws,
+            tally.score(),
+            tally.games()
+        )?;
+
+        let (low, high) = tally.elo_interval();
+        writeln!(
+            f,
+            "Elo difference: {:+.1} (95%: {:+.1} to {:+.1})",
+            tally.elo(),
+            low,
+            high
+        )?;
+
+        let sprt = &self.config.sprt;
+        let (lower, upper) = sprt.bounds();
+        let decision = match sprt.decision(tally) {
+            SprtDecision::AcceptH0 => "H0 accepted",
+            SprtDecision::AcceptH1 => "H1 accepted",
+            SprtDecision::Continue => "no decision yet",
+        };
+        write!(
+            f,
+            "SPRT elo0 {} elo1 {} alpha {} beta {}: LLR {:.2} [{:.2}, {:.2}], {decision}",
+            sprt.elo0,
+            sprt.elo1,
+            sprt.alpha,
+            sprt.beta,
+            sprt.llr(tally),
+            lower,
+            upper
+        )
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ai::error::AIError;
+use std::env;
+use std::sync::{Mutex, OnceLock};
+
+pub const DEFAULT_MAX_REQUESTS: u32 = 150;
+pub const DEFAULT_MAX_TOKENS: u64 = 150_000;
+
+/// LLM traffic of the current session
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UsageStats {
+    pub requests: u32,
+    pub cache_hits: u32,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl UsageStats {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    /// One-line summary for the game-over screen
+    pub fn summary(&self) -> String {
+        format!(
+            "AI usage: {} requests ({} cached), {} tokens",
+            self.requests,
+            self.cache_hits,
+            self.total_tokens()
+        )
+    }
+}
+
+/// Upper limits on LLM traffic for one session
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    pub max_requests: u32,
+    pub max_tokens: u64,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            max_requests: DEFAULT_MAX_REQUESTS,
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+}
+
+impl Budget {
+    /// Reads `LLM_MAX_REQUESTS` and `LLM_MAX_TOKENS`, keeping the defaults
+    /// for anything missing or malformed.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_requests: env::var("LLM_MAX_REQUESTS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(defaults.max_requests),
+            max_tokens: env::var("LLM_MAX_TOKENS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(defaults.max_tokens),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UsageTracker {
+    budget: Budget,
+    stats: UsageStats,
+}
+
+impl UsageTracker {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            stats: UsageStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> UsageStats {
+        self.stats
+    }
+
+    /// Fails once either limit has been reached; call before every request
+    pub fn check(&self) -> Result<(), AIError> {
+        if self.stats.requests >= self.budget.max_requests {
+            return Err(AIError::BudgetExceeded(format!(
+                "{} requests used",
+                self.stats.requests
+            )));
+        }
+        if self.stats.total_tokens() >= self.budget.max_tokens {
+            return Err(AIError::BudgetExceeded(format!(
+                "{} tokens used",
+                self.stats.total_tokens()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn record_request(&mut self, prompt_tokens: u64, completion_tokens: u64) {
+        self.stats.requests += 1;
+        self.stats.prompt_tokens += prompt_tokens;
+        self.stats.completion_tokens += completion_tokens;
+    }
+
+    pub fn record_cache_hit(&mut self) {
+        self.stats.cache_hits += 1;
+    }
+}
+
+fn tracker() -> &'static Mutex<UsageTracker> {
+    static TRACKER: OnceLock<Mutex<UsageTracker>> = OnceLock::new();
+    TRACKER.get_or_init(|| Mutex::new(UsageTracker::new(Budget::from_env())))
+}
+
+fn with_tracker<T>(f: impl FnOnce(&mut UsageTracker) -> T) -> T {
+    let mut tracker = tracker().lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut tracker)
+}
+
+/// Checks the session budget before an LLM request
+pub fn check_budget() -> Result<(), AIError> {
+    with_tracker(|tracker| tracker.check())
+}
+
+pub fn is_budget_exhausted() -> bool {
+    check_budget().is_err()
+}
+
+pub fn record_request(prompt_tokens: u64, completion_tokens: u64) {
+    with_tracker(|tracker| tracker.record_request(prompt_tokens, completion_tokens));
+}
+
+pub fn record_cache_hit() {
+    with_tracker(|tracker| tracker.record_cache_hit());
+}
+
+pub fn session_usage() -> UsageStats {
+    with_tracker(|tracker| tracker.stats())
+}
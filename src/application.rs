@@ -1,33 +1,172 @@
-use std::env;
-
-use crate::ai::{explain_rules, hint::HintProvider, AIError};
+use crate::ai::coach::CoachEvent;
+use crate::ai::persona::available_personas;
+use crate::ai::{explain_rules, AIError};
+use crate::config::{active_settings, PlayOptions, Settings};
+use crate::core::fen::to_fen;
+use crate::core::game::CheckersGame;
+use crate::core::notation::square_name;
 use crate::core::piece::Color;
-use crate::interface::ui_ratatui::{Input, UI};
-use crate::state::states::{WelcomeContent, WelcomeState};
-use crate::state::{GameSession, StateMachine, StateType};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::interface::animation::{Animation, AnimationSpeed, FRAME_INTERVAL};
+use crate::interface::keymap::{configured_bindings, Action, Input};
+use crate::interface::mouse::MouseGesture;
+use crate::interface::theme::configured_theme;
+use crate::interface::ui_ratatui::UI;
+use crate::net::broadcast::{self, Broadcast, Snapshot, Watch, WatchEvent, Watching};
+use crate::net::protocol::{self, Message};
+use crate::net::{remote, Link, NetEvent, NetRole, RemotePlay};
+use crate::state::autosave::{self, autosave_path};
+use crate::state::states::{GameOverState, PlayingState, WelcomeContent, WelcomeState};
+use crate::state::ui_state::FlipMode;
+use crate::state::{GameSession, StateMachine, StateTransition, StateType};
+use crate::utils::log;
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{error::TryRecvError, UnboundedReceiver};
+
+/// How often a running clock is redrawn while waiting for input
+const CLOCK_TICK: Duration = Duration::from_millis(100);
 
 pub struct Application {
     ui: UI,
     session: GameSession,
     state_machine: StateMachine,
+    coach_stream: Option<UnboundedReceiver<CoachEvent>>,
+    mouse: MouseGesture,
+    animation_speed: AnimationSpeed,
+    /// The settings the screen was last drawn with
+    settings: Settings,
+    /// Connection to the other player in network games
+    link: Option<Link>,
+    /// Watchers of this game, when it is broadcast
+    broadcast: Option<Broadcast>,
+    /// The broadcast followed, when only watching
+    watch: Option<Watch>,
+    /// Steps of the history already written to the log
+    logged_steps: usize,
+    /// Where the game is kept while `autosave` is on, for local games from
+    /// the standard start or a saved game
+    autosave: Option<PathBuf>,
+    /// Steps of the history already in the autosave file
+    autosaved_steps: usize,
 }
 
 impl Application {
-    pub async fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub async fn new(
+        options: PlayOptions,
+        network: Option<NetRole>,
+        broadcast: Option<String>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        // Bind before the terminal switches screens so errors stay readable
+        let link = match &network {
+            Some(NetRole::Host { address, seat }) => Some(Link::host(address, *seat).await?),
+            Some(NetRole::Join { address }) => Some(Link::join(address)),
+            Some(NetRole::Dxp {
+                address,
+                seat,
+                time: (minutes, moves),
+            }) => Some(Link::dxp_connect(address, *seat, *minutes, *moves)),
+            Some(NetRole::DxpListen { address }) => Some(Link::dxp_listen(address).await?),
+            // The built-in engine plays those without a board, and watchers
+            // follow a broadcast instead
+            Some(
+                NetRole::EngineDxp { .. } | NetRole::EngineDxpListen { .. } | NetRole::Watch { .. },
+            )
+            | None => None,
+        };
+        let watch = match &network {
+            Some(NetRole::Watch { address }) => Some(Watch::connect(address)),
+            _ => None,
+        };
+        let broadcast = match broadcast {
+            Some(address) => Some(Broadcast::start(&address).await?),
+            None => None,
+        };
+
         let mut ui = UI::new()?;
         ui.init()?;
 
-        let mut session = GameSession::new();
-        Self::initialize_hint_provider(&mut session);
+        let mut session = GameSession::from_options(&options);
+        let flip = FlipMode::from_env();
+        session.ui_state = session.ui_state.with_flip_mode(flip);
+        if flip == FlipMode::Off && options.color == Color::Black && network.is_none() {
+            // Playing Black against the computer, so Black sits at the bottom
+            session.ui_state.flipped = true;
+        }
+        if let (Some(role), Some(link)) = (&network, &link) {
+            // Clocks are not shared over the network, so those games are untimed
+            session.clock = None;
+            session.remote = Some(match role {
+                NetRole::Host { seat, .. } => RemotePlay::new(seat.opposite(), true)
+                    .with_notice(format!("Waiting for an opponent on {}", link.address())),
+                // The guest learns its seat from the host
+                NetRole::Join { .. } => RemotePlay::new(Color::White, false)
+                    .with_notice(format!("Connecting to {}...", link.address())),
+                NetRole::Dxp { seat, .. } => RemotePlay::new(seat.opposite(), false).with_notice(
+                    format!("Asking the engine at {} for a game...", link.address()),
+                ),
+                // The engine picks the side it plays
+                NetRole::DxpListen { .. } => RemotePlay::new(Color::Black, false)
+                    .with_notice(format!("Waiting for an engine on {}", link.address())),
+                NetRole::EngineDxp { .. }
+                | NetRole::EngineDxpListen { .. }
+                | NetRole::Watch { .. } => {
+                    unreachable!("headless roles and watchers never open a link")
+                }
+            });
+        }
+        if let Some(watch) = &watch {
+            // The clocks come with the broadcast
+            session.clock = None;
+            session.watching = Some(Watching::new(format!(
+                "Connecting to the broadcast at {}...",
+                watch.address()
+            )));
+        }
+        // Pick up where the last unfinished game left off
+        let autosave = autosave_path().filter(|_| network.is_none() && options.fen.is_none());
+        let autosaved = match &autosave {
+            Some(path) if active_settings().autosave && path.exists() => {
+                match autosave::load_game(path) {
+                    Ok((start, game)) => {
+                        session.game = game;
+                        session.start = start;
+                    }
+                    Err(error) => log::warn(
+                        "autosave",
+                        json!({ "path": path.display().to_string(), "error": error }),
+                    ),
+                }
+                session.game.move_history.moves().len()
+            }
+            _ => 0,
+        };
+        session = session.with_ai_helpers();
+        session.personas = available_personas();
         Self::initialize_welcome_content(&mut session).await;
 
-        let state_machine = StateMachine::new(Box::new(WelcomeState::new()));
+        // Watchers go straight to the board
+        let state_machine = if watch.is_some() {
+            StateMachine::new(Box::new(PlayingState::new()))
+        } else {
+            StateMachine::new(Box::new(WelcomeState::new()))
+        };
 
+        let settings = active_settings();
         Ok(Self {
             ui,
             session,
             state_machine,
+            coach_stream: None,
+            mouse: MouseGesture::new(),
+            animation_speed: settings.animation,
+            settings,
+            link,
+            broadcast,
+            watch,
+            logged_steps: 0,
+            autosave,
+            autosaved_steps: autosaved,
         })
     }
 
@@ -74,7 +213,7 @@ impl Application {
                 Self::default_welcome_content()
             }
             Err(e) => {
-                eprintln!("Failed to get checkers content from AI: {e}");
+                log::warn("welcome_content", json!({ "message": e.to_string() }));
                 Self::default_welcome_content()
             }
         }
@@ -89,51 +228,400 @@ impl Application {
         }
     }
 
-    fn initialize_hint_provider(session: &mut GameSession) {
-        if let Ok(api_key) = env::var("GEMINI_API_KEY") {
-            if env::var("GEMINI_MODEL").is_ok() {
-                if let Ok(provider) = HintProvider::new(api_key) {
-                    session.hint_provider = Some(provider);
-                }
-            }
-        }
-    }
-
     pub async fn run(mut self) -> Result<(), Box<dyn std::error::Error>> {
         loop {
             let view = self.state_machine.get_view_data(&self.session);
             self.ui.draw_view_data(&view)?;
+            self.session.ui_state.move_list_rows = self.ui.move_list_rows();
+
+            let text_entry = matches!(
+                self.state_machine.current_state_type(),
+                StateType::Coach | StateType::MoveEntry
+            );
+            self.ui.set_text_entry(text_entry);
 
+            let mut before = self.session.game.clone();
             if self.should_process_ai() {
                 self.process_ai_frame()?;
+            } else if self.analysis_pending() {
+                // The review shows as in progress for a frame, then is worked out
+                let (new_session, transition) =
+                    self.state_machine.handle_input(&self.session, Input::Tick);
+                self.session = new_session;
+                self.state_machine.process_transition(transition);
+            } else if self.coach_stream.is_some() {
+                if !self.process_coach_frame()? {
+                    break;
+                }
             } else if !self.process_user_input()? {
                 break;
             }
+            self.apply_settings();
+
+            self.exchange_network(&before);
+            self.follow_broadcast(&mut before);
+            self.log_new_moves();
+            self.run_clock(&before);
+            self.autosave_game();
+            self.animate_new_moves(&before)?;
+            self.face_player_to_move(&before);
+            self.start_coach_reply();
+            self.publish();
         }
 
         self.ui.restore()?;
+        log::info(
+            "session_end",
+            json!({
+                "fen": to_fen(&self.session.game),
+                "moves": self.session.game.move_history.to_notation(),
+                "game_over": self.session.game.is_game_over,
+            }),
+        );
         Ok(())
     }
 
+    /// Puts the theme, keys and animation chosen on the settings screen into
+    /// effect
+    fn apply_settings(&mut self) {
+        let settings = active_settings();
+        if settings == self.settings {
+            return;
+        }
+        if settings.theme != self.settings.theme {
+            self.ui
+                .set_theme(configured_theme(settings.theme.as_deref()));
+        }
+        if settings.keys != self.settings.keys {
+            self.ui
+                .set_bindings(configured_bindings(settings.keys.as_deref()));
+        }
+        self.animation_speed = settings.animation;
+        self.settings = settings;
+    }
+
+    /// Writes the steps played since the last call to the log, wherever
+    /// they came from
+    fn log_new_moves(&mut self) {
+        let game = &self.session.game;
+        let steps = game.move_history.moves();
+        if steps.len() < self.logged_steps {
+            log::info("history_reset", json!({ "steps": steps.len() }));
+            self.logged_steps = 0;
+        }
+        let Some(new_steps) = steps.get(self.logged_steps..) else {
+            return;
+        };
+        let square = |(row, col): (usize, usize)| square_name(row, col, game.board.size);
+        for step in new_steps {
+            log::info(
+                "move",
+                json!({
+                    "player": format!("{:?}", step.player),
+                    "from": square(step.from),
+                    "to": square(step.to),
+                    "captured": step.captured.iter().copied().map(square).collect::<Vec<_>>(),
+                    "king": step.became_king,
+                }),
+            );
+        }
+        if !new_steps.is_empty() {
+            log::debug("position", json!({ "fen": to_fen(game) }));
+        }
+        self.logged_steps = steps.len();
+    }
+
+    /// Keeps the game in the autosave file after every complete turn, and
+    /// forgets it once the game is over
+    fn autosave_game(&mut self) {
+        let Some(path) = self.autosave.as_deref().filter(|_| self.settings.autosave) else {
+            return;
+        };
+        let game = &self.session.game;
+        let steps = game.move_history.moves().len();
+        let result = if game.is_game_over {
+            autosave::remove_game(path)
+        } else if steps != self.autosaved_steps
+            && self.state_machine.current_state_type() != StateType::MultiCapture
+        {
+            autosave::save_game(path, game, self.session.start.as_ref())
+        } else {
+            return;
+        };
+        if let Err(e) = result {
+            log::warn(
+                "autosave",
+                json!({ "path": path.display().to_string(), "error": e.to_string() }),
+            );
+        }
+        self.autosaved_steps = steps;
+    }
+
+    /// Starts the clock once play begins, hands it over when the side to move
+    /// changes, stops it when the game ends and ends the game when a side
+    /// runs out of time
+    fn run_clock(&mut self, before: &CheckersGame) {
+        // A watcher's clock only shows what the broadcast says
+        if self.watch.is_some() {
+            return;
+        }
+        let in_play = !matches!(
+            self.state_machine.current_state_type(),
+            StateType::Welcome | StateType::Settings
+        );
+        let game = &self.session.game;
+        let Some(clock) = self.session.clock.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+
+        if game.is_game_over {
+            clock.stop(now);
+            return;
+        }
+        if !in_play {
+            return;
+        }
+        if !clock.is_running() {
+            clock.start(game.current_player, now);
+        } else if game.current_player != before.current_player {
+            clock.press(now);
+        }
+
+        if let Some(loser) = clock.flagged(now) {
+            clock.stop(now);
+            self.session.game.is_game_over = true;
+            self.state_machine
+                .process_transition(StateTransition::To(Box::new(GameOverState::new(Some(
+                    loser.opposite(),
+                )))));
+        }
+    }
+
+    /// Plays the steps added to the history since `before` as an animation.
+    /// Any input skips to the end and is otherwise ignored.
+    fn animate_new_moves(
+        &mut self,
+        before: &CheckersGame,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let played = before.move_history.moves().len();
+        let Some(steps) = self.session.game.move_history.moves().get(played..) else {
+            return Ok(());
+        };
+        let Some(animation) = Animation::new(&before.board, steps, self.animation_speed) else {
+            return Ok(());
+        };
+
+        let view = self.state_machine.get_view_data(&self.session);
+        for index in 0..animation.len() {
+            self.ui.set_animation(Some(animation.frame(index)));
+            self.ui.draw_view_data(&view)?;
+            if self.ui.wait_for_input(FRAME_INTERVAL)?.is_some() {
+                break;
+            }
+        }
+        self.ui.set_animation(None);
+
+        // Watching the move play out is on the house
+        if let Some(clock) = self.session.clock.as_mut() {
+            if let Some(side) = clock.running_side() {
+                clock.start(side, Instant::now());
+            }
+        }
+        Ok(())
+    }
+
+    /// Turns an auto-flipping board toward the side that has just been
+    /// handed the move, if that side is played from this keyboard
+    fn face_player_to_move(&mut self, before: &CheckersGame) {
+        let player = self.session.game.current_player;
+        if player != before.current_player
+            && !self.should_process_ai()
+            && self.session.is_local_turn()
+        {
+            let ui_state = self.session.ui_state.face(player);
+            self.session = self.session.with_ui_state(ui_state);
+        }
+    }
+
+    fn analysis_pending(&self) -> bool {
+        self.state_machine.current_state_type() == StateType::Analysis
+            && self.session.analysis.is_none()
+    }
+
     fn should_process_ai(&self) -> bool {
         matches!(
             self.state_machine.current_state_type(),
             StateType::AITurn
                 | StateType::Playing
-                    if self.session.game.current_player == Color::Black
+                    if self.session.ai_seat() == Some(self.session.game.current_player)
         )
     }
 
+    /// Sends the moves played here since `before` and anything else the
+    /// session queued, then takes in what the other end sent
+    fn exchange_network(&mut self, before: &CheckersGame) {
+        let Some(link) = self.link.as_mut() else {
+            return;
+        };
+        let Some(remote) = self.session.remote.as_mut() else {
+            return;
+        };
+
+        let played = before.move_history.moves().len();
+        if let Some(steps) = self.session.game.move_history.moves().get(played..) {
+            for step in steps.iter().filter(|step| step.player != remote.seat) {
+                link.send(Message::Move {
+                    from: step.from,
+                    to: step.to,
+                });
+            }
+        }
+
+        let history = protocol::steps(&self.session.game.move_history);
+        while let Some(event) = link.try_recv() {
+            match event {
+                NetEvent::Connected { seat, peer } => {
+                    log::info(
+                        "net_connected",
+                        json!({ "peer": peer.to_string(), "seat": format!("{seat:?}") }),
+                    );
+                    if let Some(remote) = self.session.remote.as_mut() {
+                        remote.seat = seat.opposite();
+                        remote.connected = true;
+                        remote.notice = Some(format!("Connected to {peer}, you play {seat:?}"));
+                        if remote.hosting {
+                            let moves = protocol::steps(&self.session.game.move_history);
+                            link.send(Message::Sync { moves });
+                        }
+                    }
+                    // The guest sees the board from its own side
+                    if FlipMode::from_env() == FlipMode::Off {
+                        self.session.ui_state.flipped = seat == Color::Black;
+                    }
+                }
+                NetEvent::Received(message) => {
+                    log::debug("net_received", json!({ "message": format!("{message:?}") }));
+                    self.session = remote::receive(&self.session, message);
+                }
+                NetEvent::Disconnected(reason) => {
+                    log::warn("net_disconnected", json!({ "reason": reason.to_string() }));
+                    if let Some(remote) = self.session.remote.as_mut() {
+                        remote.connected = false;
+                        remote.notice = Some(if remote.hosting {
+                            format!("The opponent left ({reason}), waiting for them to return")
+                        } else {
+                            format!("Lost the host ({reason}), reconnecting...")
+                        });
+                    }
+                }
+                NetEvent::Failed(error) => {
+                    log::error("net_failed", json!({ "message": error.to_string() }));
+                    if let Some(remote) = self.session.remote.as_mut() {
+                        remote.connected = false;
+                        remote.notice = Some(error.to_string());
+                    }
+                }
+            }
+        }
+
+        if let Some(remote) = self.session.remote.as_mut() {
+            for message in remote.outbox.drain(..) {
+                link.send(message);
+            }
+        }
+
+        // Leave the board states when the game ended or changed under them
+        let state = self.state_machine.current_state_type();
+        let reviewing = matches!(
+            state,
+            StateType::GameOver | StateType::Analysis | StateType::Replay
+        );
+        if let Some(winner) = remote::outcome(&self.session) {
+            if !reviewing {
+                self.session.game.is_game_over = true;
+                self.state_machine
+                    .process_transition(StateTransition::To(Box::new(GameOverState::new(winner))));
+            }
+        } else if history != protocol::steps(&self.session.game.move_history)
+            && matches!(state, StateType::PieceSelected | StateType::MultiCapture)
+        {
+            self.state_machine
+                .process_transition(StateTransition::To(Box::new(PlayingState::new())));
+        }
+    }
+
+    /// Takes in what the broadcast being watched sent. Only a single new
+    /// turn is animated: joining a game or catching up shows the position
+    /// at once.
+    fn follow_broadcast(&mut self, before: &mut CheckersGame) {
+        let Some(watch) = self.watch.as_mut() else {
+            return;
+        };
+        while let Some(event) = watch.try_recv() {
+            match event {
+                WatchEvent::Snapshot(snapshot) => {
+                    self.session = broadcast::follow(&self.session, &snapshot, Instant::now());
+                }
+                WatchEvent::Connected(address) => {
+                    log::info("watch_connected", json!({ "address": address.to_string() }));
+                    if let Some(watching) = self.session.watching.as_mut() {
+                        watching.connected = true;
+                        watching.notice = None;
+                    }
+                }
+                WatchEvent::Disconnected(reason) => {
+                    log::warn(
+                        "watch_disconnected",
+                        json!({ "reason": reason.to_string() }),
+                    );
+                    if let Some(watching) = self.session.watching.as_mut() {
+                        watching.connected = false;
+                        watching.notice =
+                            Some(format!("Lost the broadcast ({reason}), reconnecting..."));
+                    }
+                }
+                WatchEvent::Failed(error) => {
+                    log::error("watch_failed", json!({ "message": error.to_string() }));
+                    if let Some(watching) = self.session.watching.as_mut() {
+                        watching.connected = false;
+                        watching.notice = Some(error.to_string());
+                    }
+                }
+            }
+        }
+
+        if !is_next_turn(before, &self.session.game) {
+            *before = self.session.game.clone();
+        }
+        let result = self
+            .session
+            .watching
+            .as_ref()
+            .and_then(|watching| watching.result);
+        if let Some(winner) = result {
+            if self.state_machine.current_state_type() == StateType::Playing {
+                self.state_machine
+                    .process_transition(StateTransition::To(Box::new(GameOverState::new(winner))));
+            }
+        }
+    }
+
+    /// Hands the game as it now stands to anyone watching it
+    fn publish(&mut self) {
+        if let Some(broadcast) = self.broadcast.as_mut() {
+            broadcast.publish(Snapshot::of(&self.session, Instant::now()));
+        }
+    }
+
     fn process_ai_frame(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Ok(Some(input)) = self.ui.poll_input() {
-            if matches!(input, Input::Quit) {
+            if matches!(input, Input::Action(Action::Quit)) {
                 return Ok(());
             }
         }
 
-        let (new_session, transition) = self
-            .state_machine
-            .handle_input(&self.session, KeyEvent::from(KeyCode::Char(' ')));
+        let (new_session, transition) = self.state_machine.handle_input(&self.session, Input::Tick);
         self.session = new_session;
         self.state_machine.process_transition(transition);
 
@@ -141,30 +629,166 @@ impl Application {
         Ok(())
     }
 
-    fn process_user_input(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
-        if let Ok(input) = self.ui.get_input() {
-            let should_quit = matches!(input, Input::Quit);
-            let key_event = self.input_to_key_event(input);
-            let (new_session, transition) =
-                self.state_machine.handle_input(&self.session, key_event);
-            self.session = new_session;
-            self.state_machine.process_transition(transition);
+    /// Starts streaming the coach's answer once a question has been asked
+    fn start_coach_reply(&mut self) {
+        if self.coach_stream.is_none() && self.session.coach.is_awaiting_reply() {
+            if let Some(coach) = &self.session.coach_provider {
+                self.coach_stream = Some(coach.spawn_reply(&self.session.coach));
+            }
+        }
+    }
+
+    /// Appends streamed coach text while still accepting typing
+    fn process_coach_frame(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        if let Some(stream) = self.coach_stream.as_mut() {
+            loop {
+                match stream.try_recv() {
+                    Ok(CoachEvent::Chunk(text)) => {
+                        self.session.coach = self.session.coach.append_reply(&text);
+                    }
+                    Ok(CoachEvent::Done) | Err(TryRecvError::Disconnected) => {
+                        self.session.coach = self.session.coach.finish_reply();
+                        self.coach_stream = None;
+                        break;
+                    }
+                    Ok(CoachEvent::Failed(error)) => {
+                        log::error("coach_failed", json!({ "message": error }));
+                        self.session.coach = self.session.coach.fail_reply(&error);
+                        self.coach_stream = None;
+                        break;
+                    }
+                    Err(TryRecvError::Empty) => break,
+                }
+            }
+        }
 
-            if should_quit {
+        if let Ok(Some(input)) = self.ui.poll_input() {
+            if !self.handle_user_input(input) {
                 return Ok(false);
             }
         }
+
+        std::thread::sleep(std::time::Duration::from_millis(30));
         Ok(true)
     }
 
-    fn input_to_key_event(&self, input: Input) -> KeyEvent {
-        match input {
-            Input::Up => KeyEvent::from(KeyCode::Up),
-            Input::Down => KeyEvent::from(KeyCode::Down),
-            Input::Left => KeyEvent::from(KeyCode::Left),
-            Input::Right => KeyEvent::from(KeyCode::Right),
-            Input::Select => KeyEvent::from(KeyCode::Enter),
-            Input::Quit => KeyEvent::from(KeyCode::Esc),
+    /// Waits for the next input, or only briefly while a clock is running
+    /// so its display keeps counting down
+    fn process_user_input(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        let ticking = self.link.is_some()
+            || self.watch.is_some()
+            || self
+                .session
+                .clock
+                .as_ref()
+                .is_some_and(|clock| clock.is_running());
+        let input = if ticking {
+            self.ui.wait_for_input(CLOCK_TICK)
+        } else {
+            self.ui.get_input().map(Some)
+        };
+        if let Ok(Some(input)) = input {
+            return Ok(self.handle_user_input(input));
         }
+        Ok(true)
     }
+
+    fn handle_user_input(&mut self, input: Input) -> bool {
+        log::trace("input", json!({ "input": format!("{input:?}") }));
+        // Watchers may look around but not touch a game still in play
+        let looking = matches!(
+            input,
+            Input::Action(
+                Action::Quit
+                    | Action::Flip
+                    | Action::Help
+                    | Action::ScrollBack
+                    | Action::ScrollForward
+            )
+        );
+        if self.session.watching.is_some() && !self.session.game.is_game_over && !looking {
+            return true;
+        }
+
+        if matches!(input, Input::MouseDown(_) | Input::MouseUp(_)) {
+            self.handle_mouse(input);
+            return true;
+        }
+
+        // Flipping only changes the view, wherever the board is shown
+        if matches!(input, Input::Action(Action::Flip)) {
+            let ui_state = self.session.ui_state.toggle_flip();
+            self.session = self.session.with_ui_state(ui_state);
+            return true;
+        }
+
+        // The help popup swallows keys until it is closed again
+        if matches!(input, Input::Action(Action::Help)) {
+            self.ui.toggle_help();
+            return true;
+        }
+        if self.ui.help_open() {
+            if matches!(input, Input::Action(Action::Quit | Action::Select)) {
+                self.ui.toggle_help();
+            }
+            return true;
+        }
+
+        // ESC only closes the coach, the move command line, the game review,
+        // the replay or the settings, it never quits from there
+        let closes_panel = matches!(
+            self.state_machine.current_state_type(),
+            StateType::Coach
+                | StateType::MoveEntry
+                | StateType::Analysis
+                | StateType::Replay
+                | StateType::Settings
+        );
+        let should_quit = matches!(input, Input::Action(Action::Quit)) && !closes_panel;
+        let (new_session, transition) = self.state_machine.handle_input(&self.session, input);
+        self.session = new_session;
+        self.state_machine.process_transition(transition);
+
+        !should_quit
+    }
+
+    /// Clicks act like moving the cursor to the square and selecting it
+    fn handle_mouse(&mut self, input: Input) {
+        let on_board = matches!(
+            self.state_machine.current_state_type(),
+            StateType::Playing | StateType::PieceSelected | StateType::MultiCapture
+        );
+        if !on_board {
+            self.mouse.cancel();
+            return;
+        }
+
+        let selected = self.session.ui_state.selected_piece;
+        let clicked = match input {
+            Input::MouseDown(square) => self.mouse.press(square, selected),
+            Input::MouseUp(square) => self.mouse.release(square),
+            _ => None,
+        };
+
+        if let Some(square) = clicked {
+            let ui_state = self.session.ui_state.move_cursor_to(square);
+            self.session = self.session.with_ui_state(ui_state);
+            let (new_session, transition) = self
+                .state_machine
+                .handle_input(&self.session, Input::Action(Action::Select));
+            self.session = new_session;
+            self.state_machine.process_transition(transition);
+        }
+    }
+}
+
+/// Whether `after` is `before` with at most one more turn played
+fn is_next_turn(before: &CheckersGame, after: &CheckersGame) -> bool {
+    let (played, moves) = (before.move_history.moves(), after.move_history.moves());
+    let Some(added) = moves.strip_prefix(played) else {
+        return false;
+    };
+    added
+        .iter()
+        .all(|step| Some(step.player) == added.first().map(|first| first.player))
 }
@@ -0,0 +1,356 @@
+//! The command line: `checkers-rs [COMMAND] [OPTIONS]`, where the command
+//! is one of
+//!
+//! ```text
+//! play        the board, which is also what no command means
+//! analyze     score the moves of a PDN game
+//! perft       count positions, to check the move generator
+//! selfplay    engine against engine
+//! engine      the text protocol on stdin and stdout
+//! serve       the HTTP API, when built with the `server` feature
+//! ```
+//!
+//! The logging flags work with every command. Settings `play` is not given
+//! come from config.txt.
+
+use crate::ai::tournament::MatchConfig;
+use crate::config::{Difficulty, Opponent, PlayOptions, Variant, CONFIG_FILE, PLAY_SETTINGS};
+use crate::core::fen::{parse_fen, START_FEN};
+use crate::core::piece::Color;
+use crate::net::broadcast::take_broadcast_flag;
+use crate::net::NetRole;
+use crate::utils::log::{take_log_flags, LogConfig};
+use std::path::PathBuf;
+
+/// Depth `perft` counts to when none is given
+pub const DEFAULT_PERFT_DEPTH: u32 = 6;
+
+/// What the program was asked to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// A game on the board, perhaps over the network or broadcast
+    Play {
+        options: PlayOptions,
+        network: Option<NetRole>,
+        broadcast: Option<String>,
+    },
+    /// Score every move of the first game in a PDN file
+    Analyze {
+        path: PathBuf,
+    },
+    /// Count the positions `depth` turns from `fen`, or the start
+    Perft {
+        depth: u32,
+        fen: Option<String>,
+    },
+    Selfplay(MatchConfig),
+    /// The text protocol on stdin and stdout
+    Engine,
+    #[cfg(feature = "server")]
+    Serve {
+        address: String,
+    },
+    /// Gather what a bug report needs, into `path` or a file named by the time
+    DebugBundle {
+        path: Option<String>,
+    },
+    /// Print this text and stop
+    Help(String),
+    Version,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cli {
+    pub logging: LogConfig,
+    pub command: Command,
+}
+
+/// Reads the arguments after the program name. `defaults` are the settings
+/// `play` starts from, usually those of the config file.
+pub fn parse(args: impl IntoIterator<Item = String>, defaults: PlayOptions) -> Result<Cli, String> {
+    let (logging, args) = take_log_flags(args)?;
+    let mut args = args.into_iter();
+    let mut rest = Vec::new();
+
+    let name = match args.next() {
+        None => "play".to_string(),
+        Some(first) if first == "-h" || first == "--help" => "help".to_string(),
+        Some(first) if first == "-V" || first == "--version" => "version".to_string(),
+        Some(first) if first.starts_with('-') => {
+            rest.push(first);
+            "play".to_string()
+        }
+        Some(first) => first,
+    };
+    rest.extend(args);
+
+    let help = |name: &str| command_help(name, &defaults);
+    if name != "help" && rest.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Cli {
+            logging,
+            command: Command::Help(help(&name)?),
+        });
+    }
+
+    let command = match name.as_str() {
+        "play" => parse_play(rest, defaults)?,
+        "analyze" => match &rest[..] {
+            [path] => Command::Analyze {
+                path: PathBuf::from(path),
+            },
+            [] => return Err("analyze needs a PDN file".to_string()),
+            [_, extra, ..] => return Err(format!("Unknown argument {extra}")),
+        },
+        "perft" => parse_perft(rest)?,
+        "selfplay" => Command::Selfplay(MatchConfig::from_args(rest)?),
+        "engine" => no_arguments(rest, Command::Engine)?,
+        #[cfg(feature = "server")]
+        "serve" => match &rest[..] {
+            [] => Command::Serve {
+                address: crate::server::DEFAULT_SERVER_ADDRESS.to_string(),
+            },
+            [address] => Command::Serve {
+                address: address.clone(),
+            },
+            [_, extra, ..] => return Err(format!("Unknown argument {extra}")),
+        },
+        #[cfg(not(feature = "server"))]
+        "serve" => return Err(
+            "serve needs a build with the server feature: cargo build --release --features server"
+                .to_string(),
+        ),
+        "debug-bundle" => match &rest[..] {
+            [] => Command::DebugBundle { path: None },
+            [path] => Command::DebugBundle {
+                path: Some(path.clone()),
+            },
+            [_, extra, ..] => return Err(format!("Unknown argument {extra}")),
+        },
+        "help" => match &rest[..] {
+            [] => Command::Help(help("")?),
+            [command] => Command::Help(help(command)?),
+            [_, extra, ..] => return Err(format!("Unknown argument {extra}")),
+        },
+        "version" => no_arguments(rest, Command::Version)?,
+        other => return Err(format!("Unknown command {other}")),
+    };
+    Ok(Cli { logging, command })
+}
+
+fn no_arguments(rest: Vec<String>, command: Command) -> Result<Command, String> {
+    match rest.first() {
+        Some(extra) => Err(format!("Unknown argument {extra}")),
+        None => Ok(command),
+    }
+}
+
+/// Game options are taken first; what is left must describe a network game
+/// or a broadcast
+fn parse_play(args: Vec<String>, mut options: PlayOptions) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let mut rest = Vec::new();
+    let mut chose_color = false;
+
+    while let Some(arg) = args.next() {
+        let Some(name) = arg
+            .strip_prefix("--")
+            .filter(|name| PLAY_SETTINGS.contains(name) || *name == "fen")
+        else {
+            rest.push(arg);
+            continue;
+        };
+        let value = args.next().ok_or(format!("{arg} needs a value"))?;
+        options
+            .set(name, &value)
+            .map_err(|error| format!("{arg}: {error}"))?;
+        chose_color |= name == "color";
+    }
+
+    let (broadcast, rest) = take_broadcast_flag(rest);
+    let network = NetRole::from_args(rest)?;
+    if options.fen.is_some() && (network.is_some() || broadcast.is_some()) {
+        return Err(
+            "--fen only works for games on this machine that are not broadcast".to_string(),
+        );
+    }
+    if chose_color && network.is_some() {
+        return Err(
+            "--color is for games against the computer; use --seat over the network".to_string(),
+        );
+    }
+    Ok(Command::Play {
+        options,
+        network,
+        broadcast,
+    })
+}
+
+fn parse_perft(args: Vec<String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let mut depth = None;
+    let mut fen = None;
+
+    while let Some(arg) = args.next() {
+        if arg == "--fen" {
+            let value = args.next().ok_or("--fen needs a value")?;
+            parse_fen(&value).map_err(|e| format!("--fen: {e}"))?;
+            fen = Some(value);
+        } else if depth.is_none() {
+            depth = Some(
+                arg.parse()
+                    .ok()
+                    .filter(|&depth| depth > 0)
+                    .ok_or(format!("perft takes a depth of 1 or more, not {arg}"))?,
+            );
+        } else {
+            return Err(format!("Unknown argument {arg}"));
+        }
+    }
+    Ok(Command::Perft {
+        depth: depth.unwrap_or(DEFAULT_PERFT_DEPTH),
+        fen,
+    })
+}
+
+fn one_of<const N: usize>(names: [&str; N]) -> String {
+    names.join("|")
+}
+
+/// Help for one command, or the overview when `name` is empty. `play` shows
+/// the defaults it would use.
+pub fn command_help(name: &str, defaults: &PlayOptions) -> Result<String, String> {
+    let text = match name {
+        "" | "help" => {
+            let serve = if cfg!(feature = "server") {
+                "  serve [ADDRESS]     Serve the rules and the engine as JSON over HTTP\n"
+            } else {
+                ""
+            };
+            format!(
+                "checkers-rs {version} - checkers in the terminal
+
+Usage: checkers-rs [COMMAND] [OPTIONS]
+
+Commands:
+  play                Play on the board (the default)
+  analyze FILE        Score every move of a PDN game with the engine
+  perft [DEPTH]       Count the positions DEPTH turns ahead
+  selfplay            Play the engine against itself to compare settings
+  engine              Answer engine commands on stdin and stdout
+{serve}  debug-bundle [FILE] Gather what a bug report needs into one file
+  help [COMMAND]      Show the options of a command
+
+Options for every command:
+  -v, -vv, -vvv         Log moves and AI requests, then transitions, then keys
+  --log-level LEVEL     off, error, warn, info, debug or trace
+  --log-file FILE       Write the log to FILE
+  -h, --help            Show help
+  -V, --version         Show the version
+
+Settings for play are read from {CONFIG_FILE} in the config directory
+(~/.config/checkers-rs); options given here win.",
+                version = env!("CARGO_PKG_VERSION"),
+            )
+        }
+        "play" => {
+            let time = defaults
+                .time
+                .map(|time| time.to_setting())
+                .unwrap_or_else(|| "off".to_string());
+            format!(
+                "Usage: checkers-rs [play] [OPTIONS]
+
+Plays on the board. The value in brackets is the one used now.
+
+Options:
+  --variant {variant}
+      The rules played [{default_variant}]
+  --color white|black
+      Your side against the computer [{color}]
+  --ai {ai}
+      Gemini when GEMINI_API_KEY is set, otherwise the built-in engine;
+      simple for the engine always; none for two players [{default_ai}]
+  --difficulty {difficulty}
+      How far ahead the built-in engine looks [{default_difficulty}]
+  --fen FEN
+      Start from this position instead of {START_FEN}
+  --time MINUTES[+INCREMENT|dDELAY]
+      A clock for each side, e.g. 5, 3+2 or 10d5; off for none [{time}]
+
+Network:
+  --host [ADDRESS] [--seat white|black]
+      Wait for another player, on port 7878 unless ADDRESS says otherwise
+  --join ADDRESS
+      Play the one hosting at ADDRESS
+  --dxp ADDRESS | --dxp-listen [ADDRESS] [--seat white|black] [--dxp-time MINUTES/MOVES]
+      Play a draughts engine over DXP
+  --engine-dxp ADDRESS | --engine-dxp-listen [ADDRESS]
+      Let the built-in engine play a DXP engine, with no board
+  --broadcast [ADDRESS]
+      Let others watch, on 127.0.0.1:7879 unless ADDRESS says otherwise
+  --watch [ADDRESS]
+      Watch a broadcast game",
+                variant = one_of(Variant::ALL.map(Variant::name)),
+                default_variant = defaults.variant.name(),
+                color = match defaults.color {
+                    Color::White => "white",
+                    Color::Black => "black",
+                },
+                ai = one_of(Opponent::ALL.map(Opponent::name)),
+                default_ai = defaults.opponent.name(),
+                difficulty = one_of(Difficulty::ALL.map(Difficulty::name)),
+                default_difficulty = defaults.difficulty.name(),
+            )
+        }
+        "analyze" => "Usage: checkers-rs analyze FILE
+
+Replays the first game in the PDN file FILE, scores every move with the
+built-in engine and prints the game back as PDN, with a comment on each
+move that lost ground and the move that was better."
+            .to_string(),
+        "perft" => format!(
+            "Usage: checkers-rs perft [DEPTH] [--fen FEN]
+
+Counts the positions reached DEPTH turns from the start, or from FEN,
+after each first move and in total. DEPTH is {DEFAULT_PERFT_DEPTH} unless given."
+        ),
+        "selfplay" => "Usage: checkers-rs selfplay --player SPEC --player SPEC [--games N]
+                            [--pdn FILE] [--openings FILE] [--sprt ELO0,ELO1]
+
+Plays the built-in engine against itself with no board and reports the
+first player's score, the Elo difference and an SPRT verdict.
+
+Options:
+  --player SPEC      depth:N (a fixed search depth) or time:MS (per move)
+  --games N          How many games to play
+  --pdn FILE         Where the games are written [selfplay.pdn]
+  --openings FILE    Openings to start from, one per line
+  --sprt ELO0,ELO1   The Elo bounds tested [0,10]"
+            .to_string(),
+        "engine" => "Usage: checkers-rs engine
+
+Reads commands from stdin, one per line, and answers on stdout:
+  position startpos|fen FEN [moves MOVE...]
+  go depth N | go movetime MS
+  legalmoves, perft N, eval, fen, quit"
+            .to_string(),
+        #[cfg(feature = "server")]
+        "serve" => format!(
+            "Usage: checkers-rs serve [ADDRESS]
+
+Serves the rules and the engine as JSON over HTTP on {}:{}, unless
+ADDRESS says otherwise.",
+            crate::server::DEFAULT_SERVER_ADDRESS,
+            crate::server::DEFAULT_SERVER_PORT
+        ),
+        "debug-bundle" => "Usage: checkers-rs debug-bundle [FILE]
+
+Writes the version, platform, settings, custom files and the end of the
+log to FILE, or to checkers-debug-TIME.txt, to attach to a bug report.
+The API key is left out."
+            .to_string(),
+        "version" => "Usage: checkers-rs version".to_string(),
+        other => return Err(format!("Unknown command {other}")),
+    };
+    Ok(text)
+}
@@ -0,0 +1,557 @@
+//! Settings kept between runs, read from `config.txt` in the config directory
+//! (e.g. ~/.config/checkers-rs/config.txt):
+//!
+//! ```text
+//! color: black
+//! ai: simple
+//! difficulty: hard
+//! time: 5+3
+//! model: gemini-2.0-flash
+//! theme: light
+//! animation: fast
+//! autosave: on
+//! ```
+//!
+//! Options given on the command line take precedence over the file, and so
+//! do `TIME_CONTROL`, `GEMINI_MODEL` and `ANIMATION_SPEED`. The settings
+//! screen writes changes back into the file, leaving everything else as it was.
+
+use crate::core::clock::TimeControl;
+use crate::core::fen::parse_fen;
+use crate::core::game::CheckersGame;
+use crate::core::piece::Color;
+use crate::interface::animation::AnimationSpeed;
+use crate::interface::{keymap, theme};
+use crate::utils::paths;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use thiserror::Error;
+
+pub const CONFIG_FILE: &str = "config.txt";
+
+/// Settings the `play` command takes as options too
+pub const PLAY_SETTINGS: [&str; 5] = ["variant", "color", "ai", "difficulty", "time"];
+
+/// Every setting `config.txt` understands, in the order the settings screen
+/// lists them
+pub const SETTINGS: [&str; 13] = [
+    "variant",
+    "color",
+    "ai",
+    "difficulty",
+    "time",
+    "model",
+    "temperature",
+    "move_temperature",
+    "review_temperature",
+    "theme",
+    "keys",
+    "animation",
+    "autosave",
+];
+
+/// Highest temperature the model takes
+pub const MAX_TEMPERATURE: f32 = 2.0;
+
+/// The rules played. Only American checkers on the 8x8 board is built in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    American,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 1] = [Variant::American];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::American => "american",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "american" | "english" | "checkers" => Some(Variant::American),
+            _ => None,
+        }
+    }
+}
+
+/// Who plays against the keyboard
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Opponent {
+    /// The Gemini model when an API key is set, the built-in engine otherwise
+    #[default]
+    Gemini,
+    /// Always the built-in engine
+    Engine,
+    /// Nobody: two players take turns at one keyboard
+    Human,
+}
+
+impl Opponent {
+    pub const ALL: [Opponent; 3] = [Opponent::Gemini, Opponent::Engine, Opponent::Human];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opponent::Gemini => "gemini",
+            Opponent::Engine => "simple",
+            Opponent::Human => "none",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "gemini" | "llm" => Some(Opponent::Gemini),
+            "simple" | "engine" => Some(Opponent::Engine),
+            "none" | "human" | "hotseat" => Some(Opponent::Human),
+            _ => None,
+        }
+    }
+}
+
+/// How hard the built-in engine tries
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" | "normal" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// Plies the engine looks ahead when there is no clock to budget from
+    pub fn depth(self) -> u32 {
+        match self {
+            Difficulty::Easy => 2,
+            Difficulty::Medium => 4,
+            Difficulty::Hard => 8,
+        }
+    }
+
+    /// How far the engine may look in timed games, where the clock decides
+    /// otherwise; an easy engine stays easy
+    pub fn timed_depth(self) -> Option<u32> {
+        (self == Difficulty::Easy).then_some(self.depth())
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ConfigError {
+    #[error("{CONFIG_FILE}: unknown setting {0} (expected one of {settings})", settings = SETTINGS.join(", "))]
+    UnknownSetting(String),
+    #[error("{CONFIG_FILE}: {0}")]
+    BadValue(String),
+    #[error("{CONFIG_FILE}: {0}")]
+    Unreadable(String),
+}
+
+/// How a new game is set up
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayOptions {
+    pub variant: Variant,
+    /// The side played from this keyboard against the computer
+    pub color: Color,
+    pub opponent: Opponent,
+    pub difficulty: Difficulty,
+    /// Untimed unless set; `TIME_CONTROL` overrides the file
+    pub time: Option<TimeControl>,
+    /// The position to start from instead of the standard setup, as FEN
+    pub fen: Option<String>,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        Self {
+            variant: Variant::default(),
+            color: Color::White,
+            opponent: Opponent::default(),
+            difficulty: Difficulty::default(),
+            time: None,
+            fen: None,
+        }
+    }
+}
+
+impl PlayOptions {
+    /// Changes one setting by name, as the config file and the command line
+    /// spell them. `fen` is only taken from the command line.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match name {
+            "variant" => {
+                self.variant = Variant::parse(value).ok_or_else(|| {
+                    format!(
+                        "unknown variant {value} (expected {})",
+                        names(&Variant::ALL.map(Variant::name))
+                    )
+                })?;
+            }
+            "color" => {
+                self.color = match value.to_lowercase().as_str() {
+                    "white" => Color::White,
+                    "black" => Color::Black,
+                    _ => return Err(format!("unknown color {value} (expected white or black)")),
+                };
+            }
+            "ai" => {
+                self.opponent = Opponent::parse(value).ok_or_else(|| {
+                    format!(
+                        "unknown ai {value} (expected {})",
+                        names(&Opponent::ALL.map(Opponent::name))
+                    )
+                })?;
+            }
+            "difficulty" => {
+                self.difficulty = Difficulty::parse(value).ok_or_else(|| {
+                    format!(
+                        "unknown difficulty {value} (expected {})",
+                        names(&Difficulty::ALL.map(Difficulty::name))
+                    )
+                })?;
+            }
+            "time" => {
+                self.time = match value {
+                    "off" | "none" | "untimed" => None,
+                    _ => Some(TimeControl::parse(value).ok_or_else(|| {
+                        format!("can't read time control {value} (e.g. 5, 3+2 or 10d5)")
+                    })?),
+                };
+            }
+            "fen" => {
+                parse_fen(value).map_err(|e| e.to_string())?;
+                self.fen = Some(value.to_string());
+            }
+            _ => return Err(format!("unknown option {name}")),
+        }
+        Ok(())
+    }
+
+    /// The position play begins from
+    pub fn start(&self) -> CheckersGame {
+        self.fen
+            .as_deref()
+            .and_then(|fen| parse_fen(fen).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Everything `config.txt` holds
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub play: PlayOptions,
+    /// The Gemini model asked; `GEMINI_MODEL` wins when set
+    pub model: Option<String>,
+    /// For hints, the coach, remarks and the welcome screen
+    pub temperature: f32,
+    /// For the model's moves when it makes no remarks
+    pub move_temperature: f32,
+    /// For the review of a finished game
+    pub review_temperature: f32,
+    /// Color preset, in place of the one `theme.txt` or the terminal picks
+    pub theme: Option<String>,
+    /// Key binding preset, in place of the one `keys.txt` picks
+    pub keys: Option<String>,
+    pub animation: AnimationSpeed,
+    /// Whether an unfinished game is kept and picked up on the next start
+    pub autosave: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            play: PlayOptions::default(),
+            model: None,
+            temperature: 0.7,
+            move_temperature: 0.1,
+            review_temperature: 0.3,
+            theme: None,
+            keys: None,
+            animation: AnimationSpeed::default(),
+            autosave: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Changes one setting by name, as `config.txt` spells it
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match name {
+            "model" => {
+                self.model = (!value.is_empty() && value != "unset").then(|| value.to_string());
+            }
+            "temperature" | "move_temperature" | "review_temperature" => {
+                let temperature = value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|t| (0.0..=MAX_TEMPERATURE).contains(t))
+                    .ok_or_else(|| {
+                        format!("{name} takes a number from 0 to {MAX_TEMPERATURE}, not {value}")
+                    })?;
+                *match name {
+                    "temperature" => &mut self.temperature,
+                    "move_temperature" => &mut self.move_temperature,
+                    _ => &mut self.review_temperature,
+                } = temperature;
+            }
+            "theme" => {
+                self.theme = preset_choice(value, &theme::PRESETS, "auto").ok_or_else(|| {
+                    format!(
+                        "unknown theme {value} (expected auto, {})",
+                        names(&theme::PRESETS)
+                    )
+                })?;
+            }
+            "keys" => {
+                self.keys = preset_choice(value, &keymap::PRESETS, "default").ok_or_else(|| {
+                    format!(
+                        "unknown keys {value} (expected default, {})",
+                        names(&keymap::PRESETS)
+                    )
+                })?;
+            }
+            "animation" => {
+                self.animation = AnimationSpeed::parse(value).ok_or_else(|| {
+                    format!("unknown animation {value} (expected off, slow, normal, fast or milliseconds per hop)")
+                })?;
+            }
+            "autosave" => {
+                self.autosave = match value.to_lowercase().as_str() {
+                    "on" | "yes" | "true" => true,
+                    "off" | "no" | "false" => false,
+                    _ => return Err(format!("autosave takes on or off, not {value}")),
+                };
+            }
+            _ => self.play.set(name, value)?,
+        }
+        Ok(())
+    }
+
+    /// A setting's value as it is written to `config.txt`
+    pub fn value(&self, name: &str) -> String {
+        match name {
+            "variant" => self.play.variant.name().to_string(),
+            "color" => match self.play.color {
+                Color::White => "white".to_string(),
+                Color::Black => "black".to_string(),
+            },
+            "ai" => self.play.opponent.name().to_string(),
+            "difficulty" => self.play.difficulty.name().to_string(),
+            "time" => self
+                .play
+                .time
+                .map_or_else(|| "off".to_string(), |time| time.to_setting()),
+            "model" => self.model.clone().unwrap_or_else(|| "unset".to_string()),
+            "temperature" => self.temperature.to_string(),
+            "move_temperature" => self.move_temperature.to_string(),
+            "review_temperature" => self.review_temperature.to_string(),
+            "theme" => self.theme.clone().unwrap_or_else(|| "auto".to_string()),
+            "keys" => self.keys.clone().unwrap_or_else(|| "default".to_string()),
+            "animation" => self.animation.name(),
+            "autosave" => if self.autosave { "on" } else { "off" }.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Names of the settings that differ between the two
+    pub fn changes(&self, other: &Settings) -> Vec<&'static str> {
+        SETTINGS
+            .into_iter()
+            .filter(|name| self.value(name) != other.value(name))
+            .collect()
+    }
+
+    /// The environment variable that takes the place of a setting, if set
+    pub fn overridden_by(name: &str) -> Option<&'static str> {
+        let variable = match name {
+            "time" => "TIME_CONTROL",
+            "model" => "GEMINI_MODEL",
+            "animation" => "ANIMATION_SPEED",
+            _ => return None,
+        };
+        std::env::var(variable).is_ok().then_some(variable)
+    }
+
+    /// These settings with the environment's values in their place
+    pub fn with_env(mut self) -> Self {
+        if let Some(time) = TimeControl::from_env() {
+            self.play.time = Some(time);
+        }
+        if let Ok(model) = std::env::var("GEMINI_MODEL") {
+            self.model = Some(model);
+        }
+        if std::env::var("ANIMATION_SPEED").is_ok() {
+            self.animation = AnimationSpeed::from_env();
+        }
+        self
+    }
+
+    /// Reads a settings file: `setting: value` lines, `#` comments, over
+    /// the defaults. Anything invalid is skipped and reported.
+    pub fn parse(text: &str) -> (Self, Vec<ConfigError>) {
+        let mut settings = Self::default();
+        let mut warnings = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                warnings.push(ConfigError::UnknownSetting(line.to_string()));
+                continue;
+            };
+            let name = name.trim().to_lowercase();
+            if !SETTINGS.contains(&name.as_str()) {
+                warnings.push(ConfigError::UnknownSetting(name));
+                continue;
+            }
+            if let Err(error) = settings.set(&name, value) {
+                warnings.push(ConfigError::BadValue(error));
+            }
+        }
+        (settings, warnings)
+    }
+
+    /// Reads `path` if it exists, otherwise uses the defaults
+    pub fn load(path: Option<&Path>) -> (Self, Vec<ConfigError>) {
+        let Some(path) = path.filter(|path| path.exists()) else {
+            return (Self::default(), Vec::new());
+        };
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) => (
+                Self::default(),
+                vec![ConfigError::Unreadable(e.to_string())],
+            ),
+        }
+    }
+
+    /// `text` with the lines of the `changed` settings given their new
+    /// values; settings the file didn't mention yet are added at the end.
+    /// Comments, other lines and their order stay as they were.
+    pub fn update_text(&self, text: &str, changed: &[&str]) -> String {
+        let mut written = Vec::new();
+        let mut lines = text
+            .lines()
+            .map(|line| {
+                let setting = line
+                    .split_once(':')
+                    .filter(|_| !line.trim_start().starts_with('#'))
+                    .map(|(name, _)| name.trim().to_lowercase())
+                    .and_then(|name| changed.iter().find(|changed| **changed == name));
+                match setting {
+                    Some(name) => {
+                        written.push(*name);
+                        format!("{name}: {}", self.value(name))
+                    }
+                    None => line.to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+        for name in changed.iter().filter(|name| !written.contains(name)) {
+            lines.push(format!("{name}: {}", self.value(name)));
+        }
+
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// Writes the `changed` settings into the file at `path`, creating it
+    /// and its directory when needed
+    pub fn save(&self, path: &Path, changed: &[&str]) -> std::io::Result<()> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.update_text(&text, changed))
+    }
+}
+
+/// `None` for the `automatic` choice, otherwise one of `presets`
+fn preset_choice(value: &str, presets: &[&str], automatic: &str) -> Option<Option<String>> {
+    let value = value.to_lowercase();
+    if value == automatic {
+        Some(None)
+    } else {
+        presets.contains(&value.as_str()).then_some(Some(value))
+    }
+}
+
+fn names(names: &[&str]) -> String {
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Where the settings file lives
+pub fn config_path() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+fn loaded() -> &'static (Settings, Vec<ConfigError>) {
+    static FILE: OnceLock<(Settings, Vec<ConfigError>)> = OnceLock::new();
+    FILE.get_or_init(|| Settings::load(config_path().as_deref()))
+}
+
+fn active() -> &'static RwLock<Settings> {
+    static ACTIVE: OnceLock<RwLock<Settings>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(loaded().0.clone().with_env()))
+}
+
+/// The settings in effect: the file's, read once, with the environment's
+/// values in their place and any change made on the settings screen since
+pub fn active_settings() -> Settings {
+    active()
+        .read()
+        .map(|settings| settings.clone())
+        .unwrap_or_default()
+}
+
+/// Puts changed settings into effect for the rest of the run
+pub fn set_active_settings(settings: Settings) {
+    if let Ok(mut active) = active().write() {
+        *active = settings;
+    }
+}
+
+/// Game settings from the config directory
+pub fn configured_options() -> PlayOptions {
+    active_settings().play
+}
+
+/// The Gemini model to ask, if one is configured
+pub fn gemini_model() -> Option<String> {
+    active_settings().model
+}
+
+/// Problems found in the user's settings file
+pub fn config_warnings() -> &'static [ConfigError] {
+    &loaded().1
+}
@@ -0,0 +1,228 @@
+use std::env;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::core::piece::Color;
+
+/// Moves a side is assumed to still have to play when it splits up its clock
+const MOVES_TO_GO: u32 = 20;
+
+/// What a side gets back for each move on top of its main time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bonus {
+    /// Sudden death: the main time is all there is
+    None,
+    /// Fischer: added to the clock after every move
+    Increment(Duration),
+    /// Bronstein: this much of every move is not charged
+    Delay(Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeControl {
+    /// Main time for each side
+    pub base: Duration,
+    pub bonus: Bonus,
+}
+
+impl TimeControl {
+    pub fn new(base: Duration, bonus: Bonus) -> Self {
+        Self { base, bonus }
+    }
+
+    /// Accepts minutes of main time, optionally followed by `+` and seconds
+    /// of increment or `d` and seconds of delay: `5`, `3+2` or `10d5`.
+    /// Minutes may be fractional, `0.5+1` is thirty seconds plus one.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        let (base, bonus) = if let Some((base, increment)) = value.split_once('+') {
+            (base, Bonus::Increment(seconds(increment)?))
+        } else if let Some((base, delay)) = value.split_once('d') {
+            (base, Bonus::Delay(seconds(delay)?))
+        } else {
+            (value.as_str(), Bonus::None)
+        };
+
+        let minutes: f64 = base.trim().parse().ok()?;
+        if !minutes.is_finite() || minutes <= 0.0 {
+            return None;
+        }
+        let base = Duration::try_from_secs_f64(minutes * 60.0).ok()?;
+        Some(Self::new(base, bonus))
+    }
+
+    /// The form `parse` reads back, as written to `config.txt`
+    pub fn to_setting(&self) -> String {
+        let minutes = self.base.as_secs_f64() / 60.0;
+        match self.bonus {
+            Bonus::None => format!("{minutes}"),
+            Bonus::Increment(increment) => format!("{minutes}+{}", increment.as_secs_f64()),
+            Bonus::Delay(delay) => format!("{minutes}d{}", delay.as_secs_f64()),
+        }
+    }
+
+    /// Reads `TIME_CONTROL`; games are untimed when it is missing, `off` or
+    /// malformed
+    pub fn from_env() -> Option<Self> {
+        env::var("TIME_CONTROL")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+    }
+}
+
+fn seconds(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.trim().parse().ok()?;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = self.base.as_secs_f64() / 60.0;
+        write!(f, "{minutes}")?;
+        match self.bonus {
+            Bonus::None => write!(f, " min"),
+            Bonus::Increment(increment) => write!(f, "+{}", increment.as_secs_f64()),
+            Bonus::Delay(delay) => write!(f, " d{}", delay.as_secs_f64()),
+        }
+    }
+}
+
+/// A chess clock for both sides. Every method takes the current instant so
+/// the caller decides what time it is.
+#[derive(Clone, Debug)]
+pub struct Clock {
+    pub control: TimeControl,
+    white: Duration,
+    black: Duration,
+    /// The side whose time is running and when its turn began
+    running: Option<(Color, Instant)>,
+}
+
+impl Clock {
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            white: control.base,
+            black: control.base,
+            running: None,
+        }
+    }
+
+    /// A clock showing `white` and `black` left, with `running`'s time
+    /// counting from the instant given
+    pub fn showing(
+        control: TimeControl,
+        white: Duration,
+        black: Duration,
+        running: Option<(Color, Instant)>,
+    ) -> Self {
+        Self {
+            control,
+            white,
+            black,
+            running,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// The side whose time is running
+    pub fn running_side(&self) -> Option<Color> {
+        self.running.map(|(color, _)| color)
+    }
+
+    /// When the running side's turn began
+    pub fn turn_started(&self) -> Option<Instant> {
+        self.running.map(|(_, since)| since)
+    }
+
+    /// Starts `color`'s turn at `now`. Time since the previous start is not
+    /// charged to anyone, which is also how a held clock is resumed.
+    pub fn start(&mut self, color: Color, now: Instant) {
+        self.running = Some((color, now));
+    }
+
+    /// Time `color` has left at `now`
+    pub fn left(&self, color: Color, now: Instant) -> Duration {
+        let stored = match color {
+            Color::White => self.white,
+            Color::Black => self.black,
+        };
+        match self.running {
+            Some((running, since)) if running == color => {
+                let mut spent = now.saturating_duration_since(since);
+                if let Bonus::Delay(delay) = self.control.bonus {
+                    spent = spent.saturating_sub(delay);
+                }
+                stored.saturating_sub(spent)
+            }
+            _ => stored,
+        }
+    }
+
+    /// Ends the running side's turn: charges its time, adds its increment
+    /// and starts the other side
+    pub fn press(&mut self, now: Instant) {
+        let Some((color, _)) = self.running else {
+            return;
+        };
+        let mut left = self.left(color, now);
+        if let Bonus::Increment(increment) = self.control.bonus {
+            if !left.is_zero() {
+                left = left.saturating_add(increment);
+            }
+        }
+        self.set(color, left);
+        self.running = Some((color.opposite(), now));
+    }
+
+    /// Charges the running side and stops both clocks
+    pub fn stop(&mut self, now: Instant) {
+        if let Some((color, _)) = self.running {
+            let left = self.left(color, now);
+            self.set(color, left);
+        }
+        self.running = None;
+    }
+
+    /// The side that has run out of time, if any
+    pub fn flagged(&self, now: Instant) -> Option<Color> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find(|&color| self.left(color, now).is_zero())
+    }
+
+    /// How long `color` should think about its next move: an even share of
+    /// its time over the moves still to come plus most of its bonus, never
+    /// more than half of what is left
+    pub fn budget(&self, color: Color, now: Instant) -> Duration {
+        let left = self.left(color, now);
+        let bonus = match self.control.bonus {
+            Bonus::None => Duration::ZERO,
+            Bonus::Increment(bonus) | Bonus::Delay(bonus) => bonus,
+        };
+        (left / MOVES_TO_GO)
+            .saturating_add(bonus.saturating_mul(3) / 4)
+            .min(left / 2 + bonus / 2)
+    }
+
+    fn set(&mut self, color: Color, left: Duration) {
+        match color {
+            Color::White => self.white = left,
+            Color::Black => self.black = left,
+        }
+    }
+}
+
+/// Clock face text: `m:ss`, or seconds and tenths in the last ten seconds
+pub fn format_time(left: Duration) -> String {
+    if left < Duration::from_secs(10) {
+        let tenths = left.as_millis() / 100;
+        format!("{}.{}", tenths / 10, tenths % 10)
+    } else {
+        let seconds = left.as_secs();
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
@@ -0,0 +1,124 @@
+//! Positions in PDN's FEN form, e.g. `W:W21,22,K30:B1-12`: the side to move,
+//! then each side's squares by number, with `K` marking kings and ranges
+//! allowed for runs of men.
+
+use thiserror::Error;
+
+use crate::core::board::Board;
+use crate::core::game::CheckersGame;
+use crate::core::notation::{number_to_square, square_number};
+use crate::core::piece::{Color, Piece};
+
+/// FEN of the initial position
+pub const START_FEN: &str = "W:W21-32:B1-12";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FenError {
+    #[error("FEN needs the side to move and both sides' pieces, like {START_FEN}")]
+    Malformed,
+    #[error("\"{0}\" is not a side; use W or B")]
+    InvalidColor(String),
+    #[error("\"{0}\" is not a square from 1 to 32")]
+    InvalidSquare(String),
+    #[error("Square {0} is given twice")]
+    DuplicateSquare(usize),
+}
+
+fn color_from(text: &str) -> Result<Color, FenError> {
+    match text.trim() {
+        "W" | "w" => Ok(Color::White),
+        "B" | "b" => Ok(Color::Black),
+        other => Err(FenError::InvalidColor(other.to_string())),
+    }
+}
+
+/// Reads a FEN into a game with no history
+pub fn parse_fen(text: &str) -> Result<CheckersGame, FenError> {
+    let text = text.trim().trim_end_matches('.');
+    let mut fields = text.split(':');
+    let to_move = color_from(fields.next().ok_or(FenError::Malformed)?)?;
+
+    let mut board = Board::new(8);
+    let mut sides = 0;
+    for field in fields {
+        let field = field.trim();
+        let Some(side) = field.get(..1) else {
+            return Err(FenError::Malformed);
+        };
+        let color = color_from(side)?;
+        sides += 1;
+
+        for token in field[1..]
+            .split(',')
+            .filter(|token| !token.trim().is_empty())
+        {
+            let token = token.trim();
+            let (is_king, squares) = match token.strip_prefix(['K', 'k']) {
+                Some(rest) => (true, rest),
+                None => (false, token),
+            };
+            let invalid = || FenError::InvalidSquare(token.to_string());
+            let number = |text: &str| text.trim().parse::<usize>().map_err(|_| invalid());
+            let (first, last) = match squares.split_once('-') {
+                Some((first, last)) => (number(first)?, number(last)?),
+                None => (number(squares)?, number(squares)?),
+            };
+
+            for square in first..=last {
+                let (row, col) = number_to_square(square, board.size).ok_or_else(invalid)?;
+                if board.get_piece(row, col).is_some() {
+                    return Err(FenError::DuplicateSquare(square));
+                }
+                let mut piece = Piece::new(color);
+                if is_king {
+                    piece.promote_to_king();
+                }
+                board.set_piece(row, col, Some(piece));
+            }
+        }
+    }
+    if sides != 2 {
+        return Err(FenError::Malformed);
+    }
+
+    let mut game = CheckersGame::new();
+    game.board = board;
+    game.current_player = to_move;
+    Ok(game)
+}
+
+/// Writes the position of `game` as a FEN, squares in ascending order
+pub fn to_fen(game: &CheckersGame) -> String {
+    let size = game.board.size;
+    let side = |color: Color| {
+        let mut squares: Vec<(usize, bool)> = Vec::new();
+        for row in 0..size {
+            for col in 0..size {
+                if let Some(piece) = game.board.get_piece(row, col) {
+                    if piece.color == color {
+                        if let Some(number) = square_number(row, col, size) {
+                            squares.push((number, piece.is_king));
+                        }
+                    }
+                }
+            }
+        }
+        squares.sort();
+        squares
+            .iter()
+            .map(|&(number, king)| {
+                if king {
+                    format!("K{number}")
+                } else {
+                    number.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let to_move = match game.current_player {
+        Color::White => 'W',
+        Color::Black => 'B',
+    };
+    format!("{to_move}:W{}:B{}", side(Color::White), side(Color::Black))
+}
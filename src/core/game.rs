@@ -2,7 +2,7 @@ use crate::core::board::Board;
 use crate::core::game_logic::{self, can_piece_capture};
 use crate::core::move_history::MoveHistory;
 use crate::core::piece::Color;
-use crate::core::GameMove;
+use crate::core::{GameMove, Turn};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -143,6 +143,86 @@ impl CheckersGame {
         Ok((new_game, continue_capture))
     }
 
+    /// Lists every complete turn available to the current player, with
+    /// multi-jumps expanded into their full landing paths.
+    pub fn legal_turns(&self) -> Vec<Turn> {
+        let mut turns = Vec::new();
+
+        for row in 0..self.board.size {
+            for col in 0..self.board.size {
+                match self.board.get_piece(row, col) {
+                    Some(piece) if piece.color == self.current_player => {
+                        self.collect_turns_from(vec![(row, col)], &mut turns);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        turns
+    }
+
+    fn collect_turns_from(&self, path: Vec<(usize, usize)>, turns: &mut Vec<Turn>) {
+        let (row, col) = path[path.len() - 1];
+        let Some(piece) = self.board.get_piece(row, col) else {
+            return;
+        };
+        // Only jumps may follow the first step of a turn, or start one when a
+        // capture is forced
+        let distances: &[i32] = if path.len() == 1 && !self.has_captures_available() {
+            &[1, 2]
+        } else {
+            &[2]
+        };
+
+        for &distance in distances {
+            for (row_dir, col_dir) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+                let to_row = row as i32 + row_dir * distance;
+                let to_col = col as i32 + col_dir * distance;
+                if to_row < 0 || to_col < 0 {
+                    continue;
+                }
+                let (to_row, to_col) = (to_row as usize, to_col as usize);
+                if !game_logic::is_valid_move(&self.board, row, col, to_row, to_col, &piece) {
+                    continue;
+                }
+
+                if let Ok((next_game, continue_capture)) =
+                    self.make_move_coords(row, col, to_row, to_col)
+                {
+                    let mut next_path = path.clone();
+                    next_path.push((to_row, to_col));
+                    if continue_capture {
+                        next_game.collect_turns_from(next_path, turns);
+                    } else {
+                        turns.push(Turn::new(next_path));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Plays a complete turn step by step. Fails if any step is illegal or the
+    /// path stops before a multi-jump is finished.
+    pub fn apply_turn(&self, turn: &Turn) -> Result<Self, GameError> {
+        if turn.path.len() < 2 {
+            return Err(GameError::InvalidMove);
+        }
+        self.validate_piece_selection(turn.from().0, turn.from().1)?;
+
+        let mut game = self.clone();
+        for step in turn.path.windows(2) {
+            let (next_game, _) =
+                game.make_move_coords(step[0].0, step[0].1, step[1].0, step[1].1)?;
+            game = next_game;
+        }
+
+        if game.current_player == self.current_player {
+            return Err(GameError::InvalidMove);
+        }
+        Ok(game)
+    }
+
     pub fn check_winner(&self) -> Option<Color> {
         game_logic::check_winner(&self.board)
     }
@@ -1,8 +1,13 @@
 pub mod board;
+pub mod clock;
+pub mod fen;
 pub mod game;
 pub mod game_logic;
 pub mod move_history;
+pub mod notation;
+pub mod pdn;
 pub mod piece;
+pub mod replay;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Position {
@@ -42,3 +47,36 @@ impl GameMove {
         }
     }
 }
+
+/// A complete turn: the square the piece starts on followed by every square it
+/// lands on. Simple moves have two squares, multi-jumps have more.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn {
+    pub path: Vec<(usize, usize)>,
+}
+
+impl Turn {
+    pub fn new(path: Vec<(usize, usize)>) -> Self {
+        Self { path }
+    }
+
+    pub fn from(&self) -> (usize, usize) {
+        self.path[0]
+    }
+
+    pub fn to(&self) -> (usize, usize) {
+        self.path[self.path.len() - 1]
+    }
+
+    pub fn is_capture(&self) -> bool {
+        self.path.len() > 1 && (self.path[1].0 as i32 - self.path[0].0 as i32).abs() == 2
+    }
+
+    pub fn capture_count(&self) -> usize {
+        if self.is_capture() {
+            self.path.len() - 1
+        } else {
+            0
+        }
+    }
+}
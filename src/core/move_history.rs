@@ -1,4 +1,7 @@
+use crate::core::board::Board;
 use crate::core::piece::Color as PieceColor;
+use crate::core::Turn;
+use std::ops::Range;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Move {
@@ -36,6 +39,105 @@ impl MoveHistory {
         });
     }
 
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Groups the recorded steps into turns, joining the hops of a multi-jump
+    pub fn turns(&self) -> Vec<(PieceColor, Turn)> {
+        self.turn_ranges()
+            .into_iter()
+            .map(|range| {
+                let steps = &self.moves[range];
+                let mut path = vec![steps[0].from];
+                path.extend(steps.iter().map(|m| m.to));
+                (steps[0].player, Turn::new(path))
+            })
+            .collect()
+    }
+
+    /// Squares emptied by captures in each turn, in the order of `turns()`
+    pub fn turn_captures(&self) -> Vec<Vec<(usize, usize)>> {
+        self.turn_ranges()
+            .into_iter()
+            .map(|range| {
+                self.moves[range]
+                    .iter()
+                    .flat_map(|m| m.captured.iter().copied())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The most recent turn with the squares it captured on. A multi-jump
+    /// still in progress counts with the hops made so far.
+    pub fn last_turn(&self) -> Option<(Turn, Vec<(usize, usize)>)> {
+        let range = self.turn_ranges().pop()?;
+        let steps = &self.moves[range];
+        let mut path = vec![steps[0].from];
+        path.extend(steps.iter().map(|m| m.to));
+        let captured = steps
+            .iter()
+            .flat_map(|m| m.captured.iter().copied())
+            .collect();
+        Some((Turn::new(path), captured))
+    }
+
+    /// The board after each turn, starting with `start` itself. Steps are
+    /// replayed as recorded, captures and crowning included, without checking
+    /// them against the rules again.
+    pub fn positions(&self, start: &Board) -> Vec<Board> {
+        let mut board = start.clone();
+        let mut positions = vec![board.clone()];
+
+        for range in self.turn_ranges() {
+            for m in &self.moves[range] {
+                board.move_piece(m.from, m.to);
+                for &(row, col) in &m.captured {
+                    board.set_piece(row, col, None);
+                }
+                if m.became_king {
+                    if let Some(piece) = board.cells[m.to.0][m.to.1].as_mut() {
+                        piece.promote_to_king();
+                    }
+                }
+            }
+            positions.push(board.clone());
+        }
+
+        positions
+    }
+
+    /// Index ranges of the steps making up each turn
+    fn turn_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+
+        for (i, m) in self.moves.iter().enumerate() {
+            let continues_jump = i > 0 && {
+                let prev = &self.moves[i - 1];
+                prev.player == m.player
+                    && !prev.captured.is_empty()
+                    && !m.captured.is_empty()
+                    && prev.to == m.from
+            };
+            match ranges.last_mut() {
+                Some(range) if continues_jump => range.end = i + 1,
+                _ => ranges.push(i..i + 1),
+            }
+        }
+
+        ranges
+    }
+
+    /// Number of opposing pieces `player` has taken
+    pub fn captured_by(&self, player: PieceColor) -> usize {
+        self.moves
+            .iter()
+            .filter(|m| m.player == player)
+            .map(|m| m.captured.len())
+            .sum()
+    }
+
     pub fn to_notation(&self) -> String {
         self.moves
             .iter()
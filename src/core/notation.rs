@@ -0,0 +1,47 @@
+use crate::core::Turn;
+
+/// Names a square the way `MoveHistory::to_notation` does, e.g. "c3"
+pub fn square_name(row: usize, col: usize, board_size: usize) -> String {
+    format!("{}{}", (b'a' + col as u8) as char, board_size - row)
+}
+
+/// Standard draughts numbering of the playable squares: 1 is the leftmost
+/// dark square of the top row, counting left to right and top to bottom.
+pub fn square_number(row: usize, col: usize, board_size: usize) -> Option<usize> {
+    if row >= board_size || col >= board_size || (row + col) % 2 != 1 {
+        return None;
+    }
+    Some(row * (board_size / 2) + col / 2 + 1)
+}
+
+pub fn number_to_square(number: usize, board_size: usize) -> Option<(usize, usize)> {
+    let per_row = board_size / 2;
+    if number == 0 || number > per_row * board_size {
+        return None;
+    }
+    let index = number - 1;
+    let row = index / per_row;
+    let col = (index % per_row) * 2 + 1 - row % 2;
+    Some((row, col))
+}
+
+/// Formats a turn as "c3-d4" or, for jumps, "b6xd4xf2"
+pub fn turn_to_algebraic(turn: &Turn, board_size: usize) -> String {
+    let separator = if turn.is_capture() { "x" } else { "-" };
+    turn.path
+        .iter()
+        .map(|&(row, col)| square_name(row, col, board_size))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Formats a turn in numeric PDN notation such as "22-18" or "22x15x24"
+pub fn turn_to_numeric(turn: &Turn, board_size: usize) -> String {
+    let separator = if turn.is_capture() { "x" } else { "-" };
+    turn.path
+        .iter()
+        .filter_map(|&(row, col)| square_number(row, col, board_size))
+        .map(|number| number.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
@@ -0,0 +1,244 @@
+use thiserror::Error;
+
+use crate::core::fen::parse_fen;
+use crate::core::piece::Color;
+
+/// Longest line written to a PDN file, as recommended by the standard
+const MAX_LINE_WIDTH: usize = 80;
+
+/// Results the move text may end with; the draughts forms count 2 for a win
+const RESULTS: [(&str, &str); 7] = [
+    ("1-0", "1-0"),
+    ("0-1", "0-1"),
+    ("1/2-1/2", "1/2-1/2"),
+    ("*", "*"),
+    ("2-0", "1-0"),
+    ("0-2", "0-1"),
+    ("1-1", "1/2-1/2"),
+];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PdnError {
+    #[error("Tag {0} is not of the form [Name \"Value\"]")]
+    MalformedTag(String),
+    #[error("A {0} is never closed")]
+    Unclosed(&'static str),
+    #[error("The FEN tag {0} can't be read")]
+    InvalidFen(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdnMove {
+    pub player: Color,
+    pub notation: String,
+    pub comment: Option<String>,
+}
+
+/// A game in Portable Draughts Notation: tag pairs followed by the move text
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdnGame {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<PdnMove>,
+    pub result: String,
+}
+
+impl PdnGame {
+    pub fn new(result: &str) -> Self {
+        Self {
+            tags: Vec::new(),
+            moves: Vec::new(),
+            result: result.to_string(),
+        }
+    }
+
+    pub fn with_tag(mut self, name: &str, value: &str) -> Self {
+        self.tags.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_move(mut self, player: Color, notation: String, comment: Option<String>) -> Self {
+        self.moves.push(PdnMove {
+            player,
+            notation,
+            comment,
+        });
+        self
+    }
+
+    /// The value of a tag, by case-insensitive name
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn to_pdn(&self) -> String {
+        let mut output = String::new();
+
+        for (name, value) in &self.tags {
+            output.push_str(&format!("[{} \"{}\"]\n", name, value.replace('"', "'")));
+        }
+        output.push_str(&format!("[Result \"{}\"]\n\n", self.result));
+
+        let mut tokens = Vec::new();
+        let mut move_number = 0;
+        for (i, pdn_move) in self.moves.iter().enumerate() {
+            // A new move number starts with White, or with whoever opens the list
+            if i == 0 || pdn_move.player == Color::White {
+                move_number += 1;
+                if pdn_move.player == Color::White {
+                    tokens.push(format!("{move_number}."));
+                } else {
+                    tokens.push(format!("{move_number}..."));
+                }
+            }
+            tokens.push(pdn_move.notation.clone());
+            if let Some(comment) = &pdn_move.comment {
+                // Braces cannot be nested inside a PDN comment
+                tokens.push(format!("{{{}}}", comment.replace(['{', '}'], "")));
+            }
+        }
+        tokens.push(self.result.clone());
+
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_WIDTH {
+                output.push_str(&line);
+                output.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        output.push_str(&line);
+        output.push('\n');
+
+        output
+    }
+}
+
+/// Reads the first game of a PDN file. Moves are kept as written, less
+/// move numbers and `!`/`?` suffixes, and whether they are legal is left to
+/// the caller; each is taken to be played by the side whose turn it is,
+/// starting from the side the FEN tag names, or White.
+pub fn parse_pdn(text: &str) -> Result<PdnGame, PdnError> {
+    let mut game = PdnGame::new("*");
+    let mut result = None;
+    let mut player = Color::White;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                let tag = read_until(&mut chars, ']').ok_or(PdnError::Unclosed("tag"))?;
+                let (name, value) = tag
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .and_then(|(name, value)| {
+                        let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+                        Some((name, value))
+                    })
+                    .ok_or_else(|| PdnError::MalformedTag(format!("[{tag}]")))?;
+                match name {
+                    "Result" => {
+                        let value = RESULTS
+                            .iter()
+                            .find(|(text, _)| *text == value)
+                            .map_or(value, |(_, result)| result);
+                        result = result.or(Some(value.to_string()));
+                    }
+                    "FEN" => {
+                        let start = parse_fen(value)
+                            .map_err(|_| PdnError::InvalidFen(value.to_string()))?;
+                        player = start.current_player;
+                        game = game.with_tag(name, value);
+                    }
+                    _ => game = game.with_tag(name, value),
+                }
+            }
+            '{' => {
+                let comment = read_until(&mut chars, '}').ok_or(PdnError::Unclosed("comment"))?;
+                let comment = comment.split_whitespace().collect::<Vec<_>>().join(" ");
+                // A comment before the first move has nothing to belong to
+                if let Some(last) = game.moves.last_mut() {
+                    last.comment = Some(match last.comment.take() {
+                        Some(earlier) => format!("{earlier} {comment}"),
+                        None => comment,
+                    });
+                }
+            }
+            ';' => {
+                read_until(&mut chars, '\n');
+            }
+            '(' => {
+                // Variations are skipped, nested ones included
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next().ok_or(PdnError::Unclosed("variation"))? {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut token = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "[{;(".contains(next) {
+                        break;
+                    }
+                    token.push(next);
+                    chars.next();
+                }
+
+                if let Some((_, result_text)) = RESULTS.iter().find(|(text, _)| *text == token) {
+                    result = Some(result_text.to_string());
+                    break;
+                }
+                // "12." and "12..." number moves; some files run the move on
+                let notation = token
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .trim_start_matches('.');
+                let notation = if notation.len() < token.len() && token.contains('.') {
+                    notation
+                } else {
+                    token.as_str()
+                };
+                let notation = notation.trim_end_matches(['!', '?']);
+                if notation.is_empty() {
+                    continue;
+                }
+                game = game.with_move(player, notation.to_string(), None);
+                player = player.opposite();
+            }
+        }
+    }
+
+    game.result = result.unwrap_or_else(|| "*".to_string());
+    Ok(game)
+}
+
+/// Everything up to `end`, which is consumed; `None` if the text runs out
+fn read_until(chars: &mut impl Iterator<Item = char>, end: char) -> Option<String> {
+    let mut text = String::new();
+    for c in chars {
+        if c == end {
+            return Some(text);
+        }
+        text.push(c);
+    }
+    None
+}
+
+/// PDN result string for a finished game; `None` means a draw
+pub fn result_string(winner: Option<Color>) -> &'static str {
+    match winner {
+        Some(Color::White) => "1-0",
+        Some(Color::Black) => "0-1",
+        None => "1/2-1/2",
+    }
+}
@@ -0,0 +1,66 @@
+use crate::core::board::Board;
+use crate::core::game::CheckersGame;
+use crate::core::move_history::MoveHistory;
+use crate::core::piece::Color;
+use crate::core::Turn;
+
+/// Every position of a game, rebuilt from its move history so earlier
+/// positions can be browsed without touching the game itself
+#[derive(Clone, Debug)]
+pub struct Replay {
+    positions: Vec<Board>,
+    /// The side to move in the starting position
+    first_player: Color,
+    turns: Vec<(Color, Turn)>,
+    captures: Vec<Vec<(usize, usize)>>,
+}
+
+impl Replay {
+    /// Replays the history from the standard starting position
+    pub fn from_history(history: &MoveHistory, size: usize) -> Self {
+        let mut board = Board::new(size);
+        board.initialize();
+        Self::from_board(&board, Color::White, history)
+    }
+
+    /// Replays the history from a set-up position, such as one read from FEN
+    pub fn from_position(start: &CheckersGame, history: &MoveHistory) -> Self {
+        Self::from_board(&start.board, start.current_player, history)
+    }
+
+    fn from_board(start: &Board, first_player: Color, history: &MoveHistory) -> Self {
+        Self {
+            positions: history.positions(start),
+            first_player,
+            turns: history.turns(),
+            captures: history.turn_captures(),
+        }
+    }
+
+    /// Number of plies; positions run from 0 (start) to `last_ply()`
+    pub fn last_ply(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn board_at(&self, ply: usize) -> &Board {
+        &self.positions[ply.min(self.last_ply())]
+    }
+
+    /// The side to move in the position at `ply`
+    pub fn player_at(&self, ply: usize) -> Color {
+        self.turn_before(ply.min(self.last_ply()))
+            .map_or(self.first_player, |(player, _)| player.opposite())
+    }
+
+    /// The turn that led to the position at `ply`
+    pub fn turn_before(&self, ply: usize) -> Option<&(Color, Turn)> {
+        ply.checked_sub(1).and_then(|index| self.turns.get(index))
+    }
+
+    /// Squares emptied by the turn that led to the position at `ply`
+    pub fn captures_before(&self, ply: usize) -> &[(usize, usize)] {
+        ply.checked_sub(1)
+            .and_then(|index| self.captures.get(index))
+            .map_or(&[], Vec::as_slice)
+    }
+}
@@ -0,0 +1,197 @@
+use std::env;
+use std::time::Duration;
+
+use crate::core::board::Board;
+use crate::core::move_history::Move;
+use crate::core::piece::Piece;
+
+/// Time between two animation frames
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(30);
+
+/// Frames the promotion flourish lasts after the piece lands
+const FLOURISH_FRAMES: usize = 8;
+
+/// How long a piece takes to slide one hop
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnimationSpeed {
+    Off,
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+    /// Milliseconds per hop
+    Custom(u64),
+}
+
+impl AnimationSpeed {
+    /// Accepts `off`, `slow`, `normal`, `fast` or a number of milliseconds
+    /// per hop (`0` turns animation off)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Some(Self::Off),
+            "slow" => Some(Self::Slow),
+            "normal" => Some(Self::Normal),
+            "fast" => Some(Self::Fast),
+            other => match other.parse::<u64>().ok()? {
+                0 => Some(Self::Off),
+                ms => Some(Self::Custom(ms)),
+            },
+        }
+    }
+
+    /// Reads `ANIMATION_SPEED`, keeping the default when missing or malformed
+    pub fn from_env() -> Self {
+        env::var("ANIMATION_SPEED")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// How `parse` spells this speed
+    pub fn name(self) -> String {
+        match self {
+            Self::Off => "off".to_string(),
+            Self::Slow => "slow".to_string(),
+            Self::Normal => "normal".to_string(),
+            Self::Fast => "fast".to_string(),
+            Self::Custom(ms) => ms.to_string(),
+        }
+    }
+
+    pub fn hop_duration(self) -> Option<Duration> {
+        let ms = match self {
+            Self::Off => return None,
+            Self::Slow => 400,
+            Self::Normal => 220,
+            Self::Fast => 110,
+            Self::Custom(ms) => ms,
+        };
+        Some(Duration::from_millis(ms))
+    }
+
+    fn hop_frames(self) -> Option<usize> {
+        let hop = self.hop_duration()?;
+        Some(((hop.as_millis() / FRAME_INTERVAL.as_millis()) as usize).max(1))
+    }
+}
+
+/// One picture of a running animation
+#[derive(Clone, Debug)]
+pub struct AnimationFrame {
+    /// Pieces at rest: the position before the move without the moving piece
+    /// and without captured pieces that have faded out completely
+    pub board: Board,
+    /// The moving piece, crowned once the flourish starts
+    pub piece: Piece,
+    /// Fractional (row, col) of the moving piece
+    pub position: (f32, f32),
+    /// Captured pieces still on the board with their remaining opacity
+    pub fading: Vec<((usize, usize), f32)>,
+    /// Step of the promotion flourish, counted from the landing
+    pub flourish: Option<usize>,
+}
+
+/// A piece sliding along the path of one turn. Each captured piece starts to
+/// fade when the slider passes over it; a crowned piece ends with a flourish.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    before: Board,
+    piece: Piece,
+    path: Vec<(usize, usize)>,
+    /// Pieces taken on each hop
+    captures: Vec<Vec<(usize, usize)>>,
+    promoted: bool,
+    hop_frames: usize,
+}
+
+impl Animation {
+    /// Animates the recorded `steps` of one turn played on `before`. Returns
+    /// None when animation is off or the steps don't form a single path.
+    pub fn new(before: &Board, steps: &[Move], speed: AnimationSpeed) -> Option<Self> {
+        let hop_frames = speed.hop_frames()?;
+        let first = steps.first()?;
+        if steps.windows(2).any(|pair| pair[0].to != pair[1].from) {
+            return None;
+        }
+        let piece = before.get_piece(first.from.0, first.from.1)?;
+
+        let mut path = vec![first.from];
+        path.extend(steps.iter().map(|step| step.to));
+
+        Some(Self {
+            before: before.clone(),
+            piece,
+            path,
+            captures: steps.iter().map(|step| step.captured.clone()).collect(),
+            promoted: steps.iter().any(|step| step.became_king),
+            hop_frames,
+        })
+    }
+
+    fn hops(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    fn slide_frames(&self) -> usize {
+        self.hops() * self.hop_frames
+    }
+
+    /// Number of frames from the first step to the settled position
+    pub fn len(&self) -> usize {
+        let fade_tail = self.hop_frames.div_ceil(2);
+        let tail = if self.promoted {
+            FLOURISH_FRAMES.max(fade_tail)
+        } else {
+            fade_tail
+        };
+        self.slide_frames() + tail
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn frame(&self, index: usize) -> AnimationFrame {
+        let slide = self.slide_frames();
+        let progress = index.min(slide) as f32 / self.hop_frames as f32;
+        let hop = (progress as usize).min(self.hops() - 1);
+        let within = progress - hop as f32;
+        let (from, to) = (self.path[hop], self.path[hop + 1]);
+        let position = (
+            from.0 as f32 + (to.0 as f32 - from.0 as f32) * within,
+            from.1 as f32 + (to.1 as f32 - from.1 as f32) * within,
+        );
+
+        let mut board = self.before.clone();
+        board.cells[self.path[0].0][self.path[0].1] = None;
+
+        // A capture starts fading halfway through its hop
+        let mut fading = Vec::new();
+        for (hop, squares) in self.captures.iter().enumerate() {
+            let start = hop as f32 + 0.5;
+            let elapsed = index as f32 / self.hop_frames as f32 - start;
+            let opacity = (1.0 - elapsed * 2.0).clamp(0.0, 1.0);
+            for &(row, col) in squares {
+                if opacity <= 0.0 {
+                    board.cells[row][col] = None;
+                } else {
+                    fading.push(((row, col), opacity));
+                }
+            }
+        }
+
+        let flourish = (self.promoted && index >= slide).then(|| index - slide);
+        let mut piece = self.piece;
+        if flourish.is_some() {
+            piece.is_king = true;
+        }
+
+        AnimationFrame {
+            board,
+            piece,
+            position,
+            fading,
+            flourish,
+        }
+    }
+}
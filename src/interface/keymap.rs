@@ -0,0 +1,491 @@
+//! Translates key presses into game actions. The bindings start from one of
+//! the bundled presets and can be changed in `keys.txt` in the config
+//! directory (e.g. ~/.config/checkers-rs/keys.txt):
+//!
+//! ```text
+//! preset: vim
+//! hint: i
+//! quit: q, й
+//! ```
+//!
+//! Listing keys for an action replaces that action's keys; a key can only
+//! trigger one action, so it is taken away from whatever it did before. A
+//! `keys` preset chosen in config.txt takes the place of the preset line.
+
+use crate::config::active_settings;
+use crate::utils::paths;
+use crossterm::event::KeyCode;
+use std::path::Path;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+pub const KEYS_FILE: &str = "keys.txt";
+
+/// Names accepted by `KeyBindings::preset`
+pub const PRESETS: [&str; 4] = ["arrows", "vim", "wasd", "numpad"];
+
+/// Everything a key can do outside of text entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Quit,
+    Command,
+    Flip,
+    Hint,
+    Coach,
+    Replay,
+    Analyze,
+    Export,
+    ScrollBack,
+    ScrollForward,
+    First,
+    Last,
+    Settings,
+    Help,
+}
+
+impl Action {
+    /// In the order the help overlay lists them
+    pub const ALL: [Action; 19] = [
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Select,
+        Action::Quit,
+        Action::Command,
+        Action::Flip,
+        Action::Hint,
+        Action::Coach,
+        Action::Replay,
+        Action::Analyze,
+        Action::Export,
+        Action::ScrollBack,
+        Action::ScrollForward,
+        Action::First,
+        Action::Last,
+        Action::Settings,
+        Action::Help,
+    ];
+
+    /// Name used in `keys.txt`
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Select => "select",
+            Action::Quit => "quit",
+            Action::Command => "command",
+            Action::Flip => "flip",
+            Action::Hint => "hint",
+            Action::Coach => "coach",
+            Action::Replay => "replay",
+            Action::Analyze => "analyze",
+            Action::Export => "export",
+            Action::ScrollBack => "scroll_back",
+            Action::ScrollForward => "scroll_forward",
+            Action::First => "first",
+            Action::Last => "last",
+            Action::Settings => "settings",
+            Action::Help => "help",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Up => "Cursor up / previous move",
+            Action::Down => "Cursor down / next move",
+            Action::Left => "Cursor left / step back",
+            Action::Right => "Cursor right / step forward",
+            Action::Select => "Select piece or square",
+            Action::Quit => "Back / quit",
+            Action::Command => "Type a move like c3-d4",
+            Action::Flip => "Turn the board around",
+            Action::Hint => "Ask for a hint",
+            Action::Coach => "Open the coach",
+            Action::Replay => "Replay the game",
+            Action::Analyze => "Review a finished game",
+            Action::Export => "Export the review as PDN",
+            Action::ScrollBack => "Scroll the move list back",
+            Action::ScrollForward => "Scroll the move list forward",
+            Action::First => "First position",
+            Action::Last => "Latest position",
+            Action::Settings => "Open the settings",
+            Action::Help => "Show or hide this help",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// What the states are given to act on: an action whatever key it is bound
+/// to, typed text, or a mouse press on a square
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// A key bound to an action in the key map
+    Action(Action),
+    /// Typed text while entering text
+    Char(char),
+    Backspace,
+    /// Left button pressed over a board square
+    MouseDown((usize, usize)),
+    /// Left button released over a board square
+    MouseUp((usize, usize)),
+    /// No input, for states that carry on working by themselves
+    Tick,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KeyError {
+    #[error("{KEYS_FILE}: unknown preset {0} (expected one of {presets})", presets = PRESETS.join(", "))]
+    UnknownPreset(String),
+    #[error("{KEYS_FILE}: unknown action {0}")]
+    UnknownAction(String),
+    #[error("{KEYS_FILE}: {action} has an unknown key {key}")]
+    UnknownKey { action: String, key: String },
+    #[error("{KEYS_FILE}: {0}")]
+    Unreadable(String),
+}
+
+/// Which key triggers which action
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    /// Keys of each action, the one shown in the controls line first
+    bindings: Vec<(Action, Vec<KeyCode>)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::arrows()
+    }
+}
+
+impl KeyBindings {
+    /// Arrow keys and the letters named in the controls line
+    pub fn arrows() -> Self {
+        use KeyCode::*;
+        let bindings = vec![
+            (Action::Up, vec![Up]),
+            (Action::Down, vec![Down]),
+            (Action::Left, vec![Left]),
+            (Action::Right, vec![Right]),
+            (Action::Select, vec![Enter, Char(' ')]),
+            (Action::Quit, vec![Esc, Char('q')]),
+            (Action::Command, vec![Char(':'), Tab]),
+            (Action::Flip, vec![Char('f')]),
+            (Action::Hint, vec![Char('h')]),
+            (Action::Coach, vec![Char('c')]),
+            (Action::Replay, vec![Char('r')]),
+            (Action::Analyze, vec![Char('a')]),
+            (Action::Export, vec![Char('e')]),
+            (Action::ScrollBack, vec![PageUp]),
+            (Action::ScrollForward, vec![PageDown]),
+            (Action::First, vec![Home]),
+            (Action::Last, vec![End]),
+            (Action::Settings, vec![Char('o')]),
+            (Action::Help, vec![Char('?')]),
+        ];
+        Self { bindings }
+    }
+
+    /// hjkl moves, so the hint moves to `i`; `g`/`G` jump to the ends
+    pub fn vim() -> Self {
+        use KeyCode::*;
+        Self::arrows()
+            .with(Action::Up, &[Char('k'), Up])
+            .with(Action::Down, &[Char('j'), Down])
+            .with(Action::Left, &[Char('h'), Left])
+            .with(Action::Right, &[Char('l'), Right])
+            .with(Action::Hint, &[Char('i')])
+            .with(Action::First, &[Char('g'), Home])
+            .with(Action::Last, &[Char('G'), End])
+    }
+
+    /// WASD moves, so the game review opens with `v`
+    pub fn wasd() -> Self {
+        use KeyCode::*;
+        Self::arrows()
+            .with(Action::Up, &[Char('w'), Up])
+            .with(Action::Down, &[Char('s'), Down])
+            .with(Action::Left, &[Char('a'), Left])
+            .with(Action::Right, &[Char('d'), Right])
+            .with(Action::Analyze, &[Char('v')])
+    }
+
+    /// The number pad's 8/2/4/6 move and 5 selects
+    pub fn numpad() -> Self {
+        use KeyCode::*;
+        Self::arrows()
+            .with(Action::Up, &[Char('8'), Up])
+            .with(Action::Down, &[Char('2'), Down])
+            .with(Action::Left, &[Char('4'), Left])
+            .with(Action::Right, &[Char('6'), Right])
+            .with(Action::Select, &[Char('5'), Enter, Char(' ')])
+            .with(Action::ScrollBack, &[Char('9'), PageUp])
+            .with(Action::ScrollForward, &[Char('3'), PageDown])
+            .with(Action::First, &[Char('7'), Home])
+            .with(Action::Last, &[Char('1'), End])
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "arrows" => Some(Self::arrows()),
+            "vim" => Some(Self::vim()),
+            "wasd" => Some(Self::wasd()),
+            "numpad" => Some(Self::numpad()),
+            _ => None,
+        }
+    }
+
+    /// Gives `action` exactly `keys`, taking them away from other actions
+    pub fn with(mut self, action: Action, keys: &[KeyCode]) -> Self {
+        for (other, bound) in self.bindings.iter_mut() {
+            if *other == action {
+                *bound = keys.to_vec();
+            } else {
+                bound.retain(|key| !keys.contains(key));
+            }
+        }
+        self
+    }
+
+    /// The action bound to `code`. Letters fall back to their lowercase
+    /// binding, so Caps Lock doesn't get in the way.
+    pub fn action_for(&self, code: KeyCode) -> Option<Action> {
+        let find = |code: KeyCode| {
+            self.bindings
+                .iter()
+                .find(|(_, keys)| keys.contains(&code))
+                .map(|(action, _)| *action)
+        };
+        find(code).or_else(|| match code {
+            KeyCode::Char(c) if c.is_uppercase() => {
+                c.to_lowercase().next().and_then(|c| find(KeyCode::Char(c)))
+            }
+            _ => None,
+        })
+    }
+
+    pub fn keys_for(&self, action: Action) -> &[KeyCode] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys.as_slice())
+    }
+
+    /// Keys of `action` as shown in hints, e.g. "ESC/Q"
+    pub fn label(&self, action: Action) -> String {
+        let keys = self.keys_for(action);
+        if keys.is_empty() {
+            return "unbound".to_string();
+        }
+        keys.iter()
+            .map(|&key| key_label(key))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// The first key of `action`, for the controls line
+    pub fn primary_label(&self, action: Action) -> String {
+        self.keys_for(action)
+            .first()
+            .map_or_else(|| "-".to_string(), |&key| key_label(key))
+    }
+
+    /// The first key of `action` that types nothing, for hints shown while
+    /// text is being entered
+    pub fn entry_label(&self, action: Action) -> String {
+        self.entry_key(action)
+            .map_or_else(|| "-".to_string(), key_label)
+    }
+
+    /// The action of a key that types nothing while text is being entered.
+    /// Only sending, cancelling and completing mean anything there.
+    pub fn entry_action_for(&self, code: KeyCode) -> Option<Action> {
+        self.action_for(code)
+            .filter(|action| matches!(action, Action::Select | Action::Quit | Action::Command))
+    }
+
+    fn entry_key(&self, action: Action) -> Option<KeyCode> {
+        self.keys_for(action)
+            .iter()
+            .copied()
+            .find(|key| !matches!(key, KeyCode::Char(_)))
+    }
+
+    /// Every action with its keys, for the help overlay
+    pub fn describe(&self) -> Vec<(String, &'static str)> {
+        Action::ALL
+            .into_iter()
+            .map(|action| (self.label(action), action.description()))
+            .collect()
+    }
+
+    /// Reads a bindings file: `key: value` lines, `#` comments. A `preset`
+    /// line picks the base bindings, `action: key, key` lines rebind single
+    /// actions. Anything invalid is skipped and reported.
+    pub fn parse(text: &str) -> (Self, Vec<KeyError>) {
+        Self::parse_with_preset(text, None)
+    }
+
+    /// Like `parse`, with `preset` chosen in place of any preset line
+    pub fn parse_with_preset(text: &str, preset: Option<&str>) -> (Self, Vec<KeyError>) {
+        let chosen = preset.and_then(Self::preset);
+        let mut warnings = Vec::new();
+        let mut bindings = chosen.clone().unwrap_or_default();
+        let mut changes = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                warnings.push(KeyError::UnknownAction(line.to_string()));
+                continue;
+            };
+            let (name, value) = (name.trim().to_lowercase(), value.trim());
+            if name == "preset" {
+                match Self::preset(value) {
+                    Some(preset) if chosen.is_none() => bindings = preset,
+                    Some(_) => {}
+                    None => warnings.push(KeyError::UnknownPreset(value.to_string())),
+                }
+                continue;
+            }
+            let Some(action) = Action::from_name(&name) else {
+                warnings.push(KeyError::UnknownAction(name));
+                continue;
+            };
+
+            let mut keys = Vec::new();
+            for key in value
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+            {
+                match parse_key(key) {
+                    Some(code) => keys.push(code),
+                    None => warnings.push(KeyError::UnknownKey {
+                        action: name.clone(),
+                        key: key.to_string(),
+                    }),
+                }
+            }
+            if !keys.is_empty() {
+                changes.push((action, keys));
+            }
+        }
+
+        for (action, keys) in changes {
+            bindings = bindings.with(action, &keys);
+        }
+        (bindings, warnings)
+    }
+
+    /// Reads `path` if it exists, otherwise uses the arrow key bindings
+    pub fn load(path: Option<&Path>) -> (Self, Vec<KeyError>) {
+        Self::load_with_preset(path, None)
+    }
+
+    /// Like `load`, with `preset` chosen in place of any preset line
+    pub fn load_with_preset(path: Option<&Path>, preset: Option<&str>) -> (Self, Vec<KeyError>) {
+        let text = match path
+            .filter(|path| path.exists())
+            .map(std::fs::read_to_string)
+        {
+            Some(Ok(text)) => text,
+            Some(Err(e)) => {
+                let (bindings, _) = Self::parse_with_preset("", preset);
+                return (bindings, vec![KeyError::Unreadable(e.to_string())]);
+            }
+            None => String::new(),
+        };
+        Self::parse_with_preset(&text, preset)
+    }
+}
+
+/// A key name from `keys.txt`: a single character or one of the named keys
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let code = match name.to_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" | "return" => KeyCode::Enter,
+        "space" => KeyCode::Char(' '),
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        other => {
+            let number = other.strip_prefix('f')?.parse().ok()?;
+            KeyCode::F(number)
+        }
+    };
+    Some(code)
+}
+
+/// How a key is written in hints and the help overlay
+pub fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Esc => "ESC".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::F(number) => format!("F{number}"),
+        KeyCode::Char(c) if c.is_lowercase() => c.to_uppercase().to_string(),
+        KeyCode::Char(c) if c.is_uppercase() => format!("Shift+{c}"),
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+fn load_bindings(preset: Option<&str>) -> (KeyBindings, Vec<KeyError>) {
+    let path = paths::config_dir().map(|dir| dir.join(KEYS_FILE));
+    KeyBindings::load_with_preset(path.as_deref(), preset)
+}
+
+fn loaded() -> &'static (KeyBindings, Vec<KeyError>) {
+    static BINDINGS: OnceLock<(KeyBindings, Vec<KeyError>)> = OnceLock::new();
+    BINDINGS.get_or_init(|| load_bindings(active_settings().keys.as_deref()))
+}
+
+/// Key bindings for this run, read once from the config directory
+pub fn active_bindings() -> KeyBindings {
+    loaded().0.clone()
+}
+
+/// The bindings file read again over another preset, after a change on the
+/// settings screen
+pub fn configured_bindings(preset: Option<&str>) -> KeyBindings {
+    load_bindings(preset).0
+}
+
+/// Problems found in the user's key bindings file
+pub fn keymap_warnings() -> &'static [KeyError] {
+    &loaded().1
+}
@@ -1 +0,0 @@
-pub const STORY_PROMPT: &str = include_str!("../utils/prompts/story.txt");
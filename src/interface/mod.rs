@@ -1,4 +1,6 @@
-pub mod messages;
+pub mod animation;
+pub mod keymap;
+pub mod mouse;
 pub mod theme;
 pub mod ui_ratatui;
 pub mod widgets;
@@ -0,0 +1,40 @@
+/// Turns mouse presses and releases on board squares into square clicks, so
+/// that clicking and dragging both reuse the keyboard's select-and-move flow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MouseGesture {
+    pressed: Option<(usize, usize)>,
+    /// The press landed on the already selected piece and was held back
+    deferred: bool,
+}
+
+impl MouseGesture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A button press on `square`; returns the square to click right away.
+    /// Pressing the selected piece waits for the release, since it may be the
+    /// start of a drag rather than a deselect.
+    pub fn press(
+        &mut self,
+        square: (usize, usize),
+        selected: Option<(usize, usize)>,
+    ) -> Option<(usize, usize)> {
+        self.pressed = Some(square);
+        self.deferred = selected == Some(square);
+        (!self.deferred).then_some(square)
+    }
+
+    /// A button release on `square`; returns the square to click, if any.
+    /// Releasing elsewhere drops the dragged piece there.
+    pub fn release(&mut self, square: (usize, usize)) -> Option<(usize, usize)> {
+        let pressed = self.pressed.take()?;
+        let deferred = std::mem::take(&mut self.deferred);
+        (pressed != square || deferred).then_some(square)
+    }
+
+    /// Forgets a press whose release will never reach the board
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -1,31 +1,339 @@
+//! The colors every widget draws with. A theme starts from one of the bundled
+//! presets and can be adjusted by `theme.txt` in the config directory
+//! (e.g. ~/.config/checkers-rs/theme.txt):
+//!
+//! ```text
+//! preset: light
+//! text_accent: #3070a0
+//! piece_white: red
+//! ```
+//!
+//! Without a preset line, terminals that can't show 24-bit color get the
+//! monochrome preset instead of the classic one. A `theme` chosen in
+//! config.txt takes the place of the preset line.
+
+use crate::config::active_settings;
+use crate::utils::paths;
 use ratatui::style::Color;
+use std::env;
+use std::path::Path;
+use std::sync::OnceLock;
+use thiserror::Error;
 
-/// Soft hipster color scheme
-pub struct Theme;
+pub const THEME_FILE: &str = "theme.txt";
 
-impl Theme {
+/// Names accepted by `Theme::preset`
+pub const PRESETS: [&str; 4] = ["classic", "light", "high-contrast", "monochrome"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
     // Primary colors
-    pub const BORDER: Color = Color::Rgb(100, 100, 100); // Soft gray for borders
-    pub const BORDER_FOCUSED: Color = Color::Rgb(200, 200, 200); // Light gray for focused
+    pub border: Color,
+    pub border_focused: Color,
 
     // Text colors
-    pub const TEXT_PRIMARY: Color = Color::Rgb(220, 220, 220); // Off-white for main text
-    pub const TEXT_SECONDARY: Color = Color::Rgb(150, 150, 150); // Medium gray for secondary
-    pub const TEXT_ACCENT: Color = Color::Rgb(150, 180, 200); // Soft blue-gray for accents
+    pub text_primary: Color,
+    pub text_secondary: Color,
+    pub text_accent: Color,
 
     // Game pieces
-    pub const PIECE_BLACK: Color = Color::Rgb(180, 140, 100); // Warm brown/tan
-    pub const PIECE_WHITE: Color = Color::Rgb(230, 230, 220); // Cream white
+    pub piece_black: Color,
+    pub piece_white: Color,
 
     // Board colors
-    pub const BOARD_LIGHT: Color = Color::Rgb(60, 60, 60); // Pattern on light squares
-    pub const POSSIBLE_MOVE: Color = Color::Rgb(120, 140, 100); // Soft olive green
+    pub board_light: Color,
+    pub possible_move: Color,
+    pub hint_move: Color,
+    pub last_move: Color,
+    pub captured: Color,
 
     // UI elements
-    pub const SEPARATOR: Color = Color::Rgb(80, 80, 80); // Subtle separators
-    pub const HIGHLIGHT: Color = Color::Rgb(180, 180, 140); // Soft yellow highlight
+    pub separator: Color,
+    pub highlight: Color,
+    pub logo: Color,
+    pub emoji: Color,
+    pub success: Color,
+    pub error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ThemeError {
+    #[error("{THEME_FILE}: unknown preset {0} (expected one of {presets})", presets = PRESETS.join(", "))]
+    UnknownPreset(String),
+    #[error("{THEME_FILE}: unknown color name {0}")]
+    UnknownKey(String),
+    #[error("{THEME_FILE}: {key} is not a color: {value}")]
+    BadColor { key: String, value: String },
+    #[error("{THEME_FILE}: {0}")]
+    Unreadable(String),
+}
+
+impl Theme {
+    /// Soft hipster color scheme
+    pub const fn classic() -> Self {
+        Self {
+            border: Color::Rgb(100, 100, 100),
+            border_focused: Color::Rgb(200, 200, 200),
+            text_primary: Color::Rgb(220, 220, 220),
+            text_secondary: Color::Rgb(150, 150, 150),
+            text_accent: Color::Rgb(150, 180, 200),
+            piece_black: Color::Rgb(180, 140, 100),
+            piece_white: Color::Rgb(230, 230, 220),
+            board_light: Color::Rgb(60, 60, 60),
+            possible_move: Color::Rgb(120, 140, 100),
+            hint_move: Color::Rgb(130, 110, 80),
+            last_move: Color::Rgb(60, 75, 95),
+            captured: Color::Rgb(110, 65, 65),
+            separator: Color::Rgb(80, 80, 80),
+            highlight: Color::Rgb(180, 180, 140),
+            logo: Color::Magenta,
+            emoji: Color::Yellow,
+            success: Color::Green,
+            error: Color::Red,
+        }
+    }
+
+    /// Dark text for terminals with a light background. White pieces are
+    /// drawn red, as on a real board, so they stay visible.
+    pub const fn light() -> Self {
+        Self {
+            border: Color::Rgb(150, 150, 150),
+            border_focused: Color::Rgb(40, 40, 40),
+            text_primary: Color::Rgb(40, 40, 40),
+            text_secondary: Color::Rgb(100, 100, 100),
+            text_accent: Color::Rgb(40, 90, 140),
+            piece_black: Color::Rgb(30, 30, 30),
+            piece_white: Color::Rgb(170, 50, 40),
+            board_light: Color::Rgb(200, 200, 200),
+            possible_move: Color::Rgb(170, 200, 150),
+            hint_move: Color::Rgb(230, 200, 140),
+            last_move: Color::Rgb(190, 205, 225),
+            captured: Color::Rgb(230, 180, 180),
+            separator: Color::Rgb(190, 190, 190),
+            highlight: Color::Rgb(150, 110, 20),
+            logo: Color::Rgb(150, 40, 150),
+            emoji: Color::Rgb(180, 130, 0),
+            success: Color::Rgb(30, 130, 30),
+            error: Color::Rgb(180, 0, 0),
+        }
+    }
+
+    /// Saturated colors and pure white text for low vision
+    pub const fn high_contrast() -> Self {
+        Self {
+            border: Color::Rgb(255, 255, 255),
+            border_focused: Color::Rgb(255, 255, 0),
+            text_primary: Color::Rgb(255, 255, 255),
+            text_secondary: Color::Rgb(200, 200, 200),
+            text_accent: Color::Rgb(0, 255, 255),
+            piece_black: Color::Rgb(255, 170, 0),
+            piece_white: Color::Rgb(255, 255, 255),
+            board_light: Color::Rgb(90, 90, 90),
+            possible_move: Color::Rgb(0, 135, 0),
+            hint_move: Color::Rgb(175, 95, 0),
+            last_move: Color::Rgb(0, 0, 175),
+            captured: Color::Rgb(175, 0, 0),
+            separator: Color::Rgb(160, 160, 160),
+            highlight: Color::Rgb(255, 255, 0),
+            logo: Color::Rgb(255, 0, 255),
+            emoji: Color::Rgb(255, 255, 0),
+            success: Color::Rgb(0, 255, 0),
+            error: Color::Rgb(255, 0, 0),
+        }
+    }
+
+    /// Only the gray shades of the basic 16 colors. The last move and
+    /// captures are still marked by their arrows and crosses.
+    pub const fn monochrome() -> Self {
+        Self {
+            border: Color::DarkGray,
+            border_focused: Color::White,
+            text_primary: Color::White,
+            text_secondary: Color::Gray,
+            text_accent: Color::White,
+            piece_black: Color::Gray,
+            piece_white: Color::White,
+            board_light: Color::DarkGray,
+            possible_move: Color::DarkGray,
+            hint_move: Color::DarkGray,
+            last_move: Color::Reset,
+            captured: Color::Reset,
+            separator: Color::DarkGray,
+            highlight: Color::White,
+            logo: Color::White,
+            emoji: Color::White,
+            success: Color::White,
+            error: Color::White,
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "classic" => Some(Self::classic()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "border" => &mut self.border,
+            "border_focused" => &mut self.border_focused,
+            "text_primary" => &mut self.text_primary,
+            "text_secondary" => &mut self.text_secondary,
+            "text_accent" => &mut self.text_accent,
+            "piece_black" => &mut self.piece_black,
+            "piece_white" => &mut self.piece_white,
+            "board_light" => &mut self.board_light,
+            "possible_move" => &mut self.possible_move,
+            "hint_move" => &mut self.hint_move,
+            "last_move" => &mut self.last_move,
+            "captured" => &mut self.captured,
+            "separator" => &mut self.separator,
+            "highlight" => &mut self.highlight,
+            "logo" => &mut self.logo,
+            "emoji" => &mut self.emoji,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            _ => return None,
+        })
+    }
+
+    /// Reads a theme file: `key: value` lines, `#` comments. A `preset` line
+    /// picks the base theme, other keys override single colors given as a
+    /// name (`red`, `darkgray`), `#rrggbb` or a 256-color index. Anything
+    /// invalid is skipped and reported.
+    pub fn parse(text: &str, truecolor: bool) -> (Self, Vec<ThemeError>) {
+        Self::parse_with_preset(text, None, truecolor)
+    }
+
+    /// Like `parse`, with `preset` chosen in place of any preset line
+    pub fn parse_with_preset(
+        text: &str,
+        preset: Option<&str>,
+        truecolor: bool,
+    ) -> (Self, Vec<ThemeError>) {
+        let mut warnings = Vec::new();
+        let mut base = None;
+        let mut overrides = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                warnings.push(ThemeError::UnknownKey(line.to_string()));
+                continue;
+            };
+            let (key, value) = (key.trim().to_lowercase(), value.trim());
+            if key == "preset" {
+                match Self::preset(value) {
+                    Some(theme) => base = Some(theme),
+                    None => warnings.push(ThemeError::UnknownPreset(value.to_string())),
+                }
+            } else {
+                overrides.push((key, value));
+            }
+        }
+
+        let mut theme = preset
+            .and_then(Self::preset)
+            .or(base)
+            .unwrap_or_else(|| Self::auto(truecolor));
+        for (key, value) in overrides {
+            let Some(slot) = theme.color_mut(&key) else {
+                warnings.push(ThemeError::UnknownKey(key));
+                continue;
+            };
+            match value.parse::<Color>() {
+                Ok(color) => *slot = color,
+                Err(_) => warnings.push(ThemeError::BadColor {
+                    key,
+                    value: value.to_string(),
+                }),
+            }
+        }
+
+        (theme, warnings)
+    }
+
+    /// Reads `path` if it exists, otherwise picks a preset for the terminal
+    pub fn load(path: Option<&Path>, truecolor: bool) -> (Self, Vec<ThemeError>) {
+        Self::load_with_preset(path, None, truecolor)
+    }
+
+    /// Like `load`, with `preset` chosen in place of any preset line
+    pub fn load_with_preset(
+        path: Option<&Path>,
+        preset: Option<&str>,
+        truecolor: bool,
+    ) -> (Self, Vec<ThemeError>) {
+        let text = match path
+            .filter(|path| path.exists())
+            .map(std::fs::read_to_string)
+        {
+            Some(Ok(text)) => text,
+            Some(Err(e)) => {
+                let (theme, _) = Self::parse_with_preset("", preset, truecolor);
+                return (theme, vec![ThemeError::Unreadable(e.to_string())]);
+            }
+            None => String::new(),
+        };
+        Self::parse_with_preset(&text, preset, truecolor)
+    }
+
+    fn auto(truecolor: bool) -> Self {
+        if truecolor {
+            Self::classic()
+        } else {
+            Self::monochrome()
+        }
+    }
+}
+
+/// Whether the terminal can show 24-bit colors, judged from `COLORTERM` and
+/// `TERM` the way most terminal emulators advertise it
+pub fn supports_truecolor(colorterm: Option<&str>, term: Option<&str>) -> bool {
+    if colorterm.is_some_and(|value| matches!(value, "truecolor" | "24bit")) {
+        return true;
+    }
+    term.is_some_and(|term| term.contains("truecolor") || term.contains("direct"))
+}
+
+fn load_theme(preset: Option<&str>) -> (Theme, Vec<ThemeError>) {
+    let truecolor = supports_truecolor(
+        env::var("COLORTERM").ok().as_deref(),
+        env::var("TERM").ok().as_deref(),
+    );
+    let path = paths::config_dir().map(|dir| dir.join(THEME_FILE));
+    Theme::load_with_preset(path.as_deref(), preset, truecolor)
+}
+
+fn loaded() -> &'static (Theme, Vec<ThemeError>) {
+    static THEME: OnceLock<(Theme, Vec<ThemeError>)> = OnceLock::new();
+    THEME.get_or_init(|| load_theme(active_settings().theme.as_deref()))
+}
+
+/// Theme for this run, read once from the config directory
+pub fn active_theme() -> Theme {
+    loaded().0
+}
+
+/// The theme file read again over another preset, after a change on the
+/// settings screen
+pub fn configured_theme(preset: Option<&str>) -> Theme {
+    load_theme(preset).0
+}
 
-    // Special - keep original
-    pub const LOGO: Color = Color::Magenta; // Keep CHECKERS logo as is
-    pub const EMOJI: Color = Color::Yellow; // Keep emoji colors
+/// Problems found in the user's theme file
+pub fn theme_warnings() -> &'static [ThemeError] {
+    &loaded().1
 }
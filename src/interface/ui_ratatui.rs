@@ -1,35 +1,44 @@
 use std::io;
+use std::time::Instant;
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color as RatatuiColor, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
-    Terminal,
+    style::Style,
+    widgets::{Clear, Paragraph},
+    Frame, Terminal,
 };
 
 use crate::{
     core::piece::Color,
     interface::{
-        theme::Theme,
-        widgets::{CheckerBoard, GameStatus, HintDisplay, WelcomeScreen},
+        animation::AnimationFrame,
+        keymap::{active_bindings, Action, Input, KeyBindings},
+        theme::{active_theme, Theme},
+        widgets::{
+            board::BoardScale, move_list::MOVE_LIST_WIDTH, AnalysisPanel, CheckerBoard,
+            ClockDisplay, CoachPanel, GameOverScreen, GameStatus, HelpOverlay, HintDisplay,
+            MoveEntryBar, MoveList, SettingsScreen, WelcomeScreen,
+        },
     },
+    state::view_data::SettingsView,
 };
 
-#[derive(Debug, PartialEq)]
-pub enum Input {
-    Up,
-    Down,
-    Left,
-    Right,
-    Select,
-    Quit,
-}
-
 pub struct UI {
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    text_entry: bool,
+    /// Where the board was last drawn, its size and whether it was flipped,
+    /// for mapping mouse clicks to squares
+    board_area: Option<(Rect, usize, bool)>,
+    /// How many rows the move list showed when last drawn
+    move_list_rows: usize,
+    /// Move animation frame drawn instead of the settled board
+    animation: Option<AnimationFrame>,
+    theme: Theme,
+    bindings: KeyBindings,
+    /// Whether the key bindings popup is open
+    help: bool,
 }
 
 impl UI {
@@ -37,7 +46,47 @@ impl UI {
         let backend = CrosstermBackend::new(io::stdout());
         let terminal = Terminal::new(backend)?;
 
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal,
+            text_entry: false,
+            board_area: None,
+            move_list_rows: 0,
+            animation: None,
+            theme: active_theme(),
+            bindings: active_bindings(),
+            help: false,
+        })
+    }
+
+    /// In text entry mode every printable key is passed through as typed text
+    /// and only ESC leaves the input.
+    pub fn set_text_entry(&mut self, enabled: bool) {
+        self.text_entry = enabled;
+    }
+
+    pub fn set_animation(&mut self, frame: Option<AnimationFrame>) {
+        self.animation = frame;
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn set_bindings(&mut self, bindings: KeyBindings) {
+        self.bindings = bindings;
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.help = !self.help;
+    }
+
+    /// Rows of moves the side panel had room for, none when it isn't shown
+    pub fn move_list_rows(&self) -> usize {
+        self.move_list_rows
+    }
+
+    pub fn help_open(&self) -> bool {
+        self.help
     }
 
     pub fn init(&mut self) -> io::Result<()> {
@@ -62,49 +111,142 @@ impl UI {
         Ok(())
     }
 
-    pub fn draw_welcome_screen(
-        &mut self,
-        did_you_know: &str,
-        tip_of_the_day: &str,
-        todays_challenge: &str,
-        is_simple_ai: bool,
-    ) -> io::Result<()> {
+    pub fn draw_welcome_screen(&mut self, welcome: WelcomeScreen) -> io::Result<()> {
         self.terminal.draw(|f| {
-            let welcome = WelcomeScreen::new(
-                did_you_know.to_string(),
-                tip_of_the_day.to_string(),
-                todays_challenge.to_string(),
-            )
-            .simple_ai(is_simple_ai);
-            f.render_widget(welcome, f.area());
+            f.render_widget(welcome.theme(self.theme), f.area());
+
+            if self.help {
+                render_help(f, &self.bindings, self.theme);
+            }
+        })?;
+        Ok(())
+    }
+
+    pub fn draw_settings_screen(&mut self, settings: &SettingsView) -> io::Result<()> {
+        let rows = settings
+            .rows
+            .iter()
+            .map(|row| (row.name, row.value.clone(), row.overridden_by))
+            .collect::<Vec<_>>();
+        self.terminal.draw(|f| {
+            let screen = SettingsScreen::new(&rows, settings.selected)
+                .bindings(&self.bindings)
+                .message(settings.message.as_deref())
+                .theme(self.theme);
+            f.render_widget(screen, f.area());
+
+            if self.help {
+                render_help(f, &self.bindings, self.theme);
+            }
         })?;
         Ok(())
     }
 
     pub fn draw_view_data(&mut self, view: &crate::state::ViewData) -> io::Result<()> {
+        self.board_area = None;
+        self.move_list_rows = 0;
+
+        if let Some(settings) = &view.settings {
+            return self.draw_settings_screen(settings);
+        }
+
         // Check if it's a welcome screen
         if let Some((did_you_know, tip, challenge)) = view.welcome_content {
-            return self.draw_welcome_screen(did_you_know, tip, challenge, view.is_simple_ai);
+            let mut welcome = WelcomeScreen::new(
+                did_you_know.to_string(),
+                tip.to_string(),
+                challenge.to_string(),
+            )
+            .bindings(&self.bindings)
+            .simple_ai(view.is_simple_ai)
+            .two_players(view.ai_seat.is_none())
+            .resumed(
+                view.move_history
+                    .is_some_and(|history| !history.moves().is_empty()),
+            );
+            if let Some(persona) = view.persona {
+                welcome = welcome.persona(&persona.name, &persona.description);
+            }
+            return self.draw_welcome_screen(welcome);
         }
 
-        // Check if it's game over
-        if view.is_game_over {
-            let winner = if view.status_message.contains("Black wins") {
-                Some(Color::Black)
-            } else if view.status_message.contains("White wins") {
-                Some(Color::White)
-            } else {
-                None
-            };
-            return self.draw_game_over(winner);
+        // Check if it's game over, letting the winning move play out first
+        if view.is_game_over && self.animation.is_none() {
+            return self.draw_game_over(&view.status_message, view.usage_summary.as_deref());
         }
 
+        let mut board_area = None;
+        let mut move_list_rows = 0;
         self.terminal.draw(|f| {
-            // First, create a centered column of fixed width
-            let main_width = 64;
+            let has_side_panel = view.coach.is_some() || view.analysis.is_some();
+
+            // The column under the board is as wide as the board, and at
+            // least 64, unless the terminal is narrower
+            let column_width = |scale: BoardScale| {
+                scale
+                    .grid_size(view.board.size)
+                    .0
+                    .max(64)
+                    .min(f.area().width)
+            };
+
+            // Calculate dynamic hint height for a column width if hint is present
+            let hint_height = |column_width: u16| {
+                if let Some(hint) = view.hint {
+                    use ratatui::widgets::{Paragraph, Wrap};
+
+                    // Create a paragraph with the hint text to calculate its wrapped height
+                    let paragraph = Paragraph::new(hint.hint.as_str()).wrap(Wrap { trim: true });
+
+                    // Account for borders (2) + inner padding in HintDisplay (2) = 4
+                    let available_width = column_width.saturating_sub(4);
+
+                    // Use ratatui's built-in line_count method to get accurate height
+                    let lines = paragraph.line_count(available_width) as u16;
+
+                    // Add 2 for border top/bottom
+                    lines + 2
+                } else {
+                    0 // No hint, no space needed
+                }
+            };
+
+            // The board gets the biggest scale that fits between the five text
+            // lines around it. The large board must also leave room for the
+            // hint or side panel below, smaller ones squeeze them as before.
+            let size = view.board.size;
+            let below_board = if has_side_panel {
+                6
+            } else {
+                hint_height(column_width(BoardScale::Large))
+            };
+            let room = f.area().height.saturating_sub(5);
+            let width = f.area().width;
+            let scale = if BoardScale::Large.fits(size, width, room.saturating_sub(below_board)) {
+                BoardScale::Large
+            } else if BoardScale::Normal.fits(size, width, room) {
+                BoardScale::Normal
+            } else {
+                BoardScale::Compact
+            };
+            let (board_width, board_height) = scale.grid_size(size);
+            let hint_height = hint_height(column_width(scale));
+
+            // Then create a centered column wide enough for the board, leaving
+            // room for the move list on its right when the terminal is wide
+            // enough
+            let main_width = board_width.max(64);
+            let panel_gap = 2;
+            let show_move_list = view.move_history.is_some()
+                && f.area().width >= main_width + panel_gap + MOVE_LIST_WIDTH;
+            let total_width = if show_move_list {
+                main_width + panel_gap + MOVE_LIST_WIDTH
+            } else {
+                main_width
+            };
             let centered_area = if f.area().width >= main_width {
                 Rect {
-                    x: (f.area().width - main_width) / 2,
+                    x: (f.area().width - total_width) / 2,
                     y: f.area().y,
                     width: main_width,
                     height: f.area().height,
@@ -113,186 +255,327 @@ impl UI {
                 f.area()
             };
 
-            // Calculate dynamic hint height if hint is present
-            let hint_height = if let Some(hint) = view.hint {
-                use ratatui::widgets::{Paragraph, Wrap};
-
-                // Create a paragraph with the hint text to calculate its wrapped height
-                let paragraph = Paragraph::new(hint.hint.as_str()).wrap(Wrap { trim: true });
-
-                // Account for borders (2) + inner padding in HintDisplay (2) = 4
-                let available_width = centered_area.width.saturating_sub(4);
-
-                // Use ratatui's built-in line_count method to get accurate height
-                let lines = paragraph.line_count(available_width) as u16;
-
-                // Add 2 for border top/bottom
-                lines + 2
-            } else {
-                0 // No hint, no space needed
-            };
-
             // Dynamic layout using modern ratatui best practices
             let mut constraints = vec![
-                Constraint::Length(1),  // Top separator ════════════════
-                Constraint::Length(1),  // Game status "Current Turn: White"
-                Constraint::Length(1),  // One empty line
-                Constraint::Length(18), // Board (requires exactly 18 lines)
-                Constraint::Length(1),  // Bottom separator ────────────────
-                Constraint::Length(1),  // Controls line
+                Constraint::Length(1),            // Top separator ════════════════
+                Constraint::Length(1),            // Game status "Current Turn: White"
+                Constraint::Length(1),            // One empty line
+                Constraint::Length(board_height), // Board at the chosen scale
+                Constraint::Length(1),            // Bottom separator ────────────────
+                Constraint::Length(1),            // Controls line
             ];
 
-            if hint_height > 0 {
-                constraints.push(Constraint::Length(hint_height)); // Dynamic hint area
+            if has_side_panel {
+                constraints.push(Constraint::Min(6)); // Side panel takes the rest
+            } else {
+                if hint_height > 0 {
+                    constraints.push(Constraint::Length(hint_height)); // Dynamic hint area
+                }
+                constraints.push(Constraint::Fill(1)); // Fill remaining space efficiently
             }
-            constraints.push(Constraint::Fill(1)); // Fill remaining space efficiently
 
             let chunks = Layout::vertical(constraints).split(centered_area);
 
             // Top separator
-            let separator = "═".repeat(64);
-            let sep_widget = Paragraph::new(separator).style(Style::default().fg(Theme::SEPARATOR));
+            let separator = "═".repeat(centered_area.width as usize);
+            let sep_widget =
+                Paragraph::new(separator).style(Style::default().fg(self.theme.separator));
             f.render_widget(sep_widget, chunks[0]);
 
-            // Game status
-            let status = GameStatus::new(view.current_player)
-                .ai_thinking(view.show_ai_thinking)
-                .local_mode(false)
-                .ai_error(view.error_message)
-                .simple_ai(view.is_simple_ai);
-            f.render_widget(status, chunks[1]);
-
-            // chunks[2] is the empty line - leave it empty
-
-            // Board
-            let board_widget = CheckerBoard::new(view.board)
-                .cursor_position(view.cursor_pos)
-                .selected_square(view.selected_piece)
-                .possible_moves(view.possible_moves)
-                .pieces_with_captures(&view.pieces_with_captures);
+            // Game status, the replayed move while browsing history, or what
+            // the move command line expects
+            if view.replay.is_some() || view.move_entry.is_some() {
+                let (color, text) = match &view.move_entry {
+                    Some(entry) if entry.failed => (self.theme.error, view.status_message.clone()),
+                    Some(_) => {
+                        let key = |action| self.bindings.entry_label(action);
+                        let text = format!(
+                            "{}  •  {} Complete  •  {} Play  •  {} Cancel",
+                            view.status_message,
+                            key(Action::Command),
+                            key(Action::Select),
+                            key(Action::Quit)
+                        );
+                        (self.theme.text_accent, text)
+                    }
+                    None => (self.theme.text_accent, view.status_message.clone()),
+                };
+                let status_line = Paragraph::new(text).style(Style::default().fg(color));
+                f.render_widget(status_line, chunks[1]);
+            } else {
+                let status = GameStatus::new(view.current_player)
+                    .ai_thinking(view.show_ai_thinking)
+                    .local_mode(false)
+                    .ai_error(view.error_message)
+                    .ai_remark(view.ai_remark)
+                    .simple_ai(view.is_simple_ai && view.watching.is_none())
+                    .ai_seat(view.ai_seat)
+                    .remote_seat(view.remote.map(|remote| remote.seat))
+                    .theme(self.theme);
+                match view.clock {
+                    Some(clock) => {
+                        let now = Instant::now();
+                        let clocks = ClockDisplay::new(
+                            clock.left(Color::White, now),
+                            clock.left(Color::Black, now),
+                        )
+                        .running(clock.running_side())
+                        .theme(self.theme);
+                        let [status_area, clock_area] = Layout::horizontal([
+                            Constraint::Fill(1),
+                            Constraint::Length(clocks.width()),
+                        ])
+                        .areas(chunks[1]);
+                        f.render_widget(status, status_area);
+                        f.render_widget(clocks, clock_area);
+                    }
+                    None => f.render_widget(status, chunks[1]),
+                }
+            }
+
+            // Move list beside the board, or a one-line summary in the empty
+            // line under the status when there is no room for it
+            if let Some(history) = view.move_history {
+                if show_move_list {
+                    let panel_area = Rect {
+                        x: centered_area.x + main_width + panel_gap,
+                        y: chunks[1].y,
+                        width: MOVE_LIST_WIDTH,
+                        height: chunks[5].bottom() - chunks[1].y,
+                    };
+                    move_list_rows = MoveList::visible_rows(panel_area);
+                    let move_list = MoveList::new(view.board, history)
+                        .scroll(view.move_list_scroll)
+                        .theme(self.theme);
+                    f.render_widget(move_list, panel_area);
+                } else {
+                    let summary = Paragraph::new(MoveList::summary(view.board, history))
+                        .style(Style::default().fg(self.theme.text_secondary))
+                        .alignment(Alignment::Center);
+                    f.render_widget(summary, chunks[2]);
+                }
+            }
+
+            // Connection news and chat take the line under the status in
+            // network games
+            if let Some(remote) = view.remote {
+                let (text, color) = match &remote.notice {
+                    Some(notice) => (notice.clone(), self.theme.text_accent),
+                    None if remote.connected => (
+                        "Connected  •  : then /say to chat, /draw or /resign".to_string(),
+                        self.theme.text_secondary,
+                    ),
+                    None => (
+                        "Waiting for the opponent to connect...".to_string(),
+                        self.theme.text_secondary,
+                    ),
+                };
+                let line = Paragraph::new(text)
+                    .style(Style::default().fg(color))
+                    .alignment(Alignment::Center);
+                f.render_widget(Clear, chunks[2]);
+                f.render_widget(line, chunks[2]);
+            }
+            // Watchers see how the broadcast is doing in the same place
+            if let Some(watching) = view.watching {
+                let text = match &watching.notice {
+                    Some(notice) => notice.clone(),
+                    None => format!(
+                        "Watching  •  read-only, {} to leave",
+                        self.bindings.primary_label(Action::Quit)
+                    ),
+                };
+                let line = Paragraph::new(text)
+                    .style(Style::default().fg(self.theme.text_secondary))
+                    .alignment(Alignment::Center);
+                f.render_widget(Clear, chunks[2]);
+                f.render_widget(line, chunks[2]);
+            }
+
+            // Board, or the move being animated without any highlights
+            let board_widget = match &self.animation {
+                Some(frame) => CheckerBoard::new(view.board)
+                    .theme(self.theme)
+                    .flipped(view.flipped)
+                    .cursor_position(view.cursor_pos)
+                    .animation(Some(frame)),
+                None => CheckerBoard::new(view.board)
+                    .theme(self.theme)
+                    .flipped(view.flipped)
+                    .cursor_position(view.cursor_pos)
+                    .selected_square(view.selected_piece)
+                    .possible_moves(view.possible_moves)
+                    .pieces_with_captures(&view.pieces_with_captures)
+                    .hint_move(view.hint.and_then(|hint| hint.suggested_move))
+                    .last_move(view.last_move.as_ref())
+                    .captured_squares(&view.captured_squares),
+            };
             f.render_widget(board_widget, chunks[3]);
+            board_area = Some((chunks[3], view.board.size, view.flipped));
 
             // Bottom separator
-            let bottom_sep = "─".repeat(64);
+            let bottom_sep = "─".repeat(centered_area.width as usize);
             let bottom_sep_widget =
-                Paragraph::new(bottom_sep).style(Style::default().fg(Theme::SEPARATOR));
+                Paragraph::new(bottom_sep).style(Style::default().fg(self.theme.separator));
             f.render_widget(bottom_sep_widget, chunks[4]);
 
-            // Controls
-            let controls = ["↑↓←→ Move", "Space/Enter Select", "ESC/Q Quit"];
-            let controls_text = controls.join("  •  ");
-            let controls_widget = Paragraph::new(controls_text)
-                .style(Style::default().fg(Theme::TEXT_PRIMARY))
-                .alignment(Alignment::Center);
-            f.render_widget(controls_widget, chunks[5]);
-
-            // Hint (if present, render in the dynamic area)
-            if let Some(hint) = view.hint {
-                let hint_display = HintDisplay::new(Some(&hint.hint));
+            // Controls, named by the first key of each binding. The rest
+            // are listed in the help popup.
+            let key = |action| self.bindings.primary_label(action);
+            let moves = format!(
+                "{}{}{}{}",
+                key(Action::Up),
+                key(Action::Down),
+                key(Action::Left),
+                key(Action::Right)
+            );
+            let controls = if view.coach.is_some() {
+                let key = |action| self.bindings.entry_label(action);
+                vec![
+                    "Type a question".to_string(),
+                    format!("{} Send", key(Action::Select)),
+                    format!("{} Back to board", key(Action::Quit)),
+                ]
+            } else if view.analysis.is_some() {
+                vec![
+                    format!("{}{} Step", key(Action::Left), key(Action::Right)),
+                    format!("{} Export PDN", key(Action::Export)),
+                    format!("{} Back", key(Action::Quit)),
+                ]
+            } else if view.replay.is_some() {
+                vec![
+                    format!("{}{} Step", key(Action::Left), key(Action::Right)),
+                    format!("{}/{} Start/End", key(Action::First), key(Action::Last)),
+                    format!("{} Back", key(Action::Quit)),
+                ]
+            } else {
+                vec![
+                    format!("{moves} Move"),
+                    format!("{} Select", key(Action::Select)),
+                    format!("{} Hint", key(Action::Hint)),
+                    format!("{} Help", key(Action::Help)),
+                    format!("{} Quit", key(Action::Quit)),
+                ]
+            };
+            if let Some(entry) = &view.move_entry {
+                let command_line =
+                    MoveEntryBar::new(&entry.input, &entry.completions).theme(self.theme);
+                f.render_widget(command_line, chunks[5]);
+            } else {
+                let controls_text = controls.join("  •  ");
+                let controls_widget = Paragraph::new(controls_text)
+                    .style(Style::default().fg(self.theme.text_primary))
+                    .alignment(Alignment::Center);
+                f.render_widget(controls_widget, chunks[5]);
+            }
+
+            // Coach panel replaces the hint while open
+            if let Some(coach) = &view.coach {
+                let coach_panel =
+                    CoachPanel::new(coach.conversation, &coach.input).theme(self.theme);
+                f.render_widget(coach_panel, chunks[6]);
+            } else if let Some(analysis) = &view.analysis {
+                let analysis_panel =
+                    AnalysisPanel::new(analysis.analysis, analysis.selected, &view.status_message)
+                        .theme(self.theme);
+                f.render_widget(analysis_panel, chunks[6]);
+            } else if let Some(hint) = view.hint {
+                let hint_display = HintDisplay::new(Some(&hint.hint))
+                    .verified(hint.is_verified())
+                    .theme(self.theme);
                 // Hint is at index 6 if present
                 f.render_widget(hint_display, chunks[6]);
             }
+
+            if self.help {
+                render_help(f, &self.bindings, self.theme);
+            }
         })?;
+        self.board_area = board_area;
+        self.move_list_rows = move_list_rows;
         Ok(())
     }
 
-    fn draw_game_over(&mut self, winner: Option<Color>) -> io::Result<()> {
+    fn draw_game_over(&mut self, result: &str, usage: Option<&str>) -> io::Result<()> {
         self.terminal.draw(|f| {
-            let message = match winner {
-                Some(Color::Black) => "Black wins!",
-                Some(Color::White) => "White wins!",
-                None => "Stalemate! No possible moves.",
-            };
-
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(RatatuiColor::Magenta));
-
-            let area = centered_rect(50, 20, f.area());
-
-            // Calculate inner area
-            let inner = block.inner(area);
-
-            // Create vertical layout for centering
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1), // Top padding
-                    Constraint::Length(1), // "Game Over"
-                    Constraint::Length(1), // Space
-                    Constraint::Length(1), // Winner message
-                    Constraint::Length(1), // Space
-                    Constraint::Length(1), // "Press ESC to exit..."
-                    Constraint::Min(0),    // Bottom padding
-                ])
-                .split(inner);
-
-            // First render the block
-            f.render_widget(block, area);
-
-            // Render each line in its chunk
-            let game_over_line = Paragraph::new(Line::from(vec![Span::styled(
-                "Game Over",
-                Style::default().fg(RatatuiColor::Yellow),
-            )]))
-            .alignment(Alignment::Center);
-            f.render_widget(game_over_line, chunks[1]);
-
-            let winner_line = Paragraph::new(Line::from(vec![Span::styled(
-                message,
-                Style::default().fg(RatatuiColor::Green),
-            )]))
-            .alignment(Alignment::Center);
-            f.render_widget(winner_line, chunks[3]);
-
-            let exit_line = Paragraph::new(Line::from(vec![Span::styled(
-                "Press ESC to exit...",
-                Style::default().fg(RatatuiColor::White),
-            )]))
-            .alignment(Alignment::Center);
-            f.render_widget(exit_line, chunks[5]);
+            let area = centered_rect(50, 30, f.area());
+            let screen = GameOverScreen::new(result, &self.bindings)
+                .usage(usage)
+                .theme(self.theme);
+            f.render_widget(screen, area);
         })?;
         Ok(())
     }
 
     pub fn get_input(&self) -> io::Result<Input> {
         loop {
-            if let Event::Key(KeyEvent { code, .. }) = event::read()? {
-                let input = match code {
-                    KeyCode::Up => Input::Up,
-                    KeyCode::Down => Input::Down,
-                    KeyCode::Left => Input::Left,
-                    KeyCode::Right => Input::Right,
-                    KeyCode::Char(' ') | KeyCode::Enter => Input::Select,
-                    KeyCode::Esc => Input::Quit,
-                    KeyCode::Char('q') | KeyCode::Char('Q') => Input::Quit,
-                    KeyCode::Char('й') | KeyCode::Char('Й') => Input::Quit,
-                    _ => continue,
-                };
+            if let Some(input) = self.map_event(event::read()?) {
                 return Ok(input);
             }
         }
     }
 
     pub fn poll_input(&self) -> io::Result<Option<Input>> {
-        if event::poll(std::time::Duration::from_millis(0))? {
-            if let Event::Key(KeyEvent { code, .. }) = event::read()? {
-                let input = match code {
-                    KeyCode::Up => Input::Up,
-                    KeyCode::Down => Input::Down,
-                    KeyCode::Left => Input::Left,
-                    KeyCode::Right => Input::Right,
-                    KeyCode::Char(' ') | KeyCode::Enter => Input::Select,
-                    KeyCode::Esc => Input::Quit,
-                    KeyCode::Char('q') | KeyCode::Char('Q') => Input::Quit,
-                    KeyCode::Char('й') | KeyCode::Char('Й') => Input::Quit,
-                    _ => return Ok(None),
-                };
-                return Ok(Some(input));
-            }
+        self.wait_for_input(std::time::Duration::from_millis(0))
+    }
+
+    /// Waits up to `timeout` for the next input
+    pub fn wait_for_input(&self, timeout: std::time::Duration) -> io::Result<Option<Input>> {
+        if event::poll(timeout)? {
+            return Ok(self.map_event(event::read()?));
         }
         Ok(None)
     }
+
+    fn map_event(&self, event: Event) -> Option<Input> {
+        match event {
+            Event::Key(KeyEvent { code, .. }) => self.map_key(code),
+            Event::Mouse(mouse) if !self.text_entry => self.map_mouse(mouse),
+            _ => None,
+        }
+    }
+
+    fn map_mouse(&self, mouse: MouseEvent) -> Option<Input> {
+        // The wheel scrolls the move list from anywhere on the game screen
+        match mouse.kind {
+            MouseEventKind::ScrollUp if self.board_area.is_some() => {
+                return Some(Input::Action(Action::ScrollBack))
+            }
+            MouseEventKind::ScrollDown if self.board_area.is_some() => {
+                return Some(Input::Action(Action::ScrollForward))
+            }
+            _ => {}
+        }
+
+        let (area, size, flipped) = self.board_area?;
+        let square = CheckerBoard::square_at(area, size, flipped, mouse.column, mouse.row)?;
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(Input::MouseDown(square)),
+            MouseEventKind::Up(MouseButton::Left) => Some(Input::MouseUp(square)),
+            _ => None,
+        }
+    }
+
+    fn map_key(&self, code: KeyCode) -> Option<Input> {
+        if self.text_entry {
+            return match code {
+                KeyCode::Backspace => Some(Input::Backspace),
+                KeyCode::Char(c) => Some(Input::Char(c)),
+                code => self.bindings.entry_action_for(code).map(Input::Action),
+            };
+        }
+
+        // Unbound keys do nothing, so a key taken off an action stays quiet
+        self.bindings.action_for(code).map(Input::Action)
+    }
+}
+
+/// Key bindings popup drawn over whatever screen is showing
+fn render_help(f: &mut Frame, bindings: &KeyBindings, theme: Theme) {
+    let entries = bindings.describe();
+    let overlay = HelpOverlay::new(&entries).theme(theme);
+    let area = overlay.area(f.area());
+    f.render_widget(overlay, area);
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
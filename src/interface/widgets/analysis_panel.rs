@@ -0,0 +1,154 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::ai::analysis::{Annotation, GameAnalysis, ScoreSource};
+use crate::core::piece::Color;
+use crate::interface::theme::Theme;
+
+pub struct AnalysisPanel<'a> {
+    analysis: &'a GameAnalysis,
+    selected: usize,
+    status: &'a str,
+    theme: Theme,
+}
+
+impl<'a> AnalysisPanel<'a> {
+    pub fn new(analysis: &'a GameAnalysis, selected: usize, status: &'a str) -> Self {
+        Self {
+            analysis,
+            selected,
+            status,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn move_lines(&self) -> Vec<Line<'a>> {
+        self.analysis
+            .moves
+            .iter()
+            .enumerate()
+            .map(|(i, annotated)| {
+                let is_selected = i == self.selected;
+                let marker = if is_selected { "▶ " } else { "  " };
+                let player = match annotated.player {
+                    Color::White => "W",
+                    Color::Black => "B",
+                };
+                let annotation_color = match annotated.annotation {
+                    Annotation::Good => self.theme.text_secondary,
+                    Annotation::Inaccuracy => self.theme.text_accent,
+                    _ => self.theme.highlight,
+                };
+                let move_style = if is_selected {
+                    Style::default()
+                        .fg(self.theme.text_primary)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.text_primary)
+                };
+
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(self.theme.highlight)),
+                    Span::styled(
+                        format!("{:>3}. {} ", i + 1, player),
+                        Style::default().fg(self.theme.text_secondary),
+                    ),
+                    Span::styled(
+                        format!("{}{}", annotated.notation, annotated.annotation.symbol()),
+                        move_style,
+                    ),
+                    Span::styled(
+                        match annotated.annotation {
+                            Annotation::Good => String::new(),
+                            annotation => format!("  {}", annotation.label()),
+                        },
+                        Style::default().fg(annotation_color),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl<'a> Widget for AnalysisPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 4 {
+            return;
+        }
+
+        let source = match self.analysis.source {
+            ScoreSource::Engine => "engine",
+            ScoreSource::Llm => "AI",
+        };
+        let title = vec![
+            Span::styled("📋 ", Style::default().fg(self.theme.emoji)),
+            Span::styled("Game Review", Style::default().fg(self.theme.text_accent)),
+            Span::styled(
+                format!(" ({source}) "),
+                Style::default().fg(self.theme.text_secondary),
+            ),
+        ];
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused))
+            .title(Line::from(title));
+
+        // Calculate inner area for padding
+        let inner = block.inner(area);
+        let padded_area = Rect {
+            x: inner.x + 1,
+            y: inner.y,
+            width: inner.width.saturating_sub(2),
+            height: inner.height,
+        };
+
+        block.render(area, buf);
+
+        // Move list above, comment for the selected move at the bottom
+        let status = Paragraph::new(self.status)
+            .style(Style::default().fg(self.theme.text_primary))
+            .wrap(Wrap { trim: true });
+        let status_height = (status.line_count(padded_area.width) as u16)
+            .min(padded_area.height.saturating_sub(1) / 2)
+            .max(1);
+
+        let list_area = Rect {
+            height: padded_area.height.saturating_sub(status_height),
+            ..padded_area
+        };
+        let status_area = Rect {
+            y: padded_area.y + list_area.height,
+            height: status_height,
+            ..padded_area
+        };
+
+        let lines = self.move_lines();
+        if lines.is_empty() {
+            Paragraph::new(Span::styled(
+                "No moves were played",
+                Style::default().fg(self.theme.text_secondary),
+            ))
+            .render(list_area, buf);
+        } else {
+            // Keep the selected move in view
+            let visible = list_area.height as usize;
+            let scroll = (self.selected + 1).saturating_sub(visible) as u16;
+            Paragraph::new(lines)
+                .scroll((scroll, 0))
+                .render(list_area, buf);
+        }
+
+        status.render(status_area, buf);
+    }
+}
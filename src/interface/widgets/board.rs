@@ -5,15 +5,89 @@ use ratatui::{
     widgets::Widget,
 };
 
-use crate::core::{board::Board, piece::Color as PieceColor};
+use crate::core::{
+    board::Board,
+    piece::{Color as PieceColor, Piece},
+    Turn,
+};
+use crate::interface::animation::AnimationFrame;
 use crate::interface::theme::Theme;
 
+/// How much room each square gets. The board picks the largest scale that
+/// fits the area it is given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardScale {
+    /// One character per square, no grid lines
+    Compact,
+    /// 5x1 squares inside a grid
+    Normal,
+    /// 7x3 squares with drawn pieces
+    Large,
+}
+
+impl BoardScale {
+    /// Largest first
+    const ALL: [BoardScale; 3] = [BoardScale::Large, BoardScale::Normal, BoardScale::Compact];
+
+    /// Terminal columns and rows per square, counting its top and left border
+    pub const fn cell_size(self) -> (u16, u16) {
+        match self {
+            BoardScale::Compact => (1, 1),
+            BoardScale::Normal => (6, 2),
+            BoardScale::Large => (8, 4),
+        }
+    }
+
+    /// Width of the row number column
+    pub fn label_width(self) -> u16 {
+        match self {
+            BoardScale::Compact => 3,
+            _ => 4,
+        }
+    }
+
+    fn border(self) -> u16 {
+        match self {
+            BoardScale::Compact => 0,
+            _ => 1,
+        }
+    }
+
+    /// Terminal columns and rows of the whole board including its labels
+    pub fn grid_size(self, board_size: usize) -> (u16, u16) {
+        let (cell_width, cell_height) = self.cell_size();
+        let squares = board_size as u16;
+        (
+            self.label_width() + cell_width * squares + self.border(),
+            1 + cell_height * squares + self.border(),
+        )
+    }
+
+    pub fn fits(self, board_size: usize, width: u16, height: u16) -> bool {
+        let (grid_width, grid_height) = self.grid_size(board_size);
+        grid_width <= width && grid_height <= height
+    }
+
+    /// The largest scale whose grid fits in `width` x `height`
+    pub fn fit(board_size: usize, width: u16, height: u16) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scale| scale.fits(board_size, width, height))
+    }
+}
+
 pub struct CheckerBoard<'a> {
     board: &'a Board,
     cursor_pos: (usize, usize),
     selected_square: Option<(usize, usize)>,
     possible_moves: &'a [(usize, usize)],
     pieces_with_captures: &'a [(usize, usize)],
+    hint_move: Option<((usize, usize), (usize, usize))>,
+    last_move: Option<&'a Turn>,
+    captured_squares: &'a [(usize, usize)],
+    animation: Option<&'a AnimationFrame>,
+    flipped: bool,
+    theme: Theme,
 }
 
 impl<'a> CheckerBoard<'a> {
@@ -24,9 +98,26 @@ impl<'a> CheckerBoard<'a> {
             selected_square: None,
             possible_moves: &[],
             pieces_with_captures: &[],
+            hint_move: None,
+            last_move: None,
+            captured_squares: &[],
+            animation: None,
+            flipped: false,
+            theme: Theme::default(),
         }
     }
 
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Draws Black at the bottom, turning rows and columns around
+    pub fn flipped(mut self, flipped: bool) -> Self {
+        self.flipped = flipped;
+        self
+    }
+
     pub fn cursor_position(mut self, pos: (usize, usize)) -> Self {
         self.cursor_pos = pos;
         self
@@ -47,289 +138,422 @@ impl<'a> CheckerBoard<'a> {
         self
     }
 
-    fn render_cell(&self, buf: &mut Buffer, x: u16, y: u16, row: usize, col: usize) {
+    pub fn hint_move(mut self, hint_move: Option<((usize, usize), (usize, usize))>) -> Self {
+        self.hint_move = hint_move;
+        self
+    }
+
+    pub fn last_move(mut self, turn: Option<&'a Turn>) -> Self {
+        self.last_move = turn;
+        self
+    }
+
+    /// Squares where the last move removed pieces
+    pub fn captured_squares(mut self, squares: &'a [(usize, usize)]) -> Self {
+        self.captured_squares = squares;
+        self
+    }
+
+    /// Draws a frame of a move animation over the board. The frame's own
+    /// board replaces the one given to `new`.
+    pub fn animation(mut self, frame: Option<&'a AnimationFrame>) -> Self {
+        if let Some(frame) = frame {
+            self.board = &frame.board;
+        }
+        self.animation = frame;
+        self
+    }
+
+    fn piece_color(&self, piece: Piece) -> ratatui::style::Color {
+        match piece.color {
+            PieceColor::Black => self.theme.piece_black,
+            PieceColor::White => self.theme.piece_white,
+        }
+    }
+
+    /// Opacity left on a captured piece that is fading out
+    fn fade_at(&self, row: usize, col: usize) -> Option<f32> {
+        self.animation?
+            .fading
+            .iter()
+            .find(|(square, _)| *square == (row, col))
+            .map(|&(_, opacity)| opacity)
+    }
+
+    /// Arrow pointing from a square of the last move toward its next landing
+    fn last_move_arrow(&self, row: usize, col: usize) -> Option<&'static str> {
+        let turn = self.last_move?;
+        let (from, to) = turn
+            .path
+            .windows(2)
+            .map(|hop| (hop[0], hop[1]))
+            .find(|&(from, _)| from == (row, col))?;
+        let (from, to) = (
+            on_screen(from, self.board.size, self.flipped),
+            on_screen(to, self.board.size, self.flipped),
+        );
+        Some(match (to.0 < from.0, to.1 < from.1) {
+            (true, true) => "↖",
+            (true, false) => "↗",
+            (false, true) => "↙",
+            (false, false) => "↘",
+        })
+    }
+
+    /// Top-left corner and scale of the grid when rendered into `area`, or
+    /// None when the area can't hold even the compact board
+    fn layout(area: Rect, board_size: usize) -> Option<(u16, u16, BoardScale)> {
+        let scale = BoardScale::fit(board_size, area.width, area.height)?;
+        let (grid_width, grid_height) = scale.grid_size(board_size);
+
+        // Center the entire grid
+        let x_start = (area.width - grid_width) / 2 + area.x;
+        let y_start = (area.height - grid_height) / 4 + area.y; // 1/4 vertical offset
+        Some((x_start, y_start, scale))
+    }
+
+    /// Board square under the terminal cell (`column`, `row`) for a board of
+    /// `board_size` rendered into `area`. A cell's top and left borders count
+    /// as part of it.
+    pub fn square_at(
+        area: Rect,
+        board_size: usize,
+        flipped: bool,
+        column: u16,
+        row: u16,
+    ) -> Option<(usize, usize)> {
+        let (x_start, y_start, scale) = Self::layout(area, board_size)?;
+        let (cell_width, cell_height) = scale.cell_size();
+        let dx = column.checked_sub(x_start + scale.label_width())?;
+        let dy = row.checked_sub(y_start + 1)?;
+        let squares = board_size as u16;
+        if dx >= cell_width * squares || dy >= cell_height * squares {
+            return None;
+        }
+        let square = ((dy / cell_height) as usize, (dx / cell_width) as usize);
+        Some(on_screen(square, board_size, flipped))
+    }
+
+    /// Top-left terminal cell inside the borders of square (`row`, `col`)
+    fn content_origin(
+        scale: BoardScale,
+        x_start: u16,
+        y_start: u16,
+        row: f32,
+        col: f32,
+    ) -> (u16, u16) {
+        let (cell_width, cell_height) = scale.cell_size();
+        (
+            x_start
+                + scale.label_width()
+                + (col * cell_width as f32).round() as u16
+                + scale.border(),
+            y_start + 1 + (row * cell_height as f32).round() as u16 + scale.border(),
+        )
+    }
+
+    fn render_cell(
+        &self,
+        buf: &mut Buffer,
+        scale: BoardScale,
+        x: u16,
+        y: u16,
+        row: usize,
+        col: usize,
+    ) {
         let piece = self.board.get_piece(row, col);
         let is_possible_move = self.possible_moves.contains(&(row, col));
         let must_capture = self.pieces_with_captures.contains(&(row, col));
+        let is_hinted = self
+            .hint_move
+            .is_some_and(|(from, to)| from == (row, col) || to == (row, col));
+        let is_last_move = self
+            .last_move
+            .is_some_and(|turn| turn.path.contains(&(row, col)));
+        let arrow = self.last_move_arrow(row, col);
+        let was_captured = self.captured_squares.contains(&(row, col));
 
         // Determine cell background
-        let cell_style = if is_possible_move || (must_capture && piece.is_some()) {
-            Style::default().bg(Theme::POSSIBLE_MOVE)
+        let mut cell_style = if is_possible_move || (must_capture && piece.is_some()) {
+            Style::default().bg(self.theme.possible_move)
+        } else if is_hinted {
+            Style::default().bg(self.theme.hint_move)
+        } else if was_captured && piece.is_none() {
+            Style::default().bg(self.theme.captured)
+        } else if is_last_move {
+            Style::default().bg(self.theme.last_move)
         } else {
             Style::default()
         };
 
+        // Without grid lines there is no box to draw, so the cursor square
+        // is shown inverted
+        if scale == BoardScale::Compact && (row, col) == self.cursor_pos {
+            cell_style = cell_style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+
         // Check if it's a playable square (dark squares in checkers)
         let is_playable = (row + col) % 2 == 1;
 
-        // Cell content (5 chars wide)
         let content = match piece {
             None => {
-                if is_playable {
-                    "     ".to_string()
+                if let Some(arrow) = arrow {
+                    centered_mark(scale, arrow)
+                } else if was_captured {
+                    centered_mark(scale, "×")
+                } else if is_playable {
+                    centered_mark(scale, " ")
                 } else {
-                    " ░░░ ".to_string()
+                    light_square(scale)
                 }
             }
-            Some(p) => {
-                let piece_char = match (p.color, p.is_king) {
-                    (PieceColor::Black, false) => "(b)",
-                    (PieceColor::Black, true) => "(B)",
-                    (PieceColor::White, false) => "(w)",
-                    (PieceColor::White, true) => "(W)",
-                };
-                format!(" {piece_char} ")
+            // A fading piece shrinks to a dot before it disappears
+            Some(_) if self.fade_at(row, col).is_some_and(|opacity| opacity < 0.4) => {
+                centered_mark(scale, "·")
             }
+            Some(p) => piece_lines(scale, p),
         };
 
         // Render content
         let content_style = match piece {
-            Some(p) if p.color == PieceColor::Black => cell_style.fg(Theme::PIECE_BLACK),
-            Some(p) if p.color == PieceColor::White => cell_style.fg(Theme::PIECE_WHITE),
-            None if !is_playable => cell_style.fg(Theme::BOARD_LIGHT),
+            Some(p) => {
+                let style = cell_style.fg(self.piece_color(p));
+                if self.fade_at(row, col).is_some_and(|opacity| opacity < 1.0) {
+                    style.add_modifier(Modifier::DIM)
+                } else {
+                    style
+                }
+            }
+            None if arrow.is_some() => cell_style.fg(self.theme.text_accent),
+            None if was_captured => cell_style.fg(self.theme.text_primary),
+            None if !is_playable => cell_style.fg(self.theme.board_light),
             _ => cell_style,
         };
 
-        buf.set_string(x, y, &content, content_style);
-    }
-
-    fn get_border_chars(
-        &self,
-        row: usize,
-        col: usize,
-    ) -> (char, char, char, char, char, char, char, char, char) {
-        let is_cursor = (row, col) == self.cursor_pos;
-
-        if is_cursor {
-            // Double line box for cursor
-            ('╔', '═', '╗', '║', '║', '╚', '═', '╝', '─')
-        } else {
-            // Single line box
-            ('┌', '─', '┐', '│', '│', '└', '─', '┘', '─')
+        for (offset, line) in content.iter().enumerate() {
+            buf.set_string(x, y + offset as u16, line, content_style);
         }
     }
-}
 
-impl<'a> Widget for CheckerBoard<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Grid dimensions
-        const CELL_WIDTH: u16 = 6; // 5 content + 1 border
-        const CELL_HEIGHT: u16 = 2; // 1 content + 1 border
-        const LABEL_WIDTH: u16 = 4; // Row labels width
-
-        // Calculate total grid size (9x9 including labels)
-        let grid_width = LABEL_WIDTH + CELL_WIDTH * 8 + 1; // +1 for final border
-        let grid_height = 1 + CELL_HEIGHT * 8 + 1; // 1 for column labels, +1 for final border
-
-        if area.width < grid_width || area.height < grid_height {
-            // Debug: draw error message instead of nothing
-            let msg = format!(
-                "Need {}x{}, got {}x{}",
-                grid_width, grid_height, area.width, area.height
-            );
-            buf.set_string(
-                area.x,
-                area.y,
-                &msg,
-                Style::default().fg(ratatui::style::Color::Red),
-            );
+    /// Draws the sliding piece of an animation at its in-between position,
+    /// with sparkles around it while a freshly crowned piece celebrates
+    fn render_moving_piece(&self, buf: &mut Buffer, scale: BoardScale, x_start: u16, y_start: u16) {
+        let Some(frame) = self.animation else {
             return;
+        };
+        let (mut row, mut col) = frame.position;
+        if self.flipped {
+            let last = (self.board.size - 1) as f32;
+            (row, col) = (last - row, last - col);
         }
+        let (x, y) = Self::content_origin(scale, x_start, y_start, row, col);
 
-        // Center the entire grid
-        let x_start = (area.width.saturating_sub(grid_width)) / 2 + area.x;
-        let y_start = (area.height.saturating_sub(grid_height)) / 4 + area.y; // 1/4 vertical offset
-
-        // Draw column labels row
-        buf.set_string(
-            x_start,
-            y_start,
-            "    ", // Empty space for row label column
-            Style::default(),
-        );
-
-        for col in 0..8 {
-            let x = x_start + LABEL_WIDTH + col * CELL_WIDTH + CELL_WIDTH / 2;
-            buf.set_string(
-                x,
-                y_start,
-                format!("{}", (b'A' + col as u8) as char),
-                Style::default().fg(Theme::TEXT_SECONDARY),
-            );
+        let sparkle_color = match frame.flourish {
+            Some(step) if step % 2 == 0 => Some(self.theme.highlight),
+            Some(_) => Some(self.theme.text_accent),
+            None => None,
+        };
+        // Compact squares have no room for sparkles, so the piece itself
+        // flashes instead
+        let piece_color = match (scale, sparkle_color) {
+            (BoardScale::Compact, Some(color)) => color,
+            _ => self.piece_color(frame.piece),
+        };
+        let style = Style::default()
+            .fg(piece_color)
+            .add_modifier(Modifier::BOLD);
+
+        // Only the glyph itself is drawn so the grid lines it passes over
+        // stay visible
+        let lines = piece_lines(scale, frame.piece);
+        for (dy, line) in lines.iter().enumerate() {
+            for (dx, ch) in line.chars().enumerate() {
+                if ch != ' ' {
+                    buf.set_string(x + dx as u16, y + dy as u16, ch.to_string(), style);
+                }
+            }
         }
 
-        // Draw board rows with labels
-        for row in 0..8 {
-            let y_base = y_start + 1 + row * CELL_HEIGHT;
-
-            // Draw row label
-            let row_label = format!("{:>2} ", 8 - row);
-            buf.set_string(
-                x_start,
-                y_base + CELL_HEIGHT / 2,
-                &row_label,
-                Style::default().fg(Theme::TEXT_SECONDARY),
-            );
-
-            // Draw cells in this row
-            for col in 0..8 {
-                let x_pos = x_start + LABEL_WIDTH + col * CELL_WIDTH;
-                let y_pos = y_base;
-
-                // Check if this cell has cursor
-                let is_cursor_cell = (row as usize, col as usize) == self.cursor_pos;
-
-                if is_cursor_cell {
-                    // Draw complete box with double lines for cursor
-                    let cursor_style = Style::default()
-                        .fg(Theme::BORDER_FOCUSED)
-                        .add_modifier(Modifier::BOLD);
+        if let (Some(step), Some(color)) = (frame.flourish, sparkle_color) {
+            if scale != BoardScale::Compact {
+                let sparkle = ["✦", "✧", "*", "·"][step % 4];
+                let middle = y + lines.len() as u16 / 2;
+                let width = lines[0].chars().count() as u16;
+                let sparkle_style = Style::default().fg(color);
+                buf.set_string(x, middle, sparkle, sparkle_style);
+                buf.set_string(x + width - 1, middle, sparkle, sparkle_style);
+            }
+        }
+    }
 
-                    // Top border
-                    buf.set_string(x_pos, y_pos, "╔", cursor_style);
-                    for i in 1..CELL_WIDTH {
-                        buf.set_string(x_pos + i, y_pos, "═", cursor_style);
+    /// Single-line grid around every square
+    fn render_grid(&self, buf: &mut Buffer, scale: BoardScale, x_start: u16, y_start: u16) {
+        let (cell_width, cell_height) = scale.cell_size();
+        let left = x_start + scale.label_width();
+        let top = y_start + 1;
+        let last = self.board.size;
+        let border_style = Style::default().fg(self.theme.border);
+
+        for row in 0..=last {
+            for col in 0..=last {
+                let x = left + col as u16 * cell_width;
+                let y = top + row as u16 * cell_height;
+                let junction = match (row == 0, row == last, col == 0, col == last) {
+                    (true, _, true, _) => "┌",
+                    (true, _, _, true) => "┐",
+                    (_, true, true, _) => "└",
+                    (_, true, _, true) => "┘",
+                    (true, _, _, _) => "┬",
+                    (_, true, _, _) => "┴",
+                    (_, _, true, _) => "├",
+                    (_, _, _, true) => "┤",
+                    _ => "┼",
+                };
+                buf.set_string(x, y, junction, border_style);
+                if col < last {
+                    for i in 1..cell_width {
+                        buf.set_string(x + i, y, "─", border_style);
                     }
-                    buf.set_string(x_pos + CELL_WIDTH, y_pos, "╗", cursor_style);
-
-                    // Side borders
-                    buf.set_string(x_pos, y_pos + 1, "║", cursor_style);
-                    buf.set_string(x_pos + CELL_WIDTH, y_pos + 1, "║", cursor_style);
-
-                    // Bottom border
-                    buf.set_string(x_pos, y_pos + CELL_HEIGHT, "╚", cursor_style);
-                    for i in 1..CELL_WIDTH {
-                        buf.set_string(x_pos + i, y_pos + CELL_HEIGHT, "═", cursor_style);
+                }
+                if row < last {
+                    for i in 1..cell_height {
+                        buf.set_string(x, y + i, "│", border_style);
                     }
-                    buf.set_string(x_pos + CELL_WIDTH, y_pos + CELL_HEIGHT, "╝", cursor_style);
+                }
+            }
+        }
+    }
 
-                    // Render cell content
-                    self.render_cell(buf, x_pos + 1, y_pos + 1, row as usize, col as usize);
-                } else {
-                    // Normal cell - draw borders only where needed
-                    let (tl, t, tr, l, r, bl, b, br, _) =
-                        self.get_border_chars(row as usize, col as usize);
-                    let border_style = Style::default().fg(Theme::BORDER);
-
-                    // Top border (only for first row)
-                    if row == 0 {
-                        buf.set_string(x_pos, y_pos, tl.to_string(), border_style);
-                        for i in 1..CELL_WIDTH {
-                            buf.set_string(x_pos + i, y_pos, t.to_string(), border_style);
-                        }
-                        if col == 7 {
-                            buf.set_string(x_pos + CELL_WIDTH, y_pos, tr.to_string(), border_style);
-                        }
-                    }
+    /// Double-line box around the cursor square
+    fn render_cursor_box(&self, buf: &mut Buffer, scale: BoardScale, x_start: u16, y_start: u16) {
+        if self.cursor_pos.0 >= self.board.size || self.cursor_pos.1 >= self.board.size {
+            return;
+        }
+        let (row, col) = on_screen(self.cursor_pos, self.board.size, self.flipped);
+        let (cell_width, cell_height) = scale.cell_size();
+        let x = x_start + scale.label_width() + col as u16 * cell_width;
+        let y = y_start + 1 + row as u16 * cell_height;
+        let cursor_style = Style::default()
+            .fg(self.theme.border_focused)
+            .add_modifier(Modifier::BOLD);
+
+        buf.set_string(x, y, "╔", cursor_style);
+        buf.set_string(x + cell_width, y, "╗", cursor_style);
+        buf.set_string(x, y + cell_height, "╚", cursor_style);
+        buf.set_string(x + cell_width, y + cell_height, "╝", cursor_style);
+        for i in 1..cell_width {
+            buf.set_string(x + i, y, "═", cursor_style);
+            buf.set_string(x + i, y + cell_height, "═", cursor_style);
+        }
+        for i in 1..cell_height {
+            buf.set_string(x, y + i, "║", cursor_style);
+            buf.set_string(x + cell_width, y + i, "║", cursor_style);
+        }
+    }
+}
 
-                    // Left border (only for first column)
-                    if col == 0 {
-                        buf.set_string(x_pos, y_pos + 1, l.to_string(), border_style);
-                    }
+/// Where `square` is drawn: itself, or mirrored through the center when the
+/// board is flipped. Mirroring is its own inverse, so this also maps a
+/// square on screen back to the board.
+fn on_screen(square: (usize, usize), board_size: usize, flipped: bool) -> (usize, usize) {
+    if flipped {
+        (board_size - 1 - square.0, board_size - 1 - square.1)
+    } else {
+        square
+    }
+}
 
-                    // Right border (only for last column)
-                    if col == 7 {
-                        buf.set_string(x_pos + CELL_WIDTH, y_pos + 1, r.to_string(), border_style);
-                    }
+/// Content of a square at `scale`, one string per terminal row
+fn piece_lines(scale: BoardScale, piece: Piece) -> Vec<String> {
+    let letter = match (piece.color, piece.is_king) {
+        (PieceColor::Black, false) => 'b',
+        (PieceColor::Black, true) => 'B',
+        (PieceColor::White, false) => 'w',
+        (PieceColor::White, true) => 'W',
+    };
+    match scale {
+        BoardScale::Compact => vec![letter.to_string()],
+        BoardScale::Normal => vec![format!(" ({letter}) ")],
+        // Kings get a double rim
+        BoardScale::Large if piece.is_king => vec![
+            " ╔═══╗ ".to_string(),
+            format!(" ║ {letter} ║ "),
+            " ╚═══╝ ".to_string(),
+        ],
+        BoardScale::Large => vec![
+            " ╭───╮ ".to_string(),
+            format!(" │ {letter} │ "),
+            " ╰───╯ ".to_string(),
+        ],
+    }
+}
 
-                    // Bottom border (only for last row)
-                    if row == 7 {
-                        buf.set_string(x_pos, y_pos + CELL_HEIGHT, bl.to_string(), border_style);
-                        for i in 1..CELL_WIDTH {
-                            buf.set_string(
-                                x_pos + i,
-                                y_pos + CELL_HEIGHT,
-                                b.to_string(),
-                                border_style,
-                            );
-                        }
-                        if col == 7 {
-                            buf.set_string(
-                                x_pos + CELL_WIDTH,
-                                y_pos + CELL_HEIGHT,
-                                br.to_string(),
-                                border_style,
-                            );
-                        }
-                    }
+/// A single mark in the middle of an otherwise blank square
+fn centered_mark(scale: BoardScale, mark: &str) -> Vec<String> {
+    match scale {
+        BoardScale::Compact => vec![mark.to_string()],
+        BoardScale::Normal => vec![format!("  {mark}  ")],
+        BoardScale::Large => vec![" ".repeat(7), format!("   {mark}   "), " ".repeat(7)],
+    }
+}
 
-                    // Render cell content
-                    self.render_cell(buf, x_pos + 1, y_pos + 1, row as usize, col as usize);
-                }
+fn light_square(scale: BoardScale) -> Vec<String> {
+    match scale {
+        BoardScale::Compact => vec!["░".to_string()],
+        BoardScale::Normal => vec![" ░░░ ".to_string()],
+        BoardScale::Large => vec![" ░░░░░ ".to_string(); 3],
+    }
+}
 
-                // Intersections and internal borders (skip if cursor is involved)
-                if !is_cursor_cell {
-                    // Horizontal border between cells
-                    if row < 7 && (row as usize + 1, col as usize) != self.cursor_pos {
-                        // Bottom border of current cell / top border of next cell
-                        if col == 0 {
-                            buf.set_string(
-                                x_pos,
-                                y_pos + CELL_HEIGHT,
-                                "├",
-                                Style::default().fg(Theme::BORDER),
-                            );
-                        }
-                        for i in 1..CELL_WIDTH {
-                            buf.set_string(
-                                x_pos + i,
-                                y_pos + CELL_HEIGHT,
-                                "─",
-                                Style::default().fg(Theme::BORDER),
-                            );
-                        }
-                        if col == 7 {
-                            buf.set_string(
-                                x_pos + CELL_WIDTH,
-                                y_pos + CELL_HEIGHT,
-                                "┤",
-                                Style::default().fg(Theme::BORDER),
-                            );
-                        }
-                    }
+impl<'a> Widget for CheckerBoard<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let size = self.board.size;
+        let Some((x_start, y_start, scale)) = Self::layout(area, size) else {
+            let (width, height) = BoardScale::Compact.grid_size(size);
+            let msg = format!(
+                "Need {}x{}, got {}x{}",
+                width, height, area.width, area.height
+            );
+            buf.set_string(area.x, area.y, &msg, Style::default().fg(self.theme.error));
+            return;
+        };
+        let (cell_width, cell_height) = scale.cell_size();
+        let label_style = Style::default().fg(self.theme.text_secondary);
+
+        // Column letters centered over each square
+        for col in 0..size {
+            let (_, screen_col) = on_screen((0, col), size, self.flipped);
+            let x = x_start + scale.label_width() + screen_col as u16 * cell_width + cell_width / 2;
+            let letter = (b'A' + col as u8) as char;
+            buf.set_string(x, y_start, letter.to_string(), label_style);
+        }
 
-                    // Vertical border between cells
-                    if col < 7 && (row as usize, col as usize + 1) != self.cursor_pos {
-                        if row == 0 {
-                            buf.set_string(
-                                x_pos + CELL_WIDTH,
-                                y_pos,
-                                "┬",
-                                Style::default().fg(Theme::BORDER),
-                            );
-                        }
-                        buf.set_string(
-                            x_pos + CELL_WIDTH,
-                            y_pos + 1,
-                            "│",
-                            Style::default().fg(Theme::BORDER),
-                        );
-                        if row == 7 {
-                            buf.set_string(
-                                x_pos + CELL_WIDTH,
-                                y_pos + CELL_HEIGHT,
-                                "┴",
-                                Style::default().fg(Theme::BORDER),
-                            );
-                        }
-                    }
+        // Row numbers, counted from White's side
+        for row in 0..size {
+            let (screen_row, _) = on_screen((row, 0), size, self.flipped);
+            let y = y_start + 1 + screen_row as u16 * cell_height + cell_height / 2;
+            buf.set_string(x_start, y, format!("{:>2} ", size - row), label_style);
+        }
 
-                    // Intersection
-                    if row < 7
-                        && col < 7
-                        && (row as usize + 1, col as usize) != self.cursor_pos
-                        && (row as usize, col as usize + 1) != self.cursor_pos
-                        && (row as usize + 1, col as usize + 1) != self.cursor_pos
-                    {
-                        buf.set_string(
-                            x_pos + CELL_WIDTH,
-                            y_pos + CELL_HEIGHT,
-                            "┼",
-                            Style::default().fg(Theme::BORDER),
-                        );
-                    }
-                }
+        if scale != BoardScale::Compact {
+            self.render_grid(buf, scale, x_start, y_start);
+            self.render_cursor_box(buf, scale, x_start, y_start);
+        }
+
+        for row in 0..size {
+            for col in 0..size {
+                let (screen_row, screen_col) = on_screen((row, col), size, self.flipped);
+                let (x, y) = Self::content_origin(
+                    scale,
+                    x_start,
+                    y_start,
+                    screen_row as f32,
+                    screen_col as f32,
+                );
+                self.render_cell(buf, scale, x, y, row, col);
             }
         }
+
+        self.render_moving_piece(buf, scale, x_start, y_start);
     }
 }
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::core::clock::format_time;
+use crate::core::piece::Color;
+use crate::interface::theme::Theme;
+
+/// Time left below which a clock is drawn as a warning
+const LOW_TIME: Duration = Duration::from_secs(20);
+
+/// Both sides' remaining time, right-aligned, with the running side
+/// highlighted
+pub struct ClockDisplay {
+    white: Duration,
+    black: Duration,
+    running: Option<Color>,
+    theme: Theme,
+}
+
+impl ClockDisplay {
+    pub fn new(white: Duration, black: Duration) -> Self {
+        Self {
+            white,
+            black,
+            running: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn running(mut self, running: Option<Color>) -> Self {
+        self.running = running;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Columns the clocks take up
+    pub fn width(&self) -> u16 {
+        self.text().chars().count() as u16
+    }
+
+    fn text(&self) -> String {
+        self.spans()
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    fn spans(&self) -> Vec<Span<'static>> {
+        let face = |color: Color, left: Duration, name: &str| {
+            let mut style = if left < LOW_TIME {
+                Style::default().fg(self.theme.error)
+            } else if self.running == Some(color) {
+                Style::default().fg(self.theme.highlight)
+            } else {
+                Style::default().fg(self.theme.text_secondary)
+            };
+            if self.running == Some(color) {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            Span::styled(format!("{name} {}", format_time(left)), style)
+        };
+
+        vec![
+            face(Color::White, self.white, "White"),
+            Span::raw("  "),
+            face(Color::Black, self.black, "Black"),
+        ]
+    }
+}
+
+impl Widget for ClockDisplay {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(Line::from(self.spans()))
+            .alignment(Alignment::Right)
+            .render(area, buf);
+    }
+}
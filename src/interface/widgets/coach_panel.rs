@@ -0,0 +1,144 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::ai::coach::{CoachConversation, CoachRole};
+use crate::interface::theme::Theme;
+
+pub struct CoachPanel<'a> {
+    conversation: &'a CoachConversation,
+    input: &'a str,
+    theme: Theme,
+}
+
+impl<'a> CoachPanel<'a> {
+    pub fn new(conversation: &'a CoachConversation, input: &'a str) -> Self {
+        Self {
+            conversation,
+            input,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn transcript_lines(&self) -> Vec<Line<'a>> {
+        let mut lines = Vec::new();
+
+        for message in self.conversation.messages() {
+            match message.role {
+                CoachRole::Context => {
+                    lines.push(Line::from(Span::styled(
+                        "─ position sent to coach ─",
+                        Style::default().fg(self.theme.text_secondary),
+                    )));
+                }
+                CoachRole::Player => {
+                    lines.push(Line::from(vec![
+                        Span::styled("You: ", Style::default().fg(self.theme.text_accent)),
+                        Span::styled(
+                            message.text.as_str(),
+                            Style::default().fg(self.theme.text_primary),
+                        ),
+                    ]));
+                }
+                CoachRole::Coach => {
+                    let text = if message.text.is_empty() {
+                        "…"
+                    } else {
+                        message.text.trim()
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("Coach: ", Style::default().fg(self.theme.highlight)),
+                        Span::styled(text, Style::default().fg(self.theme.text_primary)),
+                    ]));
+                }
+            }
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Ask anything, e.g. \"why not D4?\" or \"what's the threat?\"",
+                Style::default().fg(self.theme.text_secondary),
+            )));
+        }
+
+        lines
+    }
+}
+
+impl<'a> Widget for CoachPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 4 {
+            return;
+        }
+
+        let title = vec![
+            Span::styled("🎓 ", Style::default().fg(self.theme.emoji)),
+            Span::styled("Coach", Style::default().fg(self.theme.text_accent)),
+        ];
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused))
+            .title(Line::from(title));
+
+        // Calculate inner area for padding
+        let inner = block.inner(area);
+        let padded_area = Rect {
+            x: inner.x + 1,
+            y: inner.y,
+            width: inner.width.saturating_sub(2),
+            height: inner.height,
+        };
+
+        block.render(area, buf);
+
+        // Transcript above, input line pinned to the bottom
+        let transcript_area = Rect {
+            height: padded_area.height.saturating_sub(1),
+            ..padded_area
+        };
+        let input_area = Rect {
+            y: padded_area.y + transcript_area.height,
+            height: 1,
+            ..padded_area
+        };
+
+        let transcript = Paragraph::new(self.transcript_lines())
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Left);
+
+        // Keep the newest messages in view
+        let total_lines = transcript.line_count(transcript_area.width) as u16;
+        let scroll = total_lines.saturating_sub(transcript_area.height);
+        transcript.scroll((scroll, 0)).render(transcript_area, buf);
+
+        let prompt_style = if self.conversation.is_awaiting_reply() {
+            Style::default().fg(self.theme.text_secondary)
+        } else {
+            Style::default()
+                .fg(self.theme.text_primary)
+                .add_modifier(Modifier::BOLD)
+        };
+
+        // Show the tail of long drafts so the caret stays visible
+        let max_input = input_area.width.saturating_sub(3) as usize;
+        let skip = self.input.chars().count().saturating_sub(max_input);
+        let visible_input: String = self.input.chars().skip(skip).collect();
+
+        let input_line = Line::from(vec![
+            Span::styled("> ", prompt_style),
+            Span::styled(visible_input, Style::default().fg(self.theme.text_primary)),
+            Span::styled("▏", Style::default().fg(self.theme.highlight)),
+        ]);
+        Paragraph::new(input_line).render(input_area, buf);
+    }
+}
@@ -0,0 +1,99 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::interface::keymap::{Action, KeyBindings};
+use crate::interface::theme::Theme;
+
+/// The result of a finished game, with the keys that review, replay or
+/// leave it
+pub struct GameOverScreen<'a> {
+    result: &'a str,
+    usage: Option<&'a str>,
+    bindings: &'a KeyBindings,
+    theme: Theme,
+}
+
+impl<'a> GameOverScreen<'a> {
+    pub fn new(result: &'a str, bindings: &'a KeyBindings) -> Self {
+        Self {
+            result,
+            usage: None,
+            bindings,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// What the AI cost this session, shown under the keys
+    pub fn usage(mut self, usage: Option<&'a str>) -> Self {
+        self.usage = usage;
+        self
+    }
+}
+
+impl<'a> Widget for GameOverScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.logo));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Top padding
+                Constraint::Length(1), // "Game Over"
+                Constraint::Length(1), // Space
+                Constraint::Length(1), // Winner message
+                Constraint::Length(1), // Space
+                Constraint::Length(1), // Analyze or replay keys
+                Constraint::Length(1), // Exit key
+                Constraint::Length(1), // Space
+                Constraint::Length(1), // AI usage summary
+                Constraint::Min(0),    // Bottom padding
+            ])
+            .split(inner);
+
+        let key = |action| self.bindings.primary_label(action);
+        let lines = [
+            (chunks[1], "Game Over".to_string(), self.theme.emoji),
+            (chunks[3], self.result.to_string(), self.theme.success),
+            (
+                chunks[5],
+                format!(
+                    "Press {} to analyze or {} to replay the game",
+                    key(Action::Analyze),
+                    key(Action::Replay)
+                ),
+                self.theme.text_primary,
+            ),
+            (
+                chunks[6],
+                format!("Press {} to exit...", key(Action::Quit)),
+                self.theme.text_primary,
+            ),
+        ];
+        for (area, text, color) in lines {
+            Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .alignment(Alignment::Center)
+                .render(area, buf);
+        }
+
+        if let Some(usage) = self.usage {
+            Paragraph::new(usage)
+                .style(Style::default().fg(self.theme.text_secondary))
+                .alignment(Alignment::Center)
+                .render(chunks[8], buf);
+        }
+    }
+}
@@ -14,7 +14,13 @@ pub struct GameStatus<'a> {
     ai_thinking: bool,
     is_local_mode: bool,
     ai_error: Option<&'a str>,
+    ai_remark: Option<&'a str>,
     is_simple_ai: bool,
+    /// The side the computer plays, if any
+    ai_seat: Option<Color>,
+    /// The side played over the network, if any
+    remote_seat: Option<Color>,
+    theme: Theme,
 }
 
 impl<'a> GameStatus<'a> {
@@ -24,10 +30,19 @@ impl<'a> GameStatus<'a> {
             ai_thinking: false,
             is_local_mode: false,
             ai_error: None,
+            ai_remark: None,
             is_simple_ai: false,
+            ai_seat: Some(Color::Black),
+            remote_seat: None,
+            theme: Theme::default(),
         }
     }
 
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     pub fn ai_thinking(mut self, thinking: bool) -> Self {
         self.ai_thinking = thinking;
         self
@@ -43,10 +58,25 @@ impl<'a> GameStatus<'a> {
         self
     }
 
+    pub fn ai_remark(mut self, remark: Option<&'a str>) -> Self {
+        self.ai_remark = remark;
+        self
+    }
+
     pub fn simple_ai(mut self, simple: bool) -> Self {
         self.is_simple_ai = simple;
         self
     }
+
+    pub fn ai_seat(mut self, seat: Option<Color>) -> Self {
+        self.ai_seat = seat;
+        self
+    }
+
+    pub fn remote_seat(mut self, seat: Option<Color>) -> Self {
+        self.remote_seat = seat;
+        self
+    }
 }
 
 impl<'a> Widget for GameStatus<'a> {
@@ -57,23 +87,40 @@ impl<'a> Widget for GameStatus<'a> {
             } else {
                 "AI is thinking..."
             }
+        } else if let Some(seat) = self.remote_seat {
+            match (self.current_player, self.current_player == seat) {
+                (Color::White, true) => "Current Turn: White (opponent)",
+                (Color::White, false) => "Current Turn: White (you)",
+                (Color::Black, true) => "Current Turn: Black (opponent)",
+                (Color::Black, false) => "Current Turn: Black (you)",
+            }
         } else {
-            match self.current_player {
-                Color::White => "Current Turn: White",
-                Color::Black => {
-                    if self.is_simple_ai {
-                        "Current Turn: Black (Simple AI)"
-                    } else {
-                        "Current Turn: Black"
-                    }
-                }
+            match (
+                self.current_player,
+                self.ai_seat == Some(self.current_player),
+            ) {
+                (Color::White, true) if self.is_simple_ai => "Current Turn: White (Simple AI)",
+                (Color::Black, true) if self.is_simple_ai => "Current Turn: Black (Simple AI)",
+                (Color::White, _) => "Current Turn: White",
+                (Color::Black, _) => "Current Turn: Black",
             }
         };
 
-        let line = Line::from(Span::styled(
+        let mut spans = vec![Span::styled(
             turn_text,
-            Style::default().fg(Theme::TEXT_PRIMARY),
-        ));
+            Style::default().fg(self.theme.text_primary),
+        )];
+        if let Some(remark) = self.ai_remark {
+            let speaker = match self.ai_seat {
+                Some(Color::White) => "White",
+                _ => "Black",
+            };
+            spans.push(Span::styled(
+                format!("   {speaker}: \"{remark}\""),
+                Style::default().fg(self.theme.text_accent),
+            ));
+        }
+        let line = Line::from(spans);
 
         let paragraph = Paragraph::new(vec![line]).alignment(Alignment::Left);
         paragraph.render(area, buf);
@@ -0,0 +1,105 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::interface::theme::Theme;
+
+const NOTE: &str = " Change them in keys.txt in the config directory";
+
+/// Popup listing every action with the keys bound to it
+pub struct HelpOverlay<'a> {
+    bindings: &'a [(String, &'static str)],
+    theme: Theme,
+}
+
+impl<'a> HelpOverlay<'a> {
+    pub fn new(bindings: &'a [(String, &'static str)]) -> Self {
+        Self {
+            bindings,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Room the popup needs, borders included
+    pub fn size(&self) -> (u16, u16) {
+        let key_width = self.key_width();
+        let description_width = self
+            .bindings
+            .iter()
+            .map(|(_, description)| description.chars().count())
+            .max()
+            .unwrap_or(0);
+        // Leading space and the gap between the columns, plus the borders
+        let width = (1 + key_width + 2 + description_width).max(NOTE.chars().count()) + 2;
+        let height = self.bindings.len() + 4;
+        (width as u16, height as u16)
+    }
+
+    fn key_width(&self) -> usize {
+        self.bindings
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// `size` centered in `area`, shrunk to fit
+    pub fn area(&self, area: Rect) -> Rect {
+        let (width, height) = self.size();
+        let width = width.min(area.width);
+        let height = height.min(area.height);
+        Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> Widget for HelpOverlay<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused))
+            .title(Span::styled(
+                " Keys ",
+                Style::default().fg(self.theme.text_accent),
+            ));
+
+        let key_width = self.key_width();
+        let mut lines: Vec<Line> = self
+            .bindings
+            .iter()
+            .map(|(keys, description)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {keys:>key_width$}  "),
+                        Style::default()
+                            .fg(self.theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(*description, Style::default().fg(self.theme.text_primary)),
+                ])
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            NOTE,
+            Style::default().fg(self.theme.text_secondary),
+        )));
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
@@ -10,25 +10,48 @@ use crate::interface::theme::Theme;
 
 pub struct HintDisplay<'a> {
     hint: Option<&'a str>,
+    verified: bool,
+    theme: Theme,
 }
 
 impl<'a> HintDisplay<'a> {
     pub fn new(hint: Option<&'a str>) -> Self {
-        Self { hint }
+        Self {
+            hint,
+            verified: true,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Unverified hints name a move the rules engine could not confirm
+    pub fn verified(mut self, verified: bool) -> Self {
+        self.verified = verified;
+        self
     }
 }
 
 impl<'a> Widget for HintDisplay<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if let Some(hint_text) = self.hint {
-            let title = vec![
-                Span::styled("💡 ", Style::default().fg(Theme::EMOJI)),
-                Span::styled("Hint", Style::default().fg(Theme::TEXT_ACCENT)),
+            let mut title = vec![
+                Span::styled("💡 ", Style::default().fg(self.theme.emoji)),
+                Span::styled("Hint", Style::default().fg(self.theme.text_accent)),
             ];
+            if !self.verified {
+                title.push(Span::styled(
+                    " (unverified) ",
+                    Style::default().fg(self.theme.text_secondary),
+                ));
+            }
 
             let block = Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Theme::BORDER))
+                .border_style(Style::default().fg(self.theme.border))
                 .title(Line::from(title));
 
             // Calculate inner area for padding
@@ -46,7 +69,7 @@ impl<'a> Widget for HintDisplay<'a> {
             // Then render the paragraph without block in the padded area
             let paragraph = Paragraph::new(Text::from(hint_text))
                 .wrap(Wrap { trim: true })
-                .style(Style::default().fg(Theme::TEXT_PRIMARY))
+                .style(Style::default().fg(self.theme.text_primary))
                 .alignment(Alignment::Left);
 
             paragraph.render(padded_area, buf);
@@ -1,9 +1,25 @@
+pub mod analysis_panel;
 pub mod board;
+pub mod clock_display;
+pub mod coach_panel;
+pub mod game_over_screen;
 pub mod game_status;
+pub mod help_overlay;
 pub mod hint_display;
+pub mod move_entry_bar;
+pub mod move_list;
+pub mod settings_screen;
 pub mod welcome_screen;
 
+pub use analysis_panel::AnalysisPanel;
 pub use board::CheckerBoard;
+pub use clock_display::ClockDisplay;
+pub use coach_panel::CoachPanel;
+pub use game_over_screen::GameOverScreen;
 pub use game_status::GameStatus;
+pub use help_overlay::HelpOverlay;
 pub use hint_display::HintDisplay;
+pub use move_entry_bar::MoveEntryBar;
+pub use move_list::MoveList;
+pub use settings_screen::SettingsScreen;
 pub use welcome_screen::WelcomeScreen;
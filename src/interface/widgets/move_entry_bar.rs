@@ -0,0 +1,74 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::interface::theme::Theme;
+
+/// Most completions listed after the input; the rest are counted
+const SHOWN_COMPLETIONS: usize = 6;
+
+/// The move command line: a prompt with the typed text, followed by the legal
+/// moves it could still become
+pub struct MoveEntryBar<'a> {
+    input: &'a str,
+    completions: &'a [String],
+    theme: Theme,
+}
+
+impl<'a> MoveEntryBar<'a> {
+    pub fn new(input: &'a str, completions: &'a [String]) -> Self {
+        Self {
+            input,
+            completions,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for MoveEntryBar<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![
+            Span::styled("Move › ", Style::default().fg(self.theme.text_accent)),
+            Span::styled(self.input, Style::default().fg(self.theme.text_primary)),
+            Span::styled(
+                "_",
+                Style::default()
+                    .fg(self.theme.highlight)
+                    .add_modifier(Modifier::SLOW_BLINK),
+            ),
+            Span::raw("   "),
+        ];
+
+        if self.completions.is_empty() {
+            spans.push(Span::styled(
+                "no legal move matches",
+                Style::default().fg(self.theme.error),
+            ));
+        } else {
+            let shown = self.completions.iter().take(SHOWN_COMPLETIONS);
+            let listed = shown.cloned().collect::<Vec<_>>().join("  ");
+            spans.push(Span::styled(
+                listed,
+                Style::default().fg(self.theme.text_secondary),
+            ));
+            let hidden = self.completions.len().saturating_sub(SHOWN_COMPLETIONS);
+            if hidden > 0 {
+                spans.push(Span::styled(
+                    format!("  +{hidden} more"),
+                    Style::default().fg(self.theme.text_secondary),
+                ));
+            }
+        }
+
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+}
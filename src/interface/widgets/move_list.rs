@@ -0,0 +1,179 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::core::{
+    board::Board, move_history::MoveHistory, notation::turn_to_algebraic, piece::Color,
+};
+use crate::interface::theme::Theme;
+
+/// Width the side panel needs next to the board
+pub const MOVE_LIST_WIDTH: u16 = 26;
+/// Lines above the moves: the material for each side and a separator
+const HEADER_HEIGHT: usize = 3;
+
+/// Numbered moves in White/Black columns with the material balance on top
+pub struct MoveList<'a> {
+    board: &'a Board,
+    history: &'a MoveHistory,
+    scroll: usize,
+    theme: Theme,
+}
+
+impl<'a> MoveList<'a> {
+    pub fn new(board: &'a Board, history: &'a MoveHistory) -> Self {
+        Self {
+            board,
+            history,
+            scroll: 0,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Rows scrolled back from the latest move
+    pub fn scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// How many move rows fit in `area` under the borders and material
+    pub fn visible_rows(area: Rect) -> usize {
+        if area.height < 5 {
+            return 0;
+        }
+        (area.height as usize - 2).saturating_sub(HEADER_HEIGHT)
+    }
+
+    /// Numbered rows of the move list, White's move first
+    pub fn rows(history: &MoveHistory, board_size: usize) -> Vec<String> {
+        let mut rows: Vec<(String, Option<String>)> = Vec::new();
+
+        for (player, turn) in history.turns() {
+            let notation = turn_to_algebraic(&turn, board_size);
+            match (player, rows.last_mut()) {
+                (Color::Black, Some((_, black @ None))) => *black = Some(notation),
+                (Color::Black, _) => rows.push(("...".to_string(), Some(notation))),
+                (Color::White, _) => rows.push((notation, None)),
+            }
+        }
+
+        rows.iter()
+            .enumerate()
+            .map(|(i, (white, black))| {
+                format!(
+                    "{:>3}. {:<9}{}",
+                    i + 1,
+                    white,
+                    black.as_deref().unwrap_or("")
+                )
+            })
+            .collect()
+    }
+
+    /// One-line material count for terminals too narrow for the panel
+    pub fn summary(board: &Board, history: &MoveHistory) -> String {
+        format!(
+            "Moves {}  •  Captured W {} B {}  •  Kings W {} B {}",
+            history.turns().len(),
+            history.captured_by(Color::White),
+            history.captured_by(Color::Black),
+            count_kings(board, Color::White),
+            count_kings(board, Color::Black),
+        )
+    }
+
+    fn material_lines(&self) -> Vec<Line<'a>> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .map(|color| {
+                let (name, piece_color) = match color {
+                    Color::White => ("White", self.theme.piece_white),
+                    Color::Black => ("Black", self.theme.piece_black),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{name:<7}"), Style::default().fg(piece_color)),
+                    Span::styled(
+                        format!(
+                            "took {:<3}kings {}",
+                            self.history.captured_by(color),
+                            count_kings(self.board, color)
+                        ),
+                        Style::default().fg(self.theme.text_secondary),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+fn count_kings(board: &Board, color: Color) -> usize {
+    (0..board.size)
+        .flat_map(|row| (0..board.size).map(move |col| (row, col)))
+        .filter_map(|(row, col)| board.get_piece(row, col))
+        .filter(|piece| piece.color == color && piece.is_king)
+        .count()
+}
+
+impl<'a> Widget for MoveList<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 5 {
+            return;
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border))
+            .title(Span::styled(
+                " Moves ",
+                Style::default().fg(self.theme.text_accent),
+            ));
+
+        let inner = block.inner(area);
+        let padded_area = Rect {
+            x: inner.x + 1,
+            y: inner.y,
+            width: inner.width.saturating_sub(1),
+            height: inner.height,
+        };
+        block.render(area, buf);
+
+        let mut lines = self.material_lines();
+        lines.push(Line::from(Span::styled(
+            "─".repeat(padded_area.width as usize),
+            Style::default().fg(self.theme.separator),
+        )));
+
+        let rows = Self::rows(self.history, self.board.size);
+        if rows.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No moves yet",
+                Style::default().fg(self.theme.text_secondary),
+            )));
+        } else {
+            // Follow the latest move unless scrolled back
+            let visible = Self::visible_rows(area);
+            let end = rows
+                .len()
+                .saturating_sub(self.scroll)
+                .max(visible.min(rows.len()));
+            let start = end.saturating_sub(visible);
+            lines.extend(rows[start..end].iter().map(|row| {
+                Line::from(Span::styled(
+                    row.clone(),
+                    Style::default().fg(self.theme.text_primary),
+                ))
+            }));
+        }
+
+        Paragraph::new(lines).render(padded_area, buf);
+    }
+}
@@ -0,0 +1,173 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::interface::keymap::{Action, KeyBindings};
+use crate::interface::theme::Theme;
+
+/// The settings of config.txt, one per line, with the selected one marked
+pub struct SettingsScreen<'a> {
+    /// Each setting's name, value and the environment variable overriding it
+    rows: &'a [(&'static str, String, Option<&'static str>)],
+    selected: usize,
+    message: Option<&'a str>,
+    bindings: KeyBindings,
+    theme: Theme,
+}
+
+impl<'a> SettingsScreen<'a> {
+    pub fn new(rows: &'a [(&'static str, String, Option<&'static str>)], selected: usize) -> Self {
+        Self {
+            rows,
+            selected,
+            message: None,
+            bindings: KeyBindings::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Keys named in the instructions
+    pub fn bindings(mut self, bindings: &KeyBindings) -> Self {
+        self.bindings = bindings.clone();
+        self
+    }
+
+    fn instructions(&self) -> String {
+        let key = |action| self.bindings.primary_label(action);
+        format!(
+            "{}/{} choose, {}/{} change, {} or {} to go back",
+            key(Action::Up),
+            key(Action::Down),
+            key(Action::Left),
+            key(Action::Right),
+            key(Action::Select),
+            key(Action::Quit)
+        )
+    }
+
+    /// Where the last change was saved, shown under the list
+    pub fn message(mut self, message: Option<&'a str>) -> Self {
+        self.message = message;
+        self
+    }
+
+    fn setting_lines(&self) -> Vec<Line<'a>> {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|(name, _, _)| name.len())
+            .max()
+            .unwrap_or(0);
+
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(i, (name, value, overridden_by))| {
+                let is_selected = i == self.selected;
+                let marker = if is_selected { "▶ " } else { "  " };
+                let value_style = if is_selected {
+                    Style::default()
+                        .fg(self.theme.text_accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.text_primary)
+                };
+                let value = if is_selected {
+                    format!("◀ {value} ▶")
+                } else {
+                    format!("  {value}")
+                };
+
+                let mut spans = vec![
+                    Span::styled(marker, Style::default().fg(self.theme.highlight)),
+                    Span::styled(
+                        format!("{:<name_width$}  ", name.replace('_', " ")),
+                        Style::default().fg(self.theme.text_secondary),
+                    ),
+                    Span::styled(value, value_style),
+                ];
+                if let Some(variable) = overridden_by {
+                    spans.push(Span::styled(
+                        format!("  ({variable} overrides)"),
+                        Style::default().fg(self.theme.text_secondary),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
+
+impl<'a> Widget for SettingsScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Centered column as wide as the welcome screen's
+        let width = 64.min(area.width);
+        let height = (self.rows.len() as u16 + 6).min(area.height);
+        let area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        if area.height < 4 {
+            return;
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.border_focused))
+            .title(Span::styled(
+                " Settings ",
+                Style::default().fg(self.theme.text_accent),
+            ));
+        let inner = block.inner(area);
+        let padded_area = Rect {
+            x: inner.x + 1,
+            y: inner.y,
+            width: inner.width.saturating_sub(2),
+            height: inner.height,
+        };
+        block.render(area, buf);
+
+        // Settings at the top, then the message and the keys at the bottom
+        let list_area = Rect {
+            height: padded_area.height.saturating_sub(3),
+            ..padded_area
+        };
+        let visible = list_area.height as usize;
+        let scroll = (self.selected + 1).saturating_sub(visible) as u16;
+        Paragraph::new(self.setting_lines())
+            .scroll((scroll, 0))
+            .render(list_area, buf);
+
+        let footer_area = Rect {
+            y: padded_area.y + list_area.height,
+            height: padded_area.height - list_area.height,
+            ..padded_area
+        };
+        let mut footer = vec![Line::default()];
+        if let Some(message) = self.message {
+            footer.push(Line::from(Span::styled(
+                message,
+                Style::default().fg(self.theme.text_primary),
+            )));
+        }
+        footer.push(Line::from(Span::styled(
+            self.instructions(),
+            Style::default().fg(self.theme.text_secondary),
+        )));
+        Paragraph::new(footer)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(footer_area, buf);
+    }
+}
@@ -1,11 +1,12 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
+use crate::interface::keymap::{Action, KeyBindings};
 use crate::interface::theme::Theme;
 
 pub struct WelcomeScreen {
@@ -13,6 +14,13 @@ pub struct WelcomeScreen {
     tip_of_the_day: String,
     todays_challenge: String,
     is_simple_ai: bool,
+    /// Two players share the keyboard, with no computer opponent
+    two_players: bool,
+    persona: Option<(String, String)>,
+    /// ENTER continues a saved game rather than starting one
+    resumed: bool,
+    bindings: KeyBindings,
+    theme: Theme,
 }
 
 impl WelcomeScreen {
@@ -22,14 +30,46 @@ impl WelcomeScreen {
             tip_of_the_day,
             todays_challenge,
             is_simple_ai: false,
+            two_players: false,
+            persona: None,
+            resumed: false,
+            bindings: KeyBindings::default(),
+            theme: Theme::default(),
         }
     }
 
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Keys named in the instructions
+    pub fn bindings(mut self, bindings: &KeyBindings) -> Self {
+        self.bindings = bindings.clone();
+        self
+    }
+
     pub fn simple_ai(mut self, simple: bool) -> Self {
         self.is_simple_ai = simple;
         self
     }
 
+    pub fn two_players(mut self, two_players: bool) -> Self {
+        self.two_players = two_players;
+        self
+    }
+
+    pub fn resumed(mut self, resumed: bool) -> Self {
+        self.resumed = resumed;
+        self
+    }
+
+    /// Name and description of the selected opponent persona
+    pub fn persona(mut self, name: &str, description: &str) -> Self {
+        self.persona = Some((name.to_string(), description.to_string()));
+        self
+    }
+
     fn wrap_text(&self, text: &str, max_width: usize) -> Vec<String> {
         let words: Vec<&str> = text.split_whitespace().collect();
         let mut lines = Vec::new();
@@ -63,7 +103,7 @@ impl WelcomeScreen {
 
         let header_text: Vec<Line> = header_lines
             .iter()
-            .map(|line| Line::from(Span::styled(*line, Style::default().fg(Color::Magenta))))
+            .map(|line| Line::from(Span::styled(*line, Style::default().fg(self.theme.logo))))
             .collect();
 
         let header = Paragraph::new(header_text).alignment(Alignment::Center);
@@ -74,7 +114,7 @@ impl WelcomeScreen {
         let separator = "░".repeat(30);
         let sep_paragraph = Paragraph::new(Line::from(Span::styled(
             separator,
-            Style::default().fg(Theme::LOGO), // Keep original color for logo
+            Style::default().fg(self.theme.logo),
         )))
         .alignment(Alignment::Center);
         sep_paragraph.render(area, buf);
@@ -88,10 +128,10 @@ impl WelcomeScreen {
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Theme::BORDER))
+            .border_style(Style::default().fg(self.theme.border))
             .title(Span::styled(
                 " Did You Know? ",
-                Style::default().fg(Theme::TEXT_ACCENT),
+                Style::default().fg(self.theme.text_accent),
             ));
 
         // Calculate inner area for padding
@@ -114,7 +154,7 @@ impl WelcomeScreen {
         // Then render the paragraph without block in the padded area
         let paragraph = Paragraph::new(Text::from(self.did_you_know.as_str()))
             .wrap(Wrap { trim: true })
-            .style(Style::default().fg(Theme::TEXT_PRIMARY))
+            .style(Style::default().fg(self.theme.text_primary))
             .alignment(Alignment::Left); // Changed to left alignment
 
         paragraph.render(padded_area, buf);
@@ -122,8 +162,11 @@ impl WelcomeScreen {
 
     fn render_tip_of_the_day(&self, area: Rect, buf: &mut Buffer) {
         let title = vec![
-            Span::styled("💡 ", Style::default().fg(Theme::EMOJI)),
-            Span::styled("Tip of the Day", Style::default().fg(Theme::TEXT_ACCENT)),
+            Span::styled("💡 ", Style::default().fg(self.theme.emoji)),
+            Span::styled(
+                "Tip of the Day",
+                Style::default().fg(self.theme.text_accent),
+            ),
         ];
 
         let underline = "════════════════";
@@ -142,14 +185,14 @@ impl WelcomeScreen {
             Line::from(title),
             Line::from(Span::styled(
                 underline,
-                Style::default().fg(Theme::HIGHLIGHT),
+                Style::default().fg(self.theme.highlight),
             )),
         ];
 
         for line in wrapped_text {
             content.push(Line::from(Span::styled(
                 line,
-                Style::default().fg(Theme::TEXT_PRIMARY),
+                Style::default().fg(self.theme.text_primary),
             )));
         }
 
@@ -160,8 +203,11 @@ impl WelcomeScreen {
 
     fn render_todays_challenge(&self, area: Rect, buf: &mut Buffer) {
         let title = vec![
-            Span::styled("🎯 ", Style::default().fg(Theme::EMOJI)),
-            Span::styled("Today's Challenge", Style::default().fg(Theme::TEXT_ACCENT)),
+            Span::styled("🎯 ", Style::default().fg(self.theme.emoji)),
+            Span::styled(
+                "Today's Challenge",
+                Style::default().fg(self.theme.text_accent),
+            ),
         ];
 
         let underline = "═══════════════════";
@@ -180,14 +226,14 @@ impl WelcomeScreen {
             Line::from(title),
             Line::from(Span::styled(
                 underline,
-                Style::default().fg(Theme::HIGHLIGHT),
+                Style::default().fg(self.theme.highlight),
             )),
         ];
 
         for line in wrapped_text {
             content.push(Line::from(Span::styled(
                 line,
-                Style::default().fg(Theme::TEXT_PRIMARY),
+                Style::default().fg(self.theme.text_primary),
             )));
         }
 
@@ -196,15 +242,51 @@ impl WelcomeScreen {
         paragraph.render(padded_area, buf);
     }
 
+    fn render_persona(&self, area: Rect, buf: &mut Buffer) {
+        // The simple AI has no style to choose, nor has a second player
+        let Some((name, description)) = self
+            .persona
+            .as_ref()
+            .filter(|_| !self.is_simple_ai && !self.two_players)
+        else {
+            return;
+        };
+
+        let line = Line::from(vec![
+            Span::styled("Opponent: ", Style::default().fg(self.theme.text_secondary)),
+            Span::styled(
+                format!("◀ {name} ▶"),
+                Style::default().fg(self.theme.text_accent),
+            ),
+            Span::styled(
+                format!("  {description}"),
+                Style::default().fg(self.theme.text_primary),
+            ),
+        ]);
+
+        Paragraph::new(line)
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+
     fn render_instructions(&self, area: Rect, buf: &mut Buffer) {
-        let text = if self.is_simple_ai {
-            "Press ENTER to play against Simple AI or Q/ESC to quit..."
+        let key = |action| self.bindings.primary_label(action);
+        let start = key(Action::Select);
+        let settings = key(Action::Settings);
+        let quit = self.bindings.label(Action::Quit);
+        let text = if self.resumed {
+            format!("Press {start} to continue your saved game, {settings} for settings or {quit} to quit...")
+        } else if self.two_players {
+            format!("Press {start} to start a two-player game, {settings} for settings or {quit} to quit...")
+        } else if self.is_simple_ai {
+            format!("Press {start} to play against Simple AI, {settings} for settings or {quit} to quit...")
         } else {
-            "Press ENTER to play against AI or Q/ESC to quit..."
+            let opponent = format!("{}/{}", key(Action::Left), key(Action::Right));
+            format!("{start} to play against AI, {opponent} to change opponent, {settings} for settings, {quit} to quit")
         };
 
         let instructions = Paragraph::new(text)
-            .style(Style::default().fg(Theme::TEXT_SECONDARY))
+            .style(Style::default().fg(self.theme.text_secondary))
             .alignment(Alignment::Center); // Keep centered for instructions
 
         instructions.render(area, buf);
@@ -226,6 +308,8 @@ impl Widget for WelcomeScreen {
                 Constraint::Length(4), // Tip of the Day
                 Constraint::Length(1), // Space
                 Constraint::Length(4), // Today's Challenge
+                Constraint::Length(1), // Space before persona
+                Constraint::Length(1), // Opponent persona
                 Constraint::Length(1), // Space before instructions
                 Constraint::Length(1), // Instructions
                 Constraint::Min(0),    // Remaining space
@@ -267,7 +351,9 @@ impl Widget for WelcomeScreen {
         };
         self.render_todays_challenge(challenge_area, buf);
 
+        self.render_persona(chunks[10], buf);
+
         // Instructions (keep full width for centering)
-        self.render_instructions(chunks[10], buf);
+        self.render_instructions(chunks[12], buf);
     }
 }
@@ -1,8 +1,14 @@
 pub mod ai;
 pub mod application;
+pub mod cli;
+pub mod config;
 pub mod core;
 pub mod interface;
+pub mod net;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod state;
+pub mod text_protocol;
 pub mod utils;
 
 // Make this available for integration tests too
@@ -1,14 +1,185 @@
-mod ai;
-mod application;
-mod core;
-mod interface;
-mod state;
-mod utils;
-
-use crate::application::Application;
+use checkers_rs::ai::analysis::GameAnalysis;
+use checkers_rs::ai::cache::flush_responses;
+use checkers_rs::ai::tournament::{run_match, MatchReport};
+use checkers_rs::application::Application;
+use checkers_rs::cli::{self, Command};
+use checkers_rs::config::{config_warnings, configured_options};
+use checkers_rs::core::fen::parse_fen;
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::pdn::result_string;
+use checkers_rs::interface::keymap::keymap_warnings;
+use checkers_rs::interface::theme::theme_warnings;
+use checkers_rs::net::{dxp_engine, NetRole};
+use checkers_rs::text_protocol::{self, perft_lines};
+use checkers_rs::utils::log;
+use checkers_rs::utils::prompts::prompt_warnings;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let app = Application::new().await?;
-    app.run().await
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let cli = match cli::parse(args.clone(), configured_options()) {
+        Ok(cli) => cli,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("Run checkers-rs help for the commands and their options");
+            std::process::exit(2);
+        }
+    };
+
+    match cli.command {
+        Command::Help(text) => {
+            println!("{text}");
+            return Ok(());
+        }
+        Command::Version => {
+            println!("checkers-rs {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        // Everything a bug report needs, gathered into one file
+        Command::DebugBundle { path } => {
+            let path = path.unwrap_or_else(|| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                format!("checkers-debug-{}.txt", now.as_secs())
+            });
+            dotenv::dotenv().ok();
+            let bundle = log::debug_bundle(cli.logging.log_path().as_deref(), |name| {
+                std::env::var(name).ok()
+            });
+            std::fs::write(&path, bundle)?;
+            println!("Wrote {path}; please attach it to the bug report");
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    log::init(&cli.logging);
+    log::info(
+        "start",
+        json!({ "version": env!("CARGO_PKG_VERSION"), "args": args }),
+    );
+
+    let (options, network, broadcast) = match cli.command {
+        Command::Play {
+            options,
+            network,
+            broadcast,
+        } => (options, network, broadcast),
+        // Other programs drive the engine over stdin and stdout, with no board
+        Command::Engine => {
+            text_protocol::run(std::io::stdin().lock(), std::io::stdout().lock())?;
+            return Ok(());
+        }
+        // Other front ends use the rules and the engine over HTTP
+        #[cfg(feature = "server")]
+        Command::Serve { address } => {
+            use checkers_rs::server;
+            let listener = server::bind(&address).await?;
+            println!("Serving the API on http://{}", listener.local_addr()?);
+            server::serve(listener, std::sync::Arc::new(server::api::Api::new())).await?;
+            return Ok(());
+        }
+        Command::Analyze { path } => {
+            let text =
+                std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+            match GameAnalysis::from_pdn(&text) {
+                Ok(analysis) => print!("{}", analysis.to_pdn()),
+                Err(error) => {
+                    eprintln!("{}: {error}", path.display());
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        Command::Perft { depth, fen } => {
+            let game = match fen {
+                Some(fen) => parse_fen(&fen)?,
+                None => CheckersGame::new(),
+            };
+            for line in perft_lines(&game, depth) {
+                println!("{line}");
+            }
+            return Ok(());
+        }
+        // Engine matches run headless too
+        Command::Selfplay(config) => {
+            let tally = run_match(&config, |game, tally| {
+                println!(
+                    "Game {}/{} ({}): {} vs {} {}, running score {} - {} - {}",
+                    game.round,
+                    config.games,
+                    game.opening,
+                    game.white,
+                    game.black,
+                    result_string(game.winner),
+                    tally.wins,
+                    tally.losses,
+                    tally.draws
+                );
+            })?;
+            println!(
+                "{}",
+                MatchReport {
+                    config: &config,
+                    tally
+                }
+            );
+            println!("Games written to {}", config.pdn.display());
+            return Ok(());
+        }
+        Command::Help(_) | Command::Version | Command::DebugBundle { .. } => unreachable!(),
+    };
+
+    // The built-in engine plays DXP games on its own, without the board
+    match &network {
+        Some(NetRole::EngineDxp {
+            address,
+            seat,
+            time: (minutes, moves),
+        }) => {
+            let game = dxp_engine::initiate(address, *seat, *minutes, *moves).await?;
+            println!("{game}");
+            return Ok(());
+        }
+        Some(NetRole::EngineDxpListen { address }) => {
+            dxp_engine::serve(address).await?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // Report rejected settings, prompts, themes and keys before the terminal switches screens
+    for warning in config_warnings() {
+        log::warn("config", json!({ "ignored": warning.to_string() }));
+        eprintln!("Ignoring {warning}");
+    }
+    for warning in prompt_warnings() {
+        log::warn(
+            "config",
+            json!({ "ignored": format!("custom prompt {warning}") }),
+        );
+        eprintln!("Ignoring custom prompt {warning}");
+    }
+    for warning in theme_warnings() {
+        log::warn(
+            "config",
+            json!({ "ignored": format!("theme setting in {warning}") }),
+        );
+        eprintln!("Ignoring theme setting in {warning}");
+    }
+    for warning in keymap_warnings() {
+        log::warn(
+            "config",
+            json!({ "ignored": format!("key binding in {warning}") }),
+        );
+        eprintln!("Ignoring key binding in {warning}");
+    }
+
+    let app = Application::new(options, network, broadcast).await?;
+    let result = app.run().await;
+    flush_responses();
+    result
 }
@@ -0,0 +1,397 @@
+//! Live broadcast of a game to read-only watchers. The game publishes a
+//! snapshot whenever something visible changes; every watcher connected to
+//! the broadcast address gets the latest one as a line of JSON, then each
+//! new one, and rebuilds the game from the steps it lists.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::core::clock::{Bonus, Clock, TimeControl};
+use crate::core::piece::Color;
+use crate::net::link::{with_port, ACCEPT_BACKOFF, RECONNECT_DELAY};
+use crate::net::protocol::{replay_steps, steps, Step};
+use crate::net::{remote, NetError};
+use crate::state::GameSession;
+
+/// Bumped whenever a snapshot changes shape; watchers must agree
+pub const BROADCAST_VERSION: u32 = 1;
+/// Port used when a broadcast address leaves it out
+pub const DEFAULT_BROADCAST_PORT: u16 = 7879;
+/// Where a game is broadcast, or watched, when no address is given
+pub const DEFAULT_BROADCAST_ADDRESS: &str = "127.0.0.1";
+
+/// Both clocks as the watchers need them to keep counting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockSnapshot {
+    /// Time each side had when the running side's turn began
+    pub white_ms: u64,
+    pub black_ms: u64,
+    pub running: Option<Color>,
+    /// How long the running side has been thinking
+    pub running_ms: u64,
+    /// Bronstein delay, which is not charged at the start of each turn
+    pub delay_ms: u64,
+}
+
+impl ClockSnapshot {
+    fn of(clock: &Clock, now: Instant) -> Self {
+        let since = clock.turn_started().unwrap_or(now);
+        let left = |color| clock.left(color, since).as_millis() as u64;
+        Self {
+            white_ms: left(Color::White),
+            black_ms: left(Color::Black),
+            running: clock.running_side(),
+            running_ms: now.saturating_duration_since(since).as_millis() as u64,
+            delay_ms: match clock.control.bonus {
+                Bonus::Delay(delay) => delay.as_millis() as u64,
+                Bonus::None | Bonus::Increment(_) => 0,
+            },
+        }
+    }
+
+    /// A clock that goes on counting from `now` the way the original does
+    pub fn to_clock(&self, now: Instant) -> Clock {
+        let (white, black) = (
+            Duration::from_millis(self.white_ms),
+            Duration::from_millis(self.black_ms),
+        );
+        let bonus = match self.delay_ms {
+            0 => Bonus::None,
+            delay => Bonus::Delay(Duration::from_millis(delay)),
+        };
+        let since = now
+            .checked_sub(Duration::from_millis(self.running_ms))
+            .unwrap_or(now);
+        Clock::showing(
+            TimeControl::new(white.max(black), bonus),
+            white,
+            black,
+            self.running.map(|color| (color, since)),
+        )
+    }
+}
+
+/// Everything a watcher shows: the steps played, the clocks and the result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub moves: Vec<Step>,
+    pub clock: Option<ClockSnapshot>,
+    pub finished: bool,
+    /// The winner of a finished game; none is a draw
+    pub winner: Option<Color>,
+}
+
+impl Snapshot {
+    pub fn of(session: &GameSession, now: Instant) -> Self {
+        let outcome = outcome(session, now);
+        Self {
+            version: BROADCAST_VERSION,
+            moves: steps(&session.game.move_history),
+            clock: session
+                .clock
+                .as_ref()
+                .map(|clock| ClockSnapshot::of(clock, now)),
+            finished: outcome.is_some(),
+            winner: outcome.flatten(),
+        }
+    }
+
+    /// Whether `other` shows the same moment of the game, however far the
+    /// running clock has got since
+    pub fn same_moment(&self, other: &Snapshot) -> bool {
+        let running = |snapshot: &Snapshot| snapshot.clock.as_ref().map(|clock| clock.running);
+        self.moves == other.moves
+            && self.finished == other.finished
+            && self.winner == other.winner
+            && running(self) == running(other)
+    }
+
+    /// The same snapshot `elapsed` later
+    pub fn aged(&self, elapsed: Duration) -> Self {
+        let mut aged = self.clone();
+        if let Some(clock) = aged.clock.as_mut().filter(|clock| clock.running.is_some()) {
+            clock.running_ms += elapsed.as_millis() as u64;
+        }
+        aged
+    }
+
+    /// Writes the snapshot as one line, newline included
+    pub fn encode(&self) -> String {
+        let mut line = serde_json::to_string(self).expect("snapshots always serialize");
+        line.push('\n');
+        line
+    }
+
+    /// Reads a line, checking the version before the rest of it
+    pub fn decode(line: &str) -> Result<Self, NetError> {
+        let value: Value =
+            serde_json::from_str(line.trim()).map_err(|e| NetError::Malformed(e.to_string()))?;
+        let theirs = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if theirs != BROADCAST_VERSION {
+            return Err(NetError::VersionMismatch {
+                ours: BROADCAST_VERSION,
+                theirs,
+            });
+        }
+        serde_json::from_value(value).map_err(|e| NetError::Malformed(e.to_string()))
+    }
+}
+
+/// How the game has ended, if it has: `Some(winner)`, where no winner is a
+/// draw. Covers flagged clocks and network results as well as the board.
+pub fn outcome(session: &GameSession, now: Instant) -> Option<Option<Color>> {
+    if let Some(loser) = session.clock.as_ref().and_then(|clock| clock.flagged(now)) {
+        return Some(Some(loser.opposite()));
+    }
+    if session.remote.is_some() {
+        return remote::outcome(session);
+    }
+    let game = &session.game;
+    if let Some(winner) = game.check_winner() {
+        Some(Some(winner))
+    } else if game.is_stalemate() {
+        Some(Some(game.current_player.opposite()))
+    } else {
+        None
+    }
+}
+
+/// Takes `--broadcast [ADDRESS]` out of the command line, returning the
+/// address to broadcast on, if asked for, and the remaining arguments
+pub fn take_broadcast_flag<I: IntoIterator<Item = String>>(
+    args: I,
+) -> (Option<String>, Vec<String>) {
+    let mut args = args.into_iter().peekable();
+    let mut address = None;
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--broadcast" {
+            address = Some(
+                args.next_if(|next| !next.starts_with("--"))
+                    .unwrap_or_else(|| DEFAULT_BROADCAST_ADDRESS.to_string()),
+            );
+        } else {
+            rest.push(arg);
+        }
+    }
+    (address, rest)
+}
+
+type Latest = Option<(Snapshot, Instant)>;
+
+/// The publishing end: listens for watchers and hands each of them the
+/// latest snapshot and every one after it
+pub struct Broadcast {
+    latest: watch::Sender<Latest>,
+    published: Option<Snapshot>,
+    address: String,
+    task: JoinHandle<()>,
+}
+
+impl Broadcast {
+    pub async fn start(address: &str) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(with_port(address, DEFAULT_BROADCAST_PORT)).await?;
+        let address = listener.local_addr()?.to_string();
+        let (latest, watching) = watch::channel(None);
+        let task = tokio::spawn(run_broadcast(listener, watching));
+        Ok(Self {
+            latest,
+            published: None,
+            address,
+            task,
+        })
+    }
+
+    /// Where watchers connect
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Watchers connected right now
+    pub fn watchers(&self) -> usize {
+        // The task waiting for new watchers holds one receiver too
+        self.latest.receiver_count().saturating_sub(1)
+    }
+
+    /// Sends `snapshot` to the watchers unless they already have this moment
+    pub fn publish(&mut self, snapshot: Snapshot) {
+        if let Some(published) = &self.published {
+            if published.same_moment(&snapshot) {
+                return;
+            }
+        }
+        self.latest
+            .send_replace(Some((snapshot.clone(), Instant::now())));
+        self.published = Some(snapshot);
+    }
+}
+
+impl Drop for Broadcast {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run_broadcast(listener: TcpListener, watching: watch::Receiver<Latest>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            tokio::time::sleep(ACCEPT_BACKOFF).await;
+            continue;
+        };
+        tokio::spawn(serve_watcher(stream, watching.clone()));
+    }
+}
+
+/// Writes snapshots to one watcher until it leaves or the game closes
+async fn serve_watcher(stream: TcpStream, mut latest: watch::Receiver<Latest>) {
+    let (_, mut writer) = stream.into_split();
+    loop {
+        let line = latest
+            .borrow_and_update()
+            .as_ref()
+            .map(|(snapshot, published)| snapshot.aged(published.elapsed()).encode());
+        if let Some(line) = line {
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+        if latest.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// What happened on a watcher's connection, in the order it happened
+#[derive(Debug)]
+pub enum WatchEvent {
+    Connected(String),
+    Snapshot(Snapshot),
+    /// The connection dropped; the watcher keeps trying to get back
+    Disconnected(String),
+    /// The watcher gave up for good
+    Failed(NetError),
+}
+
+/// The watching end: follows a broadcast, reconnecting whenever it drops
+pub struct Watch {
+    events: UnboundedReceiver<WatchEvent>,
+    address: String,
+}
+
+impl Watch {
+    pub fn connect(address: &str) -> Self {
+        let address = with_port(address, DEFAULT_BROADCAST_PORT);
+        let (events_tx, events) = unbounded_channel();
+        tokio::spawn(run_watcher(address.clone(), events_tx));
+        Self { events, address }
+    }
+
+    /// The broadcast being watched
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn try_recv(&mut self) -> Option<WatchEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits for the next event
+    pub async fn recv(&mut self) -> Option<WatchEvent> {
+        self.events.recv().await
+    }
+}
+
+async fn run_watcher(address: String, events: UnboundedSender<WatchEvent>) {
+    loop {
+        if events.is_closed() {
+            return;
+        }
+        let stream = match TcpStream::connect(&address).await {
+            Ok(stream) => stream,
+            Err(_) => {
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        let peer = stream
+            .peer_addr()
+            .map_or_else(|_| address.clone(), |peer| peer.to_string());
+        if events.send(WatchEvent::Connected(peer)).is_err() {
+            return;
+        }
+
+        let mut lines = BufReader::new(stream).lines();
+        let reason = loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => match Snapshot::decode(&line) {
+                    Ok(snapshot) => {
+                        if events.send(WatchEvent::Snapshot(snapshot)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(error) => {
+                        let _ = events.send(WatchEvent::Failed(error));
+                        return;
+                    }
+                },
+                Ok(None) => break "the broadcast ended".to_string(),
+                Err(e) => break e.to_string(),
+            }
+        };
+        if events.send(WatchEvent::Disconnected(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// A game followed from a broadcast, for the watcher's screen
+#[derive(Clone, Debug)]
+pub struct Watching {
+    pub connected: bool,
+    /// Connection news, shown under the status
+    pub notice: Option<String>,
+    /// How the game ended, once the broadcast says it has
+    pub result: Option<Option<Color>>,
+}
+
+impl Watching {
+    pub fn new(notice: impl Into<String>) -> Self {
+        Self {
+            connected: false,
+            notice: Some(notice.into()),
+            result: None,
+        }
+    }
+}
+
+/// Shows `snapshot` in a watcher's session: the game rebuilt from its
+/// steps, the clocks counting from `now` and the result
+pub fn follow(session: &GameSession, snapshot: &Snapshot, now: Instant) -> GameSession {
+    let mut new_session = session.clone();
+    let Some(watching) = new_session.watching.as_mut() else {
+        return new_session;
+    };
+
+    if steps(&session.game.move_history) != snapshot.moves {
+        match replay_steps(&snapshot.moves) {
+            Ok(game) => new_session.game = game,
+            Err(e) => {
+                watching.notice = Some(format!("Could not follow the game: {e}"));
+                return new_session;
+            }
+        }
+    }
+    watching.result = snapshot.finished.then_some(snapshot.winner);
+    new_session.game.is_game_over = snapshot.finished;
+    new_session.clock = snapshot.clock.as_ref().map(|clock| clock.to_clock(now));
+    new_session
+}
@@ -0,0 +1,383 @@
+//! The Draughts eXchange Protocol used to match engines against each other.
+//! Messages are plain ASCII with fixed-width fields, each ended by a NUL
+//! byte, and a move is a whole turn: start and end square plus every
+//! captured square, numbered the standard way for the board in play.
+//!
+//! DXP was written for 10x10 international draughts; this game plays its own
+//! rules on its own board, so the other engine has to play them too.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::core::game::CheckersGame;
+use crate::core::notation::{number_to_square, square_number};
+use crate::core::piece::Color;
+use crate::core::Turn;
+use crate::net::NetError;
+
+/// The protocol version sent in game requests
+pub const DXP_VERSION: u32 = 1;
+
+/// Port DXP engines usually listen on
+pub const DXP_PORT: u16 = 27531;
+
+/// Longest name that fits the name fields
+const NAME_WIDTH: usize = 32;
+/// Longest chat text
+const CHAT_WIDTH: usize = 126;
+
+/// Why a follower turned a game request down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Acceptance {
+    Accepted,
+    VersionUnsupported,
+    InitiatorRefused,
+    TimeRefused,
+    Refused,
+}
+
+impl Acceptance {
+    fn code(self) -> char {
+        match self {
+            Self::Accepted => '0',
+            Self::VersionUnsupported => '1',
+            Self::InitiatorRefused => '2',
+            Self::TimeRefused => '3',
+            Self::Refused => '9',
+        }
+    }
+
+    fn from_code(code: char) -> Self {
+        match code {
+            '0' => Self::Accepted,
+            '1' => Self::VersionUnsupported,
+            '2' => Self::InitiatorRefused,
+            '3' => Self::TimeRefused,
+            _ => Self::Refused,
+        }
+    }
+}
+
+/// How the sender of a GAMEEND sees the result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    Unknown,
+    ILose,
+    Draw,
+    IWin,
+}
+
+impl EndReason {
+    fn code(self) -> char {
+        match self {
+            Self::Unknown => '0',
+            Self::ILose => '1',
+            Self::Draw => '2',
+            Self::IWin => '3',
+        }
+    }
+
+    fn from_code(code: char) -> Self {
+        match code {
+            '1' => Self::ILose,
+            '2' => Self::Draw,
+            '3' => Self::IWin,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The same result seen from the other side
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::ILose => Self::IWin,
+            Self::IWin => Self::ILose,
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DxpMessage {
+    /// GAMEREQ: the initiator proposes a game from the initial position
+    GameRequest {
+        version: u32,
+        name: String,
+        /// The side the follower would play
+        follower: Color,
+        /// Thinking time in minutes for `moves` moves
+        minutes: u32,
+        moves: u32,
+    },
+    /// GAMEACC: the follower's answer
+    GameAccept {
+        name: String,
+        acceptance: Acceptance,
+    },
+    /// MOVE: a whole turn with the seconds spent on it
+    Move {
+        seconds: u32,
+        from: usize,
+        to: usize,
+        captured: Vec<usize>,
+    },
+    /// GAMEEND: `stop` asks for no further game on this connection
+    GameEnd {
+        reason: EndReason,
+        stop: bool,
+    },
+    Chat(String),
+    /// BACKREQ: take back to the position before move `move_number` with
+    /// `to_move` to play
+    BackRequest {
+        move_number: u32,
+        to_move: Color,
+    },
+    /// BACKACC: whether the take-back was done
+    BackAccept {
+        accepted: bool,
+    },
+}
+
+fn color_code(color: Color) -> char {
+    match color {
+        Color::White => 'W',
+        Color::Black => 'Z',
+    }
+}
+
+fn color_from_code(code: char) -> Option<Color> {
+    match code {
+        'W' | 'w' => Some(Color::White),
+        'Z' | 'z' | 'B' | 'b' => Some(Color::Black),
+        _ => None,
+    }
+}
+
+impl DxpMessage {
+    /// The message's text, without the closing NUL
+    pub fn encode(&self) -> String {
+        match self {
+            Self::GameRequest {
+                version,
+                name,
+                follower,
+                minutes,
+                moves,
+            } => format!(
+                "R{:02}{}{}{:03}{:03}A",
+                version,
+                fixed(name, NAME_WIDTH),
+                color_code(*follower),
+                minutes.min(&999),
+                moves.min(&999)
+            ),
+            Self::GameAccept { name, acceptance } => {
+                format!("A{}{}", fixed(name, NAME_WIDTH), acceptance.code())
+            }
+            Self::Move {
+                seconds,
+                from,
+                to,
+                captured,
+            } => {
+                let mut text = format!(
+                    "M{:04}{:02}{:02}{:02}",
+                    seconds.min(&9999),
+                    from,
+                    to,
+                    captured.len()
+                );
+                for square in captured {
+                    text.push_str(&format!("{square:02}"));
+                }
+                text
+            }
+            Self::GameEnd { reason, stop } => {
+                format!("E{}{}", reason.code(), if *stop { '1' } else { '0' })
+            }
+            Self::Chat(text) => format!("C{}", text.chars().take(CHAT_WIDTH).collect::<String>()),
+            Self::BackRequest {
+                move_number,
+                to_move,
+            } => format!("B{:03}{}", move_number.min(&999), color_code(*to_move)),
+            Self::BackAccept { accepted } => format!("K{}", if *accepted { '0' } else { '2' }),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, NetError> {
+        let malformed = || NetError::Malformed(format!("DXP message {text:?}"));
+        let mut chars = text.chars();
+        let kind = chars.next().ok_or_else(malformed)?;
+        let body: String = chars.collect();
+        let field = |range: std::ops::Range<usize>| body.get(range).ok_or_else(malformed);
+        let number = |range: std::ops::Range<usize>| -> Result<u32, NetError> {
+            field(range)?.trim().parse().map_err(|_| malformed())
+        };
+        let char_at = |index: usize| body.chars().nth(index).ok_or_else(malformed);
+
+        match kind {
+            'R' => {
+                let follower = color_from_code(char_at(34)?).ok_or_else(malformed)?;
+                if body.get(41..42).is_some_and(|start| start != "A") {
+                    return Err(NetError::Malformed(
+                        "only games from the initial position are supported".to_string(),
+                    ));
+                }
+                Ok(Self::GameRequest {
+                    version: number(0..2)?,
+                    name: field(2..34)?.trim_end().to_string(),
+                    follower,
+                    minutes: number(35..38)?,
+                    moves: number(38..41)?,
+                })
+            }
+            'A' => Ok(Self::GameAccept {
+                name: field(0..32)?.trim_end().to_string(),
+                acceptance: Acceptance::from_code(char_at(32)?),
+            }),
+            'M' => {
+                let count = number(8..10)? as usize;
+                let captured = (0..count)
+                    .map(|i| number(10 + 2 * i..12 + 2 * i).map(|square| square as usize))
+                    .collect::<Result<_, _>>()?;
+                Ok(Self::Move {
+                    seconds: number(0..4)?,
+                    from: number(4..6)? as usize,
+                    to: number(6..8)? as usize,
+                    captured,
+                })
+            }
+            'E' => Ok(Self::GameEnd {
+                reason: EndReason::from_code(char_at(0)?),
+                stop: char_at(1)? == '1',
+            }),
+            'C' => Ok(Self::Chat(body)),
+            'B' => Ok(Self::BackRequest {
+                move_number: number(0..3)?,
+                to_move: color_from_code(char_at(3)?).ok_or_else(malformed)?,
+            }),
+            'K' => Ok(Self::BackAccept {
+                accepted: char_at(0)? == '0',
+            }),
+            _ => Err(malformed()),
+        }
+    }
+}
+
+/// Pads or cuts `text` to exactly `width` characters
+fn fixed(text: &str, width: usize) -> String {
+    let cut: String = text.chars().take(width).collect();
+    format!("{cut:<width$}")
+}
+
+/// Reads one NUL-terminated message; `None` once the connection closed
+pub async fn read_dxp<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<DxpMessage>, NetError> {
+    let mut bytes = Vec::new();
+    if reader.read_until(0, &mut bytes).await? == 0 {
+        return Ok(None);
+    }
+    if bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    // Some engines end messages with a line break as well
+    let text = String::from_utf8_lossy(&bytes);
+    DxpMessage::parse(text.trim_matches(['\r', '\n'])).map(Some)
+}
+
+pub async fn write_dxp<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &DxpMessage,
+) -> Result<(), NetError> {
+    let mut bytes = message.encode().into_bytes();
+    bytes.push(0);
+    writer.write_all(&bytes).await?;
+    Ok(())
+}
+
+/// Squares captured along a turn, in the order they were jumped
+fn captured_squares(turn: &Turn) -> Vec<(usize, usize)> {
+    if !turn.is_capture() {
+        return Vec::new();
+    }
+    turn.path
+        .windows(2)
+        .map(|hop| ((hop[0].0 + hop[1].0) / 2, (hop[0].1 + hop[1].1) / 2))
+        .collect()
+}
+
+/// A turn as a DXP MOVE
+pub fn turn_to_dxp(turn: &Turn, board_size: usize, spent: Duration) -> DxpMessage {
+    let number = |(row, col): (usize, usize)| square_number(row, col, board_size).unwrap_or(0);
+    DxpMessage::Move {
+        seconds: spent.as_secs() as u32,
+        from: number(turn.from()),
+        to: number(turn.to()),
+        captured: captured_squares(turn).into_iter().map(number).collect(),
+    }
+}
+
+/// The legal turn a DXP MOVE stands for. When several jumps share their
+/// start and end, the captured squares tell them apart, in any order.
+pub fn turn_from_dxp(
+    game: &CheckersGame,
+    from: usize,
+    to: usize,
+    captured: &[usize],
+) -> Option<Turn> {
+    let size = game.board.size;
+    let from = number_to_square(from, size)?;
+    let to = number_to_square(to, size)?;
+    let mut wanted = captured
+        .iter()
+        .map(|&number| number_to_square(number, size))
+        .collect::<Option<Vec<_>>>()?;
+    wanted.sort();
+
+    game.legal_turns().into_iter().find(|turn| {
+        let mut jumped = captured_squares(turn);
+        jumped.sort();
+        turn.from() == from && turn.to() == to && (wanted.is_empty() || jumped == wanted)
+    })
+}
+
+/// How many turns into the game the position before `move_number` with
+/// `to_move` to play lies, White moving first
+pub fn back_request_turns(move_number: u32, to_move: Color) -> usize {
+    let whole_moves = move_number.saturating_sub(1) as usize * 2;
+    match to_move {
+        Color::White => whole_moves,
+        Color::Black => whole_moves + 1,
+    }
+}
+
+/// The game after its first `turns` turns
+pub fn take_back(game: &CheckersGame, turns: usize) -> Option<CheckersGame> {
+    let played = game.move_history.turns();
+    if turns > played.len() {
+        return None;
+    }
+    played
+        .iter()
+        .take(turns)
+        .try_fold(CheckersGame::new(), |game, (_, turn)| {
+            game.apply_turn(turn).ok()
+        })
+}
+
+/// How a game over on the board looks from `seat`: the side left without
+/// pieces or moves loses
+pub fn board_result(game: &CheckersGame, seat: Color) -> Option<EndReason> {
+    let loser = match game.check_winner() {
+        Some(winner) => winner.opposite(),
+        None if game.is_stalemate() => game.current_player,
+        None => return None,
+    };
+    Some(if loser == seat {
+        EndReason::ILose
+    } else {
+        EndReason::IWin
+    })
+}
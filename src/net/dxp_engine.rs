@@ -0,0 +1,291 @@
+//! The built-in engine as a DXP player, with no board on screen. It either
+//! asks another engine for a game or waits for engines to ask it, and plays
+//! with the same search as the AI.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use tokio::io::BufReader;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::ai::engine::{search_within, DRAW_AFTER_TURNS};
+use crate::core::game::CheckersGame;
+use crate::core::piece::Color;
+use crate::net::dxp::{
+    back_request_turns, board_result, read_dxp, take_back, turn_from_dxp, turn_to_dxp, write_dxp,
+    Acceptance, DxpMessage, EndReason, DXP_PORT, DXP_VERSION,
+};
+use crate::net::dxp_link::DXP_NAME;
+use crate::net::link::with_port;
+use crate::net::NetError;
+use crate::utils::log;
+
+/// Thinking time per move never drops below this
+const MIN_MOVE_TIME: Duration = Duration::from_millis(100);
+
+type Reader = BufReader<OwnedReadHalf>;
+
+/// A finished engine game, seen from the built-in engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineGame {
+    pub opponent: String,
+    pub seat: Color,
+    pub result: EndReason,
+    pub turns: usize,
+}
+
+impl fmt::Display for EngineGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = match self.result {
+            EndReason::IWin => "won",
+            EndReason::ILose => "lost",
+            EndReason::Draw => "drew",
+            EndReason::Unknown => "stopped",
+        };
+        write!(
+            f,
+            "Played {:?} against {}: {result} after {} turns",
+            self.seat, self.opponent, self.turns
+        )
+    }
+}
+
+/// Asks the engine at `address` for one game in which the built-in engine
+/// plays `seat` with `minutes` for `moves` moves
+pub async fn initiate(
+    address: &str,
+    seat: Color,
+    minutes: u32,
+    moves: u32,
+) -> Result<EngineGame, NetError> {
+    let stream = TcpStream::connect(with_port(address, DXP_PORT)).await?;
+    let (read, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    let request = DxpMessage::GameRequest {
+        version: DXP_VERSION,
+        name: DXP_NAME.to_string(),
+        follower: seat.opposite(),
+        minutes,
+        moves,
+    };
+    write_dxp(&mut writer, &request).await?;
+    let opponent = match read_dxp(&mut reader).await? {
+        Some(DxpMessage::GameAccept {
+            name,
+            acceptance: Acceptance::Accepted,
+        }) => name,
+        Some(DxpMessage::GameAccept { acceptance, .. }) => {
+            return Err(NetError::Rejected(format!(
+                "game request refused ({acceptance:?})"
+            )))
+        }
+        other => {
+            return Err(NetError::Unexpected {
+                expected: "GAMEACC",
+                got: format!("{other:?}"),
+            })
+        }
+    };
+
+    let budget = move_time(minutes, moves);
+    let (result, turns) = play(&mut reader, &mut writer, seat, budget).await?;
+    Ok(EngineGame {
+        opponent,
+        seat,
+        result,
+        turns,
+    })
+}
+
+/// Takes the next engine to connect and plays every game it asks for,
+/// returning them once it hangs up
+pub async fn follow(listener: &TcpListener) -> Result<Vec<EngineGame>, NetError> {
+    let (stream, _) = listener.accept().await?;
+    let (read, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    let mut games = Vec::new();
+
+    loop {
+        let request = match read_dxp(&mut reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(games),
+            Err(NetError::Malformed(_)) => {
+                refuse(&mut writer, Acceptance::Refused).await?;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let DxpMessage::GameRequest {
+            version,
+            name,
+            follower,
+            minutes,
+            moves,
+        } = request
+        else {
+            // Anything left over from the last game
+            continue;
+        };
+        if version != DXP_VERSION {
+            refuse(&mut writer, Acceptance::VersionUnsupported).await?;
+            continue;
+        }
+
+        let accept = DxpMessage::GameAccept {
+            name: DXP_NAME.to_string(),
+            acceptance: Acceptance::Accepted,
+        };
+        write_dxp(&mut writer, &accept).await?;
+        let budget = move_time(minutes, moves);
+        let (result, turns) = play(&mut reader, &mut writer, follower, budget).await?;
+        games.push(EngineGame {
+            opponent: name,
+            seat: follower,
+            result,
+            turns,
+        });
+    }
+}
+
+/// Listens on `address` for engines, printing each game as it ends
+pub async fn serve(address: &str) -> Result<(), NetError> {
+    let listener = TcpListener::bind(with_port(address, DXP_PORT)).await?;
+    println!("Waiting for DXP engines on {}", listener.local_addr()?);
+    loop {
+        match follow(&listener).await {
+            Ok(games) => games.iter().for_each(|game| println!("{game}")),
+            Err(e) => {
+                log::error("dxp_game_failed", json!({ "message": e.to_string() }));
+                eprintln!("{e}");
+            }
+        }
+    }
+}
+
+async fn refuse(writer: &mut OwnedWriteHalf, acceptance: Acceptance) -> Result<(), NetError> {
+    let refusal = DxpMessage::GameAccept {
+        name: DXP_NAME.to_string(),
+        acceptance,
+    };
+    write_dxp(writer, &refusal).await
+}
+
+/// An even share of the time for the moves it has to cover
+fn move_time(minutes: u32, moves: u32) -> Duration {
+    let total = Duration::from_secs(u64::from(minutes) * 60);
+    (total / moves.max(1)).max(MIN_MOVE_TIME)
+}
+
+/// Plays one game as `seat`, returning the result and the number of turns
+async fn play(
+    reader: &mut Reader,
+    writer: &mut OwnedWriteHalf,
+    seat: Color,
+    budget: Duration,
+) -> Result<(EndReason, usize), NetError> {
+    let mut game = CheckersGame::new();
+
+    loop {
+        let turns = game.move_history.turns().len();
+        let over =
+            board_result(&game, seat).or((turns >= DRAW_AFTER_TURNS).then_some(EndReason::Draw));
+        if let Some(reason) = over {
+            return end_game(reader, writer, reason, turns).await;
+        }
+
+        if game.current_player == seat {
+            let started = Instant::now();
+            let position = game.clone();
+            let best = tokio::task::spawn_blocking(move || search_within(&position, budget))
+                .await
+                .map_err(|e| NetError::Io(e.into()))?;
+            let Some(best) = best else {
+                return end_game(reader, writer, EndReason::ILose, turns).await;
+            };
+            game = game
+                .apply_turn(&best.turn)
+                .map_err(|error| NetError::IllegalMove {
+                    from: best.turn.from(),
+                    to: best.turn.to(),
+                    error,
+                })?;
+            let size = game.board.size;
+            write_dxp(writer, &turn_to_dxp(&best.turn, size, started.elapsed())).await?;
+            continue;
+        }
+
+        match read_dxp(reader).await? {
+            None => {
+                return Err(NetError::Ended(
+                    "the engine closed the connection".to_string(),
+                ))
+            }
+            Some(DxpMessage::Move {
+                from, to, captured, ..
+            }) => {
+                let Some(turn) = turn_from_dxp(&game, from, to, &captured) else {
+                    let end = DxpMessage::GameEnd {
+                        reason: EndReason::Unknown,
+                        stop: true,
+                    };
+                    write_dxp(writer, &end).await?;
+                    return Err(NetError::Unexpected {
+                        expected: "a legal move",
+                        got: format!("{from}-{to}"),
+                    });
+                };
+                game = game
+                    .apply_turn(&turn)
+                    .map_err(|error| NetError::IllegalMove {
+                        from: turn.from(),
+                        to: turn.to(),
+                        error,
+                    })?;
+            }
+            Some(DxpMessage::GameEnd { reason, .. }) => {
+                let end = DxpMessage::GameEnd {
+                    reason: reason.mirrored(),
+                    stop: false,
+                };
+                write_dxp(writer, &end).await?;
+                return Ok((reason.mirrored(), turns));
+            }
+            Some(DxpMessage::BackRequest {
+                move_number,
+                to_move,
+            }) => {
+                let taken_back = take_back(&game, back_request_turns(move_number, to_move));
+                let accepted = taken_back.is_some();
+                if let Some(taken_back) = taken_back {
+                    game = taken_back;
+                }
+                write_dxp(writer, &DxpMessage::BackAccept { accepted }).await?;
+            }
+            // Chat is for people, and the handshake is over
+            Some(_) => {}
+        }
+    }
+}
+
+/// Ends the game from this side and waits for the other engine to confirm
+async fn end_game(
+    reader: &mut Reader,
+    writer: &mut OwnedWriteHalf,
+    reason: EndReason,
+    turns: usize,
+) -> Result<(EndReason, usize), NetError> {
+    let end = DxpMessage::GameEnd {
+        reason,
+        stop: false,
+    };
+    write_dxp(writer, &end).await?;
+    loop {
+        match read_dxp(reader).await? {
+            Some(DxpMessage::GameEnd { .. }) | None => return Ok((reason, turns)),
+            Some(_) => {}
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Playing a DXP engine from the board. The link speaks DXP on the wire and
+//! the usual `Message`s to the game, so `RemotePlay` treats the engine like
+//! any other opponent: whole-turn MOVEs become one step per hop, GAMEEND
+//! becomes a resignation or a draw, and a BACKREQ becomes a resync.
+
+use std::time::Instant;
+
+use tokio::io::BufReader;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::core::game::CheckersGame;
+use crate::core::notation::number_to_square;
+use crate::core::piece::Color;
+use crate::net::dxp::{
+    back_request_turns, board_result, read_dxp, take_back, turn_from_dxp, turn_to_dxp, write_dxp,
+    Acceptance, DxpMessage, EndReason, DXP_PORT, DXP_VERSION,
+};
+use crate::net::link::{with_port, ACCEPT_BACKOFF, HANDSHAKE_TIMEOUT, RECONNECT_DELAY};
+use crate::net::protocol::{steps, Message};
+use crate::net::{Link, NetError, NetEvent};
+
+/// Name given in DXP handshakes
+pub const DXP_NAME: &str = "checkers-rs";
+
+type Reader = BufReader<OwnedReadHalf>;
+
+impl Link {
+    /// Connects to a DXP engine, trying until it is there, and asks for a
+    /// game in which this end plays `seat` with `minutes` for `moves` moves
+    pub fn dxp_connect(address: &str, seat: Color, minutes: u32, moves: u32) -> Self {
+        let (link, outgoing, events) = Self::channels(with_port(address, DXP_PORT));
+        let request = DxpMessage::GameRequest {
+            version: DXP_VERSION,
+            name: DXP_NAME.to_string(),
+            follower: seat.opposite(),
+            minutes,
+            moves,
+        };
+        tokio::spawn(run_initiator(
+            link.address().to_string(),
+            request,
+            outgoing,
+            events,
+        ));
+        link
+    }
+
+    /// Listens on `address` for an engine to ask for a game, playing the
+    /// side it leaves
+    pub async fn dxp_listen(address: &str) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(with_port(address, DXP_PORT)).await?;
+        let (link, outgoing, events) = Self::channels(listener.local_addr()?.to_string());
+        tokio::spawn(run_follower(listener, outgoing, events));
+        Ok(link)
+    }
+}
+
+async fn run_initiator(
+    address: String,
+    request: DxpMessage,
+    outgoing: UnboundedReceiver<Message>,
+    events: UnboundedSender<NetEvent>,
+) {
+    let DxpMessage::GameRequest { follower, .. } = request else {
+        return;
+    };
+    let stream = loop {
+        if events.is_closed() {
+            return;
+        }
+        match TcpStream::connect(&address).await {
+            Ok(stream) => break stream,
+            Err(_) => tokio::time::sleep(RECONNECT_DELAY).await,
+        }
+    };
+    let (read, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    if let Err(e) = write_dxp(&mut writer, &request).await {
+        let _ = events.send(NetEvent::Failed(e));
+        return;
+    }
+    let peer = match read_handshake(&mut reader).await {
+        Ok(DxpMessage::GameAccept {
+            name,
+            acceptance: Acceptance::Accepted,
+        }) => name,
+        Ok(DxpMessage::GameAccept { acceptance, .. }) => {
+            let refused = NetError::Rejected(format!("game request refused ({acceptance:?})"));
+            let _ = events.send(NetEvent::Failed(refused));
+            return;
+        }
+        Ok(other) => {
+            let unexpected = NetError::Unexpected {
+                expected: "GAMEACC",
+                got: other.encode(),
+            };
+            let _ = events.send(NetEvent::Failed(unexpected));
+            return;
+        }
+        Err(e) => {
+            let _ = events.send(NetEvent::Failed(e));
+            return;
+        }
+    };
+
+    let seat = follower.opposite();
+    if events.send(NetEvent::Connected { seat, peer }).is_ok() {
+        DxpSession::new(seat)
+            .run(reader, writer, outgoing, &events)
+            .await;
+    }
+}
+
+async fn run_follower(
+    listener: TcpListener,
+    outgoing: UnboundedReceiver<Message>,
+    events: UnboundedSender<NetEvent>,
+) {
+    loop {
+        let Ok((stream, peer)) = listener.accept().await else {
+            tokio::time::sleep(ACCEPT_BACKOFF).await;
+            continue;
+        };
+        let (read, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(read);
+
+        let (acceptance, game) = match read_handshake(&mut reader).await {
+            Ok(DxpMessage::GameRequest { version, .. }) if version != DXP_VERSION => {
+                (Acceptance::VersionUnsupported, None)
+            }
+            Ok(DxpMessage::GameRequest { name, follower, .. }) => {
+                (Acceptance::Accepted, Some((name, follower)))
+            }
+            _ => (Acceptance::Refused, None),
+        };
+        let answer = DxpMessage::GameAccept {
+            name: DXP_NAME.to_string(),
+            acceptance,
+        };
+        if write_dxp(&mut writer, &answer).await.is_err() {
+            continue;
+        }
+        let Some((name, seat)) = game else {
+            continue;
+        };
+
+        let peer = if name.is_empty() {
+            peer.to_string()
+        } else {
+            name
+        };
+        if events.send(NetEvent::Connected { seat, peer }).is_ok() {
+            DxpSession::new(seat)
+                .run(reader, writer, outgoing, &events)
+                .await;
+        }
+        // One game per session
+        return;
+    }
+}
+
+async fn read_handshake(reader: &mut Reader) -> Result<DxpMessage, NetError> {
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, read_dxp(reader))
+        .await
+        .map_err(|_| NetError::Malformed("no answer in time".to_string()))??
+        .ok_or_else(|| NetError::Malformed("connection closed".to_string()))
+}
+
+/// One game against a DXP engine, kept in step with the board so whole
+/// turns can be told apart from single hops
+struct DxpSession {
+    /// The side played at this end
+    seat: Color,
+    game: CheckersGame,
+    /// When the side to move started thinking
+    turn_started: Instant,
+    /// A draw was claimed from this end and waits for the engine's GAMEEND
+    offered_draw: bool,
+    /// The engine ended the game as a draw and waits for an answer
+    draw_claimed: bool,
+    /// A GAMEEND went out; the next one coming in confirms it
+    ending: bool,
+    /// Both ends agree the game is over
+    done: bool,
+}
+
+impl DxpSession {
+    fn new(seat: Color) -> Self {
+        Self {
+            seat,
+            game: CheckersGame::new(),
+            turn_started: Instant::now(),
+            offered_draw: false,
+            draw_claimed: false,
+            ending: false,
+            done: false,
+        }
+    }
+
+    /// Passes messages both ways until the game is over or either end goes
+    async fn run(
+        mut self,
+        reader: Reader,
+        mut writer: OwnedWriteHalf,
+        mut outgoing: UnboundedReceiver<Message>,
+        events: &UnboundedSender<NetEvent>,
+    ) {
+        // Reading a NUL-terminated message is not safe to cancel, so it
+        // happens on its own task
+        let (incoming_tx, mut incoming) = unbounded_channel();
+        tokio::spawn(async move {
+            let mut reader = reader;
+            loop {
+                let message = read_dxp(&mut reader).await;
+                let last = matches!(message, Ok(None) | Err(NetError::Io(_)));
+                if incoming_tx.send(message).is_err() || last {
+                    return;
+                }
+            }
+        });
+
+        loop {
+            let replies = tokio::select! {
+                message = incoming.recv() => match message {
+                    Some(Ok(Some(message))) => self.receive(message, events),
+                    Some(Ok(None)) | None => {
+                        if !self.ending {
+                            let closed = NetError::Ended("the engine closed the connection".to_string());
+                            let _ = events.send(NetEvent::Failed(closed));
+                        }
+                        return;
+                    }
+                    Some(Err(NetError::Malformed(reason))) => {
+                        vec![DxpMessage::Chat(format!("Unreadable message: {reason}"))]
+                    }
+                    Some(Err(e)) => {
+                        let _ = events.send(NetEvent::Failed(e));
+                        return;
+                    }
+                },
+                message = outgoing.recv() => match message {
+                    Some(message) => self.send(message),
+                    None => return,
+                },
+            };
+
+            for reply in &replies {
+                if let Err(e) = write_dxp(&mut writer, reply).await {
+                    let _ = events.send(NetEvent::Failed(e));
+                    return;
+                }
+            }
+            if self.done {
+                return;
+            }
+        }
+    }
+
+    /// Takes in a message from the engine, passing on what the game needs
+    /// and returning the answers owed to the engine
+    fn receive(
+        &mut self,
+        message: DxpMessage,
+        events: &UnboundedSender<NetEvent>,
+    ) -> Vec<DxpMessage> {
+        let emit = |message| {
+            let _ = events.send(NetEvent::Received(message));
+        };
+
+        match message {
+            DxpMessage::Move {
+                from, to, captured, ..
+            } => {
+                let engine = self.seat.opposite();
+                let turn = turn_from_dxp(&self.game, from, to, &captured)
+                    .filter(|_| self.game.current_player == engine && !self.game.is_game_over);
+                let Some(game) = turn.and_then(|turn| self.game.apply_turn(&turn).ok()) else {
+                    // Let the game say why it cannot be played
+                    let size = self.game.board.size;
+                    let square = |number| number_to_square(number, size).unwrap_or((size, size));
+                    emit(Message::Move {
+                        from: square(from),
+                        to: square(to),
+                    });
+                    return Vec::new();
+                };
+
+                let played = self.game.move_history.moves().len();
+                for step in &game.move_history.moves()[played..] {
+                    emit(Message::Move {
+                        from: step.from,
+                        to: step.to,
+                    });
+                }
+                self.game = game;
+                self.turn_started = Instant::now();
+                self.end_on_board()
+            }
+            DxpMessage::Chat(text) => {
+                emit(Message::Chat { text });
+                Vec::new()
+            }
+            DxpMessage::GameEnd { reason, .. } if self.ending => {
+                if self.offered_draw {
+                    if matches!(reason, EndReason::Draw | EndReason::Unknown) {
+                        emit(Message::DrawAccept);
+                    } else {
+                        let ended = NetError::Ended(format!("the engine answered ({reason:?})"));
+                        let _ = events.send(NetEvent::Failed(ended));
+                    }
+                }
+                self.done = true;
+                Vec::new()
+            }
+            DxpMessage::GameEnd { reason, .. } => {
+                match reason {
+                    EndReason::Draw => {
+                        // Up to the player, who answers with /accept or /decline
+                        self.draw_claimed = true;
+                        emit(Message::DrawOffer);
+                        return Vec::new();
+                    }
+                    EndReason::ILose => emit(Message::Resign),
+                    // The board already shows it
+                    _ if board_result(&self.game, self.seat).is_some() => {}
+                    EndReason::IWin | EndReason::Unknown => {
+                        let ended =
+                            NetError::Ended(format!("the engine stopped the game ({reason:?})"));
+                        let _ = events.send(NetEvent::Failed(ended));
+                    }
+                }
+                self.answer(reason)
+            }
+            DxpMessage::BackRequest {
+                move_number,
+                to_move,
+            } => {
+                let turns = back_request_turns(move_number, to_move);
+                let Some(game) = take_back(&self.game, turns) else {
+                    return vec![DxpMessage::BackAccept { accepted: false }];
+                };
+                self.game = game;
+                self.turn_started = Instant::now();
+                emit(Message::Sync {
+                    moves: steps(&self.game.move_history),
+                });
+                vec![DxpMessage::BackAccept { accepted: true }]
+            }
+            // Nothing is ever asked back from here, and the handshake is over
+            DxpMessage::BackAccept { .. }
+            | DxpMessage::GameRequest { .. }
+            | DxpMessage::GameAccept { .. } => Vec::new(),
+        }
+    }
+
+    /// Turns a message from the game into what the engine is told
+    fn send(&mut self, message: Message) -> Vec<DxpMessage> {
+        if self.ending || self.done {
+            return Vec::new();
+        }
+
+        match message {
+            Message::Move { from, to } => {
+                let Ok((game, _)) = self.game.make_move_coords(from.0, from.1, to.0, to.1) else {
+                    return Vec::new();
+                };
+                self.game = game;
+                if self.game.current_player == self.seat {
+                    // A multi-jump goes out once it is finished
+                    return Vec::new();
+                }
+
+                let Some((turn, _)) = self.game.move_history.last_turn() else {
+                    return Vec::new();
+                };
+                let size = self.game.board.size;
+                let mut replies = vec![turn_to_dxp(&turn, size, self.turn_started.elapsed())];
+                self.turn_started = Instant::now();
+                replies.extend(self.end_on_board());
+                replies
+            }
+            Message::Chat { text } => vec![DxpMessage::Chat(text)],
+            Message::Error { reason } => vec![DxpMessage::Chat(reason)],
+            Message::Resign => self.finish(EndReason::ILose),
+            Message::DrawOffer => {
+                self.offered_draw = true;
+                self.finish(EndReason::Draw)
+            }
+            Message::DrawAccept if self.draw_claimed => self.answer(EndReason::Draw),
+            Message::DrawDecline if self.draw_claimed => {
+                self.draw_claimed = false;
+                vec![DxpMessage::Chat("Draw declined, playing on".to_string())]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The GAMEEND owed when the board shows the game is over
+    fn end_on_board(&mut self) -> Vec<DxpMessage> {
+        match board_result(&self.game, self.seat) {
+            Some(reason) => self.finish(reason),
+            None => Vec::new(),
+        }
+    }
+
+    /// Ends the game from this end; the engine's GAMEEND confirms it
+    fn finish(&mut self, reason: EndReason) -> Vec<DxpMessage> {
+        self.ending = true;
+        vec![DxpMessage::GameEnd { reason, stop: true }]
+    }
+
+    /// Confirms the GAMEEND the engine sent
+    fn answer(&mut self, reason: EndReason) -> Vec<DxpMessage> {
+        self.done = true;
+        vec![DxpMessage::GameEnd {
+            reason: reason.mirrored(),
+            stop: true,
+        }]
+    }
+}
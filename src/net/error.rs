@@ -0,0 +1,29 @@
+use std::io;
+use thiserror::Error;
+
+use crate::core::game::GameError;
+use crate::core::piece::Color;
+
+#[derive(Error, Debug)]
+pub enum NetError {
+    #[error("Connection failed: {0}")]
+    Io(#[from] io::Error),
+    #[error("Unreadable message: {0}")]
+    Malformed(String),
+    #[error("Protocol version {theirs} does not match ours ({ours})")]
+    VersionMismatch { ours: u32, theirs: u32 },
+    #[error("The other side refused the connection: {0}")]
+    Rejected(String),
+    #[error("Expected {expected}, got {got}")]
+    Unexpected { expected: &'static str, got: String },
+    #[error("The game is over: {0}")]
+    Ended(String),
+    #[error("It is not {0:?}'s turn")]
+    NotTheirTurn(Color),
+    #[error("Illegal move {from:?} to {to:?}: {error}")]
+    IllegalMove {
+        from: (usize, usize),
+        to: (usize, usize),
+        error: GameError,
+    },
+}
@@ -0,0 +1,324 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::core::piece::Color;
+use crate::net::protocol::{decode, encode, Message, PROTOCOL_VERSION};
+use crate::net::NetError;
+
+/// Port used when an address leaves it out
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// How long the other end has to introduce itself
+pub(super) const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Pause between attempts to reach the host again
+pub(super) const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// Pause after a failed accept, so running out of file descriptors doesn't
+/// spin the loop
+pub(crate) const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// What happened on the connection, in the order it happened
+#[derive(Debug)]
+pub enum NetEvent {
+    /// The other end is there; `seat` is the side played at this end
+    Connected {
+        seat: Color,
+        peer: String,
+    },
+    Received(Message),
+    /// The connection dropped; the host waits for the guest to come back and
+    /// the guest keeps trying to reach the host
+    Disconnected(String),
+    /// The link gave up for good
+    Failed(NetError),
+}
+
+/// A running connection to the other player. Messages go out through
+/// `send` and everything that arrives is picked up with `try_recv`.
+pub struct Link {
+    outgoing: UnboundedSender<Message>,
+    events: UnboundedReceiver<NetEvent>,
+    address: String,
+}
+
+impl Link {
+    /// Listens on `address` for a guest who plays the side opposite `seat`.
+    /// After a dropped connection only that guest may take the seat again.
+    pub async fn host(address: &str, seat: Color) -> Result<Self, NetError> {
+        let listener = TcpListener::bind(with_port(address, DEFAULT_PORT)).await?;
+        let (link, outgoing, events) = Self::channels(listener.local_addr()?.to_string());
+        tokio::spawn(run_host(listener, seat, outgoing, events));
+        Ok(link)
+    }
+
+    /// Connects to a host, reconnecting with the same seat whenever the
+    /// connection drops
+    pub fn join(address: &str) -> Self {
+        let (link, outgoing, events) = Self::channels(with_port(address, DEFAULT_PORT));
+        tokio::spawn(run_guest(link.address.clone(), outgoing, events));
+        link
+    }
+
+    /// A link with the two ends of its channels that the connection task
+    /// works with
+    pub(super) fn channels(
+        address: String,
+    ) -> (Self, UnboundedReceiver<Message>, UnboundedSender<NetEvent>) {
+        let (outgoing, outgoing_rx) = unbounded_channel();
+        let (events_tx, events) = unbounded_channel();
+        let link = Self {
+            outgoing,
+            events,
+            address,
+        };
+        (link, outgoing_rx, events_tx)
+    }
+
+    /// Where the host listens, or the host being joined
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Queues a message; it is dropped if the link has shut down
+    pub fn send(&self, message: Message) {
+        let _ = self.outgoing.send(message);
+    }
+
+    pub fn try_recv(&mut self) -> Option<NetEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Waits for the next event
+    pub async fn recv(&mut self) -> Option<NetEvent> {
+        self.events.recv().await
+    }
+}
+
+/// Appends `port` to an address without one
+pub fn with_port(address: &str, port: u16) -> String {
+    if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{address}:{port}")
+    }
+}
+
+type Reader = Lines<BufReader<OwnedReadHalf>>;
+
+async fn run_host(
+    listener: TcpListener,
+    seat: Color,
+    mut outgoing: UnboundedReceiver<Message>,
+    events: UnboundedSender<NetEvent>,
+) {
+    // Handed to the first guest, who needs it to get back in
+    let mut token: Option<String> = None;
+
+    loop {
+        let Ok((stream, peer)) = listener.accept().await else {
+            tokio::time::sleep(ACCEPT_BACKOFF).await;
+            continue;
+        };
+        let (mut reader, mut writer) = split(stream);
+
+        let hello = read_message(&mut reader).await;
+        let reason = match hello {
+            Ok(Message::Hello { version, .. }) if version != PROTOCOL_VERSION => Some(
+                NetError::VersionMismatch {
+                    ours: PROTOCOL_VERSION,
+                    theirs: version,
+                }
+                .to_string(),
+            ),
+            Ok(Message::Hello { token: theirs, .. }) => match &token {
+                Some(token) if theirs.as_ref() != Some(token) => {
+                    Some("this game already has an opponent".to_string())
+                }
+                _ => None,
+            },
+            Ok(other) => Some(format!("expected hello, got {other:?}")),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(reason) = reason {
+            let _ = write_message(&mut writer, &Message::Reject { reason }).await;
+            continue;
+        }
+
+        let token = token.get_or_insert_with(new_token).clone();
+        let welcome = Message::Welcome {
+            version: PROTOCOL_VERSION,
+            seat: seat.opposite(),
+            token,
+        };
+        if write_message(&mut writer, &welcome).await.is_err() {
+            continue;
+        }
+
+        // Whatever piled up while nobody was there is covered by the resync
+        while outgoing.try_recv().is_ok() {}
+        let connected = NetEvent::Connected {
+            seat,
+            peer: peer.to_string(),
+        };
+        if events.send(connected).is_err() {
+            return;
+        }
+        match pump(reader, writer, &mut outgoing, &events).await {
+            Some(reason) => {
+                if events.send(NetEvent::Disconnected(reason)).is_err() {
+                    return;
+                }
+            }
+            None => return,
+        }
+    }
+}
+
+async fn run_guest(
+    address: String,
+    mut outgoing: UnboundedReceiver<Message>,
+    events: UnboundedSender<NetEvent>,
+) {
+    let mut token = None;
+
+    loop {
+        if events.is_closed() {
+            return;
+        }
+        let stream = match TcpStream::connect(&address).await {
+            Ok(stream) => stream,
+            Err(_) => {
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        let peer = stream
+            .peer_addr()
+            .map_or_else(|_| address.clone(), |peer| peer.to_string());
+        let (mut reader, mut writer) = split(stream);
+
+        let hello = Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: token.clone(),
+        };
+        if write_message(&mut writer, &hello).await.is_err() {
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        let seat = match read_message(&mut reader).await {
+            Ok(Message::Welcome { version, .. }) if version != PROTOCOL_VERSION => {
+                let mismatch = NetError::VersionMismatch {
+                    ours: PROTOCOL_VERSION,
+                    theirs: version,
+                };
+                let _ = events.send(NetEvent::Failed(mismatch));
+                return;
+            }
+            Ok(Message::Welcome {
+                seat, token: given, ..
+            }) => {
+                token = Some(given);
+                seat
+            }
+            Ok(Message::Reject { reason }) => {
+                let _ = events.send(NetEvent::Failed(NetError::Rejected(reason)));
+                return;
+            }
+            Ok(other) => {
+                let unexpected = NetError::Unexpected {
+                    expected: "welcome",
+                    got: format!("{other:?}"),
+                };
+                let _ = events.send(NetEvent::Failed(unexpected));
+                return;
+            }
+            Err(_) => {
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        // The host's resync replaces anything sent while cut off
+        while outgoing.try_recv().is_ok() {}
+        if events.send(NetEvent::Connected { seat, peer }).is_err() {
+            return;
+        }
+        match pump(reader, writer, &mut outgoing, &events).await {
+            Some(reason) => {
+                if events.send(NetEvent::Disconnected(reason)).is_err() {
+                    return;
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+            None => return,
+        }
+    }
+}
+
+fn split(stream: TcpStream) -> (Reader, OwnedWriteHalf) {
+    let (read, write) = stream.into_split();
+    (BufReader::new(read).lines(), write)
+}
+
+async fn read_message(reader: &mut Reader) -> Result<Message, NetError> {
+    let line = tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.next_line())
+        .await
+        .map_err(|_| NetError::Malformed("no answer in time".to_string()))??;
+    match line {
+        Some(line) => decode(&line),
+        None => Err(NetError::Malformed("connection closed".to_string())),
+    }
+}
+
+async fn write_message(writer: &mut OwnedWriteHalf, message: &Message) -> Result<(), NetError> {
+    writer.write_all(encode(message).as_bytes()).await?;
+    Ok(())
+}
+
+/// Passes messages both ways until the connection drops, returning why, or
+/// `None` once the game has closed its end of the link
+async fn pump(
+    mut reader: Reader,
+    mut writer: OwnedWriteHalf,
+    outgoing: &mut UnboundedReceiver<Message>,
+    events: &UnboundedSender<NetEvent>,
+) -> Option<String> {
+    loop {
+        tokio::select! {
+            line = reader.next_line() => match line {
+                Ok(Some(line)) => match decode(&line) {
+                    Ok(message) => {
+                        events.send(NetEvent::Received(message)).ok()?;
+                    }
+                    Err(e) => {
+                        let error = Message::Error { reason: e.to_string() };
+                        if let Err(e) = write_message(&mut writer, &error).await {
+                            return Some(e.to_string());
+                        }
+                    }
+                },
+                Ok(None) => return Some("connection closed".to_string()),
+                Err(e) => return Some(e.to_string()),
+            },
+            message = outgoing.recv() => match message {
+                Some(message) => {
+                    if let Err(e) = write_message(&mut writer, &message).await {
+                        return Some(e.to_string());
+                    }
+                }
+                None => return None,
+            },
+        }
+    }
+}
+
+/// Tokens only have to tell one guest from another
+fn new_token() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    format!("{nanos:x}")
+}
@@ -0,0 +1,164 @@
+pub mod broadcast;
+pub mod dxp;
+pub mod dxp_engine;
+pub mod dxp_link;
+pub mod error;
+pub mod link;
+pub mod protocol;
+pub mod remote;
+
+pub use error::NetError;
+pub use link::{Link, NetEvent};
+pub use remote::RemotePlay;
+
+use crate::core::piece::Color;
+
+/// Address a host listens on when none is given
+pub const DEFAULT_HOST_ADDRESS: &str = "0.0.0.0";
+
+/// Time asked for in DXP games: minutes for a number of moves
+pub const DEFAULT_DXP_TIME: (u32, u32) = (5, 50);
+
+/// Which end of a network game this is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetRole {
+    /// Waits for a guest, playing `seat`
+    Host {
+        address: String,
+        seat: Color,
+    },
+    Join {
+        address: String,
+    },
+    /// Plays `seat` from the board against the DXP engine at `address`
+    Dxp {
+        address: String,
+        seat: Color,
+        time: (u32, u32),
+    },
+    /// Waits for a DXP engine to ask for a game
+    DxpListen {
+        address: String,
+    },
+    /// Lets the built-in engine play `seat` against the DXP engine at
+    /// `address`, without the board
+    EngineDxp {
+        address: String,
+        seat: Color,
+        time: (u32, u32),
+    },
+    /// Lets the built-in engine take on every DXP engine that connects
+    EngineDxpListen {
+        address: String,
+    },
+    /// Follows a game broadcast from `address`, without playing
+    Watch {
+        address: String,
+    },
+}
+
+impl NetRole {
+    /// Reads `--host [ADDRESS] [--seat white|black]` or `--join ADDRESS`
+    /// from the command line, or for DXP `--dxp ADDRESS`, `--dxp-listen
+    /// [ADDRESS]` and their `--engine-` forms, with `--seat` and `--dxp-time
+    /// MINUTES/MOVES`, or `--watch [ADDRESS]` to follow a broadcast game.
+    /// No flags means a game against the AI.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Self>, String> {
+        let mut args = args.into_iter().peekable();
+        let mut role = None;
+        let mut seat = Color::White;
+        let mut time = DEFAULT_DXP_TIME;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--host" => {
+                    let address = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .unwrap_or_else(|| DEFAULT_HOST_ADDRESS.to_string());
+                    role = Some(Self::Host {
+                        address,
+                        seat: Color::White,
+                    });
+                }
+                "--join" => {
+                    let address = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .ok_or("--join needs the host's address")?;
+                    role = Some(Self::Join { address });
+                }
+                "--dxp" | "--engine-dxp" => {
+                    let address = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .ok_or(format!("{arg} needs the engine's address"))?;
+                    role = Some(if arg == "--dxp" {
+                        Self::Dxp {
+                            address,
+                            seat: Color::White,
+                            time,
+                        }
+                    } else {
+                        Self::EngineDxp {
+                            address,
+                            seat: Color::White,
+                            time,
+                        }
+                    });
+                }
+                "--dxp-listen" | "--engine-dxp-listen" => {
+                    let address = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .unwrap_or_else(|| DEFAULT_HOST_ADDRESS.to_string());
+                    role = Some(if arg == "--dxp-listen" {
+                        Self::DxpListen { address }
+                    } else {
+                        Self::EngineDxpListen { address }
+                    });
+                }
+                "--watch" => {
+                    let address = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .unwrap_or_else(|| broadcast::DEFAULT_BROADCAST_ADDRESS.to_string());
+                    role = Some(Self::Watch { address });
+                }
+                "--dxp-time" => {
+                    time = args
+                        .next()
+                        .as_deref()
+                        .and_then(parse_dxp_time)
+                        .ok_or("--dxp-time takes MINUTES/MOVES, e.g. 5/50")?;
+                }
+                "--seat" => {
+                    seat = match args.next().as_deref() {
+                        Some("white") => Color::White,
+                        Some("black") => Color::Black,
+                        _ => return Err("--seat takes white or black".to_string()),
+                    };
+                }
+                other => return Err(format!("Unknown argument {other}")),
+            }
+        }
+
+        Ok(match role {
+            Some(Self::Host { address, .. }) => Some(Self::Host { address, seat }),
+            Some(Self::Dxp { address, .. }) => Some(Self::Dxp {
+                address,
+                seat,
+                time,
+            }),
+            Some(Self::EngineDxp { address, .. }) => Some(Self::EngineDxp {
+                address,
+                seat,
+                time,
+            }),
+            role => role,
+        })
+    }
+}
+
+/// Reads "MINUTES/MOVES"; both must be positive
+fn parse_dxp_time(text: &str) -> Option<(u32, u32)> {
+    let (minutes, moves) = text.split_once('/')?;
+    let minutes = minutes.trim().parse().ok().filter(|&m| m > 0)?;
+    let moves = moves.trim().parse().ok().filter(|&m| m > 0)?;
+    Some((minutes, moves))
+}
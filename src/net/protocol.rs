@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::game::{CheckersGame, GameError};
+use crate::core::move_history::MoveHistory;
+use crate::core::piece::Color;
+use crate::net::NetError;
+
+/// Bumped whenever a message changes shape; both ends must agree
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// One hop of a move, as `CheckersGame::make_move_coords` plays it
+pub type Step = ((usize, usize), (usize, usize));
+
+/// Everything the two ends say to each other. Each message travels as one
+/// line of JSON, tagged with its `type`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// First line from the joining side. `token` is the one the host handed
+    /// out earlier, when coming back after a dropped connection.
+    Hello {
+        version: u32,
+        token: Option<String>,
+    },
+    /// The host's answer: which side the guest plays and the token to
+    /// reconnect with
+    Welcome {
+        version: u32,
+        seat: Color,
+        token: String,
+    },
+    /// The host turned the guest away
+    Reject {
+        reason: String,
+    },
+    /// Every step played so far. The host sends it after each (re)connection
+    /// and whenever the two ends disagree; the guest replays it.
+    Sync {
+        moves: Vec<Step>,
+    },
+    Move {
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+    DrawOffer,
+    DrawAccept,
+    DrawDecline,
+    Resign,
+    Chat {
+        text: String,
+    },
+    /// The last message could not be used
+    Error {
+        reason: String,
+    },
+}
+
+/// Writes a message as one line, newline included
+pub fn encode(message: &Message) -> String {
+    let mut line = serde_json::to_string(message).expect("messages always serialize");
+    line.push('\n');
+    line
+}
+
+pub fn decode(line: &str) -> Result<Message, NetError> {
+    serde_json::from_str(line.trim()).map_err(|e| NetError::Malformed(e.to_string()))
+}
+
+/// The steps of a game in the order they were played
+pub fn steps(history: &MoveHistory) -> Vec<Step> {
+    history.moves().iter().map(|m| (m.from, m.to)).collect()
+}
+
+/// Plays a step received from the other end for `seat`. It must be that
+/// side's turn and its own piece, a multi-jump must go on with the piece that
+/// started it, and the rules get the final say through `make_move_coords`.
+pub fn apply_remote_step(
+    game: &CheckersGame,
+    seat: Color,
+    (from, to): Step,
+) -> Result<CheckersGame, NetError> {
+    if game.is_game_over || game.current_player != seat {
+        return Err(NetError::NotTheirTurn(seat));
+    }
+    let illegal = |error| NetError::IllegalMove { from, to, error };
+
+    game.validate_piece_selection(from.0, from.1)
+        .map_err(illegal)?;
+    if let Some(last) = game.move_history.moves().last() {
+        let jumping = last.player == seat && !last.captured.is_empty();
+        if jumping && last.to != from {
+            return Err(illegal(GameError::InvalidMove));
+        }
+    }
+
+    game.make_move_coords(from.0, from.1, to.0, to.1)
+        .map(|(played, _)| played)
+        .map_err(illegal)
+}
+
+/// Rebuilds a game from the initial position, checking every step
+pub fn replay_steps(moves: &[Step]) -> Result<CheckersGame, NetError> {
+    moves.iter().try_fold(CheckersGame::new(), |game, &step| {
+        let player = game.current_player;
+        apply_remote_step(&game, player, step)
+    })
+}
@@ -0,0 +1,223 @@
+use crate::core::piece::Color;
+use crate::net::protocol::{apply_remote_step, replay_steps, steps, Message};
+use crate::state::GameSession;
+
+/// Chat lines kept for the session
+const CHAT_HISTORY: usize = 50;
+
+/// What can be typed on the move line in a network game
+pub const COMMANDS: [&str; 5] = ["/say", "/draw", "/accept", "/decline", "/resign"];
+
+/// How a network game ended other than on the board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoteResult {
+    DrawAgreed,
+    Resigned(Color),
+}
+
+/// The network side of a game against someone on another machine
+#[derive(Clone, Debug)]
+pub struct RemotePlay {
+    /// The side played from the other end of the connection
+    pub seat: Color,
+    /// Whether this end listens for the connection. The host's game is the
+    /// one both ends fall back to when they disagree.
+    pub hosting: bool,
+    pub connected: bool,
+    /// Connection news or the latest chat line, shown under the status
+    pub notice: Option<String>,
+    pub chat: Vec<(Color, String)>,
+    /// The side whose draw offer is still waiting for an answer
+    pub draw_offer: Option<Color>,
+    pub result: Option<RemoteResult>,
+    /// Messages waiting to be sent
+    pub outbox: Vec<Message>,
+}
+
+impl RemotePlay {
+    pub fn new(seat: Color, hosting: bool) -> Self {
+        Self {
+            seat,
+            hosting,
+            connected: false,
+            notice: None,
+            chat: Vec::new(),
+            draw_offer: None,
+            result: None,
+            outbox: Vec::new(),
+        }
+    }
+
+    /// The side played at this end
+    pub fn local_seat(&self) -> Color {
+        self.seat.opposite()
+    }
+
+    pub fn with_notice(&self, notice: impl Into<String>) -> Self {
+        let mut new_remote = self.clone();
+        new_remote.notice = Some(notice.into());
+        new_remote
+    }
+
+    fn say(&mut self, color: Color, text: &str) {
+        self.chat.push((color, text.to_string()));
+        if self.chat.len() > CHAT_HISTORY {
+            self.chat.remove(0);
+        }
+        self.notice = Some(format!("{}: {text}", name(color)));
+    }
+}
+
+fn name(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Black => "Black",
+    }
+}
+
+/// Applies a message from the other end. Moves are checked against the
+/// rules first; the host answers anything it cannot use with the full game
+/// so the guest can catch up.
+pub fn receive(session: &GameSession, message: Message) -> GameSession {
+    let mut new_session = session.clone();
+    let Some(remote) = new_session.remote.as_mut() else {
+        return new_session;
+    };
+    let seat = remote.seat;
+
+    match message {
+        Message::Move { from, to } => {
+            match apply_remote_step(&new_session.game, seat, (from, to)) {
+                Ok(game) => {
+                    new_session.game = game;
+                    new_session.ui_state = new_session.ui_state.clear_selection();
+                }
+                Err(e) => {
+                    remote.notice = Some(format!("Ignored the opponent's move: {e}"));
+                    remote.outbox.push(Message::Error {
+                        reason: e.to_string(),
+                    });
+                    if remote.hosting {
+                        remote.outbox.push(Message::Sync {
+                            moves: steps(&new_session.game.move_history),
+                        });
+                    }
+                }
+            }
+        }
+        Message::Sync { moves } if !remote.hosting => match replay_steps(&moves) {
+            Ok(game) => {
+                if steps(&new_session.game.move_history) != moves {
+                    remote.notice = Some("Caught up with the host's game".to_string());
+                    new_session.game = game;
+                    new_session.ui_state = new_session.ui_state.clear_selection();
+                }
+            }
+            Err(e) => remote.notice = Some(format!("Could not follow the host's game: {e}")),
+        },
+        Message::DrawOffer => {
+            remote.draw_offer = Some(seat);
+            remote.notice = Some(format!(
+                "{} offers a draw - type /accept or /decline",
+                name(seat)
+            ));
+        }
+        Message::DrawAccept if remote.draw_offer == Some(remote.local_seat()) => {
+            remote.result = Some(RemoteResult::DrawAgreed);
+            new_session.game.is_game_over = true;
+        }
+        Message::DrawDecline => {
+            remote.draw_offer = None;
+            remote.notice = Some(format!("{} declined the draw", name(seat)));
+        }
+        Message::Resign => {
+            remote.result = Some(RemoteResult::Resigned(seat));
+            new_session.game.is_game_over = true;
+        }
+        Message::Chat { text } => remote.say(seat, &text),
+        Message::Error { reason } => {
+            remote.notice = Some(format!("The opponent could not use our message: {reason}"));
+            if remote.hosting {
+                remote.outbox.push(Message::Sync {
+                    moves: steps(&new_session.game.move_history),
+                });
+            }
+        }
+        // Handshake messages are handled by the link, and a draw can only
+        // be accepted once offered
+        _ => {}
+    }
+
+    new_session
+}
+
+/// Runs a command typed on the move line: `/say <text>`, `/draw`,
+/// `/accept`, `/decline` or `/resign`
+pub fn command(session: &GameSession, input: &str) -> Result<GameSession, String> {
+    let mut new_session = session.clone();
+    let Some(remote) = new_session.remote.as_mut() else {
+        return Err("Commands only work in network games".to_string());
+    };
+    let local = remote.local_seat();
+    let (command, rest) = input.trim().split_once(' ').unwrap_or((input.trim(), ""));
+
+    match command {
+        "/say" if !rest.trim().is_empty() => {
+            let text = rest.trim();
+            remote.say(local, text);
+            remote.outbox.push(Message::Chat {
+                text: text.to_string(),
+            });
+        }
+        "/draw" => {
+            remote.draw_offer = Some(local);
+            remote.notice = Some("Draw offered".to_string());
+            remote.outbox.push(Message::DrawOffer);
+        }
+        "/accept" if remote.draw_offer == Some(remote.seat) => {
+            remote.result = Some(RemoteResult::DrawAgreed);
+            remote.outbox.push(Message::DrawAccept);
+            new_session.game.is_game_over = true;
+        }
+        "/decline" if remote.draw_offer == Some(remote.seat) => {
+            remote.draw_offer = None;
+            remote.notice = None;
+            remote.outbox.push(Message::DrawDecline);
+        }
+        "/accept" | "/decline" => return Err("There is no draw offer to answer".to_string()),
+        "/resign" => {
+            remote.result = Some(RemoteResult::Resigned(local));
+            remote.outbox.push(Message::Resign);
+            new_session.game.is_game_over = true;
+        }
+        _ => {
+            return Err(format!(
+                "Unknown command \"{}\" - try /say, /draw, /accept, /decline or /resign",
+                input.trim()
+            ))
+        }
+    }
+
+    Ok(new_session)
+}
+
+/// How a network game ended, if it has: `Some(winner)`, where no winner is
+/// a draw. Covers agreements and resignations as well as the board.
+pub fn outcome(session: &GameSession) -> Option<Option<Color>> {
+    let remote = session.remote.as_ref()?;
+    match remote.result {
+        Some(RemoteResult::DrawAgreed) => return Some(None),
+        Some(RemoteResult::Resigned(color)) => return Some(Some(color.opposite())),
+        None => {}
+    }
+
+    let game = &session.game;
+    if let Some(winner) = game.check_winner() {
+        Some(Some(winner))
+    } else if game.is_stalemate() {
+        // If current player has no moves, the other player wins
+        Some(Some(game.current_player.opposite()))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,299 @@
+//! The games behind the API, each kept under the ID it was created with,
+//! and the routes that read and change them.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::ai::engine::{search, search_within, SearchResult, DRAW_AFTER_TURNS};
+use crate::ai::formatting::{parse_move, resolve_turn};
+use crate::core::fen::{parse_fen, to_fen};
+use crate::core::game::CheckersGame;
+use crate::core::notation::{square_number, turn_to_numeric};
+use crate::core::pdn::result_string;
+use crate::core::piece::Color;
+use crate::server::http::{Request, Response};
+use crate::text_protocol::DEFAULT_GO_DEPTH;
+
+/// Deepest search an AI move may ask for
+pub const MAX_AI_DEPTH: u32 = 12;
+/// Longest search an AI move may ask for
+pub const MAX_AI_MOVETIME: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize, Default)]
+struct NewGame {
+    fen: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MoveRequest {
+    #[serde(rename = "move")]
+    text: String,
+}
+
+#[derive(Deserialize, Default)]
+struct AiRequest {
+    depth: Option<u32>,
+    movetime_ms: Option<u64>,
+}
+
+/// Reads a JSON body, taking an empty one as all defaults
+fn body<T: for<'de> Deserialize<'de> + Default>(request: &Request) -> Result<T, Response> {
+    if request.body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(&request.body).map_err(|e| Response::error(400, e.to_string()))
+}
+
+/// How the game ended, with `Some(None)` for a draw
+fn outcome(game: &CheckersGame) -> Option<Option<Color>> {
+    if let Some(winner) = game.check_winner() {
+        return Some(Some(winner));
+    }
+    // Having no move left loses the game
+    if game.legal_turns().is_empty() {
+        return Some(Some(game.current_player.opposite()));
+    }
+    if game.move_history.turns().len() >= DRAW_AFTER_TURNS {
+        return Some(None);
+    }
+    None
+}
+
+/// Everything a client needs to draw the game: the rows from Black's side
+/// down, the side to move, the turns so far and the result once there is one
+pub fn game_state(id: u64, game: &CheckersGame) -> Value {
+    let size = game.board.size;
+    let board: Vec<Vec<Value>> = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| match game.board.get_piece(row, col) {
+                    Some(piece) => json!({
+                        "color": piece.color,
+                        "king": piece.is_king,
+                        "square": square_number(row, col, size),
+                    }),
+                    None => Value::Null,
+                })
+                .collect()
+        })
+        .collect();
+    let history: Vec<String> = game
+        .move_history
+        .turns()
+        .iter()
+        .map(|(_, turn)| turn_to_numeric(turn, size))
+        .collect();
+    let outcome = outcome(game);
+
+    json!({
+        "id": id,
+        "fen": to_fen(game),
+        "board": board,
+        "to_move": game.current_player,
+        "history": history,
+        "result": outcome.map(result_string),
+        "winner": outcome.flatten(),
+    })
+}
+
+/// The sessions the server is keeping
+#[derive(Default)]
+pub struct Api {
+    games: Mutex<HashMap<u64, CheckersGame>>,
+    next_id: AtomicU64,
+}
+
+impl Api {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn games(&self) -> std::sync::MutexGuard<'_, HashMap<u64, CheckersGame>> {
+        // A panic elsewhere leaves the map itself intact
+        self.games
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn game(&self, id: u64) -> Result<CheckersGame, Response> {
+        self.games()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| Response::error(404, format!("no game {id}")))
+    }
+
+    /// Answers one request:
+    ///
+    /// ```text
+    /// POST   /games            {"fen": FEN}?      start a game
+    /// GET    /games/ID                            the game's state
+    /// DELETE /games/ID                            forget the game
+    /// GET    /games/ID/moves                      the legal moves
+    /// POST   /games/ID/moves   {"move": "22-18"}  play a move
+    /// POST   /games/ID/ai      {"depth": N} | {"movetime_ms": MS}
+    ///                                             let the engine move
+    /// ```
+    pub async fn handle(&self, request: &Request) -> Response {
+        let segments: Vec<&str> = request
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let method = request.method.as_str();
+        if method == "OPTIONS" {
+            return Response::empty(204);
+        }
+
+        let id = match segments.get(1) {
+            Some(id) => match id.parse::<u64>() {
+                Ok(id) => Some(id),
+                Err(_) => return Response::error(404, format!("no game {id}")),
+            },
+            None => None,
+        };
+        let reply = match (segments.as_slice(), id) {
+            (["games"], _) => match method {
+                "POST" => self.create(request),
+                _ => Err(Response::error(405, "use POST to start a game")),
+            },
+            ([_, _], Some(id)) => match method {
+                "GET" => self
+                    .game(id)
+                    .map(|game| Response::json(200, game_state(id, &game))),
+                "DELETE" => self.delete(id),
+                _ => Err(Response::error(405, "use GET or DELETE on a game")),
+            },
+            ([_, _, "moves"], Some(id)) => match method {
+                "GET" => self.legal_moves(id),
+                "POST" => self.play(id, request),
+                _ => Err(Response::error(405, "use GET or POST on moves")),
+            },
+            ([_, _, "ai"], Some(id)) => match method {
+                "POST" => self.ai_move(id, request).await,
+                _ => Err(Response::error(405, "use POST to ask for an AI move")),
+            },
+            _ => Err(Response::error(404, format!("no route {}", request.path))),
+        };
+        reply.unwrap_or_else(|error| error)
+    }
+
+    fn create(&self, request: &Request) -> Result<Response, Response> {
+        let NewGame { fen } = body(request)?;
+        let game = match fen {
+            Some(fen) => parse_fen(&fen).map_err(|e| Response::error(400, e.to_string()))?,
+            None => CheckersGame::new(),
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let state = game_state(id, &game);
+        self.games().insert(id, game);
+        Ok(Response::json(201, state))
+    }
+
+    fn delete(&self, id: u64) -> Result<Response, Response> {
+        self.games()
+            .remove(&id)
+            .map(|_| Response::empty(204))
+            .ok_or_else(|| Response::error(404, format!("no game {id}")))
+    }
+
+    fn legal_moves(&self, id: u64) -> Result<Response, Response> {
+        let game = self.game(id)?;
+        let size = game.board.size;
+        let moves: Vec<Value> = if outcome(&game).is_some() {
+            Vec::new()
+        } else {
+            game.legal_turns()
+                .iter()
+                .map(|turn| {
+                    json!({
+                        "move": turn_to_numeric(turn, size),
+                        "path": turn
+                            .path
+                            .iter()
+                            .map(|&(row, col)| square_number(row, col, size))
+                            .collect::<Vec<_>>(),
+                        "capture": turn.is_capture(),
+                    })
+                })
+                .collect()
+        };
+        Ok(Response::json(200, json!({ "id": id, "moves": moves })))
+    }
+
+    fn play(&self, id: u64, request: &Request) -> Result<Response, Response> {
+        let MoveRequest { text } = serde_json::from_slice(&request.body)
+            .map_err(|e| Response::error(400, format!("expected {{\"move\": ...}}: {e}")))?;
+        let mut games = self.games();
+        let game = games
+            .get_mut(&id)
+            .ok_or_else(|| Response::error(404, format!("no game {id}")))?;
+        if outcome(game).is_some() {
+            return Err(Response::error(409, "the game is over"));
+        }
+        let path = parse_move(&text, game.board.size)
+            .ok_or_else(|| Response::error(400, format!("can't read move {text}")))?;
+        let turn = resolve_turn(game, path);
+        *game = game
+            .apply_turn(&turn)
+            .map_err(|e| Response::error(422, format!("illegal move {text}: {e}")))?;
+        Ok(Response::json(200, game_state(id, game)))
+    }
+
+    /// Searches without holding the sessions, then plays the move unless the
+    /// game moved on in the meantime
+    async fn ai_move(&self, id: u64, request: &Request) -> Result<Response, Response> {
+        let AiRequest { depth, movetime_ms } = body(request)?;
+        let game = self.game(id)?;
+        if outcome(&game).is_some() {
+            return Err(Response::error(409, "the game is over"));
+        }
+
+        let searched = game.clone();
+        let best: Option<SearchResult> = match (depth, movetime_ms) {
+            (None, None) => {
+                tokio::task::spawn_blocking(move || search(&searched, DEFAULT_GO_DEPTH)).await
+            }
+            (Some(depth), None) if (1..=MAX_AI_DEPTH).contains(&depth) => {
+                tokio::task::spawn_blocking(move || search(&searched, depth)).await
+            }
+            (None, Some(millis)) if millis > 0 => {
+                let budget = Duration::from_millis(millis).min(MAX_AI_MOVETIME);
+                tokio::task::spawn_blocking(move || search_within(&searched, budget)).await
+            }
+            _ => {
+                return Err(Response::error(
+                    400,
+                    format!("give either depth 1-{MAX_AI_DEPTH} or a positive movetime_ms"),
+                ))
+            }
+        }
+        .map_err(|e| Response::error(500, e.to_string()))?;
+        let Some(best) = best else {
+            return Err(Response::error(409, "the game is over"));
+        };
+
+        let mut games = self.games();
+        let current = games
+            .get_mut(&id)
+            .ok_or_else(|| Response::error(404, format!("no game {id}")))?;
+        if current.move_history.moves().len() != game.move_history.moves().len() {
+            return Err(Response::error(409, "the game changed during the search"));
+        }
+        *current = current
+            .apply_turn(&best.turn)
+            .map_err(|e| Response::error(500, e.to_string()))?;
+        Ok(Response::json(
+            200,
+            json!({
+                "move": turn_to_numeric(&best.turn, current.board.size),
+                "score": best.score,
+                "game": game_state(id, current),
+            }),
+        ))
+    }
+}
@@ -0,0 +1,187 @@
+//! Just enough HTTP/1.1 for the API: one request at a time per connection,
+//! bodies sized by `Content-Length`, and JSON answers.
+
+use std::io;
+
+use serde_json::Value;
+use thiserror::Error;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Longest request line or header accepted
+const MAX_LINE: usize = 8 * 1024;
+/// Most headers accepted on one request
+const MAX_HEADERS: usize = 64;
+/// Largest request body accepted
+const MAX_BODY: usize = 64 * 1024;
+
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error("Connection failed: {0}")]
+    Io(#[from] io::Error),
+    #[error("Bad request: {0}")]
+    Malformed(String),
+    #[error("Request too large")]
+    TooLarge,
+}
+
+impl HttpError {
+    /// The status to answer with before closing, if the client is still there
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            HttpError::Io(_) => None,
+            HttpError::Malformed(_) => Some(400),
+            HttpError::TooLarge => Some(413),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// The path without its query string
+    pub path: String,
+    pub body: Vec<u8>,
+    /// The client asked for the connection to close after the answer
+    pub close: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Option<Value>,
+}
+
+impl Response {
+    pub fn json(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            body: Some(body),
+        }
+    }
+
+    pub fn empty(status: u16) -> Self {
+        Self { status, body: None }
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(status, serde_json::json!({ "error": message.into() }))
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        _ => "Unknown",
+    }
+}
+
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<String>, HttpError> {
+    let mut line = Vec::new();
+    let read = (&mut *reader)
+        .take(MAX_LINE as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .await?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.len() > MAX_LINE {
+        return Err(HttpError::TooLarge);
+    }
+    let line = String::from_utf8(line).map_err(|_| HttpError::Malformed("not UTF-8".into()))?;
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+/// Reads the next request, or `None` when the client has closed the
+/// connection between requests
+pub async fn read_request<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<Request>, HttpError> {
+    let Some(request_line) = read_line(reader).await? else {
+        return Ok(None);
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(HttpError::Malformed(format!(
+            "request line \"{request_line}\""
+        )));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(HttpError::Malformed(format!("version {version}")));
+    }
+
+    let mut length = 0;
+    let mut close = version == "HTTP/1.0";
+    for count in 0.. {
+        let line = read_line(reader)
+            .await?
+            .ok_or_else(|| HttpError::Malformed("headers cut short".into()))?;
+        if line.is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Err(HttpError::TooLarge);
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| HttpError::Malformed(format!("header \"{line}\"")))?;
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value
+                .parse()
+                .map_err(|_| HttpError::Malformed(format!("Content-Length {value}")))?;
+        } else if name.eq_ignore_ascii_case("connection") {
+            close = value.eq_ignore_ascii_case("close");
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(HttpError::Malformed(
+                "chunked bodies are not supported".into(),
+            ));
+        }
+    }
+    if length > MAX_BODY {
+        return Err(HttpError::TooLarge);
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    let path = target.split('?').next().unwrap_or_default().to_string();
+    Ok(Some(Request {
+        method: method.to_string(),
+        path,
+        body,
+        close,
+    }))
+}
+
+/// Writes `response`, letting browsers on other origins read it
+pub async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    response: &Response,
+) -> io::Result<()> {
+    let body = match &response.body {
+        Some(body) => serde_json::to_vec(body).map_err(io::Error::other)?,
+        None => Vec::new(),
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, DELETE, OPTIONS\r\nAccess-Control-Allow-Headers: Content-Type\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    if response.body.is_some() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    head.push_str("\r\n");
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(&body).await?;
+    writer.flush().await
+}
@@ -0,0 +1,67 @@
+//! A local HTTP server speaking JSON, so other front ends can use the rules
+//! and the engine. Built with the `server` feature and started with
+//! `checkers-rs serve [ADDRESS]`; see [`api::Api::handle`] for the routes.
+
+pub mod api;
+pub mod http;
+
+use std::io;
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio::io::BufReader;
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::net::link::{with_port, ACCEPT_BACKOFF};
+use crate::utils::log;
+use api::Api;
+use http::{read_request, write_response, Response};
+
+/// Port used when an address leaves it out
+pub const DEFAULT_SERVER_PORT: u16 = 8080;
+/// Where the server listens by default: this machine only
+pub const DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1";
+
+/// Binds `address`, filling in the default port
+pub async fn bind(address: &str) -> io::Result<TcpListener> {
+    TcpListener::bind(with_port(address, DEFAULT_SERVER_PORT)).await
+}
+
+/// Answers connections on `listener` until the task is dropped, each
+/// connection on its own task. A failed accept is logged and the server
+/// keeps listening.
+pub async fn serve(listener: TcpListener, api: Arc<Api>) -> io::Result<()> {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(connection(stream, Arc::clone(&api)));
+            }
+            Err(e) => {
+                log::warn("accept_failed", json!({ "message": e.to_string() }));
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+            }
+        }
+    }
+}
+
+async fn connection(stream: TcpStream, api: Arc<Api>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    loop {
+        let request = match read_request(&mut reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return,
+            Err(error) => {
+                if let Some(status) = error.status() {
+                    let response = Response::error(status, error.to_string());
+                    let _ = write_response(&mut writer, &response).await;
+                }
+                return;
+            }
+        };
+        let response = api.handle(&request).await;
+        if write_response(&mut writer, &response).await.is_err() || request.close {
+            return;
+        }
+    }
+}
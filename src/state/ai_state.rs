@@ -1,7 +1,12 @@
+use crate::utils::log;
+use serde_json::json;
+
 #[derive(Clone)]
 pub struct AIState {
     pub is_thinking: bool,
     pub last_error: Option<String>,
+    /// What the opponent persona said with its last move
+    pub last_remark: Option<String>,
 }
 
 impl AIState {
@@ -9,6 +14,7 @@ impl AIState {
         Self {
             is_thinking: false,
             last_error: None,
+            last_remark: None,
         }
     }
 
@@ -16,10 +22,18 @@ impl AIState {
         let mut new_state = self.clone();
         new_state.is_thinking = true;
         new_state.last_error = None;
+        new_state.last_remark = None;
+        new_state
+    }
+
+    pub fn set_remark(&self, remark: Option<String>) -> Self {
+        let mut new_state = self.clone();
+        new_state.last_remark = remark;
         new_state
     }
 
     pub fn set_error(&self, error: String) -> Self {
+        log::error("ai_error", json!({ "message": error }));
         let mut new_state = self.clone();
         new_state.last_error = Some(error);
         new_state.is_thinking = false;
@@ -0,0 +1,57 @@
+//! An unfinished local game is kept in `autosave.pdn` in the state directory
+//! while `autosave` is on, and picked up again on the next start. The file
+//! goes away when the game ends.
+
+use crate::ai::formatting::play_pdn;
+use crate::core::fen::to_fen;
+use crate::core::game::CheckersGame;
+use crate::core::notation::turn_to_numeric;
+use crate::core::pdn::{parse_pdn, PdnGame};
+use crate::utils::paths;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const AUTOSAVE_FILE: &str = "autosave.pdn";
+
+/// Where the unfinished game is kept
+pub fn autosave_path() -> Option<PathBuf> {
+    paths::state_dir().map(|dir| dir.join(AUTOSAVE_FILE))
+}
+
+/// The game so far as PDN, with the position it began from when that was
+/// not the standard setup
+pub fn game_pdn(game: &CheckersGame, start: Option<&CheckersGame>) -> String {
+    let mut pdn = PdnGame::new("*").with_tag("Event", "checkers-rs autosave");
+    if let Some(start) = start {
+        pdn = pdn.with_tag("SetUp", "1").with_tag("FEN", &to_fen(start));
+    }
+    for (player, turn) in game.move_history.turns() {
+        pdn = pdn.with_move(player, turn_to_numeric(&turn, game.board.size), None);
+    }
+    pdn.to_pdn()
+}
+
+/// Writes the game to `path`, creating its directory when needed
+pub fn save_game(path: &Path, game: &CheckersGame, start: Option<&CheckersGame>) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, game_pdn(game, start))
+}
+
+/// The saved game at `path`: the position it began from, when that was not
+/// the standard setup, and the position reached
+pub fn load_game(path: &Path) -> Result<(Option<CheckersGame>, CheckersGame), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let pdn = parse_pdn(&text).map_err(|e| e.to_string())?;
+    let (start, game) = play_pdn(&pdn)?;
+    Ok((pdn.tag("FEN").map(|_| start), game))
+}
+
+/// Forgets the saved game, if there is one
+pub fn remove_game(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
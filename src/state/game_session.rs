@@ -1,6 +1,16 @@
+use crate::ai::analysis::GameAnalysis;
+use crate::ai::coach::{Coach, CoachConversation};
+use crate::ai::persona::Persona;
+use crate::ai::usage::is_budget_exhausted;
 use crate::ai::{hint::HintProvider, Hint};
+use crate::config::{gemini_model, Difficulty, Opponent, PlayOptions};
+use crate::core::clock::Clock;
 use crate::core::game::{CheckersGame, GameError};
 use crate::core::game_logic::find_capture_path;
+use crate::core::piece::Color;
+use crate::core::replay::Replay;
+use crate::net::broadcast::Watching;
+use crate::net::RemotePlay;
 use crate::state::ai_state::AIState;
 use crate::state::states::WelcomeContent;
 use crate::state::ui_state::UIState;
@@ -13,6 +23,26 @@ pub struct GameSession {
     pub hint: Option<Hint>,
     pub hint_provider: Option<HintProvider>,
     pub welcome_content: Option<WelcomeContent>,
+    pub coach: CoachConversation,
+    pub coach_provider: Option<Coach>,
+    pub analysis: Option<GameAnalysis>,
+    pub replay: Option<Replay>,
+    pub personas: Vec<Persona>,
+    /// Index into `personas` of the chosen opponent
+    pub persona_index: usize,
+    /// Both sides' clocks, for timed games
+    pub clock: Option<Clock>,
+    /// The opponent on another machine, for network games
+    pub remote: Option<RemotePlay>,
+    /// The broadcast being followed, for watchers who only look on
+    pub watching: Option<Watching>,
+    /// Who plays against the keyboard
+    pub opponent: Opponent,
+    /// The side played from this keyboard when the computer has the other
+    pub human_color: Color,
+    pub difficulty: Difficulty,
+    /// The position play began from, when it was not the standard setup
+    pub start: Option<CheckersGame>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -25,6 +55,19 @@ impl Default for GameSession {
             hint: None,
             hint_provider: None,
             welcome_content: None,
+            coach: CoachConversation::new(),
+            coach_provider: None,
+            analysis: None,
+            replay: None,
+            personas: Persona::builtins(),
+            persona_index: 0,
+            clock: None,
+            remote: None,
+            watching: None,
+            opponent: Opponent::default(),
+            human_color: Color::White,
+            difficulty: Difficulty::default(),
+            start: None,
         }
     }
 }
@@ -34,6 +77,134 @@ impl GameSession {
         Self::default()
     }
 
+    /// A session set up for a new game as `options` say
+    pub fn from_options(options: &PlayOptions) -> Self {
+        let start = options.fen.as_ref().map(|_| options.start());
+        Self {
+            game: options.start(),
+            clock: options.time.map(Clock::new),
+            opponent: options.opponent,
+            human_color: options.color,
+            difficulty: options.difficulty,
+            start,
+            ..Self::default()
+        }
+    }
+
+    /// Hints and the coach, asking the configured model when there is an API
+    /// key to ask it with
+    pub fn with_ai_helpers(&self) -> Self {
+        let mut new_session = self.clone();
+        let api_key = std::env::var("GEMINI_API_KEY").ok();
+        new_session.hint_provider = api_key.clone().and_then(|key| HintProvider::new(key).ok());
+        new_session.coach_provider = api_key.and_then(|key| Coach::new(key).ok());
+        new_session
+    }
+
+    /// The opponent style used for LLM moves
+    pub fn persona(&self) -> &Persona {
+        static FALLBACK: std::sync::OnceLock<Persona> = std::sync::OnceLock::new();
+        self.personas
+            .get(self.persona_index)
+            .unwrap_or_else(|| FALLBACK.get_or_init(Persona::default))
+    }
+
+    /// Who plays `color`, as an exported game names them. Two players at one
+    /// keyboard, and both sides of a watched broadcast, go by their order.
+    pub fn player_name(&self, color: Color) -> String {
+        if let Some(remote) = &self.remote {
+            let name = if remote.seat == color {
+                "Remote player"
+            } else {
+                "Player"
+            };
+            return name.to_string();
+        }
+        match self.ai_seat() {
+            Some(seat) if seat == color && self.is_simple_ai() => "Computer".to_string(),
+            Some(seat) if seat == color => format!("Computer ({})", self.persona().name),
+            Some(_) => "Player".to_string(),
+            None if color == Color::White => "Player 1".to_string(),
+            None => "Player 2".to_string(),
+        }
+    }
+
+    /// Whether the side to move is played at this end rather than over the
+    /// network; watchers play neither side
+    pub fn is_local_turn(&self) -> bool {
+        self.watching.is_none()
+            && self
+                .remote
+                .as_ref()
+                .is_none_or(|remote| remote.seat != self.game.current_player)
+    }
+
+    /// The side played by the computer: the one not played from this
+    /// keyboard, unless two players share it, the opponent is on the network
+    /// or the game is only being watched
+    pub fn ai_seat(&self) -> Option<Color> {
+        (self.opponent != Opponent::Human && self.remote.is_none() && self.watching.is_none())
+            .then_some(self.human_color.opposite())
+    }
+
+    /// Whether the computer's moves come from the built-in engine, because
+    /// it was asked for or no model is configured
+    pub fn is_simple_ai(&self) -> bool {
+        self.opponent == Opponent::Engine
+            || std::env::var("GEMINI_API_KEY").is_err()
+            || gemini_model().is_none()
+    }
+
+    /// Whether the computer's next move should be asked of the model; the
+    /// engine also takes over once the session's LLM budget is spent
+    pub fn uses_llm(&self) -> bool {
+        !self.is_simple_ai() && !is_budget_exhausted()
+    }
+
+    /// Moves the persona choice forward or back, wrapping around the list
+    pub fn cycle_persona(&self, forward: bool) -> Self {
+        let mut new_session = self.clone();
+        let count = new_session.personas.len();
+        if count > 0 {
+            new_session.persona_index = if forward {
+                (self.persona_index + 1) % count
+            } else {
+                (self.persona_index + count - 1) % count
+            };
+        }
+        new_session
+    }
+
+    /// Asks the hint provider for a hint on the current position. Without a
+    /// provider or an async runtime the session is returned unchanged.
+    pub fn request_hint(&self) -> Self {
+        let mut new_session = self.clone();
+        let Some(provider) = &self.hint_provider else {
+            return new_session;
+        };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return new_session;
+        };
+
+        let hint_result = tokio::task::block_in_place(|| {
+            handle.block_on(provider.get_hint(
+                &self.game.board,
+                self.game.current_player,
+                &self.game.move_history,
+            ))
+        });
+        new_session.hint = hint_result.ok();
+        new_session
+    }
+
+    /// Pages the move list back through the game or toward the latest move
+    pub fn scroll_move_list(&self, back: bool) -> Self {
+        const PAGE: usize = 5;
+        let rows = self.game.move_history.turns().len().div_ceil(2);
+        let ui_state = self.ui_state.scroll_move_list(back, PAGE, rows);
+        self.with_ui_state(ui_state)
+    }
+
     pub fn with_ui_state(&self, ui_state: UIState) -> Self {
         let mut new_session = self.clone();
         new_session.ui_state = ui_state;
@@ -1,11 +1,13 @@
+use crate::interface::keymap::Input;
 use crate::state::game_session::GameSession;
 use crate::state::transition::StateTransition;
 use crate::state::view_data::ViewData;
 use crate::state::StateType;
-use crossterm::event::KeyEvent;
+use crate::utils::log::{self, Level};
+use serde_json::json;
 
 pub trait State {
-    fn handle_input(&self, session: &GameSession, key: KeyEvent) -> (GameSession, StateTransition);
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition);
 
     fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a>;
 
@@ -26,9 +28,9 @@ impl StateMachine {
     pub fn handle_input(
         &self,
         session: &GameSession,
-        key: KeyEvent,
+        input: Input,
     ) -> (GameSession, StateTransition) {
-        self.current_state.handle_input(session, key)
+        self.current_state.handle_input(session, input)
     }
 
     pub fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
@@ -43,10 +45,26 @@ impl StateMachine {
         match transition {
             StateTransition::None => {}
             StateTransition::To(new_state) => {
+                let (from, to) = (self.current_state.state_type(), new_state.state_type());
+                // A state replacing itself is only news when tracing
+                let level = if from == to {
+                    Level::Trace
+                } else {
+                    Level::Debug
+                };
+                log::log(
+                    level,
+                    "transition",
+                    json!({ "from": format!("{from:?}"), "to": format!("{to:?}") }),
+                );
                 self.current_state = new_state;
             }
             StateTransition::Exit => {
                 // Handle exit in main loop
+                log::debug(
+                    "transition",
+                    json!({ "from": format!("{:?}", self.current_state.state_type()), "to": "Exit" }),
+                );
             }
         }
     }
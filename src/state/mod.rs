@@ -1,4 +1,5 @@
 pub mod ai_state;
+pub mod autosave;
 pub mod game_session;
 pub mod machine;
 pub mod transition;
@@ -18,6 +19,11 @@ pub enum StateType {
     Playing,
     PieceSelected,
     AITurn,
+    Coach,
+    MoveEntry,
     MultiCapture,
     GameOver,
+    Analysis,
+    Replay,
+    Settings,
 }
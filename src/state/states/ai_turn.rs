@@ -1,7 +1,9 @@
+use crate::ai::engine;
 use crate::ai::genai_client::get_ai_move;
-use crate::core::piece::Color;
+use crate::ai::AIError;
+use crate::interface::keymap::Input;
 use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
-use crossterm::event::KeyEvent;
+use std::time::{Duration, Instant};
 
 #[derive(Default)]
 pub struct AITurnState {
@@ -12,71 +14,57 @@ impl AITurnState {
     pub fn new() -> Self {
         Self::default()
     }
-
-    fn with_move_requested(&self) -> Self {
-        Self {
-            move_requested: true,
-        }
-    }
 }
 
 impl State for AITurnState {
-    fn handle_input(
-        &self,
-        session: &GameSession,
-        _key: KeyEvent,
-    ) -> (GameSession, StateTransition) {
+    fn handle_input(&self, session: &GameSession, _input: Input) -> (GameSession, StateTransition) {
         // Make AI move if not done yet
         if !self.move_requested {
             // Start thinking
             let mut new_session = session.clone();
             new_session.ai_state = new_session.ai_state.start_thinking();
 
-            // Check if we should use real AI or test fallback
-            let use_real_ai = std::env::var("AI_TEST_MODE").is_err()
-                && std::env::var("GEMINI_API_KEY").is_ok()
-                && std::env::var("GEMINI_MODEL").is_ok()
-                && tokio::runtime::Handle::try_current().is_ok();
+            // Check if we should use real AI or the simple fallback, which
+            // also takes over once the session's LLM budget is spent
+            let use_real_ai = session.uses_llm() && tokio::runtime::Handle::try_current().is_ok();
 
             if use_real_ai {
-                // Use real AI with async calls
+                // Use real AI with async calls, only waiting as long as the
+                // clock allows in timed games
+                let budget = thinking_budget(session);
                 let ai_result = tokio::task::block_in_place(|| {
-                    tokio::runtime::Handle::current().block_on(get_ai_move(&session.game))
+                    tokio::runtime::Handle::current().block_on(async {
+                        let request = get_ai_move(&session.game, session.persona());
+                        match budget {
+                            Some(budget) => tokio::time::timeout(budget, request)
+                                .await
+                                .unwrap_or(Err(AIError::OutOfTime(budget))),
+                            None => request.await,
+                        }
+                    })
                 });
 
                 match ai_result {
-                    Ok((from, to)) => {
-                        let game_move = crate::core::GameMove::from_tuples(from, to);
+                    Err(AIError::OutOfTime(_)) => {
+                        // Too slow to wait for, so the engine answers quickly
+                        return engine_move(new_session, Some(Duration::ZERO));
+                    }
+                    Ok(ai_move) => {
+                        let game_move =
+                            crate::core::GameMove::from_tuples(ai_move.from, ai_move.to);
                         match new_session.game.make_move(game_move) {
                             Ok((updated_game, _)) => {
                                 new_session.game = updated_game;
-                                new_session.ai_state = new_session.ai_state.clear_error();
+                                new_session.ai_state = new_session
+                                    .ai_state
+                                    .clear_error()
+                                    .set_remark(ai_move.remark);
 
                                 // Update hint after AI move
-                                if let Some(ref provider) = new_session.hint_provider {
-                                    if new_session.game.current_player == Color::White
-                                        && !new_session.game.is_game_over
-                                    {
-                                        let hint_result = tokio::task::block_in_place(|| {
-                                            tokio::runtime::Handle::current().block_on(
-                                                provider.get_hint(
-                                                    &new_session.game.board,
-                                                    Color::White,
-                                                    &new_session.game.move_history,
-                                                ),
-                                            )
-                                        });
-
-                                        match hint_result {
-                                            Ok(hint_text) => {
-                                                new_session.hint =
-                                                    Some(crate::ai::Hint { hint: hint_text });
-                                            }
-                                            Err(_) => {
-                                                new_session.hint = None;
-                                            }
-                                        }
-                                    }
+                                if new_session.ai_seat() != Some(new_session.game.current_player)
+                                    && !new_session.game.is_game_over
+                                {
+                                    new_session = new_session.request_hint();
                                 }
 
                                 // Check for game over
@@ -130,115 +118,48 @@ impl State for AITurnState {
                     }
                 }
             } else {
-                // Fallback to simple AI for tests
-                use crate::core::game_logic;
-
-                let all_moves = game_logic::get_all_valid_moves_for_player(
-                    &new_session.game.board,
-                    Color::Black,
-                );
-
-                if all_moves.is_empty() {
-                    // No valid moves - game over
-                    new_session.game.is_game_over = true;
-                    return (
-                        new_session,
-                        StateTransition::To(Box::new(super::GameOverState::new(Some(
-                            Color::White,
-                        )))),
-                    );
-                }
-
-                // Separate captures from regular moves
-                let captures: Vec<_> = all_moves
-                    .iter()
-                    .filter(|(_, _, is_capture)| *is_capture)
-                    .cloned()
-                    .collect();
-
-                let moves_to_consider = if !captures.is_empty() {
-                    captures // Must make a capture if available
-                } else {
-                    all_moves
-                };
-
-                if !moves_to_consider.is_empty() {
-                    // Pick first available move (simple AI)
-                    let ((from_row, from_col), (to_row, to_col), _) = moves_to_consider[0];
-
-                    match new_session
-                        .game
-                        .make_move_coords(from_row, from_col, to_row, to_col)
-                    {
-                        Ok((updated_game, _)) => {
-                            new_session.game = updated_game;
-                            new_session.ai_state = new_session.ai_state.clear_error();
-
-                            // Check for game over
-                            let winner = new_session.game.check_winner();
-                            if winner.is_some() {
-                                new_session.game.is_game_over = true;
-                                return (
-                                    new_session,
-                                    StateTransition::To(Box::new(super::GameOverState::new(
-                                        winner,
-                                    ))),
-                                );
-                            } else if new_session.game.is_stalemate() {
-                                // If current player has no moves, the other player wins
-                                let winner = Some(new_session.game.current_player.opposite());
-                                new_session.game.is_game_over = true;
-                                return (
-                                    new_session,
-                                    StateTransition::To(Box::new(super::GameOverState::new(
-                                        winner,
-                                    ))),
-                                );
-                            }
-
-                            return (
-                                new_session,
-                                StateTransition::To(Box::new(super::PlayingState::new())),
-                            );
-                        }
-                        Err(e) => {
-                            new_session.ai_state =
-                                new_session.ai_state.set_error(format!("AI error: {e}"));
-                            return (
-                                new_session,
-                                StateTransition::To(Box::new(super::PlayingState::new())),
-                            );
-                        }
-                    }
-                }
+                // The built-in engine, asked for or standing in for the model
+                let budget = thinking_budget(&new_session);
+                return engine_move(new_session, budget);
             }
-
-            // Return with move requested state to prevent re-execution
-            return (
-                new_session,
-                StateTransition::To(Box::new(self.with_move_requested())),
-            );
         }
 
         (session.clone(), StateTransition::None)
     }
 
     fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let (last_move, captured_squares) = session.game.move_history.last_turn().unzip();
         ViewData {
             board: &session.game.board,
             current_player: session.game.current_player,
             cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
             selected_piece: None,
             possible_moves: &[],
             pieces_with_captures: Vec::new(),
             status_message: "AI is thinking...".to_string(),
             show_ai_thinking: true,
             error_message: session.ai_state.last_error.as_deref(),
-            is_simple_ai: std::env::var("GEMINI_API_KEY").is_err()
-                || std::env::var("GEMINI_MODEL").is_err(),
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: session.ai_state.last_remark.as_deref(),
             hint: session.hint.as_ref(),
             is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: Some(&session.game.move_history),
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move,
+            captured_squares: captured_squares.unwrap_or_default(),
             welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: None,
         }
     }
 
@@ -246,3 +167,70 @@ impl State for AITurnState {
         StateType::AITurn
     }
 }
+
+/// How long the side to move may think in a timed game, from its own clock
+fn thinking_budget(session: &GameSession) -> Option<Duration> {
+    let clock = session.clock.as_ref()?;
+    Some(clock.budget(session.game.current_player, Instant::now()))
+}
+
+/// Plays the engine's choice for the side to move, searching as deep as
+/// `budget` allows or to the difficulty's depth in untimed games
+fn engine_move(
+    mut new_session: GameSession,
+    budget: Option<Duration>,
+) -> (GameSession, StateTransition) {
+    let difficulty = new_session.difficulty;
+    let game = &new_session.game;
+    let result = match (budget, difficulty.timed_depth()) {
+        (Some(budget), Some(depth)) => engine::search_within_depth(game, budget, depth),
+        (Some(budget), None) => engine::search_within(game, budget),
+        (None, _) => engine::search(game, difficulty.depth()),
+    };
+    let Some(result) = result else {
+        // No valid moves - game over
+        let winner = new_session.game.current_player.opposite();
+        new_session.game.is_game_over = true;
+        return (
+            new_session,
+            StateTransition::To(Box::new(super::GameOverState::new(Some(winner)))),
+        );
+    };
+
+    match new_session.game.apply_turn(&result.turn) {
+        Ok(updated_game) => {
+            new_session.game = updated_game;
+            new_session.ai_state = new_session.ai_state.clear_error();
+
+            // Check for game over
+            let winner = new_session.game.check_winner();
+            if winner.is_some() {
+                new_session.game.is_game_over = true;
+                return (
+                    new_session,
+                    StateTransition::To(Box::new(super::GameOverState::new(winner))),
+                );
+            } else if new_session.game.is_stalemate() {
+                // If current player has no moves, the other player wins
+                let winner = Some(new_session.game.current_player.opposite());
+                new_session.game.is_game_over = true;
+                return (
+                    new_session,
+                    StateTransition::To(Box::new(super::GameOverState::new(winner))),
+                );
+            }
+
+            (
+                new_session,
+                StateTransition::To(Box::new(super::PlayingState::new())),
+            )
+        }
+        Err(e) => {
+            new_session.ai_state = new_session.ai_state.set_error(format!("AI error: {e}"));
+            (
+                new_session,
+                StateTransition::To(Box::new(super::PlayingState::new())),
+            )
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::ai::analysis::{review_with_llm, GameAnalysis};
+use crate::ai::usage::is_budget_exhausted;
+use crate::config::gemini_model;
+use crate::core::piece::Color;
+use crate::interface::keymap::{Action, Input};
+use crate::state::view_data::AnalysisView;
+use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
+
+/// Steps through the annotated review of a finished game. The review is
+/// worked out on the first input after opening, so the screen can say so
+/// first; it is kept for later visits.
+pub struct AnalysisState {
+    winner: Option<Color>,
+    selected: usize,
+    status: Option<String>,
+}
+
+impl AnalysisState {
+    pub fn new(winner: Option<Color>) -> Self {
+        Self {
+            winner,
+            selected: 0,
+            status: None,
+        }
+    }
+
+    fn with_selected(&self, selected: usize) -> Self {
+        Self {
+            winner: self.winner,
+            selected,
+            status: None,
+        }
+    }
+
+    fn with_status(&self, status: String) -> Self {
+        Self {
+            winner: self.winner,
+            selected: self.selected,
+            status: Some(status),
+        }
+    }
+}
+
+impl State for AnalysisState {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        let Some(analysis) = &session.analysis else {
+            if input == Input::Action(Action::Quit) {
+                return (
+                    session.clone(),
+                    StateTransition::To(Box::new(super::GameOverState::new(self.winner))),
+                );
+            }
+            let mut new_session = session.clone();
+            new_session.analysis = Some(analyze(session, self.winner));
+            return (new_session, StateTransition::None);
+        };
+        let last = analysis.moves.len().saturating_sub(1);
+
+        let transition = match input {
+            Input::Action(Action::Left | Action::Up) if self.selected > 0 => {
+                StateTransition::To(Box::new(self.with_selected(self.selected - 1)))
+            }
+            Input::Action(Action::Right | Action::Down) if self.selected < last => {
+                StateTransition::To(Box::new(self.with_selected(self.selected + 1)))
+            }
+            Input::Action(Action::Export) => {
+                let status = match std::env::current_dir().and_then(|dir| analysis.export_pdn(&dir))
+                {
+                    Ok(path) => format!("Saved {}", path.display()),
+                    Err(e) => format!("Export failed: {e}"),
+                };
+                StateTransition::To(Box::new(self.with_status(status)))
+            }
+            Input::Action(Action::Quit) => {
+                StateTransition::To(Box::new(super::GameOverState::new(self.winner)))
+            }
+            _ => StateTransition::None,
+        };
+
+        (session.clone(), transition)
+    }
+
+    fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let selected_move = session
+            .analysis
+            .as_ref()
+            .and_then(|analysis| analysis.moves.get(self.selected));
+
+        let (board, current_player) = match selected_move {
+            Some(annotated) => (&annotated.board_after, annotated.player.opposite()),
+            None => (&session.game.board, session.game.current_player),
+        };
+
+        let status_message = self.status.clone().unwrap_or_else(|| match selected_move {
+            Some(annotated) => match &annotated.comment {
+                Some(comment) => format!("{}: {}", annotated.annotation.label(), comment),
+                None => annotated.annotation.label().to_string(),
+            },
+            None if session.analysis.is_none() => "Analyzing the game...".to_string(),
+            None => "No moves to review".to_string(),
+        });
+
+        ViewData {
+            board,
+            current_player,
+            cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
+            selected_piece: None,
+            possible_moves: &[],
+            pieces_with_captures: Vec::new(),
+            status_message,
+            show_ai_thinking: false,
+            error_message: None,
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
+            hint: None,
+            is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: None,
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move: selected_move.map(|annotated| annotated.turn.clone()),
+            captured_squares: Vec::new(),
+            welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: session.analysis.as_ref().map(|analysis| AnalysisView {
+                analysis,
+                selected: self.selected,
+            }),
+            replay: None,
+            move_entry: None,
+            settings: None,
+        }
+    }
+
+    fn state_type(&self) -> StateType {
+        StateType::Analysis
+    }
+}
+
+/// Scores the game with the engine, then lets the LLM judge it when configured
+fn analyze(session: &GameSession, winner: Option<Color>) -> GameAnalysis {
+    let history = &session.game.move_history;
+    let analysis = match &session.start {
+        Some(start) => GameAnalysis::from_position(start, history, winner),
+        None => GameAnalysis::from_history(history, winner),
+    }
+    .with_players(
+        &session.player_name(Color::White),
+        &session.player_name(Color::Black),
+    );
+
+    let use_real_ai = std::env::var("GEMINI_API_KEY").is_ok()
+        && gemini_model().is_some()
+        && !is_budget_exhausted()
+        && tokio::runtime::Handle::try_current().is_ok();
+
+    if use_real_ai {
+        let review = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(review_with_llm(&analysis))
+        });
+        // Fall back to the engine's verdicts if the request fails
+        if let Ok(review) = review {
+            return review;
+        }
+    }
+
+    analysis
+}
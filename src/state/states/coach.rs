@@ -0,0 +1,114 @@
+use crate::interface::keymap::{Action, Input};
+use crate::state::view_data::CoachView;
+use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
+
+#[derive(Default)]
+pub struct CoachState {
+    input: String,
+}
+
+impl CoachState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_input(input: String) -> Self {
+        Self { input }
+    }
+}
+
+impl State for CoachState {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        match input {
+            Input::Action(Action::Quit) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::PlayingState::new())),
+            ),
+            Input::Char(c) => {
+                let mut input = self.input.clone();
+                input.push(c);
+                (
+                    session.clone(),
+                    StateTransition::To(Box::new(Self::with_input(input))),
+                )
+            }
+            Input::Backspace => {
+                let mut input = self.input.clone();
+                input.pop();
+                (
+                    session.clone(),
+                    StateTransition::To(Box::new(Self::with_input(input))),
+                )
+            }
+            Input::Action(Action::Select) => {
+                // One question at a time; keep the draft while an answer streams in
+                if self.input.trim().is_empty() || session.coach.is_awaiting_reply() {
+                    return (session.clone(), StateTransition::None);
+                }
+
+                let mut new_session = session.clone();
+                new_session.coach = session.coach.ask(
+                    &self.input,
+                    &session.game.board,
+                    session.game.current_player,
+                    &session.game.move_history,
+                );
+
+                if new_session.coach_provider.is_none() {
+                    new_session.coach = new_session.coach.fail_reply(
+                        "Coach unavailable - add GEMINI_API_KEY and GEMINI_MODEL to your .env file",
+                    );
+                }
+
+                (
+                    new_session,
+                    StateTransition::To(Box::new(CoachState::new())),
+                )
+            }
+            _ => (session.clone(), StateTransition::None),
+        }
+    }
+
+    fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let (last_move, captured_squares) = session.game.move_history.last_turn().unzip();
+        ViewData {
+            board: &session.game.board,
+            current_player: session.game.current_player,
+            cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
+            selected_piece: None,
+            possible_moves: &[],
+            pieces_with_captures: Vec::new(),
+            status_message: "Ask the coach about the position".to_string(),
+            show_ai_thinking: false,
+            error_message: None,
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
+            hint: None,
+            is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: Some(&session.game.move_history),
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move,
+            captured_squares: captured_squares.unwrap_or_default(),
+            welcome_content: None,
+            persona: None,
+            coach: Some(CoachView {
+                conversation: &session.coach,
+                input: self.input.clone(),
+            }),
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: None,
+        }
+    }
+
+    fn state_type(&self) -> StateType {
+        StateType::Coach
+    }
+}
@@ -1,6 +1,9 @@
+use crate::ai::usage::session_usage;
 use crate::core::piece::Color;
+use crate::interface::keymap::{Action, Input};
+use crate::net::remote::RemoteResult;
 use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
-use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
 
 pub struct GameOverState {
     winner: Option<Color>,
@@ -13,36 +16,74 @@ impl GameOverState {
 }
 
 impl State for GameOverState {
-    fn handle_input(&self, session: &GameSession, key: KeyEvent) -> (GameSession, StateTransition) {
-        let transition = match key.code {
-            KeyCode::Esc => StateTransition::Exit,
-            _ => StateTransition::None,
-        };
-        (session.clone(), transition)
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        match input {
+            Input::Action(Action::Quit) => (session.clone(), StateTransition::Exit),
+            // The review opens at once and is worked out on the next frame
+            Input::Action(Action::Analyze) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::AnalysisState::new(self.winner))),
+            ),
+            Input::Action(Action::Replay) => {
+                super::ReplayState::open(session, super::ReplayExit::GameOver(self.winner))
+            }
+            _ => (session.clone(), StateTransition::None),
+        }
     }
 
     fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        // A stopped clock showing zero means the loser ran out of time
+        let on_time = session
+            .clock
+            .as_ref()
+            .and_then(|clock| clock.flagged(Instant::now()))
+            .is_some();
+        let result = session.remote.as_ref().and_then(|remote| remote.result);
+        let how = match result {
+            Some(RemoteResult::Resigned(_)) => " by resignation",
+            _ if on_time => " on time",
+            _ => "",
+        };
         let message = match self.winner {
-            Some(Color::White) => "White wins! Press ESC to exit".to_string(),
-            Some(Color::Black) => "Black wins! Press ESC to exit".to_string(),
-            None => "Stalemate! No possible moves. Press ESC to exit".to_string(),
+            Some(Color::White) => format!("White wins{how}!"),
+            Some(Color::Black) => format!("Black wins{how}!"),
+            None if result == Some(RemoteResult::DrawAgreed) => "Draw agreed!".to_string(),
+            None => "Stalemate! No possible moves.".to_string(),
         };
 
         ViewData {
             board: &session.game.board,
             current_player: session.game.current_player,
             cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
             selected_piece: None,
             possible_moves: &[],
             pieces_with_captures: Vec::new(),
             status_message: message,
             show_ai_thinking: false,
             error_message: None,
-            is_simple_ai: std::env::var("GEMINI_API_KEY").is_err()
-                || std::env::var("GEMINI_MODEL").is_err(),
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
             hint: None,
             is_game_over: true,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: Some(session_usage())
+                .filter(|usage| usage.requests + usage.cache_hits > 0)
+                .map(|usage| usage.summary()),
+            move_history: None,
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move: None,
+            captured_squares: Vec::new(),
             welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: None,
         }
     }
 
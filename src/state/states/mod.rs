@@ -1,13 +1,23 @@
 pub mod ai_turn;
+pub mod analysis;
+pub mod coach;
 pub mod game_over;
+pub mod move_entry;
 pub mod multi_capture;
 pub mod piece_selected;
 pub mod playing;
+pub mod replay;
+pub mod settings;
 pub mod welcome;
 
 pub use ai_turn::AITurnState;
+pub use analysis::AnalysisState;
+pub use coach::CoachState;
 pub use game_over::GameOverState;
+pub use move_entry::MoveEntryState;
 pub use multi_capture::MultiCaptureState;
 pub use piece_selected::PieceSelectedState;
 pub use playing::PlayingState;
+pub use replay::{ReplayExit, ReplayState};
+pub use settings::SettingsState;
 pub use welcome::{WelcomeContent, WelcomeState};
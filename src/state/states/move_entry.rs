@@ -0,0 +1,267 @@
+use crate::ai::formatting::{parse_move, resolve_turn};
+use crate::core::game::CheckersGame;
+use crate::core::notation::{turn_to_algebraic, turn_to_numeric};
+use crate::core::Turn;
+use crate::interface::keymap::{Action, Input};
+use crate::net::remote;
+use crate::state::view_data::MoveEntryView;
+use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
+
+/// Command line for typing a move in notation instead of steering the cursor
+#[derive(Default)]
+pub struct MoveEntryState {
+    input: String,
+    error: Option<String>,
+}
+
+impl MoveEntryState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_input(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            error: None,
+        }
+    }
+
+    /// Legal turns that start with what has been typed so far, written the
+    /// way the player is typing them: numbered squares once the input starts
+    /// with a digit, square names otherwise.
+    pub fn completions(&self, game: &CheckersGame) -> Vec<String> {
+        let numeric = self
+            .input
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_digit());
+        let typed = normalize(&self.input);
+
+        let mut completions: Vec<String> = game
+            .legal_turns()
+            .iter()
+            .map(|turn| {
+                if numeric {
+                    turn_to_numeric(turn, game.board.size)
+                } else {
+                    turn_to_algebraic(turn, game.board.size)
+                }
+            })
+            .filter(|text| normalize(text).starts_with(&typed))
+            .collect();
+        completions.sort();
+        completions.dedup();
+        completions
+    }
+
+    /// Completions for the input: the network commands once it starts with
+    /// `/` in a network game, legal moves otherwise
+    fn suggestions(&self, session: &GameSession) -> Vec<String> {
+        let typed = self.input.trim_start();
+        if session.remote.is_some() && typed.starts_with('/') {
+            return remote::COMMANDS
+                .iter()
+                .filter(|command| command.starts_with(typed))
+                .map(|command| command.to_string())
+                .collect();
+        }
+        self.completions(&session.game)
+    }
+
+    /// Extends the input as far as every completion agrees, which is the
+    /// whole move once only one is left
+    fn complete(&self, session: &GameSession) -> Self {
+        let completions = self.suggestions(session);
+        let Some((first, rest)) = completions.split_first() else {
+            return Self::with_input(&self.input);
+        };
+        let shared = rest.iter().fold(first.len(), |shared, other| {
+            first
+                .bytes()
+                .zip(other.bytes())
+                .take(shared)
+                .take_while(|(a, b)| a == b)
+                .count()
+        });
+        if shared > self.input.trim().len() {
+            Self::with_input(&first[..shared])
+        } else {
+            Self::with_input(&self.input)
+        }
+    }
+
+    /// Plays the typed move. A jump may name only its first and last square
+    /// when that leaves no doubt about the path in between.
+    pub fn play(&self, game: &CheckersGame) -> Result<(CheckersGame, Turn), String> {
+        let input = self.input.trim();
+        if input.starts_with('/') {
+            return Err("Commands only work in network games".to_string());
+        }
+        let Some(path) = parse_move(input, game.board.size) else {
+            return Err(format!("Can't read \"{input}\" - try c3-d4 or 22-18"));
+        };
+
+        let turn = resolve_turn(game, path);
+        game.apply_turn(&turn)
+            .map(|played| (played, turn))
+            .map_err(|e| format!("{input}: {e}"))
+    }
+
+    fn with_error(&self, error: String) -> Self {
+        Self {
+            input: self.input.clone(),
+            error: Some(error),
+        }
+    }
+}
+
+/// Lowercase with jumps and steps written alike, so "C3xD4" still matches
+/// the completion "c3-d4" and the rules decide which it is
+fn normalize(text: &str) -> String {
+    text.trim().to_lowercase().replace('x', "-")
+}
+
+impl State for MoveEntryState {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        match input {
+            Input::Action(Action::Quit) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::PlayingState::new())),
+            ),
+            Input::Char(c) => {
+                let mut input = self.input.clone();
+                input.push(c);
+                (
+                    session.clone(),
+                    StateTransition::To(Box::new(Self::with_input(&input))),
+                )
+            }
+            Input::Backspace => {
+                let mut input = self.input.clone();
+                input.pop();
+                (
+                    session.clone(),
+                    StateTransition::To(Box::new(Self::with_input(&input))),
+                )
+            }
+            Input::Action(Action::Command) => (
+                session.clone(),
+                StateTransition::To(Box::new(self.complete(session))),
+            ),
+            Input::Action(Action::Select) => {
+                if self.input.trim().is_empty() {
+                    return (session.clone(), StateTransition::None);
+                }
+                if session.remote.is_some() && self.input.trim().starts_with('/') {
+                    return match remote::command(session, &self.input) {
+                        Ok(new_session) => match remote::outcome(&new_session) {
+                            Some(winner) if new_session.game.is_game_over => (
+                                new_session,
+                                StateTransition::To(Box::new(super::GameOverState::new(winner))),
+                            ),
+                            _ => (
+                                new_session,
+                                StateTransition::To(Box::new(super::PlayingState::new())),
+                            ),
+                        },
+                        Err(error) => (
+                            session.clone(),
+                            StateTransition::To(Box::new(self.with_error(error))),
+                        ),
+                    };
+                }
+                if !session.is_local_turn() {
+                    return (
+                        session.clone(),
+                        StateTransition::To(Box::new(
+                            self.with_error("Wait for your opponent's move".to_string()),
+                        )),
+                    );
+                }
+                match self.play(&session.game) {
+                    Ok((game, turn)) => {
+                        let mut new_session = session.clone();
+                        new_session.game = game;
+                        new_session.hint = None;
+                        new_session.ui_state =
+                            session.ui_state.clear_selection().move_cursor_to(turn.to());
+
+                        let game = &new_session.game;
+                        let winner = if let Some(winner) = game.check_winner() {
+                            winner
+                        } else if game.is_stalemate() {
+                            // If current player has no moves, the other player wins
+                            game.current_player.opposite()
+                        } else {
+                            return (
+                                new_session,
+                                StateTransition::To(Box::new(super::PlayingState::new())),
+                            );
+                        };
+                        new_session.game.is_game_over = true;
+                        (
+                            new_session,
+                            StateTransition::To(Box::new(super::GameOverState::new(Some(winner)))),
+                        )
+                    }
+                    Err(error) => (
+                        session.clone(),
+                        StateTransition::To(Box::new(self.with_error(error))),
+                    ),
+                }
+            }
+            _ => (session.clone(), StateTransition::None),
+        }
+    }
+
+    fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let (last_move, captured_squares) = session.game.move_history.last_turn().unzip();
+        let status_message = self.error.clone().unwrap_or_else(|| {
+            if session.remote.is_some() {
+                "Type a move, or /say, /draw, /resign".to_string()
+            } else {
+                "Type a move like c3-d4 or 22-18".to_string()
+            }
+        });
+
+        ViewData {
+            board: &session.game.board,
+            current_player: session.game.current_player,
+            cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
+            selected_piece: None,
+            possible_moves: &[],
+            pieces_with_captures: Vec::new(),
+            status_message,
+            show_ai_thinking: false,
+            error_message: None,
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
+            hint: session.hint.as_ref(),
+            is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: Some(&session.game.move_history),
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move,
+            captured_squares: captured_squares.unwrap_or_default(),
+            welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: Some(MoveEntryView {
+                input: self.input.clone(),
+                completions: self.suggestions(session),
+                failed: self.error.is_some(),
+            }),
+            settings: None,
+        }
+    }
+
+    fn state_type(&self) -> StateType {
+        StateType::MoveEntry
+    }
+}
@@ -1,5 +1,5 @@
+use crate::interface::keymap::{Action, Input};
 use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
-use crossterm::event::{KeyCode, KeyEvent};
 
 pub struct MultiCaptureState {
     capturing_piece: (usize, usize),
@@ -12,25 +12,37 @@ impl MultiCaptureState {
 }
 
 impl State for MultiCaptureState {
-    fn handle_input(&self, session: &GameSession, key: KeyEvent) -> (GameSession, StateTransition) {
-        match key.code {
-            KeyCode::Up => {
-                let new_ui = session.ui_state.move_cursor_up();
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        match input {
+            Input::Action(Action::Up) => {
+                let new_ui = session.ui_state.move_cursor_up(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Down => {
-                let new_ui = session.ui_state.move_cursor_down(7);
+            Input::Action(Action::Down) => {
+                let new_ui = session
+                    .ui_state
+                    .move_cursor_down(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Left => {
-                let new_ui = session.ui_state.move_cursor_left();
+            Input::Action(Action::Left) => {
+                let new_ui = session
+                    .ui_state
+                    .move_cursor_left(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Right => {
-                let new_ui = session.ui_state.move_cursor_right(7);
+            Input::Action(Action::Right) => {
+                let new_ui = session
+                    .ui_state
+                    .move_cursor_right(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Char(' ') | KeyCode::Enter => {
+            Input::Action(Action::ScrollBack) => {
+                (session.scroll_move_list(true), StateTransition::None)
+            }
+            Input::Action(Action::ScrollForward) => {
+                (session.scroll_move_list(false), StateTransition::None)
+            }
+            Input::Action(Action::Select) => {
                 let cursor = session.ui_state.cursor_pos;
                 if session.ui_state.possible_moves.contains(&cursor) {
                     match session.try_multicapture_move(cursor.0, cursor.1) {
@@ -78,21 +90,38 @@ impl State for MultiCaptureState {
     }
 
     fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let (last_move, captured_squares) = session.game.move_history.last_turn().unzip();
         ViewData {
             board: &session.game.board,
             current_player: session.game.current_player,
             cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
             selected_piece: Some(self.capturing_piece),
             possible_moves: &session.ui_state.possible_moves,
             pieces_with_captures: Vec::new(),
             status_message: "You must continue capturing!".to_string(),
             show_ai_thinking: false,
             error_message: None,
-            is_simple_ai: std::env::var("GEMINI_API_KEY").is_err()
-                || std::env::var("GEMINI_MODEL").is_err(),
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: session.ai_state.last_remark.as_deref(),
             hint: session.hint.as_ref(),
             is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: Some(&session.game.move_history),
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move,
+            captured_squares: captured_squares.unwrap_or_default(),
             welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: None,
         }
     }
 
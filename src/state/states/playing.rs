@@ -1,6 +1,6 @@
 use crate::core::piece::Color;
+use crate::interface::keymap::{Action, Input};
 use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
-use crossterm::event::{KeyCode, KeyEvent};
 
 pub struct PlayingState;
 
@@ -17,36 +17,49 @@ impl PlayingState {
 }
 
 impl State for PlayingState {
-    fn handle_input(&self, session: &GameSession, key: KeyEvent) -> (GameSession, StateTransition) {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
         // Check if it's AI's turn
-        if session.game.current_player == Color::Black {
+        if session.ai_seat() == Some(session.game.current_player) {
             return (
                 session.clone(),
                 StateTransition::To(Box::new(super::AITurnState::new())),
             );
         }
 
-        let (new_session, transition) = match key.code {
-            KeyCode::Up => {
-                let new_ui = session.ui_state.move_cursor_up();
+        let (new_session, transition) = match input {
+            Input::Action(Action::Up) => {
+                let new_ui = session.ui_state.move_cursor_up(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Down => {
-                let new_ui = session.ui_state.move_cursor_down(7);
+            Input::Action(Action::Down) => {
+                let new_ui = session
+                    .ui_state
+                    .move_cursor_down(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Left => {
-                let new_ui = session.ui_state.move_cursor_left();
+            Input::Action(Action::Left) => {
+                let new_ui = session
+                    .ui_state
+                    .move_cursor_left(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Right => {
-                let new_ui = session.ui_state.move_cursor_right(7);
+            Input::Action(Action::Right) => {
+                let new_ui = session
+                    .ui_state
+                    .move_cursor_right(session.game.board.size - 1);
                 (session.with_ui_state(new_ui), StateTransition::None)
             }
-            KeyCode::Char(' ') | KeyCode::Enter => {
+            Input::Action(Action::ScrollBack) => {
+                (session.scroll_move_list(true), StateTransition::None)
+            }
+            Input::Action(Action::ScrollForward) => {
+                (session.scroll_move_list(false), StateTransition::None)
+            }
+            Input::Action(Action::Select) => {
                 let cursor_pos = session.ui_state.cursor_pos;
                 if let Some(piece) = session.game.board.get_piece(cursor_pos.0, cursor_pos.1) {
                     if piece.color == session.game.current_player
+                        && session.is_local_turn()
                         && session
                             .game
                             .validate_piece_selection(cursor_pos.0, cursor_pos.1)
@@ -67,7 +80,19 @@ impl State for PlayingState {
                 }
                 (session.clone(), StateTransition::None)
             }
-            KeyCode::Esc | KeyCode::Char('q') => (session.clone(), StateTransition::Exit),
+            Input::Action(Action::Hint) => (session.request_hint(), StateTransition::None),
+            Input::Action(Action::Command) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::MoveEntryState::new())),
+            ),
+            Input::Action(Action::Coach) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::CoachState::new())),
+            ),
+            Input::Action(Action::Replay) => {
+                super::ReplayState::open(session, super::ReplayExit::Playing)
+            }
+            Input::Action(Action::Quit) => (session.clone(), StateTransition::Exit),
             _ => (session.clone(), StateTransition::None),
         };
 
@@ -75,6 +100,7 @@ impl State for PlayingState {
     }
 
     fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let (last_move, captured_squares) = session.game.move_history.last_turn().unzip();
         use crate::core::game_logic::get_pieces_with_captures;
 
         let pieces_with_captures = if session.game.has_captures_available() {
@@ -83,7 +109,16 @@ impl State for PlayingState {
             Vec::new()
         };
 
-        let status_message = if !pieces_with_captures.is_empty() {
+        let status_message = if !session.is_local_turn() {
+            format!(
+                "Waiting for {}'s move",
+                if session.game.current_player == Color::White {
+                    "White"
+                } else {
+                    "Black"
+                }
+            )
+        } else if !pieces_with_captures.is_empty() {
             format!(
                 "{} must capture!",
                 if session.game.current_player == Color::White {
@@ -107,17 +142,33 @@ impl State for PlayingState {
             board: &session.game.board,
             current_player: session.game.current_player,
             cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
             selected_piece: session.ui_state.selected_piece,
             possible_moves: &session.ui_state.possible_moves,
             pieces_with_captures,
             status_message,
             show_ai_thinking: false,
             error_message: None,
-            is_simple_ai: std::env::var("GEMINI_API_KEY").is_err()
-                || std::env::var("GEMINI_MODEL").is_err(),
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: session.ai_state.last_remark.as_deref(),
             hint: session.hint.as_ref(),
             is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: Some(&session.game.move_history),
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move,
+            captured_squares: captured_squares.unwrap_or_default(),
             welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: None,
         }
     }
 
@@ -0,0 +1,167 @@
+use crate::core::notation::turn_to_algebraic;
+use crate::core::piece::Color;
+use crate::core::replay::Replay;
+use crate::interface::keymap::{Action, Input};
+use crate::state::view_data::ReplayView;
+use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
+
+/// Where ESC takes the player back to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayExit {
+    Playing,
+    GameOver(Option<Color>),
+}
+
+/// Browses earlier positions of the game one ply at a time. The positions
+/// live in `GameSession::replay`; the game itself is never touched.
+pub struct ReplayState {
+    ply: usize,
+    exit: ReplayExit,
+}
+
+impl ReplayState {
+    /// Shows the latest position of `GameSession::replay`
+    pub fn new(exit: ReplayExit) -> Self {
+        Self {
+            ply: usize::MAX,
+            exit,
+        }
+    }
+
+    /// Rebuilds the replay from the game's history and starts the viewer on
+    /// the current position
+    pub fn open(session: &GameSession, exit: ReplayExit) -> (GameSession, StateTransition) {
+        let mut new_session = session.clone();
+        let history = &session.game.move_history;
+        let replay = match &session.start {
+            Some(start) => Replay::from_position(start, history),
+            None => Replay::from_history(history, session.game.board.size),
+        };
+        let ply = replay.last_ply();
+        new_session.replay = Some(replay);
+        (
+            new_session,
+            StateTransition::To(Box::new(Self { ply, exit })),
+        )
+    }
+
+    fn with_ply(&self, ply: usize) -> Self {
+        Self {
+            ply,
+            exit: self.exit,
+        }
+    }
+
+    fn exit_state(&self) -> StateTransition {
+        match self.exit {
+            ReplayExit::Playing => StateTransition::To(Box::new(super::PlayingState::new())),
+            ReplayExit::GameOver(winner) => {
+                StateTransition::To(Box::new(super::GameOverState::new(winner)))
+            }
+        }
+    }
+}
+
+impl State for ReplayState {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        let Some(replay) = &session.replay else {
+            return (session.clone(), self.exit_state());
+        };
+        let last = replay.last_ply();
+        let ply = self.ply.min(last);
+
+        let transition = match input {
+            Input::Action(Action::Left) if ply > 0 => {
+                StateTransition::To(Box::new(self.with_ply(ply - 1)))
+            }
+            Input::Action(Action::Right) if ply < last => {
+                StateTransition::To(Box::new(self.with_ply(ply + 1)))
+            }
+            Input::Action(Action::First) => StateTransition::To(Box::new(self.with_ply(0))),
+            Input::Action(Action::Last) => StateTransition::To(Box::new(self.with_ply(last))),
+            Input::Action(Action::Quit) => {
+                let mut new_session = session.clone();
+                new_session.replay = None;
+                return (new_session, self.exit_state());
+            }
+            _ => StateTransition::None,
+        };
+
+        (session.clone(), transition)
+    }
+
+    fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let (board, to_move, last_move, captured_squares, ply, last) = match &session.replay {
+            Some(replay) => {
+                let ply = self.ply.min(replay.last_ply());
+                (
+                    replay.board_at(ply),
+                    replay.player_at(ply),
+                    replay.turn_before(ply),
+                    replay.captures_before(ply).to_vec(),
+                    ply,
+                    replay.last_ply(),
+                )
+            }
+            None => (
+                &session.game.board,
+                session.game.current_player,
+                None,
+                Vec::new(),
+                0,
+                0,
+            ),
+        };
+
+        let status_message = match last_move {
+            Some((player, turn)) => format!(
+                "Move {ply}/{last}: {} {}",
+                if *player == Color::White {
+                    "White"
+                } else {
+                    "Black"
+                },
+                turn_to_algebraic(turn, board.size)
+            ),
+            None => format!("Move 0/{last}: starting position"),
+        };
+
+        ViewData {
+            board,
+            // Side to move in the shown position
+            current_player: to_move,
+            cursor_pos: last_move.map_or(session.ui_state.cursor_pos, |(_, turn)| turn.to()),
+            flipped: session.ui_state.flipped,
+            selected_piece: None,
+            possible_moves: &[],
+            pieces_with_captures: Vec::new(),
+            status_message,
+            show_ai_thinking: false,
+            error_message: None,
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
+            hint: None,
+            is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: None,
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move: last_move.map(|(_, turn)| turn.clone()),
+            captured_squares,
+            welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: Some(ReplayView { ply, last }),
+            move_entry: None,
+            settings: None,
+        }
+    }
+
+    fn state_type(&self) -> StateType {
+        StateType::Replay
+    }
+}
@@ -0,0 +1,253 @@
+use crate::config::{
+    config_path, set_active_settings, Difficulty, Opponent, Settings, Variant, MAX_TEMPERATURE,
+    SETTINGS,
+};
+use crate::core::clock::Clock;
+use crate::core::piece::Color;
+use crate::interface::keymap::{Action, Input};
+use crate::interface::{keymap, theme};
+use crate::state::ui_state::FlipMode;
+use crate::state::view_data::{SettingsRow, SettingsView};
+use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
+use std::path::PathBuf;
+
+/// Time controls offered on the settings screen
+pub const TIME_CHOICES: [&str; 8] = ["off", "1", "3+2", "5", "5+3", "10", "15+10", "30"];
+
+/// Models offered on the settings screen, besides the one configured
+const MODEL_CHOICES: [&str; 4] = [
+    "unset",
+    "gemini-2.0-flash-lite",
+    "gemini-2.0-flash",
+    "gemini-2.5-flash",
+];
+
+const ANIMATION_CHOICES: [&str; 4] = ["off", "slow", "normal", "fast"];
+
+/// Edits `config.txt` one setting at a time, opened from the welcome screen.
+/// Every change is written to the file at once and put into effect, so
+/// leaving the screen needs no confirmation.
+pub struct SettingsState {
+    /// The file's settings, without the environment's values
+    settings: Settings,
+    path: Option<PathBuf>,
+    selected: usize,
+    message: Option<String>,
+}
+
+impl SettingsState {
+    /// Edits `settings`, writing the changes to `path` when there is one
+    pub fn new(settings: Settings, path: Option<PathBuf>) -> Self {
+        Self {
+            settings,
+            path,
+            selected: 0,
+            message: None,
+        }
+    }
+
+    /// Edits the settings file in the config directory
+    pub fn open() -> Self {
+        let path = config_path();
+        let (settings, _) = Settings::load(path.as_deref());
+        Self::new(settings, path)
+    }
+
+    fn with_selected(&self, selected: usize) -> Self {
+        Self {
+            settings: self.settings.clone(),
+            path: self.path.clone(),
+            selected,
+            message: self.message.clone(),
+        }
+    }
+
+    /// Moves the selected setting to its next or previous value, saves it
+    /// and applies it to the session
+    fn change(&self, session: &GameSession, forward: bool) -> (GameSession, StateTransition) {
+        let name = SETTINGS[self.selected];
+        let mut settings = self.settings.clone();
+        if settings
+            .set(name, &next_value(&settings, name, forward))
+            .is_err()
+        {
+            return (session.clone(), StateTransition::None);
+        }
+        let changed = settings.changes(&self.settings);
+        if changed.is_empty() {
+            return (session.clone(), StateTransition::None);
+        }
+
+        let message = match &self.path {
+            Some(path) => match settings.save(path, &changed) {
+                Ok(()) => format!("Saved to {}", path.display()),
+                Err(e) => format!("Couldn't save to {}: {e}", path.display()),
+            },
+            None => "No config directory, so this only lasts until you quit".to_string(),
+        };
+        let active = settings.clone().with_env();
+        set_active_settings(active.clone());
+
+        let state = Self {
+            settings,
+            path: self.path.clone(),
+            selected: self.selected,
+            message: Some(message),
+        };
+        (
+            apply(session, &active, &changed),
+            StateTransition::To(Box::new(state)),
+        )
+    }
+}
+
+/// The value after or before the current one among those offered
+fn next_value(settings: &Settings, name: &str, forward: bool) -> String {
+    if matches!(
+        name,
+        "temperature" | "move_temperature" | "review_temperature"
+    ) {
+        let current = settings.value(name).parse::<f32>().unwrap_or_default();
+        let step = if forward { 0.1 } else { -0.1 };
+        let next = ((current + step) * 10.0).round() / 10.0;
+        return next.clamp(0.0, MAX_TEMPERATURE).to_string();
+    }
+
+    let mut choices: Vec<String> = match name {
+        "variant" => Variant::ALL.map(|v| v.name().to_string()).to_vec(),
+        "color" => vec!["white".to_string(), "black".to_string()],
+        "ai" => Opponent::ALL.map(|o| o.name().to_string()).to_vec(),
+        "difficulty" => Difficulty::ALL.map(|d| d.name().to_string()).to_vec(),
+        "time" => TIME_CHOICES.map(String::from).to_vec(),
+        "model" => MODEL_CHOICES.map(String::from).to_vec(),
+        "theme" => ["auto"]
+            .iter()
+            .chain(theme::PRESETS.iter())
+            .map(|name| name.to_string())
+            .collect(),
+        "keys" => ["default"]
+            .iter()
+            .chain(keymap::PRESETS.iter())
+            .map(|name| name.to_string())
+            .collect(),
+        "animation" => ANIMATION_CHOICES.map(String::from).to_vec(),
+        "autosave" => vec!["off".to_string(), "on".to_string()],
+        _ => Vec::new(),
+    };
+    // A value typed into the file stays among the choices
+    let current = settings.value(name);
+    let index = match choices.iter().position(|choice| *choice == current) {
+        Some(index) => index,
+        None => {
+            choices.insert(0, current);
+            0
+        }
+    };
+    let next = if forward {
+        (index + 1) % choices.len()
+    } else {
+        (index + choices.len() - 1) % choices.len()
+    };
+    choices.swap_remove(next)
+}
+
+/// The session with the `changed` game settings taken from `settings`.
+/// Network games keep their own clocks and board side.
+fn apply(session: &GameSession, settings: &Settings, changed: &[&str]) -> GameSession {
+    let mut new_session = session.clone();
+    let networked = session.remote.is_some() || session.watching.is_some();
+    for name in changed {
+        match *name {
+            "ai" => new_session.opponent = settings.play.opponent,
+            "difficulty" => new_session.difficulty = settings.play.difficulty,
+            "color" => {
+                new_session.human_color = settings.play.color;
+                if !networked && FlipMode::from_env() == FlipMode::Off {
+                    new_session.ui_state.flipped = settings.play.color == Color::Black;
+                }
+            }
+            "time" if !networked => new_session.clock = settings.play.time.map(Clock::new),
+            "model" => new_session = new_session.with_ai_helpers(),
+            _ => {}
+        }
+    }
+    new_session
+}
+
+impl State for SettingsState {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        match input {
+            Input::Action(Action::Up) => (
+                session.clone(),
+                StateTransition::To(Box::new(
+                    self.with_selected((self.selected + SETTINGS.len() - 1) % SETTINGS.len()),
+                )),
+            ),
+            Input::Action(Action::Down) => (
+                session.clone(),
+                StateTransition::To(Box::new(
+                    self.with_selected((self.selected + 1) % SETTINGS.len()),
+                )),
+            ),
+            Input::Action(Action::Left) => self.change(session, false),
+            Input::Action(Action::Right) => self.change(session, true),
+            Input::Action(Action::Quit | Action::Select | Action::Settings) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::WelcomeState::new())),
+            ),
+            _ => (session.clone(), StateTransition::None),
+        }
+    }
+
+    fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
+        let rows = SETTINGS
+            .into_iter()
+            .map(|name| SettingsRow {
+                name,
+                value: self.settings.value(name),
+                overridden_by: Settings::overridden_by(name),
+            })
+            .collect();
+
+        ViewData {
+            board: &session.game.board,
+            current_player: session.game.current_player,
+            cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
+            selected_piece: None,
+            possible_moves: &[],
+            pieces_with_captures: Vec::new(),
+            status_message: "Settings".to_string(),
+            show_ai_thinking: false,
+            error_message: None,
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
+            hint: None,
+            is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            move_history: None,
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move: None,
+            captured_squares: Vec::new(),
+            welcome_content: None,
+            persona: None,
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: Some(SettingsView {
+                rows,
+                selected: self.selected,
+                message: self.message.clone(),
+            }),
+        }
+    }
+
+    fn state_type(&self) -> StateType {
+        StateType::Settings
+    }
+}
@@ -1,5 +1,5 @@
+use crate::interface::keymap::{Action, Input};
 use crate::state::{GameSession, State, StateTransition, StateType, ViewData};
-use crossterm::event::{KeyCode, KeyEvent};
 
 pub struct WelcomeState;
 
@@ -23,17 +23,25 @@ impl WelcomeState {
 }
 
 impl State for WelcomeState {
-    fn handle_input(&self, session: &GameSession, key: KeyEvent) -> (GameSession, StateTransition) {
-        let transition = match key.code {
-            KeyCode::Enter => {
+    fn handle_input(&self, session: &GameSession, input: Input) -> (GameSession, StateTransition) {
+        match input {
+            Input::Action(Action::Select) => {
                 // Transition to PlayingState
-                StateTransition::To(Box::new(super::PlayingState::new()))
+                (
+                    session.clone(),
+                    StateTransition::To(Box::new(super::PlayingState::new())),
+                )
             }
-            KeyCode::Esc | KeyCode::Char('q') => StateTransition::Exit,
-            _ => StateTransition::None,
-        };
-
-        (session.clone(), transition)
+            // Pick the opponent persona before the game starts
+            Input::Action(Action::Left) => (session.cycle_persona(false), StateTransition::None),
+            Input::Action(Action::Right) => (session.cycle_persona(true), StateTransition::None),
+            Input::Action(Action::Settings) => (
+                session.clone(),
+                StateTransition::To(Box::new(super::SettingsState::open())),
+            ),
+            Input::Action(Action::Quit) => (session.clone(), StateTransition::Exit),
+            _ => (session.clone(), StateTransition::None),
+        }
     }
 
     fn get_view_data<'a>(&self, session: &'a GameSession) -> ViewData<'a> {
@@ -41,16 +49,27 @@ impl State for WelcomeState {
             board: &session.game.board,
             current_player: session.game.current_player,
             cursor_pos: session.ui_state.cursor_pos,
+            flipped: session.ui_state.flipped,
             selected_piece: None,
             possible_moves: &[],
             pieces_with_captures: Vec::new(),
             status_message: "Welcome to Checkers!".to_string(),
             show_ai_thinking: false,
             error_message: None,
-            is_simple_ai: std::env::var("GEMINI_API_KEY").is_err()
-                || std::env::var("GEMINI_MODEL").is_err(),
+            is_simple_ai: session.is_simple_ai(),
+            ai_seat: session.ai_seat(),
+            ai_remark: None,
             hint: None,
             is_game_over: false,
+            clock: session.clock.as_ref(),
+            remote: session.remote.as_ref(),
+            watching: session.watching.as_ref(),
+            usage_summary: None,
+            // A saved game picked up again, if any
+            move_history: Some(&session.game.move_history),
+            move_list_scroll: session.ui_state.move_list_scroll,
+            last_move: None,
+            captured_squares: Vec::new(),
             welcome_content: session.welcome_content.as_ref().map(|content| {
                 (
                     content.did_you_know.as_str(),
@@ -58,6 +77,12 @@ impl State for WelcomeState {
                     content.todays_challenge.as_str(),
                 )
             }),
+            persona: Some(session.persona()),
+            coach: None,
+            analysis: None,
+            replay: None,
+            move_entry: None,
+            settings: None,
         }
     }
 
@@ -1,11 +1,54 @@
 use crate::core::board::Board;
 use crate::core::game_logic::get_all_possible_moves;
+use crate::core::piece::Color;
+use std::env;
+
+/// Which way round the board is drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlipMode {
+    /// White at the bottom
+    #[default]
+    Off,
+    /// Black at the bottom
+    On,
+    /// The side about to move at the bottom, for two people sharing a
+    /// keyboard
+    Auto,
+}
+
+impl FlipMode {
+    /// Accepts `off`, `on` or `auto`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(Self::Off),
+            "on" => Some(Self::On),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    /// Reads `BOARD_FLIP`, keeping the default when missing or malformed
+    pub fn from_env() -> Self {
+        env::var("BOARD_FLIP")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+}
 
 #[derive(Clone)]
 pub struct UIState {
     pub selected_piece: Option<(usize, usize)>,
     pub possible_moves: Vec<(usize, usize)>,
     pub cursor_pos: (usize, usize),
+    /// Move list rows scrolled back from the latest move
+    pub move_list_scroll: usize,
+    /// Move list rows the screen last had room for
+    pub move_list_rows: usize,
+    /// Whether Black is drawn at the bottom
+    pub flipped: bool,
+    /// Whether the board turns to the side to move on every human turn
+    pub auto_flip: bool,
 }
 
 impl UIState {
@@ -14,41 +57,86 @@ impl UIState {
             selected_piece: None,
             possible_moves: Vec::new(),
             cursor_pos: (0, 0),
+            move_list_scroll: 0,
+            move_list_rows: 0,
+            flipped: false,
+            auto_flip: false,
         }
     }
 
-    pub fn move_cursor_up(&self) -> Self {
+    // The cursor moves the way the arrows point on screen, so on a flipped
+    // board "up" walks toward the higher rows
+
+    pub fn move_cursor_up(&self, max_row: usize) -> Self {
+        self.step_cursor(-1, 0, max_row)
+    }
+
+    pub fn move_cursor_down(&self, max_row: usize) -> Self {
+        self.step_cursor(1, 0, max_row)
+    }
+
+    pub fn move_cursor_left(&self, max_col: usize) -> Self {
+        self.step_cursor(0, -1, max_col)
+    }
+
+    pub fn move_cursor_right(&self, max_col: usize) -> Self {
+        self.step_cursor(0, 1, max_col)
+    }
+
+    fn step_cursor(&self, rows: isize, cols: isize, max: usize) -> Self {
+        let (rows, cols) = if self.flipped {
+            (-rows, -cols)
+        } else {
+            (rows, cols)
+        };
+        let step = |value: usize, delta: isize| value.saturating_add_signed(delta).min(max);
         let mut new_state = self.clone();
-        if new_state.cursor_pos.0 > 0 {
-            new_state.cursor_pos.0 -= 1;
-        }
+        new_state.cursor_pos = (step(self.cursor_pos.0, rows), step(self.cursor_pos.1, cols));
         new_state
     }
 
-    pub fn move_cursor_down(&self, max_row: usize) -> Self {
+    /// Turns the board around, or back
+    pub fn toggle_flip(&self) -> Self {
         let mut new_state = self.clone();
-        if new_state.cursor_pos.0 < max_row {
-            new_state.cursor_pos.0 += 1;
-        }
+        new_state.flipped = !self.flipped;
         new_state
     }
 
-    pub fn move_cursor_left(&self) -> Self {
+    pub fn with_flip_mode(&self, mode: FlipMode) -> Self {
         let mut new_state = self.clone();
-        if new_state.cursor_pos.1 > 0 {
-            new_state.cursor_pos.1 -= 1;
-        }
+        new_state.flipped = mode == FlipMode::On;
+        new_state.auto_flip = mode == FlipMode::Auto;
         new_state
     }
 
-    pub fn move_cursor_right(&self, max_col: usize) -> Self {
+    /// With auto-flip on, turns the board so `player` sits at the bottom
+    pub fn face(&self, player: Color) -> Self {
         let mut new_state = self.clone();
-        if new_state.cursor_pos.1 < max_col {
-            new_state.cursor_pos.1 += 1;
+        if self.auto_flip {
+            new_state.flipped = player == Color::Black;
         }
         new_state
     }
 
+    /// Puts the cursor on a square picked with the mouse
+    pub fn move_cursor_to(&self, pos: (usize, usize)) -> Self {
+        let mut new_state = self.clone();
+        new_state.cursor_pos = pos;
+        new_state
+    }
+
+    /// Scrolls the move list back (`back`) or toward the latest move by
+    /// `page` rows, never past the first of its `rows` at the top
+    pub fn scroll_move_list(&self, back: bool, page: usize, rows: usize) -> Self {
+        let mut new_state = self.clone();
+        new_state.move_list_scroll = if back {
+            (self.move_list_scroll + page).min(rows.saturating_sub(self.move_list_rows))
+        } else {
+            self.move_list_scroll.saturating_sub(page)
+        };
+        new_state
+    }
+
     pub fn clear_selection(&self) -> Self {
         let mut new_state = self.clone();
         new_state.selected_piece = None;
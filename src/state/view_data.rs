@@ -1,11 +1,18 @@
-use crate::ai::Hint;
+use crate::ai::{analysis::GameAnalysis, coach::CoachConversation, persona::Persona, Hint};
 use crate::core::board::Board;
+use crate::core::clock::Clock;
+use crate::core::move_history::MoveHistory;
 use crate::core::piece::Color;
+use crate::core::Turn;
+use crate::net::broadcast::Watching;
+use crate::net::RemotePlay;
 
 pub struct ViewData<'a> {
     pub board: &'a Board,
     pub current_player: Color,
     pub cursor_pos: (usize, usize),
+    /// Whether Black is drawn at the bottom
+    pub flipped: bool,
     pub selected_piece: Option<(usize, usize)>,
     pub possible_moves: &'a [(usize, usize)],
     pub pieces_with_captures: Vec<(usize, usize)>,
@@ -14,10 +21,83 @@ pub struct ViewData<'a> {
     pub show_ai_thinking: bool,
     pub error_message: Option<&'a str>,
     pub is_simple_ai: bool,
+    /// The side the computer plays, if any
+    pub ai_seat: Option<Color>,
+    /// What the opponent persona said with its last move
+    pub ai_remark: Option<&'a str>,
 
     pub hint: Option<&'a Hint>,
     pub is_game_over: bool,
+    /// Both sides' clocks, shown next to the status in timed games
+    pub clock: Option<&'a Clock>,
+    /// Connection state and chat, for network games
+    pub remote: Option<&'a RemotePlay>,
+    /// Connection state, for watchers of a broadcast game
+    pub watching: Option<&'a Watching>,
+    pub usage_summary: Option<String>,
+
+    // Move list side panel data (optional)
+    pub move_history: Option<&'a MoveHistory>,
+    pub move_list_scroll: usize,
+
+    /// Path of the move that led to the shown position, drawn as arrows,
+    /// and the squares it captured on
+    pub last_move: Option<Turn>,
+    pub captured_squares: Vec<(usize, usize)>,
 
     // Welcome screen data (optional)
     pub welcome_content: Option<(&'a str, &'a str, &'a str)>,
+    pub persona: Option<&'a Persona>,
+
+    // Coach panel data (optional)
+    pub coach: Option<CoachView<'a>>,
+
+    // Post-game review data (optional)
+    pub analysis: Option<AnalysisView<'a>>,
+
+    // Replay viewer data (optional)
+    pub replay: Option<ReplayView>,
+
+    // Typed move command line (optional)
+    pub move_entry: Option<MoveEntryView>,
+
+    // Settings screen data (optional)
+    pub settings: Option<SettingsView>,
+}
+
+pub struct CoachView<'a> {
+    pub conversation: &'a CoachConversation,
+    pub input: String,
+}
+
+pub struct MoveEntryView {
+    pub input: String,
+    /// Legal moves starting with the input
+    pub completions: Vec<String>,
+    /// Whether the last attempt was rejected, shown in the status line
+    pub failed: bool,
+}
+
+pub struct AnalysisView<'a> {
+    pub analysis: &'a GameAnalysis,
+    pub selected: usize,
+}
+
+pub struct ReplayView {
+    pub ply: usize,
+    pub last: usize,
+}
+
+pub struct SettingsView {
+    pub rows: Vec<SettingsRow>,
+    pub selected: usize,
+    /// Where the changes went, or why they couldn't be saved
+    pub message: Option<String>,
+}
+
+pub struct SettingsRow {
+    pub name: &'static str,
+    pub value: String,
+    /// The environment variable used in place of the value, if set
+    pub overridden_by: Option<&'static str>,
 }
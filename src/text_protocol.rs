@@ -0,0 +1,184 @@
+//! A line-based protocol on stdin and stdout for driving the rules and the
+//! engine from other programs, in the manner of UCI. Moves are written in
+//! numeric notation ("22-18", "22x15x24") and read in any notation the move
+//! command line takes.
+//!
+//! ```text
+//! position startpos moves 22-18 11-15
+//! position fen W:W21-32:B1-12
+//! go depth 6 | go movetime 500
+//! legalmoves
+//! perft 4
+//! eval
+//! fen
+//! quit
+//! ```
+
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use crate::ai::engine::{evaluate, perft_divide, search, search_within};
+use crate::ai::formatting::{parse_move, resolve_turn};
+use crate::core::fen::{parse_fen, to_fen};
+use crate::core::game::CheckersGame;
+use crate::core::notation::turn_to_numeric;
+
+/// Depth searched by a bare `go`
+pub const DEFAULT_GO_DEPTH: u32 = 6;
+
+/// The position the commands work on
+#[derive(Default)]
+pub struct TextProtocol {
+    game: CheckersGame,
+}
+
+impl TextProtocol {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn game(&self) -> &CheckersGame {
+        &self.game
+    }
+
+    /// Runs one command and returns the lines it answers with, or `None`
+    /// once told to quit. Mistakes are answered with a line starting
+    /// `error` and leave the position as it was.
+    pub fn execute(&mut self, line: &str) -> Option<Vec<String>> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Some(Vec::new());
+        };
+        let args: Vec<&str> = words.collect();
+
+        let reply = match command {
+            "position" => self.position(&args),
+            "go" => self.go(&args),
+            "legalmoves" => Ok(vec![self.legal_moves().join(" ")]),
+            "perft" => self.perft(&args),
+            "eval" => Ok(vec![format!(
+                "eval {}",
+                evaluate(&self.game.board, self.game.current_player)
+            )]),
+            "fen" => Ok(vec![to_fen(&self.game)]),
+            "quit" => return None,
+            other => Err(format!("unknown command \"{other}\"")),
+        };
+        Some(reply.unwrap_or_else(|error| vec![format!("error {error}")]))
+    }
+
+    /// `position startpos|fen FEN [moves MOVE...]`
+    fn position(&mut self, args: &[&str]) -> Result<Vec<String>, String> {
+        let split = args
+            .iter()
+            .position(|&arg| arg == "moves")
+            .unwrap_or(args.len());
+        let (setup, moves) = args.split_at(split);
+
+        let mut game = match setup {
+            ["startpos"] => CheckersGame::new(),
+            ["fen", fen @ ..] if !fen.is_empty() => {
+                parse_fen(&fen.concat()).map_err(|e| e.to_string())?
+            }
+            _ => return Err("position takes startpos or fen FEN, then moves".to_string()),
+        };
+        for text in moves.iter().skip(1) {
+            let path = parse_move(text, game.board.size)
+                .ok_or_else(|| format!("can't read move \"{text}\""))?;
+            let turn = resolve_turn(&game, path);
+            game = game
+                .apply_turn(&turn)
+                .map_err(|e| format!("illegal move {text}: {e}"))?;
+        }
+
+        self.game = game;
+        Ok(Vec::new())
+    }
+
+    /// `go [depth N | movetime MS]`
+    fn go(&self, args: &[&str]) -> Result<Vec<String>, String> {
+        let (result, info) = match args {
+            [] => (
+                search(&self.game, DEFAULT_GO_DEPTH),
+                format!("info depth {DEFAULT_GO_DEPTH}"),
+            ),
+            ["depth", depth] => {
+                let depth = depth
+                    .parse()
+                    .ok()
+                    .filter(|&depth| depth > 0)
+                    .ok_or("go depth takes a positive number")?;
+                (search(&self.game, depth), format!("info depth {depth}"))
+            }
+            ["movetime", millis] => {
+                let millis = millis
+                    .parse()
+                    .map_err(|_| "go movetime takes milliseconds")?;
+                let budget = Duration::from_millis(millis);
+                (search_within(&self.game, budget), "info".to_string())
+            }
+            _ => return Err("go takes depth N or movetime MS".to_string()),
+        };
+
+        let size = self.game.board.size;
+        Ok(match result {
+            Some(best) => vec![
+                format!("{info} score {}", best.score),
+                format!("bestmove {}", turn_to_numeric(&best.turn, size)),
+            ],
+            None => vec!["bestmove none".to_string()],
+        })
+    }
+
+    /// `perft N`: the turns under each legal move, then the total
+    fn perft(&self, args: &[&str]) -> Result<Vec<String>, String> {
+        let depth: u32 = match args {
+            [depth] => depth.parse().map_err(|_| "perft takes a depth")?,
+            _ => return Err("perft takes a depth".to_string()),
+        };
+        if depth == 0 {
+            return Ok(vec!["nodes 1".to_string()]);
+        }
+
+        Ok(perft_lines(&self.game, depth))
+    }
+
+    fn legal_moves(&self) -> Vec<String> {
+        let size = self.game.board.size;
+        self.game
+            .legal_turns()
+            .iter()
+            .map(|turn| turn_to_numeric(turn, size))
+            .collect()
+    }
+}
+
+/// The turns under each legal move in numeric notation, then the total as
+/// `nodes N`
+pub fn perft_lines(game: &CheckersGame, depth: u32) -> Vec<String> {
+    let size = game.board.size;
+    let divided = perft_divide(game, depth);
+    let total: u64 = divided.iter().map(|(_, nodes)| nodes).sum();
+    let mut lines = divided
+        .iter()
+        .map(|(turn, nodes)| format!("{} {nodes}", turn_to_numeric(turn, size)))
+        .collect::<Vec<_>>();
+    lines.push(format!("nodes {total}"));
+    lines
+}
+
+/// Answers commands from `input` on `output` until `quit` or the end of the
+/// input
+pub fn run<R: BufRead, W: Write>(input: R, mut output: W) -> io::Result<()> {
+    let mut protocol = TextProtocol::new();
+    for line in input.lines() {
+        let Some(reply) = protocol.execute(&line?) else {
+            break;
+        };
+        for line in reply {
+            writeln!(output, "{line}")?;
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
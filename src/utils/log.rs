@@ -0,0 +1,407 @@
+//! Structured log kept in a file, one JSON object per line. The board takes
+//! over the terminal, so whatever is worth reading after a game goes here
+//! rather than to stderr.
+
+use crate::config::CONFIG_FILE;
+use crate::interface::keymap::KEYS_FILE;
+use crate::interface::theme::THEME_FILE;
+use crate::utils::paths;
+use serde_json::{json, Map, Value};
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const LOG_FILE: &str = "checkers.log";
+
+/// A log grown past this is moved aside to `checkers.log.1` on start
+const MAX_LOG_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Lines of the log copied into a debug bundle
+const BUNDLE_LOG_LINES: usize = 500;
+
+/// Settings worth knowing about in a bug report; anything named like a
+/// secret is only reported as set
+const BUNDLE_ENV: &[&str] = &[
+    "GEMINI_API_KEY",
+    "GEMINI_MODEL",
+    "LLM_MAX_REQUESTS",
+    "LLM_MAX_TOKENS",
+    "ANIMATION_SPEED",
+    "BOARD_FLIP",
+    "TIME_CONTROL",
+    "CHECKERS_LOG",
+    "TERM",
+    "COLORTERM",
+    "XDG_CONFIG_HOME",
+    "XDG_CACHE_HOME",
+    "XDG_STATE_HOME",
+];
+
+/// How much goes into the log, from only errors to everything
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    /// Reads a level name; `off` turns the log off altogether
+    pub fn parse(text: &str) -> Result<Option<Self>, String> {
+        let text = text.trim().to_lowercase();
+        if text == "off" {
+            return Ok(None);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|level| level.name() == text)
+            .map(Some)
+            .ok_or_else(|| {
+                format!(
+                    "unknown log level {text:?}, expected off, error, warn, info, debug or trace"
+                )
+            })
+    }
+
+    /// The level for `-v` given `count` times: info, debug, then trace
+    pub fn verbose(count: usize) -> Self {
+        Self::ALL[(Level::Warn as usize + count).min(Self::ALL.len()) - 1]
+    }
+}
+
+/// Where the log goes and how much of it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogConfig {
+    /// `None` keeps no log at all
+    pub level: Option<Level>,
+    /// `None` uses `checkers.log` in the state directory
+    pub path: Option<PathBuf>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: Some(Level::Warn),
+            path: None,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Reads the level from `CHECKERS_LOG`, keeping the default when it is
+    /// missing or malformed
+    pub fn from_env() -> Self {
+        let level = env::var("CHECKERS_LOG")
+            .ok()
+            .and_then(|text| Level::parse(&text).ok())
+            .unwrap_or(Self::default().level);
+        Self { level, path: None }
+    }
+
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.path
+            .clone()
+            .or_else(|| paths::state_dir().map(|dir| dir.join(LOG_FILE)))
+    }
+}
+
+/// Takes `-v` (repeatable, or `-vv`), `--log-level LEVEL` and
+/// `--log-file PATH` out of `args`, starting from `CHECKERS_LOG`
+pub fn take_log_flags(
+    args: impl IntoIterator<Item = String>,
+) -> Result<(LogConfig, Vec<String>), String> {
+    let mut config = LogConfig::from_env();
+    let mut verbosity = 0;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verbose" => verbosity += 1,
+            "--log-level" => {
+                let level = args.next().ok_or("--log-level needs a level")?;
+                config.level = Level::parse(&level)?;
+            }
+            "--log-file" => {
+                let path = args.next().ok_or("--log-file needs a path")?;
+                config.path = Some(PathBuf::from(path));
+            }
+            flag if flag.len() > 1
+                && flag.starts_with('-')
+                && flag[1..].bytes().all(|b| b == b'v') =>
+            {
+                verbosity += flag.len() - 1;
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    if verbosity > 0 {
+        config.level = Some(Level::verbose(verbosity));
+    }
+    Ok((config, rest))
+}
+
+struct Logger {
+    path: PathBuf,
+    /// Opened on the first entry, so a quiet session leaves no file behind
+    file: Option<File>,
+}
+
+/// The enabled level as its number, 0 while nothing is logged
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+fn logger() -> &'static Mutex<Option<Logger>> {
+    static LOGGER: OnceLock<Mutex<Option<Logger>>> = OnceLock::new();
+    LOGGER.get_or_init(|| Mutex::new(None))
+}
+
+/// Starts logging as `config` says, replacing any earlier log, and returns
+/// the file written to. Panics are logged from then on too.
+pub fn init(config: &LogConfig) -> Option<PathBuf> {
+    let mut logger = logger().lock().unwrap_or_else(|e| e.into_inner());
+    let (Some(level), Some(path)) = (config.level, config.log_path()) else {
+        *logger = None;
+        LEVEL.store(0, Ordering::Relaxed);
+        return None;
+    };
+
+    if fs::metadata(&path).is_ok_and(|meta| meta.len() > MAX_LOG_BYTES) {
+        let mut old = path.clone().into_os_string();
+        old.push(".1");
+        let _ = fs::rename(&path, old);
+    }
+    *logger = Some(Logger {
+        path: path.clone(),
+        file: None,
+    });
+    LEVEL.store(level as u8, Ordering::Relaxed);
+
+    static HOOKED: OnceLock<()> = OnceLock::new();
+    HOOKED.get_or_init(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(|at| at.to_string());
+            error(
+                "panic",
+                json!({ "message": info.to_string(), "location": location }),
+            );
+            previous(info);
+        }));
+    });
+    Some(path)
+}
+
+/// The file being logged to, if any
+pub fn log_path() -> Option<PathBuf> {
+    let logger = logger().lock().unwrap_or_else(|e| e.into_inner());
+    logger.as_ref().map(|logger| logger.path.clone())
+}
+
+/// Whether entries at `level` are written; check before building costly
+/// fields such as whole prompts
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Writes one entry. `fields` should be an object; anything else is kept
+/// under `value`. Failing to write is not worth interrupting a game for.
+pub fn log(level: Level, event: &str, fields: Value) {
+    if !enabled(level) {
+        return;
+    }
+    let mut logger = logger().lock().unwrap_or_else(|e| e.into_inner());
+    let Some(logger) = logger.as_mut() else {
+        return;
+    };
+    if logger.file.is_none() {
+        logger.file = open(&logger.path).ok();
+    }
+    if let Some(file) = logger.file.as_mut() {
+        let _ = writeln!(file, "{}", entry(level, event, fields, SystemTime::now()));
+    }
+}
+
+pub fn error(event: &str, fields: Value) {
+    log(Level::Error, event, fields);
+}
+
+pub fn warn(event: &str, fields: Value) {
+    log(Level::Warn, event, fields);
+}
+
+pub fn info(event: &str, fields: Value) {
+    log(Level::Info, event, fields);
+}
+
+pub fn debug(event: &str, fields: Value) {
+    log(Level::Debug, event, fields);
+}
+
+pub fn trace(event: &str, fields: Value) {
+    log(Level::Trace, event, fields);
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// One line of the log: time, level and event first, then the fields
+pub fn entry(level: Level, event: &str, fields: Value, time: SystemTime) -> String {
+    let fields = match fields {
+        Value::Object(fields) => fields,
+        Value::Null => Map::new(),
+        value => Map::from_iter([("value".to_string(), value)]),
+    };
+    let mut line = format!(
+        "{{\"time\":\"{}\",\"level\":\"{}\",\"event\":{}",
+        timestamp(time),
+        level.name(),
+        Value::from(event)
+    );
+    for (key, value) in fields {
+        line.push_str(&format!(",{}:{value}", Value::from(key)));
+    }
+    line.push('}');
+    line
+}
+
+/// UTC time as `2024-05-01T12:30:00.250Z`
+pub fn timestamp(time: SystemTime) -> String {
+    let since = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since.as_secs();
+    let (days, of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // Days to a civil date, after Howard Hinnant's algorithm
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        of_day / 3_600,
+        of_day % 3_600 / 60,
+        of_day % 60,
+        since.subsec_millis()
+    )
+}
+
+/// A plain text report to attach to a bug: version, platform, the settings
+/// in effect, any custom files and the end of the log at `log`. Environment
+/// variables are read through `env_var`. The API key is never included, not
+/// even where it turns up in a logged error.
+pub fn debug_bundle(log: Option<&Path>, env_var: impl Fn(&str) -> Option<String>) -> String {
+    let secret = env_var("GEMINI_API_KEY").filter(|key| !key.is_empty());
+    let redact = |text: &str| match &secret {
+        Some(key) => text.replace(key.as_str(), "[redacted]"),
+        None => text.to_string(),
+    };
+
+    let mut report = format!(
+        "checkers-rs {} debug bundle\ncreated: {}\nplatform: {} {}\n",
+        env!("CARGO_PKG_VERSION"),
+        timestamp(SystemTime::now()),
+        env::consts::OS,
+        env::consts::ARCH
+    );
+
+    report.push_str("\n[environment]\n");
+    for name in BUNDLE_ENV {
+        let value = match env_var(name) {
+            Some(_) if name.contains("KEY") => "(set)".to_string(),
+            Some(value) => redact(&value),
+            None => "(unset)".to_string(),
+        };
+        report.push_str(&format!("{name}={value}\n"));
+    }
+
+    report.push_str("\n[settings]\n");
+    match paths::config_dir() {
+        Some(dir) => {
+            for file in [CONFIG_FILE, KEYS_FILE, THEME_FILE] {
+                match fs::read_to_string(dir.join(file)) {
+                    Ok(text) => {
+                        report.push_str(&format!("--- {file}\n{}\n", redact(text.trim_end())))
+                    }
+                    Err(_) => report.push_str(&format!("--- {file} (none)\n")),
+                }
+            }
+            for folder in ["prompts", "personas"] {
+                let mut names = fs::read_dir(dir.join(folder))
+                    .map(|entries| {
+                        entries
+                            .filter_map(|entry| entry.ok())
+                            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                names.sort();
+                if names.is_empty() {
+                    names.push("(none)".to_string());
+                }
+                report.push_str(&format!("--- {folder}/: {}\n", names.join(", ")));
+            }
+        }
+        None => report.push_str("no config directory\n"),
+    }
+
+    match log {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => {
+                let lines = text.lines().collect::<Vec<_>>();
+                let tail = &lines[lines.len().saturating_sub(BUNDLE_LOG_LINES)..];
+                report.push_str(&format!(
+                    "\n[log: last {} of {} lines of {}]\n",
+                    tail.len(),
+                    lines.len(),
+                    path.display()
+                ));
+                for line in tail {
+                    report.push_str(&redact(line));
+                    report.push('\n');
+                }
+            }
+            Err(e) => report.push_str(&format!("\n[log: {} unreadable: {e}]\n", path.display())),
+        },
+        None => report.push_str("\n[log: none kept]\n"),
+    }
+    report
+}
@@ -1 +1,3 @@
+pub mod log;
+pub mod paths;
 pub mod prompts;
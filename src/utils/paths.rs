@@ -0,0 +1,35 @@
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "checkers-rs";
+
+/// Directory for user settings, `$XDG_CONFIG_HOME/checkers-rs` or
+/// `~/.config/checkers-rs`
+pub fn config_dir() -> Option<PathBuf> {
+    base_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join(APP_DIR))
+}
+
+/// Directory for disposable data, `$XDG_CACHE_HOME/checkers-rs` or
+/// `~/.cache/checkers-rs`
+pub fn cache_dir() -> Option<PathBuf> {
+    base_dir("XDG_CACHE_HOME", ".cache").map(|dir| dir.join(APP_DIR))
+}
+
+/// Directory for what the game leaves behind to look at later, such as its
+/// log: `$XDG_STATE_HOME/checkers-rs` or `~/.local/state/checkers-rs`
+pub fn state_dir() -> Option<PathBuf> {
+    base_dir("XDG_STATE_HOME", ".local/state").map(|dir| dir.join(APP_DIR))
+}
+
+fn base_dir(xdg_var: &str, home_fallback: &str) -> Option<PathBuf> {
+    // The spec says relative paths are invalid and must be ignored
+    if let Some(dir) = env::var_os(xdg_var).map(PathBuf::from) {
+        if dir.is_absolute() {
+            return Some(dir);
+        }
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(home_fallback))
+}
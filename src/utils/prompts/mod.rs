@@ -1,9 +1,192 @@
-// This module provides access to text prompts used in the game
+// This module provides access to text prompts used in the game. Every prompt
+// ships built in and can be overridden by a file of the same name in the
+// user's `prompts` config directory (e.g. ~/.config/checkers-rs/prompts/hint.txt).
+
+use crate::utils::paths;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// A prompt template and the placeholders it may use
+pub struct PromptSpec {
+    pub file_name: &'static str,
+    pub builtin: &'static str,
+    pub required: &'static [&'static str],
+    pub optional: &'static [&'static str],
+}
+
+pub const HINT: PromptSpec = PromptSpec {
+    file_name: "hint.txt",
+    builtin: include_str!("hint.txt"),
+    required: &["{board_state}", "{available_moves}", "{move_history}"],
+    optional: &["{player_color}"],
+};
+
+pub const AI_MOVE: PromptSpec = PromptSpec {
+    file_name: "ai_move.txt",
+    builtin: include_str!("ai_move.txt"),
+    required: &["{board_state}", "{available_moves}"],
+    optional: &["{move_history}", "{persona}", "{player_color}"],
+};
+
+pub const STORY: PromptSpec = PromptSpec {
+    file_name: "story.txt",
+    builtin: include_str!("story.txt"),
+    required: &[],
+    optional: &[],
+};
+
+pub const COACH: PromptSpec = PromptSpec {
+    file_name: "coach.txt",
+    builtin: include_str!("coach.txt"),
+    required: &[],
+    optional: &[],
+};
+
+pub const REVIEW: PromptSpec = PromptSpec {
+    file_name: "review.txt",
+    builtin: include_str!("review.txt"),
+    required: &["{moves}"],
+    optional: &["{result}"],
+};
+
+const ALL_PROMPTS: [&PromptSpec; 5] = [&HINT, &AI_MOVE, &STORY, &COACH, &REVIEW];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PromptError {
+    #[error("{file}: missing placeholder {placeholder}")]
+    MissingPlaceholder { file: String, placeholder: String },
+    #[error("{file}: unknown placeholder {placeholder}")]
+    UnknownPlaceholder { file: String, placeholder: String },
+    #[error("{file}: {reason}")]
+    Unreadable { file: String, reason: String },
+}
+
+/// Checks that a template uses every required placeholder and nothing the
+/// game would leave unreplaced.
+pub fn validate_template(spec: &PromptSpec, text: &str) -> Result<(), PromptError> {
+    for placeholder in spec.required {
+        if !text.contains(placeholder) {
+            return Err(PromptError::MissingPlaceholder {
+                file: spec.file_name.to_string(),
+                placeholder: placeholder.to_string(),
+            });
+        }
+    }
+
+    for placeholder in placeholders(text) {
+        if !spec.required.contains(&placeholder) && !spec.optional.contains(&placeholder) {
+            return Err(PromptError::UnknownPlaceholder {
+                file: spec.file_name.to_string(),
+                placeholder: placeholder.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds `{snake_case}` tokens; other braces are left alone
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        let candidate = &rest[start..];
+        match candidate[1..].find(|c: char| !(c.is_ascii_lowercase() || c == '_')) {
+            Some(end) if end > 0 && candidate[1 + end..].starts_with('}') => {
+                found.push(&candidate[..end + 2]);
+                rest = &candidate[end + 2..];
+            }
+            _ => rest = &candidate[1..],
+        }
+    }
+
+    found
+}
+
+/// Prompt texts in use for this run, with the reasons any override was
+/// rejected
+pub struct PromptSet {
+    texts: HashMap<&'static str, String>,
+    warnings: Vec<PromptError>,
+}
+
+impl PromptSet {
+    /// Reads overrides from `dir`. Missing files use the built-in prompt and
+    /// so do invalid ones, which are reported in `warnings`.
+    pub fn load(dir: Option<&Path>) -> Self {
+        let mut texts = HashMap::new();
+        let mut warnings = Vec::new();
+
+        for spec in ALL_PROMPTS {
+            let Some(path) = dir.map(|dir| dir.join(spec.file_name)) else {
+                continue;
+            };
+            if !path.exists() {
+                continue;
+            }
+
+            let loaded = std::fs::read_to_string(&path)
+                .map_err(|e| PromptError::Unreadable {
+                    file: spec.file_name.to_string(),
+                    reason: e.to_string(),
+                })
+                .and_then(|text| validate_template(spec, &text).map(|_| text));
+
+            match loaded {
+                Ok(text) => {
+                    texts.insert(spec.file_name, text);
+                }
+                Err(e) => warnings.push(e),
+            }
+        }
+
+        Self { texts, warnings }
+    }
+
+    pub fn get(&self, spec: &PromptSpec) -> &str {
+        self.texts
+            .get(spec.file_name)
+            .map(String::as_str)
+            .unwrap_or(spec.builtin)
+    }
+
+    pub fn warnings(&self) -> &[PromptError] {
+        &self.warnings
+    }
+}
+
+fn prompts() -> &'static PromptSet {
+    static PROMPTS: OnceLock<PromptSet> = OnceLock::new();
+    PROMPTS.get_or_init(|| {
+        let dir = paths::config_dir().map(|dir| dir.join("prompts"));
+        PromptSet::load(dir.as_deref())
+    })
+}
+
+/// Reasons user prompt overrides were ignored
+pub fn prompt_warnings() -> &'static [PromptError] {
+    prompts().warnings()
+}
 
 pub fn get_hint_prompt() -> &'static str {
-    include_str!("hint.txt")
+    prompts().get(&HINT)
 }
 
 pub fn get_ai_move_prompt() -> &'static str {
-    include_str!("ai_move.txt")
+    prompts().get(&AI_MOVE)
+}
+
+pub fn get_story_prompt() -> &'static str {
+    prompts().get(&STORY)
+}
+
+pub fn get_coach_prompt() -> &'static str {
+    prompts().get(&COACH)
+}
+
+pub fn get_review_prompt() -> &'static str {
+    prompts().get(&REVIEW)
 }
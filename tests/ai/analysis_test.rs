@@ -0,0 +1,134 @@
+use checkers_rs::ai::analysis::{Annotation, GameAnalysis, ScoreSource};
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::core::Turn;
+
+fn empty_game() -> CheckersGame {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game
+}
+
+fn play(game: &CheckersGame, path: &[(usize, usize)]) -> CheckersGame {
+    game.apply_turn(&Turn::new(path.to_vec())).unwrap()
+}
+
+#[test]
+fn test_analysis_replays_full_game_from_history() {
+    let start = CheckersGame::new();
+    let game = play(&start, &[(5, 2), (4, 3)]);
+    let game = play(&game, &[(2, 1), (3, 2)]);
+
+    let analysis = GameAnalysis::from_history(&game.move_history, None);
+
+    assert_eq!(analysis.source, ScoreSource::Engine);
+    assert_eq!(analysis.moves.len(), 2);
+    assert_eq!(analysis.moves[0].player, Color::White);
+    assert_eq!(analysis.moves[0].notation, "c3-d4");
+    assert_eq!(analysis.moves[1].notation, "b6-c5");
+    assert_eq!(analysis.moves[1].board_after.cells, game.board.cells);
+}
+
+#[test]
+fn test_multi_jump_is_one_annotated_move() {
+    let mut start = empty_game();
+    start.board.cells[6][1] = Some(Piece::new(Color::White));
+    start.board.cells[5][2] = Some(Piece::new(Color::Black));
+    start.board.cells[3][4] = Some(Piece::new(Color::Black));
+    let game = play(&start, &[(6, 1), (4, 3), (2, 5)]);
+
+    let analysis = GameAnalysis::from_position(&start, &game.move_history, Some(Color::White));
+
+    assert_eq!(analysis.moves.len(), 1);
+    assert_eq!(analysis.moves[0].notation, "b2xd4xf6");
+    assert_eq!(analysis.moves[0].annotation, Annotation::Good);
+}
+
+#[test]
+fn test_flags_missed_capture() {
+    let mut start = empty_game();
+    start.board.cells[7][0] = Some(Piece::new(Color::White));
+    start.board.cells[6][1] = Some(Piece::new(Color::White));
+    start.board.cells[6][7] = Some(Piece::new(Color::White));
+    start.board.cells[5][2] = Some(Piece::new(Color::Black));
+    start.board.cells[3][2] = Some(Piece::new(Color::Black));
+    start.board.cells[5][6] = Some(Piece::new(Color::Black));
+    let game = play(&start, &[(6, 7), (4, 5)]);
+
+    let analysis = GameAnalysis::from_position(&start, &game.move_history, None);
+
+    let annotated = &analysis.moves[0];
+    assert_eq!(annotated.annotation, Annotation::MissedCapture);
+    assert!(annotated.comment.as_ref().unwrap().contains("b2xd4xb6"));
+}
+
+#[test]
+fn test_flags_blunder() {
+    let mut start = empty_game();
+    start.board.cells[5][2] = Some(Piece::new(Color::White));
+    start.board.cells[3][4] = Some(Piece::new(Color::Black));
+    start.board.cells[0][7] = Some(Piece::new(Color::Black));
+    let game = play(&start, &[(5, 2), (4, 3)]);
+
+    let analysis = GameAnalysis::from_position(&start, &game.move_history, Some(Color::Black));
+
+    let annotated = &analysis.moves[0];
+    assert_eq!(annotated.annotation, Annotation::Blunder);
+    assert_eq!(annotated.best, Some(Turn::new(vec![(5, 2), (4, 1)])));
+    assert!(annotated.comment.as_ref().unwrap().contains("c3-b4"));
+}
+
+#[test]
+fn test_apply_review_uses_llm_verdicts() {
+    let start = CheckersGame::new();
+    let game = play(&start, &[(5, 2), (4, 3)]);
+    let game = play(&game, &[(2, 1), (3, 2)]);
+    let analysis = GameAnalysis::from_history(&game.move_history, None);
+
+    let reviewed = analysis.apply_review(
+        "1: good - Claims the center\n2: mistake - Walks into an exchange\nnot a move line",
+    );
+
+    assert_eq!(reviewed.source, ScoreSource::Llm);
+    assert_eq!(reviewed.moves[0].annotation, Annotation::Good);
+    assert_eq!(
+        reviewed.moves[0].comment.as_deref(),
+        Some("Claims the center")
+    );
+    assert_eq!(reviewed.moves[1].annotation, Annotation::Mistake);
+}
+
+#[test]
+fn test_pdn_export_includes_annotations() {
+    let mut start = empty_game();
+    start.board.cells[5][2] = Some(Piece::new(Color::White));
+    start.board.cells[3][4] = Some(Piece::new(Color::Black));
+    start.board.cells[0][7] = Some(Piece::new(Color::Black));
+    let game = play(&start, &[(5, 2), (4, 3)]);
+    let analysis = GameAnalysis::from_position(&start, &game.move_history, Some(Color::Black));
+
+    let pdn = analysis.to_pdn();
+
+    assert!(pdn.contains("[Result \"0-1\"]"));
+    assert!(pdn.contains("1. c3-d4?? {Blunder: Better was c3-b4}"));
+}
+
+#[test]
+fn test_analysis_reads_a_pdn_game() {
+    let text = r#"[White "Ann"]
+[Black "Bob"]
+[FEN "W:W22:B5,15"]
+1. 22-18 {Threatens} 15x22 0-1"#;
+
+    let analysis = GameAnalysis::from_pdn(text).unwrap();
+
+    assert_eq!(analysis.moves.len(), 2);
+    assert_eq!(analysis.moves[0].notation, "c3-d4");
+    assert_eq!(analysis.winner, Some(Color::Black));
+    let pdn = analysis.to_pdn();
+    assert!(pdn.contains("[White \"Ann\"]\n[Black \"Bob\"]\n[SetUp \"1\"]\n[FEN \"W:W22:B5,15\"]"));
+    assert!(pdn.contains("{Blunder: Threatens; Better was"));
+
+    let illegal = GameAnalysis::from_pdn("1. 22-13 11-15 *").unwrap_err();
+    assert!(illegal.contains("Move 1 (22-13)"));
+}
@@ -0,0 +1,43 @@
+use checkers_rs::ai::cache::ResponseCache;
+
+use crate::common::TempDir;
+
+#[test]
+fn test_cache_is_keyed_by_model_temperature_and_prompt() {
+    let mut cache = ResponseCache::new();
+
+    cache.insert("model-a", 0.1, "board 1", "Move C3 to D4");
+
+    assert_eq!(cache.get("model-a", 0.1, "board 1"), Some("Move C3 to D4"));
+    assert_eq!(cache.get("model-a", 0.1, "board 2"), None);
+    assert_eq!(cache.get("model-b", 0.1, "board 1"), None);
+    assert_eq!(cache.get("model-a", 0.3, "board 1"), None);
+}
+
+#[test]
+fn test_cache_persists_between_runs() {
+    let dir = TempDir::new("cache-persist");
+    let path = dir.join("responses.json");
+
+    let mut cache = ResponseCache::load(&path);
+    assert!(cache.is_empty());
+    cache.insert("model", 0.0, "prompt", "response");
+    assert_eq!(cache.unsaved(), 1);
+    cache.save().unwrap();
+    assert_eq!(cache.unsaved(), 0);
+
+    let reloaded = ResponseCache::load(&path);
+    assert_eq!(reloaded.len(), 1);
+    assert_eq!(reloaded.get("model", 0.0, "prompt"), Some("response"));
+}
+
+#[test]
+fn test_corrupt_cache_file_starts_empty() {
+    let dir = TempDir::new("cache-corrupt");
+    let path = dir.join("responses.json");
+    std::fs::write(&path, "{ not json").unwrap();
+
+    let cache = ResponseCache::load(&path);
+
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,126 @@
+use checkers_rs::ai::coach::{CoachConversation, CoachRole};
+use checkers_rs::core::game::CheckersGame;
+
+fn roles(conversation: &CoachConversation) -> Vec<CoachRole> {
+    conversation.messages().iter().map(|m| m.role).collect()
+}
+
+#[test]
+fn test_first_question_sends_position() {
+    let game = CheckersGame::new();
+    let conversation = CoachConversation::new().ask(
+        "what's the threat?",
+        &game.board,
+        game.current_player,
+        &game.move_history,
+    );
+
+    assert_eq!(
+        roles(&conversation),
+        vec![CoachRole::Context, CoachRole::Player, CoachRole::Coach]
+    );
+    assert!(conversation.messages()[0].text.contains("A B C D E F G H"));
+    assert!(conversation.messages()[0].text.contains("No moves yet"));
+    assert_eq!(conversation.messages()[1].text, "what's the threat?");
+    assert!(conversation.is_awaiting_reply());
+}
+
+#[test]
+fn test_position_not_resent_when_unchanged() {
+    let game = CheckersGame::new();
+    let conversation = CoachConversation::new()
+        .ask(
+            "first?",
+            &game.board,
+            game.current_player,
+            &game.move_history,
+        )
+        .append_reply("Develop your pieces.")
+        .finish_reply()
+        .ask(
+            "second?",
+            &game.board,
+            game.current_player,
+            &game.move_history,
+        );
+
+    let context_count = roles(&conversation)
+        .iter()
+        .filter(|role| **role == CoachRole::Context)
+        .count();
+    assert_eq!(context_count, 1);
+}
+
+#[test]
+fn test_position_resent_after_move() {
+    let game = CheckersGame::new();
+    let conversation = CoachConversation::new()
+        .ask(
+            "first?",
+            &game.board,
+            game.current_player,
+            &game.move_history,
+        )
+        .finish_reply();
+
+    let (moved, _) = game.make_move_coords(5, 0, 4, 1).unwrap();
+    let conversation = conversation.ask(
+        "why not D4?",
+        &moved.board,
+        moved.current_player,
+        &moved.move_history,
+    );
+
+    assert_eq!(
+        roles(&conversation),
+        vec![
+            CoachRole::Context,
+            CoachRole::Player,
+            CoachRole::Coach,
+            CoachRole::Context,
+            CoachRole::Player,
+            CoachRole::Coach,
+        ]
+    );
+    assert!(conversation.messages()[3].text.contains("a3-b4"));
+}
+
+#[test]
+fn test_streamed_chunks_build_reply() {
+    let game = CheckersGame::new();
+    let conversation = CoachConversation::new()
+        .ask(
+            "plan?",
+            &game.board,
+            game.current_player,
+            &game.move_history,
+        )
+        .append_reply("Control ")
+        .append_reply("the center.");
+
+    assert!(conversation.is_awaiting_reply());
+    assert_eq!(
+        conversation.messages().last().unwrap().text,
+        "Control the center."
+    );
+
+    let finished = conversation.finish_reply();
+    assert!(!finished.is_awaiting_reply());
+}
+
+#[test]
+fn test_chat_messages_exclude_pending_reply() {
+    let game = CheckersGame::new();
+    let conversation = CoachConversation::new().ask(
+        "plan?",
+        &game.board,
+        game.current_player,
+        &game.move_history,
+    );
+
+    // System prompt + position + question, but not the empty coach answer
+    assert_eq!(conversation.to_chat_messages().len(), 3);
+
+    let answered = conversation.append_reply("Advance.").finish_reply();
+    assert_eq!(answered.to_chat_messages().len(), 4);
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use checkers_rs::ai::engine::{evaluate, search, search_within, WIN_SCORE};
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::core::Turn;
+
+fn empty_game() -> CheckersGame {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game
+}
+
+#[test]
+fn test_initial_position_is_balanced() {
+    let game = CheckersGame::new();
+
+    assert_eq!(evaluate(&game.board, Color::White), 0);
+    assert_eq!(evaluate(&game.board, Color::Black), 0);
+}
+
+#[test]
+fn test_evaluate_counts_material() {
+    let mut game = empty_game();
+    game.board.cells[5][0] = Some(Piece::new(Color::White));
+    game.board.cells[5][2] = Some(Piece::new(Color::White));
+    game.board.cells[2][1] = Some(Piece::new(Color::Black));
+
+    assert!(evaluate(&game.board, Color::White) > 0);
+    assert!(evaluate(&game.board, Color::Black) < 0);
+}
+
+#[test]
+fn test_search_takes_the_full_double_jump() {
+    let mut game = empty_game();
+    game.board.cells[6][1] = Some(Piece::new(Color::White));
+    game.board.cells[5][2] = Some(Piece::new(Color::Black));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+
+    let result = search(&game, 3).unwrap();
+
+    assert_eq!(result.turn, Turn::new(vec![(6, 1), (4, 3), (2, 5)]));
+    assert!(result.score >= WIN_SCORE);
+}
+
+#[test]
+fn test_search_avoids_hanging_a_piece() {
+    let mut game = empty_game();
+    game.board.cells[5][2] = Some(Piece::new(Color::White));
+    game.board.cells[7][0] = Some(Piece::new(Color::White));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+    game.board.cells[0][7] = Some(Piece::new(Color::Black));
+
+    let result = search(&game, 4).unwrap();
+
+    assert_ne!(result.turn, Turn::new(vec![(5, 2), (4, 3)]));
+}
+
+#[test]
+fn test_search_without_moves_returns_none() {
+    let mut game = empty_game();
+    game.board.cells[0][1] = Some(Piece::new(Color::Black));
+
+    assert!(search(&game, 4).is_none());
+}
+
+#[test]
+fn test_search_within_a_budget_still_finds_the_win() {
+    let mut game = empty_game();
+    game.board.cells[6][1] = Some(Piece::new(Color::White));
+    game.board.cells[5][2] = Some(Piece::new(Color::Black));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+
+    let result = search_within(&game, Duration::from_millis(50)).unwrap();
+
+    assert_eq!(result.turn, Turn::new(vec![(6, 1), (4, 3), (2, 5)]));
+}
+
+#[test]
+fn test_search_within_no_time_returns_a_legal_turn() {
+    let game = CheckersGame::new();
+
+    let result = search_within(&game, Duration::ZERO).unwrap();
+
+    assert!(game.legal_turns().contains(&result.turn));
+}
@@ -0,0 +1,49 @@
+use checkers_rs::ai::cache::cached_response;
+use checkers_rs::ai::genai_client::{accept_ai_move, prompt_key};
+use checkers_rs::ai::persona::Persona;
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::game_logic::get_all_valid_moves_for_player;
+use genai::chat::{ChatMessage, ChatOptions};
+
+const MODEL: &str = "test-model";
+
+#[test]
+fn test_invalid_move_reply_is_not_cached() {
+    let game = CheckersGame::new();
+    let moves = get_all_valid_moves_for_player(&game.board, game.current_player);
+    let messages = [ChatMessage::user(
+        "genai_client_test: pick a move, invalid reply",
+    )];
+    let options = ChatOptions::default().with_temperature(0.1);
+
+    let result = accept_ai_move(
+        MODEL,
+        &messages,
+        &options,
+        "99",
+        &moves,
+        &Persona::default(),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(cached_response(MODEL, 0.1, &prompt_key(&messages)), None);
+}
+
+#[test]
+fn test_valid_move_reply_is_cached() {
+    let game = CheckersGame::new();
+    let moves = get_all_valid_moves_for_player(&game.board, game.current_player);
+    let messages = [ChatMessage::user(
+        "genai_client_test: pick a move, valid reply",
+    )];
+    let options = ChatOptions::default().with_temperature(0.1);
+
+    let ai_move =
+        accept_ai_move(MODEL, &messages, &options, "1", &moves, &Persona::default()).unwrap();
+
+    assert_eq!((ai_move.from, ai_move.to), (moves[0].0, moves[0].1));
+    assert_eq!(
+        cached_response(MODEL, 0.1, &prompt_key(&messages)).as_deref(),
+        Some("1")
+    );
+}
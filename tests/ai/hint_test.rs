@@ -0,0 +1,98 @@
+use checkers_rs::ai::formatting::{find_squares, format_square, parse_square};
+use checkers_rs::ai::hint::verify_hint;
+use checkers_rs::core::board::Board;
+use checkers_rs::core::piece::{Color, Piece};
+
+fn initial_board() -> Board {
+    let mut board = Board::new(8);
+    board.initialize();
+    board
+}
+
+#[test]
+fn test_parse_square_round_trips_format_square() {
+    for row in 0..8 {
+        for col in 0..8 {
+            assert_eq!(parse_square(&format_square(row, col), 8), Some((row, col)));
+        }
+    }
+    assert_eq!(parse_square("d4", 8), Some((4, 3)));
+    assert_eq!(parse_square("I9", 8), None);
+    assert_eq!(parse_square("A0", 8), None);
+    assert_eq!(parse_square("C10", 8), None);
+    // Larger boards have more files and two-digit ranks
+    assert_eq!(parse_square("C10", 10), Some((0, 2)));
+    assert_eq!(parse_square("j1", 10), Some((9, 9)));
+    assert_eq!(parse_square("K1", 10), None);
+}
+
+#[test]
+fn test_find_squares_skips_embedded_tokens() {
+    let squares = find_squares(
+        "1. Move piece from C3 to D4 (not a1b2 or h10), e.g. safe",
+        8,
+    );
+
+    assert_eq!(squares, vec![(5, 2), (4, 3)]);
+}
+
+#[test]
+fn test_verify_hint_accepts_legal_move() {
+    let board = initial_board();
+
+    let verified = verify_hint(
+        "Move piece from C3 to D4. It claims the center.",
+        &board,
+        Color::White,
+    );
+
+    assert_eq!(verified, Some(((5, 2), (4, 3))));
+}
+
+#[test]
+fn test_verify_hint_finds_the_legal_pair_in_prose() {
+    let board = initial_board();
+
+    let verified = verify_hint(
+        "Don't play D6, instead move C3 to D4.",
+        &board,
+        Color::White,
+    );
+
+    assert_eq!(verified, Some(((5, 2), (4, 3))));
+}
+
+#[test]
+fn test_verify_hint_rejects_illegal_moves() {
+    let board = initial_board();
+
+    // Backwards, onto an occupied square, opponent's piece, and off the board
+    assert_eq!(verify_hint("Move C3 to B2", &board, Color::White), None);
+    assert_eq!(verify_hint("Move B2 to C3", &board, Color::White), None);
+    assert_eq!(verify_hint("Move D6 to E5", &board, Color::White), None);
+    assert_eq!(verify_hint("Move C3 to I9", &board, Color::White), None);
+    assert_eq!(
+        verify_hint("Play aggressively!", &board, Color::White),
+        None
+    );
+}
+
+#[test]
+fn test_verify_hint_enforces_forced_capture() {
+    let mut board = Board::new(8);
+    board.set_piece(6, 1, Some(Piece::new(Color::White)));
+    board.set_piece(5, 2, Some(Piece::new(Color::Black)));
+    board.set_piece(3, 4, Some(Piece::new(Color::Black)));
+    board.set_piece(6, 7, Some(Piece::new(Color::White)));
+
+    assert_eq!(verify_hint("Move H2 to G3", &board, Color::White), None);
+    assert_eq!(
+        verify_hint("Capture B2 to D4", &board, Color::White),
+        Some(((6, 1), (4, 3)))
+    );
+    // Naming only the final square of the double jump is fine too
+    assert_eq!(
+        verify_hint("Jump from B2 all the way to F6", &board, Color::White),
+        Some(((6, 1), (2, 5)))
+    );
+}
@@ -0,0 +1,9 @@
+mod analysis_test;
+mod cache_test;
+mod coach_test;
+mod engine_test;
+mod genai_client_test;
+mod hint_test;
+mod persona_test;
+mod tournament_test;
+mod usage_test;
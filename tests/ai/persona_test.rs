@@ -0,0 +1,68 @@
+use checkers_rs::ai::persona::Persona;
+
+use crate::common::TempDir;
+
+#[test]
+fn test_builtin_personas() {
+    let names: Vec<String> = Persona::builtins().into_iter().map(|p| p.name).collect();
+
+    assert_eq!(
+        names,
+        ["classic", "aggressive", "defensive", "trash-talker"]
+    );
+    assert_eq!(Persona::builtins()[0], Persona::default());
+}
+
+#[test]
+fn test_parse_header_and_instructions() {
+    let persona = Persona::parse(
+        "drawish",
+        "description: Happy with a draw\nremarks: yes\n\nTrade pieces whenever you can.\nKeep it level.",
+    );
+
+    assert_eq!(persona.name, "drawish");
+    assert_eq!(persona.description, "Happy with a draw");
+    assert!(persona.remarks);
+    assert_eq!(
+        persona.instructions,
+        "Trade pieces whenever you can.\nKeep it level."
+    );
+}
+
+#[test]
+fn test_parse_without_header() {
+    let persona = Persona::parse("plain", "Style: sharp. Attack the left flank.");
+
+    assert_eq!(persona.description, "Custom opponent");
+    assert!(!persona.remarks);
+    assert_eq!(persona.instructions, "Style: sharp. Attack the left flank.");
+}
+
+#[test]
+fn test_prompt_section_asks_for_remarks_only_when_enabled() {
+    let quiet = Persona::builtins()[1].prompt_section();
+    let chatty = Persona::builtins()[3].prompt_section();
+
+    assert!(quiet.starts_with("Playing style: Play aggressively."));
+    assert!(quiet.contains("ONLY the move number"));
+    assert!(chatty.contains(" | "));
+    assert!(!Persona::default()
+        .prompt_section()
+        .contains("Playing style"));
+}
+
+#[test]
+fn test_load_all_adds_and_replaces_personas() {
+    let dir = TempDir::new("personas-load");
+    std::fs::write(dir.join("zen.txt"), "description: Calm\n\nPlay slowly.").unwrap();
+    std::fs::write(dir.join("defensive.txt"), "Never move the back row.").unwrap();
+    std::fs::write(dir.join("notes.md"), "not a persona").unwrap();
+
+    let personas = Persona::load_all(Some(dir.path()));
+
+    assert_eq!(personas.len(), 5);
+    assert_eq!(personas[2].name, "defensive");
+    assert_eq!(personas[2].instructions, "Never move the back row.");
+    assert_eq!(personas[4].name, "zen");
+    assert_eq!(personas[4].description, "Calm");
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use checkers_rs::ai::engine::score_position;
+use checkers_rs::ai::formatting::{parse_move, resolve_turn};
+use checkers_rs::ai::tournament::{
+    play_game, run_match, MatchConfig, PlayerSpec, Sprt, SprtDecision, Tally, Termination, OPENINGS,
+};
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::Color;
+
+use crate::common::TempDir;
+
+fn tally(wins: usize, draws: usize, losses: usize) -> Tally {
+    Tally {
+        wins,
+        draws,
+        losses,
+    }
+}
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn test_player_specs_parse_and_print() {
+    assert_eq!(PlayerSpec::parse("depth:4"), Some(PlayerSpec::Depth(4)));
+    assert_eq!(
+        PlayerSpec::parse("time:250"),
+        Some(PlayerSpec::MoveTime(Duration::from_millis(250)))
+    );
+    assert_eq!(
+        PlayerSpec::MoveTime(Duration::from_millis(250)).to_string(),
+        "time:250"
+    );
+    for bad in ["depth:0", "depth", "speed:3", "time:-5"] {
+        assert_eq!(PlayerSpec::parse(bad), None, "{bad}");
+    }
+}
+
+#[test]
+fn test_elo_follows_the_score() {
+    assert_eq!(tally(5, 10, 5).elo(), 0.0);
+    let ahead = tally(3, 0, 1);
+    assert!((ahead.elo() - 190.85).abs() < 0.01);
+
+    let (low, high) = tally(30, 40, 20).elo_interval();
+    let elo = tally(30, 40, 20).elo();
+    assert!(low < elo && elo < high);
+    assert!(tally(4, 0, 0).elo().is_infinite());
+}
+
+#[test]
+fn test_sprt_decides_clear_results_only() {
+    let sprt = Sprt::default();
+    let (lower, upper) = sprt.bounds();
+    assert!((upper - 2.944).abs() < 0.001);
+    assert!((lower + 2.944).abs() < 0.001);
+
+    assert_eq!(sprt.decision(&tally(300, 400, 150)), SprtDecision::AcceptH1);
+    assert_eq!(sprt.decision(&tally(150, 400, 300)), SprtDecision::AcceptH0);
+    assert_eq!(sprt.decision(&tally(3, 4, 2)), SprtDecision::Continue);
+    assert_eq!(sprt.llr(&tally(0, 0, 0)), 0.0);
+}
+
+#[test]
+fn test_openings_are_legal_and_level() {
+    for opening in OPENINGS {
+        let game = opening
+            .split_whitespace()
+            .fold(CheckersGame::new(), |game, text| {
+                let turn = resolve_turn(&game, parse_move(text, game.board.size).unwrap());
+                game.apply_turn(&turn).expect(opening)
+            });
+        assert_eq!(game.current_player, Color::Black, "{opening}");
+        assert!(score_position(&game, 4).abs() <= 50, "{opening}");
+    }
+}
+
+#[test]
+fn test_games_start_from_the_opening_and_finish() {
+    let opening = args("22-18 10-15 23-19");
+    let played = play_game(PlayerSpec::Depth(3), PlayerSpec::Depth(1), &opening).unwrap();
+
+    assert_eq!(played.turns[0].1.path, vec![(5, 2), (4, 3)]);
+    assert_eq!(played.turns[2].0, Color::White);
+    if played.termination == Termination::Board {
+        assert!(played.winner.is_some());
+    } else {
+        assert_eq!(played.winner, None);
+    }
+    assert!(play_game(PlayerSpec::Depth(1), PlayerSpec::Depth(1), &args("22-10")).is_err());
+}
+
+#[test]
+fn test_match_alternates_colors_and_writes_every_game() {
+    let dir = TempDir::new("selfplay");
+    let pdn = dir.join("games.pdn");
+    let config = MatchConfig::from_args(args(&format!(
+        "--player depth:2 --player depth:1 --games 3 --pdn {} --sprt -5,5",
+        pdn.display()
+    )))
+    .unwrap();
+    assert_eq!(config.sprt.elo0, -5.0);
+
+    let mut whites = Vec::new();
+    let tally = run_match(&config, |game, _| whites.push(game.white)).unwrap();
+    assert_eq!(tally.games(), 3);
+    assert_eq!(
+        whites,
+        vec![
+            PlayerSpec::Depth(2),
+            PlayerSpec::Depth(1),
+            PlayerSpec::Depth(2)
+        ]
+    );
+
+    let written = std::fs::read_to_string(&pdn).unwrap();
+    assert_eq!(
+        written.matches("[Event \"checkers-rs self-play\"]").count(),
+        3
+    );
+    assert!(written.contains(&format!("[Opening \"{}\"]", OPENINGS[1])));
+}
+
+#[test]
+fn test_match_needs_two_players() {
+    assert!(MatchConfig::from_args(args("--player depth:2")).is_err());
+    assert!(MatchConfig::from_args(args("--player depth:2 --player depth:3 --games 0")).is_err());
+    assert!(MatchConfig::from_args(args("--player depth:2 --player fast")).is_err());
+    assert!(MatchConfig::from_args(args("--player depth:2 --player depth:3 --sprt 10,0")).is_err());
+}
@@ -0,0 +1,77 @@
+use checkers_rs::ai::usage::{Budget, UsageTracker, DEFAULT_MAX_REQUESTS, DEFAULT_MAX_TOKENS};
+use checkers_rs::ai::AIError;
+use serial_test::serial;
+
+#[test]
+fn test_tracker_counts_requests_and_tokens() {
+    let mut tracker = UsageTracker::new(Budget::default());
+
+    tracker.record_request(120, 30);
+    tracker.record_request(80, 20);
+    tracker.record_cache_hit();
+
+    let stats = tracker.stats();
+    assert_eq!(stats.requests, 2);
+    assert_eq!(stats.cache_hits, 1);
+    assert_eq!(stats.total_tokens(), 250);
+    assert_eq!(
+        stats.summary(),
+        "AI usage: 2 requests (1 cached), 250 tokens"
+    );
+}
+
+#[test]
+fn test_tracker_enforces_request_budget() {
+    let mut tracker = UsageTracker::new(Budget {
+        max_requests: 2,
+        max_tokens: 10_000,
+    });
+
+    tracker.record_request(10, 10);
+    assert!(tracker.check().is_ok());
+    tracker.record_request(10, 10);
+
+    assert!(matches!(tracker.check(), Err(AIError::BudgetExceeded(_))));
+}
+
+#[test]
+fn test_tracker_enforces_token_budget() {
+    let mut tracker = UsageTracker::new(Budget {
+        max_requests: 100,
+        max_tokens: 500,
+    });
+
+    tracker.record_request(400, 150);
+
+    assert!(matches!(tracker.check(), Err(AIError::BudgetExceeded(_))));
+}
+
+#[test]
+fn test_cache_hits_do_not_use_budget() {
+    let mut tracker = UsageTracker::new(Budget {
+        max_requests: 1,
+        max_tokens: 500,
+    });
+
+    for _ in 0..10 {
+        tracker.record_cache_hit();
+    }
+
+    assert!(tracker.check().is_ok());
+}
+
+#[test]
+#[serial]
+fn test_budget_from_env() {
+    std::env::set_var("LLM_MAX_REQUESTS", "12");
+    std::env::set_var("LLM_MAX_TOKENS", "not a number");
+
+    let budget = Budget::from_env();
+
+    std::env::remove_var("LLM_MAX_REQUESTS");
+    std::env::remove_var("LLM_MAX_TOKENS");
+
+    assert_eq!(budget.max_requests, 12);
+    assert_eq!(budget.max_tokens, DEFAULT_MAX_TOKENS);
+    assert_eq!(Budget::from_env().max_requests, DEFAULT_MAX_REQUESTS);
+}
@@ -0,0 +1,108 @@
+use checkers_rs::cli::{parse, Command, DEFAULT_PERFT_DEPTH};
+use checkers_rs::config::{Difficulty, Opponent, PlayOptions};
+use checkers_rs::core::clock::TimeControl;
+use checkers_rs::core::piece::Color;
+use checkers_rs::net::NetRole;
+use checkers_rs::utils::log::Level;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn command(list: &[&str]) -> Result<Command, String> {
+    parse(args(list), PlayOptions::default()).map(|cli| cli.command)
+}
+
+#[test]
+fn test_no_command_means_play_with_the_defaults() {
+    let defaults = PlayOptions {
+        difficulty: Difficulty::Hard,
+        ..PlayOptions::default()
+    };
+    let cli = parse(Vec::new(), defaults.clone()).unwrap();
+    assert_eq!(
+        cli.command,
+        Command::Play {
+            options: defaults.clone(),
+            network: None,
+            broadcast: None
+        }
+    );
+
+    // Options on the command line win over the defaults
+    let cli = parse(
+        args(&["-vv", "--color", "black", "--ai", "simple", "--time", "3+2"]),
+        defaults,
+    )
+    .unwrap();
+    assert_eq!(cli.logging.level, Some(Level::Debug));
+    let Command::Play { options, .. } = cli.command else {
+        panic!("expected a game");
+    };
+    assert_eq!(options.color, Color::Black);
+    assert_eq!(options.opponent, Opponent::Engine);
+    assert_eq!(options.difficulty, Difficulty::Hard);
+    assert_eq!(options.time, TimeControl::parse("3+2"));
+}
+
+#[test]
+fn test_play_takes_network_flags_but_not_with_a_position() {
+    assert_eq!(
+        command(&["play", "--join", "example.org", "--broadcast"]),
+        Ok(Command::Play {
+            options: PlayOptions::default(),
+            network: Some(NetRole::Join {
+                address: "example.org".to_string()
+            }),
+            broadcast: Some("127.0.0.1".to_string())
+        })
+    );
+    assert!(command(&["--fen", "B:W18:B14", "--host"]).is_err());
+    assert!(command(&["--color", "black", "--host"]).is_err());
+    assert!(command(&["--fen", "nonsense"]).is_err());
+    assert!(command(&["--difficulty", "brutal"])
+        .unwrap_err()
+        .contains("easy, medium or hard"));
+    assert!(command(&["--time"]).is_err());
+}
+
+#[test]
+fn test_subcommands_and_help() {
+    assert_eq!(
+        command(&["perft"]),
+        Ok(Command::Perft {
+            depth: DEFAULT_PERFT_DEPTH,
+            fen: None
+        })
+    );
+    assert_eq!(
+        command(&["perft", "--fen", "B:W18:B14", "3"]),
+        Ok(Command::Perft {
+            depth: 3,
+            fen: Some("B:W18:B14".to_string())
+        })
+    );
+    assert!(command(&["perft", "0"]).is_err());
+    assert!(matches!(
+        command(&["analyze", "game.pdn"]),
+        Ok(Command::Analyze { .. })
+    ));
+    assert!(command(&["analyze"]).is_err());
+    assert_eq!(command(&["engine"]), Ok(Command::Engine));
+    assert!(matches!(
+        command(&["selfplay", "--player", "depth:2", "--player", "depth:1"]),
+        Ok(Command::Selfplay(_))
+    ));
+    assert_eq!(command(&["--version"]), Ok(Command::Version));
+    assert!(command(&["conquer"]).is_err());
+
+    let Ok(Command::Help(overview)) = command(&["--help"]) else {
+        panic!("expected help");
+    };
+    assert!(overview.contains("analyze FILE"));
+    let Ok(Command::Help(play)) = command(&["help", "play"]) else {
+        panic!("expected help");
+    };
+    assert!(play.contains("--difficulty easy|medium|hard"));
+    assert_eq!(command(&["perft", "-h"]), command(&["help", "perft"]));
+}
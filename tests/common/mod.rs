@@ -0,0 +1,32 @@
+use std::path::{Path, PathBuf};
+
+/// A directory of its own under the system temp dir, removed with
+/// everything in it when dropped, so a failing test cleans up too
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// `name` keeps the tests running at the same time apart
+    pub fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("checkers-rs-test-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn join(&self, file: &str) -> PathBuf {
+        self.path.join(file)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
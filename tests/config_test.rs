@@ -0,0 +1,96 @@
+use checkers_rs::config::{ConfigError, Difficulty, Opponent, PlayOptions, Settings};
+use checkers_rs::core::piece::Color;
+use checkers_rs::interface::animation::AnimationSpeed;
+use checkers_rs::state::states::settings::TIME_CHOICES;
+use checkers_rs::state::GameSession;
+
+use crate::common::TempDir;
+
+#[test]
+fn test_config_file_sets_game_defaults() {
+    let (settings, warnings) = Settings::parse(
+        "# my settings\ncolor: Black\nai: none\ndifficulty: easy\nspeed: fast\ntime: forever\n",
+    );
+    let options = settings.play;
+
+    assert_eq!(options.color, Color::Black);
+    assert_eq!(options.opponent, Opponent::Human);
+    assert_eq!(options.difficulty, Difficulty::Easy);
+    assert_eq!(options.time, None);
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(
+        warnings[0],
+        ConfigError::UnknownSetting("speed".to_string())
+    );
+    assert!(matches!(warnings[1], ConfigError::BadValue(_)));
+}
+
+#[test]
+fn test_session_follows_the_options() {
+    let mut options = PlayOptions::default();
+    options.set("color", "black").unwrap();
+    options.set("fen", "B:W18:B14").unwrap();
+    options.set("ai", "simple").unwrap();
+    let session = GameSession::from_options(&options);
+
+    assert_eq!(session.game.current_player, Color::Black);
+    assert!(session.start.is_some());
+    assert_eq!(session.ai_seat(), Some(Color::White));
+    assert!(session.is_simple_ai());
+
+    options.set("ai", "none").unwrap();
+    assert_eq!(GameSession::from_options(&options).ai_seat(), None);
+    assert!(options.set("variant", "international").is_err());
+}
+
+#[test]
+fn test_config_file_sets_model_look_and_autosave() {
+    let (settings, warnings) = Settings::parse(
+        "model: gemini-2.0-flash\ntemperature: 1.2\nreview_temperature: 3\ntheme: Light\nkeys: vim\nanimation: fast\nautosave: on\n",
+    );
+
+    assert_eq!(settings.model.as_deref(), Some("gemini-2.0-flash"));
+    assert_eq!(settings.temperature, 1.2);
+    assert_eq!(
+        settings.review_temperature,
+        Settings::default().review_temperature
+    );
+    assert_eq!(settings.theme.as_deref(), Some("light"));
+    assert_eq!(settings.keys.as_deref(), Some("vim"));
+    assert_eq!(settings.animation, AnimationSpeed::Fast);
+    assert!(settings.autosave);
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0], ConfigError::BadValue(_)));
+}
+
+#[test]
+fn test_saving_rewrites_only_the_changed_lines() {
+    let text = "# mine\ncolor: black\ntheme: classic\nunknown: kept\n";
+    let mut settings = Settings::parse(text).0;
+    settings.set("theme", "light").unwrap();
+    settings.set("autosave", "on").unwrap();
+    let changed = settings.changes(&Settings::parse(text).0);
+
+    assert_eq!(changed, vec!["theme", "autosave"]);
+    assert_eq!(
+        settings.update_text(text, &changed),
+        "# mine\ncolor: black\ntheme: light\nunknown: kept\nautosave: on\n"
+    );
+}
+
+#[test]
+fn test_every_time_choice_survives_saving() {
+    let dir = TempDir::new("config-time");
+    let path = dir.join("config.txt");
+
+    for choice in TIME_CHOICES {
+        let mut settings = Settings::default();
+        settings.set("time", choice).unwrap();
+        settings.save(&path, &["time"]).unwrap();
+
+        let (loaded, warnings) = Settings::load(Some(&path));
+        assert!(warnings.is_empty(), "{choice}: {warnings:?}");
+        assert_eq!(loaded.play.time, settings.play.time, "{choice}");
+        assert_eq!(loaded.value("time"), choice);
+    }
+}
@@ -12,7 +12,7 @@ fn parse_square(square: &str) -> Option<(usize, usize)> {
     let col = (chars[0] as u8).wrapping_sub(b'A') as usize;
     let display_row_num = chars[1].to_digit(10)? as usize;
 
-    if col < 8 && display_row_num >= 1 && display_row_num <= 8 {
+    if col < 8 && (1..=8).contains(&display_row_num) {
         let row = 8 - display_row_num; // Convert display row (1-8) to internal row (7-0)
         Some((row, col))
     } else {
@@ -68,3 +68,28 @@ fn test_format_parse_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_parse_move_reads_names_and_numbers() {
+    use checkers_rs::ai::formatting::parse_move;
+
+    assert_eq!(parse_move("c3-d4", 8), Some(vec![(5, 2), (4, 3)]));
+    assert_eq!(parse_move(" C3-D4 ", 8), Some(vec![(5, 2), (4, 3)]));
+    // Numbered squares name the same squares as their algebraic names
+    assert_eq!(parse_move("22-18", 8), parse_move("c3-d4", 8));
+    assert_eq!(
+        parse_move("b6xd4xf2", 8),
+        Some(vec![(2, 1), (4, 3), (6, 5)])
+    );
+
+    assert_eq!(parse_move("c3", 8), None);
+    assert_eq!(parse_move("c3-", 8), None);
+    assert_eq!(parse_move("c3-i4", 8), None);
+    assert_eq!(parse_move("0-18", 8), None);
+
+    // Larger boards name more files, ranks and numbered squares
+    assert_eq!(parse_move("a10-b9", 10), Some(vec![(0, 0), (1, 1)]));
+    assert!(parse_move("c3-i4", 10).is_some());
+    assert!(parse_move("46-41", 10).is_some());
+    assert_eq!(parse_move("46-41", 8), None);
+}
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+use checkers_rs::core::clock::{format_time, Bonus, Clock, TimeControl};
+use checkers_rs::core::piece::Color;
+
+fn secs(seconds: u64) -> Duration {
+    Duration::from_secs(seconds)
+}
+
+#[test]
+fn test_time_controls_parse() {
+    assert_eq!(
+        TimeControl::parse("5"),
+        Some(TimeControl::new(secs(300), Bonus::None))
+    );
+    assert_eq!(
+        TimeControl::parse(" 3+2 "),
+        Some(TimeControl::new(secs(180), Bonus::Increment(secs(2))))
+    );
+    assert_eq!(
+        TimeControl::parse("10D5"),
+        Some(TimeControl::new(secs(600), Bonus::Delay(secs(5))))
+    );
+    assert_eq!(
+        TimeControl::parse("0.5+1"),
+        Some(TimeControl::new(secs(30), Bonus::Increment(secs(1))))
+    );
+    assert_eq!(TimeControl::parse("off"), None);
+    assert_eq!(TimeControl::parse("0"), None);
+    assert_eq!(TimeControl::parse("5+x"), None);
+    assert_eq!(TimeControl::parse("5+-1"), None);
+    // Too long for a Duration
+    assert_eq!(TimeControl::parse("1e300"), None);
+    assert_eq!(TimeControl::parse("1+1e30"), None);
+    assert_eq!(TimeControl::parse("1d1e30"), None);
+}
+
+#[test]
+fn test_time_controls_display() {
+    assert_eq!(TimeControl::parse("5").unwrap().to_string(), "5 min");
+    assert_eq!(TimeControl::parse("3+2").unwrap().to_string(), "3+2");
+    assert_eq!(TimeControl::parse("10d5").unwrap().to_string(), "10 d5");
+
+    assert_eq!(TimeControl::parse("5").unwrap().to_setting(), "5");
+    assert_eq!(TimeControl::parse("0.5+1").unwrap().to_setting(), "0.5+1");
+    assert_eq!(TimeControl::parse("10D5").unwrap().to_setting(), "10d5");
+}
+
+#[test]
+fn test_only_the_running_side_loses_time() {
+    let start = Instant::now();
+    let mut clock = Clock::new(TimeControl::parse("5").unwrap());
+    clock.start(Color::White, start);
+
+    let later = start + secs(10);
+    assert_eq!(clock.left(Color::White, later), secs(290));
+    assert_eq!(clock.left(Color::Black, later), secs(300));
+
+    clock.press(later);
+    assert_eq!(clock.running_side(), Some(Color::Black));
+    assert_eq!(clock.left(Color::White, later + secs(20)), secs(290));
+    assert_eq!(clock.left(Color::Black, later + secs(20)), secs(280));
+}
+
+#[test]
+fn test_fischer_increment_is_added_after_each_move() {
+    let start = Instant::now();
+    let mut clock = Clock::new(TimeControl::parse("1+5").unwrap());
+    clock.start(Color::White, start);
+
+    clock.press(start + secs(2));
+
+    assert_eq!(clock.left(Color::White, start + secs(2)), secs(63));
+}
+
+#[test]
+fn test_huge_increments_saturate() {
+    let start = Instant::now();
+    let mut clock = Clock::new(TimeControl::parse("1+1e19").unwrap());
+    clock.start(Color::White, start);
+
+    clock.press(start + secs(1));
+    clock.press(start + secs(2));
+    clock.press(start + secs(3));
+
+    assert_eq!(clock.left(Color::White, start + secs(3)), Duration::MAX);
+    assert!(clock.budget(Color::White, start + secs(3)) > secs(60));
+}
+
+#[test]
+fn test_bronstein_delay_is_not_charged() {
+    let start = Instant::now();
+    let mut clock = Clock::new(TimeControl::parse("1d5").unwrap());
+    clock.start(Color::White, start);
+
+    assert_eq!(clock.left(Color::White, start + secs(3)), secs(60));
+    clock.press(start + secs(8));
+    assert_eq!(clock.left(Color::White, start + secs(8)), secs(57));
+}
+
+#[test]
+fn test_running_out_of_time_flags_that_side() {
+    let start = Instant::now();
+    let mut clock = Clock::new(TimeControl::parse("1").unwrap());
+    clock.start(Color::Black, start);
+
+    assert_eq!(clock.flagged(start + secs(59)), None);
+    assert_eq!(clock.flagged(start + secs(61)), Some(Color::Black));
+
+    // A flagged side gets no increment and stays flagged once stopped
+    clock.stop(start + secs(61));
+    assert!(!clock.is_running());
+    assert_eq!(clock.flagged(start + secs(1000)), Some(Color::Black));
+}
+
+#[test]
+fn test_budget_is_a_share_of_the_time_left() {
+    let start = Instant::now();
+    let sudden_death = Clock::new(TimeControl::parse("5").unwrap());
+    let increment = Clock::new(TimeControl::parse("5+4").unwrap());
+    let mut short = Clock::new(TimeControl::parse("0.1").unwrap());
+    short.start(Color::Black, start);
+
+    assert_eq!(sudden_death.budget(Color::Black, start), secs(15));
+    assert_eq!(increment.budget(Color::Black, start), secs(18));
+    assert!(short.budget(Color::Black, start + secs(5)) <= Duration::from_millis(500));
+}
+
+#[test]
+fn test_clock_faces() {
+    assert_eq!(format_time(secs(300)), "5:00");
+    assert_eq!(format_time(secs(65)), "1:05");
+    assert_eq!(format_time(Duration::from_millis(9_450)), "9.4");
+    assert_eq!(format_time(Duration::ZERO), "0.0");
+}
@@ -0,0 +1,41 @@
+use checkers_rs::core::fen::{parse_fen, to_fen, FenError, START_FEN};
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::Color;
+
+#[test]
+fn test_start_fen_is_the_initial_position() {
+    let game = parse_fen(START_FEN).unwrap();
+    assert_eq!(game.board.cells, CheckersGame::new().board.cells);
+    assert_eq!(game.current_player, Color::White);
+    assert_eq!(
+        to_fen(&CheckersGame::new()),
+        "W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12"
+    );
+}
+
+#[test]
+fn test_fen_round_trips_kings_and_side_to_move() {
+    let game = parse_fen("B:WK1,18,30:BK32,5.").unwrap();
+    assert_eq!(game.current_player, Color::Black);
+    assert!(game.board.get_piece(0, 1).unwrap().is_king);
+    assert_eq!(game.board.get_piece(0, 1).unwrap().color, Color::White);
+    assert!(!game.board.get_piece(1, 0).unwrap().is_king);
+    assert_eq!(to_fen(&game), "B:WK1,18,30:B5,K32");
+}
+
+#[test]
+fn test_bad_fens_are_rejected() {
+    assert_eq!(parse_fen("W:W21-32").err(), Some(FenError::Malformed));
+    assert_eq!(
+        parse_fen("X:W21:B1").err(),
+        Some(FenError::InvalidColor("X".to_string()))
+    );
+    assert_eq!(
+        parse_fen("W:W33:B1").err(),
+        Some(FenError::InvalidSquare("33".to_string()))
+    );
+    assert_eq!(
+        parse_fen("W:W1:B1").err(),
+        Some(FenError::DuplicateSquare(1))
+    );
+}
@@ -518,10 +518,10 @@ mod get_all_possible_moves_tests {
         white_king.promote_to_king();
         let friendly_blocking_piece = Piece::new(Color::White); // Friendly piece
         board.set_piece(3, 3, Some(white_king));
-        board.set_piece(2, 2, Some(friendly_blocking_piece.clone())); // Blocked by friendly
-        board.set_piece(2, 4, Some(friendly_blocking_piece.clone())); // Blocked by friendly
-        board.set_piece(4, 2, Some(friendly_blocking_piece.clone())); // Blocked by friendly
-        board.set_piece(4, 4, Some(friendly_blocking_piece.clone())); // Blocked by friendly
+        board.set_piece(2, 2, Some(friendly_blocking_piece)); // Blocked by friendly
+        board.set_piece(2, 4, Some(friendly_blocking_piece)); // Blocked by friendly
+        board.set_piece(4, 2, Some(friendly_blocking_piece)); // Blocked by friendly
+        board.set_piece(4, 4, Some(friendly_blocking_piece)); // Blocked by friendly
 
         let moves = get_all_possible_moves(&board, 3, 3);
         let expected_moves: Vec<(usize, usize)> = vec![]; // Expect no moves
@@ -593,10 +593,10 @@ mod get_all_possible_moves_tests {
         white_king.promote_to_king();
         let black_piece = Piece::new(Color::Black);
         board.set_piece(3, 3, Some(white_king));
-        board.set_piece(2, 2, Some(black_piece.clone())); // Top-left
-        board.set_piece(2, 4, Some(black_piece.clone())); // Top-right
-        board.set_piece(4, 2, Some(black_piece.clone())); // Bottom-left
-        board.set_piece(4, 4, Some(black_piece.clone())); // Bottom-right
+        board.set_piece(2, 2, Some(black_piece)); // Top-left
+        board.set_piece(2, 4, Some(black_piece)); // Top-right
+        board.set_piece(4, 2, Some(black_piece)); // Bottom-left
+        board.set_piece(4, 4, Some(black_piece)); // Bottom-right
 
         let moves = get_all_possible_moves(&board, 3, 3);
         let expected_moves = vec![(1, 1), (1, 5), (5, 1), (5, 5)];
@@ -610,7 +610,7 @@ mod get_all_possible_moves_tests {
         white_king.promote_to_king();
         let black_piece = Piece::new(Color::Black);
         board.set_piece(3, 3, Some(white_king));
-        board.set_piece(2, 2, Some(black_piece.clone())); // Opponent for capture
+        board.set_piece(2, 2, Some(black_piece)); // Opponent for capture
 
         // Add other pieces to make sure regular moves would be possible if not for capture
         // board.set_piece(2, 4, None); // Empty for potential regular move
@@ -1,4 +1,9 @@
 mod board_test;
+mod clock_test;
+mod fen_test;
 mod game_logic_test;
 mod game_test;
+mod pdn_test;
 mod piece_test;
+mod replay_test;
+mod turn_test;
@@ -0,0 +1,72 @@
+use checkers_rs::core::pdn::{parse_pdn, result_string, PdnError, PdnGame};
+use checkers_rs::core::piece::Color;
+
+#[test]
+fn test_pdn_writes_tags_moves_and_comments() {
+    let pdn = PdnGame::new(result_string(Some(Color::White)))
+        .with_tag("Event", "Club night")
+        .with_move(Color::White, "c3-d4".to_string(), None)
+        .with_move(
+            Color::Black,
+            "f6-e5".to_string(),
+            Some("Blunder".to_string()),
+        )
+        .with_move(Color::White, "d4xf6".to_string(), None)
+        .to_pdn();
+
+    assert!(pdn.starts_with("[Event \"Club night\"]\n[Result \"1-0\"]\n\n"));
+    assert!(pdn.contains("1. c3-d4 f6-e5 {Blunder} 2. d4xf6 1-0"));
+}
+
+#[test]
+fn test_pdn_wraps_long_move_text() {
+    let mut game = PdnGame::new(result_string(None));
+    for _ in 0..40 {
+        game = game
+            .with_move(Color::White, "c3-d4".to_string(), None)
+            .with_move(Color::Black, "d4-c3".to_string(), None);
+    }
+
+    let pdn = game.to_pdn();
+
+    assert!(pdn.lines().all(|line| line.len() <= 80));
+    assert!(pdn.trim_end().ends_with("1/2-1/2"));
+}
+
+#[test]
+fn test_pdn_reads_tags_moves_and_comments() {
+    let text = r#"[Event "Club night"]
+[Result "2-0"]
+; the club's own notes
+1. 22-18 11-15 {Usual} 2. 18x11?! {Forced
+  anyway} 8x15 (2... 7x16 3. 25-22) 3. 24-19! 1-0
+[Event "Next game"]
+1. 21-17 *"#;
+
+    let game = parse_pdn(text).unwrap();
+    assert_eq!(game.tag("event"), Some("Club night"));
+    assert_eq!(game.result, "1-0");
+    let moves = game
+        .moves
+        .iter()
+        .map(|m| m.notation.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(moves, ["22-18", "11-15", "18x11", "8x15", "24-19"]);
+    assert_eq!(game.moves[1].comment.as_deref(), Some("Usual"));
+    assert_eq!(game.moves[2].comment.as_deref(), Some("Forced anyway"));
+    assert_eq!(game.moves[3].player, Color::Black);
+
+    // The side to move comes from the FEN tag
+    let game = parse_pdn("[FEN \"B:W18:B14\"]\n1... 14-23 0-1").unwrap();
+    assert_eq!(game.moves[0].player, Color::Black);
+    assert_eq!(game.result, "0-1");
+
+    assert_eq!(
+        parse_pdn("1. 22-18 {never closed"),
+        Err(PdnError::Unclosed("comment"))
+    );
+    assert!(matches!(
+        parse_pdn("[Event]"),
+        Err(PdnError::MalformedTag(_))
+    ));
+}
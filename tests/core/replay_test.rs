@@ -0,0 +1,65 @@
+use checkers_rs::core::fen::parse_fen;
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::core::replay::Replay;
+use checkers_rs::core::Turn;
+
+#[test]
+fn test_replay_rebuilds_every_position() {
+    let mut game = CheckersGame::new();
+    let mut boards = vec![game.board.clone()];
+    for path in [
+        vec![(5, 2), (4, 3)],
+        vec![(2, 1), (3, 2)],
+        vec![(4, 3), (2, 1)],
+    ] {
+        game = game.apply_turn(&Turn::new(path)).unwrap();
+        boards.push(game.board.clone());
+    }
+
+    let replay = Replay::from_history(&game.move_history, 8);
+
+    assert_eq!(replay.last_ply(), 3);
+    for (ply, board) in boards.iter().enumerate() {
+        assert_eq!(replay.board_at(ply).cells, board.cells, "ply {ply}");
+    }
+    assert!(replay.turn_before(0).is_none());
+    assert_eq!(
+        replay.turn_before(3),
+        Some(&(Color::White, Turn::new(vec![(4, 3), (2, 1)])))
+    );
+}
+
+#[test]
+fn test_replay_applies_multi_jumps_and_crowning() {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game.board.cells[4][3] = Some(Piece::new(Color::White));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+    game.board.cells[1][4] = Some(Piece::new(Color::Black));
+    let start = game.clone();
+
+    let game = game
+        .apply_turn(&Turn::new(vec![(4, 3), (2, 5), (0, 3)]))
+        .unwrap();
+    let replay = Replay::from_position(&start, &game.move_history);
+
+    assert_eq!(replay.last_ply(), 1);
+    let end = replay.board_at(1);
+    assert_eq!(end.cells, game.board.cells);
+    assert!(end.get_piece(0, 3).is_some_and(|piece| piece.is_king));
+}
+
+#[test]
+fn test_replay_starts_with_the_side_to_move_in_the_position() {
+    let start = parse_fen("B:W18,24,K27:B14,K5").unwrap();
+    let turn = start.legal_turns()[0].clone();
+    let game = start.apply_turn(&turn).unwrap();
+
+    let replay = Replay::from_position(&start, &game.move_history);
+
+    assert_eq!(replay.player_at(0), Color::Black);
+    assert_eq!(replay.player_at(1), Color::White);
+    let standard = Replay::from_history(&game.move_history, 8);
+    assert_eq!(standard.player_at(0), Color::White);
+}
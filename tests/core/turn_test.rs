@@ -0,0 +1,135 @@
+use checkers_rs::core::game::{CheckersGame, GameError};
+use checkers_rs::core::notation::{
+    number_to_square, square_name, square_number, turn_to_algebraic, turn_to_numeric,
+};
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::core::Turn;
+
+fn double_jump_game() -> CheckersGame {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game.board.cells[6][1] = Some(Piece::new(Color::White));
+    game.board.cells[5][2] = Some(Piece::new(Color::Black));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+    game
+}
+
+#[test]
+fn test_legal_turns_from_initial_position() {
+    let game = CheckersGame::new();
+    let turns = game.legal_turns();
+
+    assert_eq!(turns.len(), 7);
+    assert!(turns.iter().all(|turn| turn.path.len() == 2));
+    assert!(turns.iter().all(|turn| !turn.is_capture()));
+}
+
+#[test]
+fn test_legal_turns_expand_multi_jumps() {
+    let game = double_jump_game();
+    let turns = game.legal_turns();
+
+    assert_eq!(turns, vec![Turn::new(vec![(6, 1), (4, 3), (2, 5)])]);
+    assert_eq!(turns[0].capture_count(), 2);
+}
+
+#[test]
+fn test_apply_turn_plays_whole_multi_jump() {
+    let game = double_jump_game();
+    let turn = Turn::new(vec![(6, 1), (4, 3), (2, 5)]);
+
+    let after = game.apply_turn(&turn).unwrap();
+
+    assert!(after.board.get_piece(2, 5).is_some());
+    assert!(after.board.get_piece(5, 2).is_none());
+    assert!(after.board.get_piece(3, 4).is_none());
+    assert_eq!(after.current_player, Color::Black);
+    assert_eq!(after.move_history.moves().len(), 2);
+}
+
+#[test]
+fn test_apply_turn_rejects_unfinished_multi_jump() {
+    let game = double_jump_game();
+    let turn = Turn::new(vec![(6, 1), (4, 3)]);
+
+    assert!(matches!(
+        game.apply_turn(&turn),
+        Err(GameError::InvalidMove)
+    ));
+}
+
+#[test]
+fn test_square_numbering_round_trips() {
+    assert_eq!(square_number(0, 1, 8), Some(1));
+    assert_eq!(square_number(7, 6, 8), Some(32));
+    assert_eq!(square_number(0, 0, 8), None);
+
+    for number in 1..=32 {
+        let (row, col) = number_to_square(number, 8).unwrap();
+        assert_eq!(square_number(row, col, 8), Some(number));
+    }
+    assert_eq!(number_to_square(33, 8), None);
+}
+
+#[test]
+fn test_turn_notation() {
+    let simple = Turn::new(vec![(5, 2), (4, 3)]);
+    let jump = Turn::new(vec![(6, 1), (4, 3), (2, 5)]);
+
+    assert_eq!(square_name(5, 2, 8), "c3");
+    assert_eq!(turn_to_algebraic(&simple, 8), "c3-d4");
+    assert_eq!(turn_to_algebraic(&jump, 8), "b2xd4xf6");
+    assert_eq!(turn_to_numeric(&simple, 8), "22-18");
+    assert_eq!(turn_to_numeric(&jump, 8), "25x18x11");
+}
+
+#[test]
+fn test_history_turns_join_multi_jump_hops() {
+    let game = double_jump_game();
+    let after = game
+        .apply_turn(&Turn::new(vec![(6, 1), (4, 3), (2, 5)]))
+        .unwrap();
+
+    let turns = after.move_history.turns();
+
+    assert_eq!(
+        turns,
+        vec![(Color::White, Turn::new(vec![(6, 1), (4, 3), (2, 5)]))]
+    );
+    assert_eq!(after.move_history.captured_by(Color::White), 2);
+    assert_eq!(after.move_history.captured_by(Color::Black), 0);
+}
+
+#[test]
+fn test_history_turns_keep_simple_moves_apart() {
+    let game = CheckersGame::new();
+    let game = game.apply_turn(&Turn::new(vec![(5, 0), (4, 1)])).unwrap();
+    let game = game.apply_turn(&Turn::new(vec![(2, 1), (3, 2)])).unwrap();
+
+    let turns = game.move_history.turns();
+
+    assert_eq!(turns.len(), 2);
+    assert_eq!(turns[0].0, Color::White);
+    assert_eq!(turns[1], (Color::Black, Turn::new(vec![(2, 1), (3, 2)])));
+}
+
+#[test]
+fn test_history_last_turn_lists_captured_squares() {
+    let game = double_jump_game();
+    let after = game
+        .apply_turn(&Turn::new(vec![(6, 1), (4, 3), (2, 5)]))
+        .unwrap();
+
+    assert_eq!(
+        after.move_history.last_turn(),
+        Some((
+            Turn::new(vec![(6, 1), (4, 3), (2, 5)]),
+            vec![(5, 2), (3, 4)]
+        ))
+    );
+    assert_eq!(
+        after.move_history.turn_captures(),
+        vec![vec![(5, 2), (3, 4)]]
+    );
+    assert!(CheckersGame::new().move_history.last_turn().is_none());
+}
@@ -0,0 +1,102 @@
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::core::Turn;
+use checkers_rs::interface::animation::{Animation, AnimationSpeed};
+
+fn double_jump_game() -> CheckersGame {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game.board.cells[6][1] = Some(Piece::new(Color::White));
+    game.board.cells[5][2] = Some(Piece::new(Color::Black));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+    game
+}
+
+fn animate(game: &CheckersGame, path: Vec<(usize, usize)>) -> Animation {
+    let after = game.apply_turn(&Turn::new(path)).unwrap();
+    Animation::new(
+        &game.board,
+        after.move_history.moves(),
+        AnimationSpeed::Custom(90),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_speed_parsing() {
+    assert_eq!(AnimationSpeed::parse("fast"), Some(AnimationSpeed::Fast));
+    assert_eq!(AnimationSpeed::parse(" Slow "), Some(AnimationSpeed::Slow));
+    assert_eq!(AnimationSpeed::parse("off"), Some(AnimationSpeed::Off));
+    assert_eq!(AnimationSpeed::parse("0"), Some(AnimationSpeed::Off));
+    assert_eq!(
+        AnimationSpeed::parse("150"),
+        Some(AnimationSpeed::Custom(150))
+    );
+    assert_eq!(AnimationSpeed::parse("warp"), None);
+    assert!(AnimationSpeed::Off.hop_duration().is_none());
+}
+
+#[test]
+fn test_animation_off_plays_nothing() {
+    let game = double_jump_game();
+    let after = game
+        .apply_turn(&Turn::new(vec![(6, 1), (4, 3), (2, 5)]))
+        .unwrap();
+
+    assert!(Animation::new(&game.board, after.move_history.moves(), AnimationSpeed::Off).is_none());
+    assert!(Animation::new(&game.board, &[], AnimationSpeed::Normal).is_none());
+}
+
+#[test]
+fn test_piece_slides_along_every_hop() {
+    let game = double_jump_game();
+    // 90ms hops at 30ms per frame
+    let animation = animate(&game, vec![(6, 1), (4, 3), (2, 5)]);
+
+    let start = animation.frame(0);
+    assert_eq!(start.position, (6.0, 1.0));
+    assert!(start.board.get_piece(6, 1).is_none());
+
+    assert_eq!(animation.frame(3).position, (4.0, 3.0));
+    assert_eq!(animation.frame(6).position, (2.0, 5.0));
+    assert_eq!(animation.frame(animation.len() - 1).position, (2.0, 5.0));
+
+    let (row, col) = animation.frame(1).position;
+    assert!(row < 6.0 && row > 4.0 && col > 1.0 && col < 3.0);
+}
+
+#[test]
+fn test_captured_pieces_fade_after_being_jumped() {
+    let game = double_jump_game();
+    let animation = animate(&game, vec![(6, 1), (4, 3), (2, 5)]);
+
+    let early = animation.frame(1);
+    assert!(early.board.get_piece(5, 2).is_some());
+    assert!(early.fading.contains(&((5, 2), 1.0)));
+
+    let first_gone = animation.frame(3);
+    assert!(first_gone.board.get_piece(5, 2).is_none());
+    assert!(first_gone.board.get_piece(3, 4).is_some());
+
+    let last = animation.frame(animation.len() - 1);
+    assert!(last.board.get_piece(3, 4).is_none());
+    assert!(last.fading.is_empty());
+}
+
+#[test]
+fn test_promotion_ends_with_flourish() {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game.board.cells[1][2] = Some(Piece::new(Color::White));
+    game.board.cells[7][0] = Some(Piece::new(Color::Black));
+    let animation = animate(&game, vec![(1, 2), (0, 1)]);
+
+    let sliding = animation.frame(1);
+    assert!(sliding.flourish.is_none());
+    assert!(!sliding.piece.is_king);
+
+    let landed = animation.frame(3);
+    assert_eq!(landed.flourish, Some(0));
+    assert!(landed.piece.is_king);
+    assert!(animation.len() > 3 + 1);
+}
@@ -0,0 +1,123 @@
+use checkers_rs::core::board::Board;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::interface::widgets::board::BoardScale;
+use checkers_rs::interface::widgets::CheckerBoard;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+
+fn initial(size: usize) -> Board {
+    let mut board = Board::new(size);
+    board.initialize();
+    board
+}
+
+fn render(board: &Board, width: u16, height: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut buf = Buffer::empty(area);
+    CheckerBoard::new(board)
+        .cursor_position((7, 0))
+        .render(area, &mut buf);
+    buf
+}
+
+fn row_text(buf: &Buffer, y: u16) -> String {
+    (0..buf.area.width)
+        .map(|x| buf[(x, y)].symbol().to_string())
+        .collect()
+}
+
+fn text(buf: &Buffer) -> String {
+    (0..buf.area.height)
+        .map(|y| row_text(buf, y))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_grid_sizes_follow_board_size() {
+    assert_eq!(BoardScale::Normal.grid_size(8), (53, 18));
+    assert_eq!(BoardScale::Compact.grid_size(8), (11, 9));
+    assert_eq!(BoardScale::Large.grid_size(8), (69, 34));
+    assert_eq!(BoardScale::Normal.grid_size(10), (65, 22));
+}
+
+#[test]
+fn test_largest_scale_that_fits_is_picked() {
+    assert_eq!(BoardScale::fit(8, 80, 40), Some(BoardScale::Large));
+    assert_eq!(BoardScale::fit(8, 64, 18), Some(BoardScale::Normal));
+    assert_eq!(BoardScale::fit(8, 40, 12), Some(BoardScale::Compact));
+    assert_eq!(BoardScale::fit(8, 10, 12), None);
+    assert_eq!(BoardScale::fit(10, 64, 30), Some(BoardScale::Compact));
+}
+
+#[test]
+fn test_normal_board_draws_pieces_in_a_grid() {
+    let buf = render(&initial(8), 64, 18);
+    let text = text(&buf);
+
+    assert!(row_text(&buf, 0).contains('A') && row_text(&buf, 0).contains('H'));
+    assert!(row_text(&buf, 2).contains(" 8 "));
+    assert!(row_text(&buf, 2).contains("(b)"));
+    assert!(row_text(&buf, 16).contains("(w)"));
+    // Cursor box around A1
+    assert!(text.contains('╔') && text.contains('╝'));
+    assert!(!text.contains("Need"));
+}
+
+#[test]
+fn test_compact_board_uses_one_character_per_square() {
+    let buf = render(&initial(8), 20, 10);
+
+    assert_eq!(row_text(&buf, 0).trim(), "ABCDEFGH");
+    assert_eq!(row_text(&buf, 1).trim(), "8 ░b░b░b░b");
+    assert_eq!(row_text(&buf, 8).trim(), "1 w░w░w░w░");
+}
+
+#[test]
+fn test_flipped_board_puts_black_at_the_bottom() {
+    let area = Rect::new(0, 0, 20, 10);
+    let mut buf = Buffer::empty(area);
+    CheckerBoard::new(&initial(8))
+        .flipped(true)
+        .render(area, &mut buf);
+
+    assert_eq!(row_text(&buf, 0).trim(), "HGFEDCBA");
+    assert_eq!(row_text(&buf, 1).trim(), "1 ░w░w░w░w");
+    assert_eq!(row_text(&buf, 8).trim(), "8 b░b░b░b░");
+}
+
+#[test]
+fn test_large_board_draws_bigger_pieces() {
+    let mut board = Board::new(8);
+    board.cells[0][1] = Some(Piece::new(Color::Black));
+    let mut king = Piece::new(Color::White);
+    king.is_king = true;
+    board.cells[7][0] = Some(king);
+
+    let text = text(&render(&board, 80, 40));
+
+    assert!(text.contains("╭───╮"));
+    assert!(text.contains("│ b │"));
+    assert!(text.contains("║ W ║"));
+}
+
+#[test]
+fn test_boards_of_other_sizes_render_every_square() {
+    let buf = render(&initial(10), 80, 30);
+
+    assert!(text(&buf).contains('J'));
+    assert!(text(&buf).contains("10 "));
+    assert_eq!(
+        CheckerBoard::square_at(buf.area, 10, false, 0, 0),
+        None,
+        "labels are not squares"
+    );
+}
+
+#[test]
+fn test_too_small_area_reports_needed_size() {
+    let buf = render(&Board::new(8), 10, 5);
+
+    assert!(row_text(&buf, 0).starts_with("Need 11x9"));
+}
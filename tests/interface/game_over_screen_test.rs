@@ -0,0 +1,37 @@
+use checkers_rs::interface::keymap::KeyBindings;
+use checkers_rs::interface::widgets::GameOverScreen;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+
+fn render(bindings: &KeyBindings) -> String {
+    let area = Rect::new(0, 0, 60, 12);
+    let mut buf = Buffer::empty(area);
+    GameOverScreen::new("White wins!", bindings).render(area, &mut buf);
+    (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_game_over_names_the_bound_keys() {
+    let text = render(&KeyBindings::arrows());
+
+    assert!(text.contains("White wins!"));
+    assert!(text.contains("Press A to analyze or R to replay the game"));
+    assert!(text.contains("Press ESC to exit"));
+}
+
+#[test]
+fn test_game_over_follows_the_wasd_preset() {
+    let text = render(&KeyBindings::wasd());
+
+    // A moves left under WASD, so the review is on V
+    assert!(text.contains("Press V to analyze or R to replay the game"));
+    assert!(!text.contains("Press A"));
+}
@@ -0,0 +1,175 @@
+use std::fs;
+
+use checkers_rs::interface::keymap::{
+    key_label, parse_key, Action, KeyBindings, KeyError, PRESETS,
+};
+use crossterm::event::KeyCode;
+
+use crate::common::TempDir;
+
+#[test]
+fn test_every_preset_loads_by_name() {
+    for name in PRESETS {
+        assert!(KeyBindings::preset(name).is_some(), "{name}");
+    }
+    assert_eq!(KeyBindings::preset("VIM"), Some(KeyBindings::vim()));
+    assert!(KeyBindings::preset("emacs").is_none());
+}
+
+#[test]
+fn test_default_bindings() {
+    let keys = KeyBindings::default();
+
+    assert_eq!(keys.action_for(KeyCode::Up), Some(Action::Up));
+    assert_eq!(keys.action_for(KeyCode::Char(' ')), Some(Action::Select));
+    assert_eq!(keys.action_for(KeyCode::Enter), Some(Action::Select));
+    assert_eq!(keys.action_for(KeyCode::Char('q')), Some(Action::Quit));
+    assert_eq!(keys.action_for(KeyCode::Char('h')), Some(Action::Hint));
+    assert_eq!(keys.action_for(KeyCode::Char('?')), Some(Action::Help));
+    assert_eq!(keys.action_for(KeyCode::Char('x')), None);
+}
+
+#[test]
+fn test_uppercase_letters_fall_back_to_lowercase() {
+    let keys = KeyBindings::default();
+    assert_eq!(keys.action_for(KeyCode::Char('Q')), Some(Action::Quit));
+
+    // Unless the uppercase letter has a binding of its own
+    let vim = KeyBindings::vim();
+    assert_eq!(vim.action_for(KeyCode::Char('g')), Some(Action::First));
+    assert_eq!(vim.action_for(KeyCode::Char('G')), Some(Action::Last));
+}
+
+#[test]
+fn test_movement_presets_keep_every_action_reachable() {
+    for keys in [
+        KeyBindings::vim(),
+        KeyBindings::wasd(),
+        KeyBindings::numpad(),
+    ] {
+        for action in Action::ALL {
+            assert!(!keys.keys_for(action).is_empty(), "{action:?} unbound");
+        }
+        // The arrows keep working alongside the preset's keys
+        assert_eq!(keys.action_for(KeyCode::Left), Some(Action::Left));
+    }
+
+    let vim = KeyBindings::vim();
+    assert_eq!(vim.action_for(KeyCode::Char('h')), Some(Action::Left));
+    assert_eq!(vim.action_for(KeyCode::Char('i')), Some(Action::Hint));
+
+    let wasd = KeyBindings::wasd();
+    assert_eq!(wasd.action_for(KeyCode::Char('a')), Some(Action::Left));
+    assert_eq!(wasd.action_for(KeyCode::Char('v')), Some(Action::Analyze));
+
+    let numpad = KeyBindings::numpad();
+    assert_eq!(numpad.action_for(KeyCode::Char('8')), Some(Action::Up));
+    assert_eq!(numpad.action_for(KeyCode::Char('5')), Some(Action::Select));
+}
+
+#[test]
+fn test_rebinding_takes_the_key_from_its_old_action() {
+    let keys = KeyBindings::default().with(Action::Quit, &[KeyCode::Char('c')]);
+
+    assert_eq!(keys.action_for(KeyCode::Char('c')), Some(Action::Quit));
+    assert_eq!(keys.action_for(KeyCode::Esc), None);
+    assert!(keys.keys_for(Action::Coach).is_empty());
+    assert_eq!(keys.label(Action::Coach), "unbound");
+}
+
+#[test]
+fn test_keys_file_picks_preset_and_rebinds_actions() {
+    let text = "# my keys\npreset: wasd\nquit: esc, й\nhint: F1\n";
+
+    let (keys, warnings) = KeyBindings::parse(text);
+
+    assert!(warnings.is_empty());
+    assert_eq!(keys.action_for(KeyCode::Char('й')), Some(Action::Quit));
+    assert_eq!(keys.action_for(KeyCode::Char('Й')), Some(Action::Quit));
+    assert_eq!(keys.action_for(KeyCode::Char('q')), None);
+    assert_eq!(keys.action_for(KeyCode::F(1)), Some(Action::Hint));
+    assert_eq!(keys.action_for(KeyCode::Char('w')), Some(Action::Up));
+}
+
+#[test]
+fn test_preset_from_config_replaces_the_files_preset() {
+    let text = "preset: wasd\nhint: F1\n";
+
+    let (keys, warnings) = KeyBindings::parse_with_preset(text, Some("vim"));
+
+    assert!(warnings.is_empty());
+    assert_eq!(keys.action_for(KeyCode::Char('k')), Some(Action::Up));
+    assert_eq!(keys.action_for(KeyCode::Char('w')), None);
+    assert_eq!(keys.action_for(KeyCode::F(1)), Some(Action::Hint));
+    assert_eq!(keys.action_for(KeyCode::Char('o')), Some(Action::Settings));
+}
+
+#[test]
+fn test_keys_file_reports_bad_lines_and_keeps_the_rest() {
+    let text = "preset: emacs\njump: j\nhint: ctrl-h, x\n";
+
+    let (keys, warnings) = KeyBindings::parse(text);
+
+    assert_eq!(
+        warnings,
+        vec![
+            KeyError::UnknownPreset("emacs".to_string()),
+            KeyError::UnknownAction("jump".to_string()),
+            KeyError::UnknownKey {
+                action: "hint".to_string(),
+                key: "ctrl-h".to_string()
+            },
+        ]
+    );
+    assert_eq!(keys.keys_for(Action::Hint), &[KeyCode::Char('x')]);
+    assert_eq!(keys.action_for(KeyCode::Up), Some(Action::Up));
+}
+
+#[test]
+fn test_keys_load_from_file() {
+    let dir = TempDir::new("keys-load");
+    let path = dir.join("keys.txt");
+    fs::write(&path, "preset: vim\n").unwrap();
+
+    let (keys, warnings) = KeyBindings::load(Some(&path));
+
+    assert!(warnings.is_empty());
+    assert_eq!(keys, KeyBindings::vim());
+    assert_eq!(KeyBindings::load(None).0, KeyBindings::default());
+}
+
+#[test]
+fn test_key_names() {
+    assert_eq!(parse_key("PageUp"), Some(KeyCode::PageUp));
+    assert_eq!(parse_key("space"), Some(KeyCode::Char(' ')));
+    assert_eq!(parse_key("F12"), Some(KeyCode::F(12)));
+    assert_eq!(parse_key("?"), Some(KeyCode::Char('?')));
+    assert_eq!(parse_key("fx"), None);
+
+    assert_eq!(key_label(KeyCode::Char('h')), "H");
+    assert_eq!(key_label(KeyCode::Char('G')), "Shift+G");
+    assert_eq!(KeyBindings::default().label(Action::Quit), "ESC/Q");
+}
+
+#[test]
+fn test_help_lists_every_action() {
+    let help = KeyBindings::vim().describe();
+
+    assert_eq!(help.len(), Action::ALL.len());
+    assert!(help.contains(&("K/↑".to_string(), Action::Up.description())));
+}
+
+#[test]
+fn test_text_entry_keys_skip_typed_characters() {
+    let bindings = KeyBindings::arrows();
+
+    // `:` also opens the command line, but types itself while entering text
+    assert_eq!(bindings.primary_label(Action::Command), ":");
+    assert_eq!(bindings.entry_label(Action::Command), "Tab");
+    assert_eq!(
+        bindings.entry_action_for(KeyCode::Tab),
+        Some(Action::Command)
+    );
+    assert_eq!(bindings.entry_action_for(KeyCode::Esc), Some(Action::Quit));
+    assert_eq!(bindings.entry_action_for(KeyCode::Up), None);
+}
@@ -0,0 +1,7 @@
+mod animation_test;
+mod board_test;
+mod game_over_screen_test;
+mod keymap_test;
+mod mouse_test;
+mod move_list_test;
+mod theme_test;
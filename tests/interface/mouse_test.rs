@@ -0,0 +1,113 @@
+use checkers_rs::interface::mouse::MouseGesture;
+use checkers_rs::interface::widgets::board::BoardScale;
+use checkers_rs::interface::widgets::CheckerBoard;
+use ratatui::layout::Rect;
+
+// The game view gives the board a 64x18 area
+const BOARD_AREA: Rect = Rect {
+    x: 8,
+    y: 3,
+    width: 64,
+    height: 18,
+};
+
+const CELL_WIDTH: u16 = BoardScale::Normal.cell_size().0;
+
+/// Terminal cell in the middle of a square's content
+fn cell_of(square: (usize, usize)) -> (u16, u16) {
+    let scale = BoardScale::Normal;
+    let (cell_width, cell_height) = scale.cell_size();
+    let x_start = BOARD_AREA.x + (64 - scale.grid_size(8).0) / 2;
+    let y_start = BOARD_AREA.y;
+    (
+        x_start + scale.label_width() + square.1 as u16 * cell_width + cell_width / 2,
+        y_start + 2 + square.0 as u16 * cell_height,
+    )
+}
+
+#[test]
+fn test_square_at_maps_cells_to_squares() {
+    for square in [(0, 0), (0, 7), (5, 2), (7, 7)] {
+        let (column, row) = cell_of(square);
+        assert_eq!(
+            CheckerBoard::square_at(BOARD_AREA, 8, false, column, row),
+            Some(square)
+        );
+    }
+}
+
+#[test]
+fn test_square_at_follows_a_flipped_board() {
+    for (shown, square) in [((0, 0), (7, 7)), ((5, 2), (2, 5)), ((7, 6), (0, 1))] {
+        let (column, row) = cell_of(shown);
+        assert_eq!(
+            CheckerBoard::square_at(BOARD_AREA, 8, true, column, row),
+            Some(square)
+        );
+    }
+}
+
+#[test]
+fn test_square_at_ignores_labels_and_outside() {
+    let (column, row) = cell_of((0, 0));
+
+    // Row label column and column label row
+    assert_eq!(
+        CheckerBoard::square_at(BOARD_AREA, 8, false, column - CELL_WIDTH, row),
+        None
+    );
+    assert_eq!(
+        CheckerBoard::square_at(BOARD_AREA, 8, false, column, row - 2),
+        None
+    );
+    // Past the last column
+    let (column, row) = cell_of((3, 7));
+    assert_eq!(
+        CheckerBoard::square_at(BOARD_AREA, 8, false, column + CELL_WIDTH, row),
+        None
+    );
+    // Area too small for the board
+    let small = Rect::new(0, 0, 10, 6);
+    assert_eq!(CheckerBoard::square_at(small, 8, false, 5, 5), None);
+}
+
+#[test]
+fn test_click_selects_on_press() {
+    let mut gesture = MouseGesture::new();
+
+    assert_eq!(gesture.press((5, 2), None), Some((5, 2)));
+    assert_eq!(gesture.release((5, 2)), None);
+}
+
+#[test]
+fn test_drag_drops_on_release() {
+    let mut gesture = MouseGesture::new();
+
+    assert_eq!(gesture.press((5, 2), None), Some((5, 2)));
+    assert_eq!(gesture.release((4, 3)), Some((4, 3)));
+}
+
+#[test]
+fn test_dragging_selected_piece_does_not_deselect_it() {
+    let mut gesture = MouseGesture::new();
+
+    assert_eq!(gesture.press((5, 2), Some((5, 2))), None);
+    assert_eq!(gesture.release((4, 3)), Some((4, 3)));
+}
+
+#[test]
+fn test_clicking_selected_piece_deselects_on_release() {
+    let mut gesture = MouseGesture::new();
+
+    assert_eq!(gesture.press((5, 2), Some((5, 2))), None);
+    assert_eq!(gesture.release((5, 2)), Some((5, 2)));
+}
+
+#[test]
+fn test_release_without_press_is_ignored() {
+    let mut gesture = MouseGesture::new();
+    gesture.press((5, 2), None);
+    gesture.cancel();
+
+    assert_eq!(gesture.release((4, 3)), None);
+}
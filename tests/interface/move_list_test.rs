@@ -0,0 +1,46 @@
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::move_history::MoveHistory;
+use checkers_rs::core::piece::Color;
+use checkers_rs::core::Turn;
+use checkers_rs::interface::widgets::MoveList;
+
+fn play(turns: &[&[(usize, usize)]]) -> CheckersGame {
+    turns.iter().fold(CheckersGame::new(), |game, path| {
+        game.apply_turn(&Turn::new(path.to_vec())).unwrap()
+    })
+}
+
+#[test]
+fn test_rows_pair_white_and_black_moves() {
+    let game = play(&[&[(5, 0), (4, 1)], &[(2, 1), (3, 2)], &[(5, 2), (4, 3)]]);
+
+    let rows = MoveList::rows(&game.move_history, 8);
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], "  1. a3-b4    b6-c5");
+    assert_eq!(rows[1].trim_end(), "  2. c3-d4");
+}
+
+#[test]
+fn test_rows_pad_a_leading_black_move() {
+    let mut history = MoveHistory::new();
+    history.add_move((2, 1), (3, 2), Color::Black, vec![], false);
+
+    let rows = MoveList::rows(&history, 8);
+
+    assert_eq!(rows, vec!["  1. ...      b6-c5".to_string()]);
+}
+
+#[test]
+fn test_summary_counts_captures_and_kings() {
+    let mut game = CheckersGame::new();
+    game.move_history
+        .add_move((5, 0), (3, 2), Color::White, vec![(4, 1)], false);
+    if let Some(piece) = game.board.cells[7][0].as_mut() {
+        piece.is_king = true;
+    }
+
+    let summary = MoveList::summary(&game.board, &game.move_history);
+
+    assert_eq!(summary, "Moves 1  •  Captured W 1 B 0  •  Kings W 1 B 0");
+}
@@ -0,0 +1,109 @@
+use std::fs;
+
+use checkers_rs::interface::theme::{supports_truecolor, Theme, ThemeError, PRESETS};
+use ratatui::style::Color;
+
+use crate::common::TempDir;
+
+#[test]
+fn test_every_preset_loads_by_name() {
+    for name in PRESETS {
+        assert!(Theme::preset(name).is_some(), "{name}");
+    }
+    assert_eq!(Theme::preset("High-Contrast"), Some(Theme::high_contrast()));
+    assert!(Theme::preset("neon").is_none());
+}
+
+#[test]
+fn test_monochrome_sticks_to_sixteen_colors() {
+    let theme = Theme::monochrome();
+    for color in [
+        theme.border,
+        theme.text_primary,
+        theme.text_accent,
+        theme.piece_black,
+        theme.piece_white,
+        theme.possible_move,
+        theme.logo,
+    ] {
+        assert!(!matches!(color, Color::Rgb(..) | Color::Indexed(_)));
+    }
+}
+
+#[test]
+fn test_theme_file_picks_preset_and_overrides_colors() {
+    let text = "# my colors\npreset: light\ntext_accent: #3070a0\npiece_white: red\n";
+
+    let (theme, warnings) = Theme::parse(text, true);
+
+    assert!(warnings.is_empty());
+    assert_eq!(theme.text_accent, Color::Rgb(0x30, 0x70, 0xa0));
+    assert_eq!(theme.piece_white, Color::Red);
+    assert_eq!(theme.text_primary, Theme::light().text_primary);
+}
+
+#[test]
+fn test_preset_from_config_replaces_the_files_preset() {
+    let text = "preset: light\npiece_white: red\n";
+
+    let (theme, warnings) = Theme::parse_with_preset(text, Some("high-contrast"), true);
+
+    assert!(warnings.is_empty());
+    assert_eq!(theme.piece_white, Color::Red);
+    assert_eq!(theme.text_primary, Theme::high_contrast().text_primary);
+}
+
+#[test]
+fn test_theme_file_reports_bad_lines_and_keeps_the_rest() {
+    let text = "preset: neon\nborder: blurple\nsparkles: yellow\nlogo: cyan\n";
+
+    let (theme, warnings) = Theme::parse(text, true);
+
+    assert_eq!(
+        warnings,
+        vec![
+            ThemeError::UnknownPreset("neon".to_string()),
+            ThemeError::BadColor {
+                key: "border".to_string(),
+                value: "blurple".to_string()
+            },
+            ThemeError::UnknownKey("sparkles".to_string()),
+        ]
+    );
+    assert_eq!(theme.border, Theme::classic().border);
+    assert_eq!(theme.logo, Color::Cyan);
+}
+
+#[test]
+fn test_without_truecolor_the_default_is_monochrome() {
+    assert_eq!(Theme::load(None, false).0, Theme::monochrome());
+    assert_eq!(Theme::load(None, true).0, Theme::classic());
+
+    // An explicit preset wins over detection
+    let (theme, _) = Theme::parse("preset: high-contrast", false);
+    assert_eq!(theme, Theme::high_contrast());
+}
+
+#[test]
+fn test_theme_loads_from_file() {
+    let dir = TempDir::new("theme-load");
+    let path = dir.join("theme.txt");
+    fs::write(&path, "preset: monochrome\n").unwrap();
+
+    let (theme, warnings) = Theme::load(Some(&path), true);
+
+    assert!(warnings.is_empty());
+    assert_eq!(theme, Theme::monochrome());
+}
+
+#[test]
+fn test_truecolor_detection() {
+    assert!(supports_truecolor(
+        Some("truecolor"),
+        Some("xterm-256color")
+    ));
+    assert!(supports_truecolor(Some("24bit"), None));
+    assert!(supports_truecolor(None, Some("xterm-direct")));
+    assert!(!supports_truecolor(None, Some("xterm-256color")));
+    assert!(!supports_truecolor(None, Some("linux")));
+}
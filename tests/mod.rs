@@ -1,5 +1,15 @@
+mod ai;
+mod cli_test;
+mod common;
+mod config_test;
 mod coordinate_format_test;
 mod core;
 mod deselection_test;
+mod interface;
+mod net;
+#[cfg(feature = "server")]
+mod server_test;
 mod state;
+mod text_protocol_test;
 mod two_player_mode_test;
+mod utils;
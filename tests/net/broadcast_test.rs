@@ -0,0 +1,165 @@
+use std::time::{Duration, Instant};
+
+use checkers_rs::core::clock::{Bonus, Clock, TimeControl};
+use checkers_rs::core::piece::Color;
+use checkers_rs::net::broadcast::{
+    follow, take_broadcast_flag, Broadcast, Snapshot, Watch, WatchEvent, Watching,
+    BROADCAST_VERSION,
+};
+use checkers_rs::net::{NetError, NetRole};
+use checkers_rs::state::GameSession;
+
+async fn next(watch: &mut Watch) -> WatchEvent {
+    tokio::time::timeout(Duration::from_secs(5), watch.recv())
+        .await
+        .expect("no event in time")
+        .expect("watch closed")
+}
+
+async fn next_snapshot(watch: &mut Watch) -> Snapshot {
+    loop {
+        match next(watch).await {
+            WatchEvent::Snapshot(snapshot) => return snapshot,
+            WatchEvent::Connected(_) => {}
+            other => panic!("expected a snapshot, got {other:?}"),
+        }
+    }
+}
+
+/// A session two steps into the game
+fn played() -> GameSession {
+    let mut session = GameSession::new();
+    session.game = session.game.make_move_coords(5, 0, 4, 1).unwrap().0;
+    session.game = session.game.make_move_coords(2, 3, 3, 2).unwrap().0;
+    session
+}
+
+#[test]
+fn test_snapshot_carries_moves_clock_and_result() {
+    let now = Instant::now();
+    let mut session = played();
+    let control = TimeControl::new(
+        Duration::from_secs(60),
+        Bonus::Delay(Duration::from_secs(2)),
+    );
+    let mut clock = Clock::new(control);
+    clock.start(Color::White, now);
+    session.clock = Some(clock);
+
+    let later = now + Duration::from_secs(5);
+    let snapshot = Snapshot::of(&session, later);
+    assert_eq!(snapshot.moves, vec![((5, 0), (4, 1)), ((2, 3), (3, 2))]);
+    assert!(!snapshot.finished);
+    let clocks = snapshot.clock.as_ref().unwrap();
+    assert_eq!((clocks.white_ms, clocks.running_ms), (60_000, 5_000));
+    assert_eq!(clocks.delay_ms, 2_000);
+
+    // The watcher's clock has run just as far, delay included
+    let copy = clocks.to_clock(later);
+    assert_eq!(copy.left(Color::White, later), Duration::from_secs(57));
+    assert_eq!(copy.running_side(), Some(Color::White));
+
+    let aged = snapshot.aged(Duration::from_secs(1));
+    assert_eq!(aged.clock.as_ref().unwrap().running_ms, 6_000);
+    assert!(aged.same_moment(&snapshot));
+    assert_eq!(Snapshot::decode(&snapshot.encode()).unwrap(), snapshot);
+
+    let stale = snapshot.encode().replace(
+        &format!("\"version\":{BROADCAST_VERSION}"),
+        "\"version\":99",
+    );
+    assert!(matches!(
+        Snapshot::decode(&stale),
+        Err(NetError::VersionMismatch { theirs: 99, .. })
+    ));
+}
+
+#[test]
+fn test_watcher_follows_snapshots_without_playing() {
+    let mut watcher = GameSession::new();
+    watcher.watching = Some(Watching::new("Connecting..."));
+    assert_eq!(watcher.ai_seat(), None);
+    assert!(!watcher.is_local_turn());
+
+    let snapshot = Snapshot::of(&played(), Instant::now());
+    let watcher = follow(&watcher, &snapshot, Instant::now());
+    assert_eq!(watcher.game.move_history.moves().len(), 2);
+    assert_eq!(watcher.game.current_player, Color::White);
+    assert_eq!(watcher.watching.as_ref().unwrap().result, None);
+
+    let mut finished = snapshot.clone();
+    finished.finished = true;
+    finished.winner = Some(Color::Black);
+    let watcher = follow(&watcher, &finished, Instant::now());
+    assert!(watcher.game.is_game_over);
+    assert_eq!(
+        watcher.watching.as_ref().unwrap().result,
+        Some(Some(Color::Black))
+    );
+
+    let mut broken = snapshot;
+    broken.moves.push(((0, 0), (7, 7)));
+    let unchanged = follow(&watcher, &broken, Instant::now());
+    assert_eq!(unchanged.game.move_history.moves().len(), 2);
+    assert!(unchanged.watching.unwrap().notice.is_some());
+}
+
+#[tokio::test]
+async fn test_watchers_get_the_latest_snapshot_and_every_change() {
+    let mut broadcast = Broadcast::start("127.0.0.1:0").await.unwrap();
+    let mut session = GameSession::new();
+    broadcast.publish(Snapshot::of(&session, Instant::now()));
+
+    let mut first = Watch::connect(broadcast.address());
+    assert!(next_snapshot(&mut first).await.moves.is_empty());
+
+    session.game = session.game.make_move_coords(5, 0, 4, 1).unwrap().0;
+    broadcast.publish(Snapshot::of(&session, Instant::now()));
+    assert_eq!(next_snapshot(&mut first).await.moves.len(), 1);
+    // Nothing new to show, so nothing is sent
+    broadcast.publish(Snapshot::of(&session, Instant::now()));
+
+    let mut late = Watch::connect(broadcast.address());
+    assert_eq!(next_snapshot(&mut late).await.moves.len(), 1);
+    assert_eq!(broadcast.watchers(), 2);
+
+    session.game = session.game.make_move_coords(2, 3, 3, 2).unwrap().0;
+    broadcast.publish(Snapshot::of(&session, Instant::now()));
+    assert_eq!(next_snapshot(&mut first).await.moves.len(), 2);
+    assert_eq!(next_snapshot(&mut late).await.moves.len(), 2);
+
+    drop(broadcast);
+    match next(&mut first).await {
+        WatchEvent::Disconnected(_) => {}
+        other => panic!("expected the broadcast to end, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_command_line_broadcast_and_watch() {
+    let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        take_broadcast_flag(args(&["--broadcast", "--host"])),
+        (Some("127.0.0.1".to_string()), args(&["--host"]))
+    );
+    assert_eq!(
+        take_broadcast_flag(args(&[
+            "--join",
+            "example.org",
+            "--broadcast",
+            "0.0.0.0:9100"
+        ])),
+        (
+            Some("0.0.0.0:9100".to_string()),
+            args(&["--join", "example.org"])
+        )
+    );
+    assert_eq!(take_broadcast_flag(args(&[])), (None, Vec::new()));
+    assert_eq!(
+        NetRole::from_args(args(&["--watch", "10.0.0.2"])),
+        Ok(Some(NetRole::Watch {
+            address: "10.0.0.2".to_string()
+        }))
+    );
+}
@@ -0,0 +1,331 @@
+use std::time::Duration;
+
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::Color;
+use checkers_rs::net::dxp::{
+    back_request_turns, read_dxp, take_back, turn_from_dxp, turn_to_dxp, write_dxp, Acceptance,
+    DxpMessage, EndReason, DXP_VERSION,
+};
+use checkers_rs::net::dxp_engine;
+use checkers_rs::net::protocol::Message;
+use checkers_rs::net::{Link, NetError, NetEvent};
+use tokio::io::BufReader;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+
+async fn next(link: &mut Link) -> NetEvent {
+    tokio::time::timeout(Duration::from_secs(5), link.recv())
+        .await
+        .expect("no event in time")
+        .expect("link closed")
+}
+
+/// One end of a DXP connection, played by hand in place of an engine
+struct StandIn {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl StandIn {
+    fn new(stream: TcpStream) -> Self {
+        let (read, writer) = stream.into_split();
+        Self {
+            reader: BufReader::new(read),
+            writer,
+        }
+    }
+
+    async fn send(&mut self, message: DxpMessage) {
+        write_dxp(&mut self.writer, &message).await.unwrap();
+    }
+
+    async fn receive(&mut self) -> DxpMessage {
+        tokio::time::timeout(Duration::from_secs(5), read_dxp(&mut self.reader))
+            .await
+            .expect("no message in time")
+            .unwrap()
+            .expect("connection closed")
+    }
+}
+
+fn request(follower: Color, version: u32) -> DxpMessage {
+    DxpMessage::GameRequest {
+        version,
+        name: "stand-in".to_string(),
+        follower,
+        minutes: 1,
+        moves: 600,
+    }
+}
+
+#[test]
+fn test_messages_round_trip_through_their_wire_form() {
+    let messages = [
+        request(Color::Black, DXP_VERSION),
+        DxpMessage::GameAccept {
+            name: "engine".to_string(),
+            acceptance: Acceptance::TimeRefused,
+        },
+        DxpMessage::Move {
+            seconds: 12,
+            from: 9,
+            to: 25,
+            captured: vec![13, 22],
+        },
+        DxpMessage::GameEnd {
+            reason: EndReason::Draw,
+            stop: true,
+        },
+        DxpMessage::Chat("good game".to_string()),
+        DxpMessage::BackRequest {
+            move_number: 7,
+            to_move: Color::Black,
+        },
+        DxpMessage::BackAccept { accepted: true },
+    ];
+
+    for message in messages {
+        assert_eq!(DxpMessage::parse(&message.encode()).unwrap(), message);
+    }
+}
+
+#[test]
+fn test_fields_have_fixed_widths() {
+    let request = request(Color::Black, DXP_VERSION).encode();
+    assert_eq!(request.len(), 43);
+    assert!(request.starts_with("R01stand-in "));
+    assert!(request.ends_with("Z001600A"));
+
+    let capture = DxpMessage::Move {
+        seconds: 3,
+        from: 9,
+        to: 18,
+        captured: vec![14],
+    };
+    assert_eq!(capture.encode(), "M000309180114");
+}
+
+#[test]
+fn test_unreadable_messages_are_malformed() {
+    for text in ["", "X", "M12", "R01", "Bxyzw"] {
+        assert!(matches!(
+            DxpMessage::parse(text),
+            Err(NetError::Malformed(_))
+        ));
+    }
+    // Only games from the initial position are played
+    let from_position = request(Color::White, DXP_VERSION)
+        .encode()
+        .replace("600A", "600B");
+    assert!(DxpMessage::parse(&format!("{from_position}W{}", "e".repeat(32))).is_err());
+}
+
+#[test]
+fn test_moves_are_numbered_the_standard_way() {
+    let game = CheckersGame::new();
+    let turn = turn_from_dxp(&game, 21, 17, &[]).expect("21-17 opens the game");
+    assert_eq!(turn.path, vec![(5, 0), (4, 1)]);
+    assert_eq!(
+        turn_to_dxp(&turn, 8, Duration::from_millis(2500)),
+        DxpMessage::Move {
+            seconds: 2,
+            from: 21,
+            to: 17,
+            captured: Vec::new(),
+        }
+    );
+
+    // Black cannot move first, and nothing reaches three rows ahead
+    assert!(turn_from_dxp(&game, 9, 13, &[]).is_none());
+    assert!(turn_from_dxp(&game, 21, 9, &[]).is_none());
+}
+
+#[test]
+fn test_back_requests_count_whole_moves() {
+    assert_eq!(back_request_turns(1, Color::White), 0);
+    assert_eq!(back_request_turns(1, Color::Black), 1);
+    assert_eq!(back_request_turns(3, Color::Black), 5);
+
+    let game = CheckersGame::new();
+    let game = game
+        .apply_turn(&turn_from_dxp(&game, 21, 17, &[]).unwrap())
+        .unwrap();
+    let game = game
+        .apply_turn(&turn_from_dxp(&game, 9, 13, &[]).unwrap())
+        .unwrap();
+    let taken_back = take_back(&game, 1).unwrap();
+    assert_eq!(taken_back.move_history.moves().len(), 1);
+    assert_eq!(taken_back.current_player, Color::Black);
+    assert!(take_back(&game, 3).is_none());
+}
+
+#[tokio::test]
+async fn test_board_plays_a_stand_in_engine_over_localhost() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let mut link = Link::dxp_connect(&address, Color::White, 5, 50);
+    let mut engine = StandIn::new(listener.accept().await.unwrap().0);
+
+    match engine.receive().await {
+        DxpMessage::GameRequest {
+            follower,
+            minutes,
+            moves,
+            ..
+        } => assert_eq!((follower, minutes, moves), (Color::Black, 5, 50)),
+        other => panic!("expected a game request, got {other:?}"),
+    }
+    engine
+        .send(DxpMessage::GameAccept {
+            name: "stand-in".to_string(),
+            acceptance: Acceptance::Accepted,
+        })
+        .await;
+    match next(&mut link).await {
+        NetEvent::Connected { seat, peer } => {
+            assert_eq!(seat, Color::White);
+            assert_eq!(peer, "stand-in");
+        }
+        other => panic!("expected a connection, got {other:?}"),
+    }
+
+    // Steps from the board go out as whole turns
+    link.send(Message::Move {
+        from: (5, 0),
+        to: (4, 1),
+    });
+    assert!(matches!(
+        engine.receive().await,
+        DxpMessage::Move { from: 21, to: 17, ref captured, .. } if captured.is_empty()
+    ));
+
+    engine
+        .send(DxpMessage::Move {
+            seconds: 1,
+            from: 9,
+            to: 13,
+            captured: Vec::new(),
+        })
+        .await;
+    match next(&mut link).await {
+        NetEvent::Received(message) => assert_eq!(
+            message,
+            Message::Move {
+                from: (2, 1),
+                to: (3, 0),
+            }
+        ),
+        other => panic!("expected the engine's move, got {other:?}"),
+    }
+
+    // Taking back the engine's move resyncs the board
+    engine
+        .send(DxpMessage::BackRequest {
+            move_number: 1,
+            to_move: Color::Black,
+        })
+        .await;
+    assert_eq!(
+        engine.receive().await,
+        DxpMessage::BackAccept { accepted: true }
+    );
+    match next(&mut link).await {
+        NetEvent::Received(Message::Sync { moves }) => assert_eq!(moves, vec![((5, 0), (4, 1))]),
+        other => panic!("expected a resync, got {other:?}"),
+    }
+
+    engine.send(DxpMessage::Chat("resigning".to_string())).await;
+    engine
+        .send(DxpMessage::GameEnd {
+            reason: EndReason::ILose,
+            stop: true,
+        })
+        .await;
+    assert!(matches!(
+        next(&mut link).await,
+        NetEvent::Received(Message::Chat { .. })
+    ));
+    assert!(matches!(
+        next(&mut link).await,
+        NetEvent::Received(Message::Resign)
+    ));
+    assert!(matches!(
+        engine.receive().await,
+        DxpMessage::GameEnd {
+            reason: EndReason::IWin,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_board_turns_away_other_protocol_versions() {
+    let link = Link::dxp_listen("127.0.0.1:0").await.unwrap();
+    let mut engine = StandIn::new(TcpStream::connect(link.address()).await.unwrap());
+
+    engine.send(request(Color::White, DXP_VERSION + 1)).await;
+    assert!(matches!(
+        engine.receive().await,
+        DxpMessage::GameAccept {
+            acceptance: Acceptance::VersionUnsupported,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_built_in_engine_answers_a_stand_in_engine() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let follower = tokio::spawn(async move { dxp_engine::follow(&listener).await });
+    let mut initiator = StandIn::new(TcpStream::connect(address).await.unwrap());
+
+    initiator.send(request(Color::Black, DXP_VERSION)).await;
+    assert!(matches!(
+        initiator.receive().await,
+        DxpMessage::GameAccept {
+            acceptance: Acceptance::Accepted,
+            ..
+        }
+    ));
+
+    let game = CheckersGame::new();
+    let game = game
+        .apply_turn(&turn_from_dxp(&game, 21, 17, &[]).unwrap())
+        .unwrap();
+    initiator
+        .send(DxpMessage::Move {
+            seconds: 0,
+            from: 21,
+            to: 17,
+            captured: Vec::new(),
+        })
+        .await;
+    match initiator.receive().await {
+        DxpMessage::Move {
+            from, to, captured, ..
+        } => assert!(turn_from_dxp(&game, from, to, &captured).is_some()),
+        other => panic!("expected the engine's reply, got {other:?}"),
+    }
+
+    initiator
+        .send(DxpMessage::GameEnd {
+            reason: EndReason::ILose,
+            stop: true,
+        })
+        .await;
+    assert!(matches!(
+        initiator.receive().await,
+        DxpMessage::GameEnd {
+            reason: EndReason::IWin,
+            ..
+        }
+    ));
+    drop(initiator);
+
+    let games = follower.await.unwrap().unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].seat, Color::Black);
+    assert_eq!(games[0].result, EndReason::IWin);
+    assert_eq!(games[0].opponent, "stand-in");
+}
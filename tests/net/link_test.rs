@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use checkers_rs::core::piece::Color;
+use checkers_rs::net::protocol::{decode, encode, Message, PROTOCOL_VERSION};
+use checkers_rs::net::{Link, NetError, NetEvent};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+async fn next(link: &mut Link) -> NetEvent {
+    tokio::time::timeout(Duration::from_secs(5), link.recv())
+        .await
+        .expect("no event in time")
+        .expect("link closed")
+}
+
+/// Says hello the way a guest does and returns the host's answer
+async fn hello(address: &str, token: Option<String>) -> (TcpStream, Message) {
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    let hello = Message::Hello {
+        version: PROTOCOL_VERSION,
+        token,
+    };
+    stream.write_all(encode(&hello).as_bytes()).await.unwrap();
+    let mut line = String::new();
+    BufReader::new(&mut stream)
+        .read_line(&mut line)
+        .await
+        .unwrap();
+    (stream, decode(&line).unwrap())
+}
+
+#[tokio::test]
+async fn test_host_and_guest_connect_and_talk_over_localhost() {
+    let mut host = Link::host("127.0.0.1:0", Color::White).await.unwrap();
+    let mut guest = Link::join(host.address());
+
+    match next(&mut host).await {
+        NetEvent::Connected { seat, .. } => assert_eq!(seat, Color::White),
+        other => panic!("expected a connection, got {other:?}"),
+    }
+    match next(&mut guest).await {
+        NetEvent::Connected { seat, .. } => assert_eq!(seat, Color::Black),
+        other => panic!("expected a connection, got {other:?}"),
+    }
+
+    let step = Message::Move {
+        from: (5, 0),
+        to: (4, 1),
+    };
+    host.send(step.clone());
+    match next(&mut guest).await {
+        NetEvent::Received(message) => assert_eq!(message, step),
+        other => panic!("expected the move, got {other:?}"),
+    }
+
+    guest.send(Message::Chat {
+        text: "hi".to_string(),
+    });
+    match next(&mut host).await {
+        NetEvent::Received(Message::Chat { text }) => assert_eq!(text, "hi"),
+        other => panic!("expected chat, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_host_seats_the_guest_opposite_itself() {
+    let host = Link::host("127.0.0.1:0", Color::Black).await.unwrap();
+
+    let (_stream, welcome) = hello(host.address(), None).await;
+
+    assert!(matches!(
+        welcome,
+        Message::Welcome {
+            seat: Color::White,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_only_the_first_guest_may_reconnect() {
+    let mut host = Link::host("127.0.0.1:0", Color::White).await.unwrap();
+
+    let (stream, welcome) = hello(host.address(), None).await;
+    let Message::Welcome { token, .. } = welcome else {
+        panic!("expected welcome, got {welcome:?}");
+    };
+    assert!(matches!(next(&mut host).await, NetEvent::Connected { .. }));
+
+    drop(stream);
+    assert!(matches!(next(&mut host).await, NetEvent::Disconnected(_)));
+
+    let (_stranger, answer) = hello(host.address(), None).await;
+    assert!(matches!(answer, Message::Reject { .. }));
+
+    let (_back, answer) = hello(host.address(), Some(token)).await;
+    assert!(matches!(answer, Message::Welcome { .. }));
+    assert!(matches!(next(&mut host).await, NetEvent::Connected { .. }));
+}
+
+#[tokio::test]
+async fn test_guest_reconnects_with_its_token_after_the_connection_drops() {
+    // A bare listener stands in for the host so the connection can be cut
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut guest = Link::join(&listener.local_addr().unwrap().to_string());
+
+    let welcome = |token: &str| Message::Welcome {
+        version: PROTOCOL_VERSION,
+        seat: Color::Black,
+        token: token.to_string(),
+    };
+    let (mut stream, _) = listener.accept().await.unwrap();
+    let mut line = String::new();
+    BufReader::new(&mut stream)
+        .read_line(&mut line)
+        .await
+        .unwrap();
+    assert_eq!(
+        decode(&line).unwrap(),
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: None
+        }
+    );
+    stream
+        .write_all(encode(&welcome("t1")).as_bytes())
+        .await
+        .unwrap();
+    assert!(matches!(next(&mut guest).await, NetEvent::Connected { .. }));
+
+    drop(stream);
+    assert!(matches!(next(&mut guest).await, NetEvent::Disconnected(_)));
+
+    let (mut stream, _) = listener.accept().await.unwrap();
+    let mut line = String::new();
+    BufReader::new(&mut stream)
+        .read_line(&mut line)
+        .await
+        .unwrap();
+    assert_eq!(
+        decode(&line).unwrap(),
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: Some("t1".to_string())
+        }
+    );
+    stream
+        .write_all(encode(&welcome("t1")).as_bytes())
+        .await
+        .unwrap();
+    assert!(matches!(next(&mut guest).await, NetEvent::Connected { .. }));
+}
+
+#[tokio::test]
+async fn test_a_second_guest_is_turned_away() {
+    let mut host = Link::host("127.0.0.1:0", Color::White).await.unwrap();
+    let guest = Link::join(host.address());
+    assert!(matches!(next(&mut host).await, NetEvent::Connected { .. }));
+
+    // Once the first guest is gone, a newcomer without its token is refused
+    drop(guest);
+    assert!(matches!(next(&mut host).await, NetEvent::Disconnected(_)));
+    let mut stranger = Link::join(host.address());
+    match next(&mut stranger).await {
+        NetEvent::Failed(NetError::Rejected(_)) => {}
+        other => panic!("expected a rejection, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_mismatched_versions_are_rejected() {
+    let host = Link::host("127.0.0.1:0", Color::White).await.unwrap();
+    let mut stream = TcpStream::connect(host.address()).await.unwrap();
+    let hello = Message::Hello {
+        version: PROTOCOL_VERSION + 1,
+        token: None,
+    };
+    stream.write_all(encode(&hello).as_bytes()).await.unwrap();
+
+    let mut line = String::new();
+    BufReader::new(&mut stream)
+        .read_line(&mut line)
+        .await
+        .unwrap();
+
+    assert!(matches!(decode(&line).unwrap(), Message::Reject { .. }));
+}
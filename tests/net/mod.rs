@@ -0,0 +1,5 @@
+mod broadcast_test;
+mod dxp_test;
+mod link_test;
+mod protocol_test;
+mod remote_test;
@@ -0,0 +1,160 @@
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::net::protocol::{
+    apply_remote_step, decode, encode, replay_steps, steps, Message, PROTOCOL_VERSION,
+};
+use checkers_rs::net::{NetError, NetRole};
+
+#[test]
+fn test_messages_travel_as_tagged_json_lines() {
+    let message = Message::Move {
+        from: (5, 0),
+        to: (4, 1),
+    };
+
+    let line = encode(&message);
+
+    assert!(line.ends_with('\n'));
+    assert_eq!(line.matches('\n').count(), 1);
+    assert!(line.contains("\"type\":\"move\""));
+    assert_eq!(decode(&line).unwrap(), message);
+}
+
+#[test]
+fn test_every_message_survives_the_round_trip() {
+    let messages = [
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            token: Some("abc".to_string()),
+        },
+        Message::Welcome {
+            version: PROTOCOL_VERSION,
+            seat: Color::Black,
+            token: "abc".to_string(),
+        },
+        Message::Reject {
+            reason: "full".to_string(),
+        },
+        Message::Sync {
+            moves: vec![((5, 0), (4, 1))],
+        },
+        Message::DrawOffer,
+        Message::DrawAccept,
+        Message::DrawDecline,
+        Message::Resign,
+        Message::Chat {
+            text: "good luck".to_string(),
+        },
+        Message::Error {
+            reason: "huh".to_string(),
+        },
+    ];
+
+    for message in messages {
+        assert_eq!(decode(&encode(&message)).unwrap(), message);
+    }
+}
+
+#[test]
+fn test_garbage_is_malformed() {
+    assert!(matches!(decode("hello"), Err(NetError::Malformed(_))));
+    assert!(matches!(
+        decode("{\"type\":\"teleport\"}"),
+        Err(NetError::Malformed(_))
+    ));
+}
+
+#[test]
+fn test_remote_step_is_played_through_the_rules() {
+    let game = CheckersGame::new();
+
+    let played = apply_remote_step(&game, Color::White, ((5, 0), (4, 1))).unwrap();
+
+    assert!(played.board.get_piece(4, 1).is_some());
+    assert_eq!(played.current_player, Color::Black);
+}
+
+#[test]
+fn test_remote_step_out_of_turn_is_refused() {
+    let game = CheckersGame::new();
+
+    let result = apply_remote_step(&game, Color::Black, ((2, 1), (3, 0)));
+
+    assert!(matches!(result, Err(NetError::NotTheirTurn(Color::Black))));
+}
+
+#[test]
+fn test_remote_step_must_be_legal_and_own_piece() {
+    let game = CheckersGame::new();
+
+    // Two squares forward without a capture
+    assert!(matches!(
+        apply_remote_step(&game, Color::White, ((5, 0), (3, 2))),
+        Err(NetError::IllegalMove { .. })
+    ));
+    // An empty square
+    assert!(matches!(
+        apply_remote_step(&game, Color::White, ((4, 1), (3, 2))),
+        Err(NetError::IllegalMove { .. })
+    ));
+}
+
+#[test]
+fn test_multi_jump_must_continue_with_the_same_piece() {
+    let mut game = CheckersGame::new();
+    game.board.cells = vec![vec![None; 8]; 8];
+    game.board.cells[6][1] = Some(Piece::new(Color::White));
+    game.board.cells[6][5] = Some(Piece::new(Color::White));
+    game.board.cells[5][2] = Some(Piece::new(Color::Black));
+    game.board.cells[3][4] = Some(Piece::new(Color::Black));
+    game.board.cells[5][6] = Some(Piece::new(Color::Black));
+
+    let jumped = apply_remote_step(&game, Color::White, ((6, 1), (4, 3))).unwrap();
+    assert_eq!(jumped.current_player, Color::White);
+
+    assert!(apply_remote_step(&jumped, Color::White, ((6, 5), (4, 7))).is_err());
+    assert!(apply_remote_step(&jumped, Color::White, ((4, 3), (2, 5))).is_ok());
+}
+
+#[test]
+fn test_replaying_steps_rebuilds_the_game() {
+    let game = CheckersGame::new();
+    let game = game.make_move_coords(5, 0, 4, 1).unwrap().0;
+    let game = game.make_move_coords(2, 3, 3, 2).unwrap().0;
+
+    let replayed = replay_steps(&steps(&game.move_history)).unwrap();
+
+    assert_eq!(replayed.board.cells, game.board.cells);
+    assert_eq!(replayed.current_player, game.current_player);
+    assert!(replay_steps(&[((2, 1), (3, 0))]).is_err());
+}
+
+#[test]
+fn test_command_line_roles() {
+    let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(NetRole::from_args(args(&[])), Ok(None));
+    assert_eq!(
+        NetRole::from_args(args(&["--host"])),
+        Ok(Some(NetRole::Host {
+            address: "0.0.0.0".to_string(),
+            seat: Color::White
+        }))
+    );
+    assert_eq!(
+        NetRole::from_args(args(&["--host", "127.0.0.1:9000", "--seat", "black"])),
+        Ok(Some(NetRole::Host {
+            address: "127.0.0.1:9000".to_string(),
+            seat: Color::Black
+        }))
+    );
+    assert_eq!(
+        NetRole::from_args(args(&["--join", "example.org"])),
+        Ok(Some(NetRole::Join {
+            address: "example.org".to_string()
+        }))
+    );
+    assert!(NetRole::from_args(args(&["--join"])).is_err());
+    assert!(NetRole::from_args(args(&["--seat", "red"])).is_err());
+    assert!(NetRole::from_args(args(&["--fast"])).is_err());
+}
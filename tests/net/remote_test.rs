@@ -0,0 +1,164 @@
+use checkers_rs::core::piece::Color;
+use checkers_rs::net::protocol::Message;
+use checkers_rs::net::remote::{command, outcome, receive, RemoteResult};
+use checkers_rs::net::RemotePlay;
+use checkers_rs::state::GameSession;
+
+/// A session at this end playing White against Black over the network
+fn session(hosting: bool) -> GameSession {
+    let mut session = GameSession::new();
+    session.remote = Some(RemotePlay::new(Color::Black, hosting));
+    session
+}
+
+fn remote(session: &GameSession) -> &RemotePlay {
+    session.remote.as_ref().unwrap()
+}
+
+#[test]
+fn test_only_the_local_side_moves_from_the_keyboard() {
+    let mut session = session(true);
+    assert!(session.is_local_turn());
+    assert_eq!(session.ai_seat(), None);
+
+    session.game = session.game.make_move_coords(5, 0, 4, 1).unwrap().0;
+    assert!(!session.is_local_turn());
+    assert_eq!(GameSession::new().ai_seat(), Some(Color::Black));
+}
+
+#[test]
+fn test_received_move_is_applied() {
+    let mut session = session(true);
+    session.game = session.game.make_move_coords(5, 0, 4, 1).unwrap().0;
+
+    let session = receive(
+        &session,
+        Message::Move {
+            from: (2, 1),
+            to: (3, 0),
+        },
+    );
+
+    assert!(session.game.board.get_piece(3, 0).is_some());
+    assert_eq!(session.game.current_player, Color::White);
+    assert!(remote(&session).outbox.is_empty());
+}
+
+#[test]
+fn test_illegal_move_is_refused_and_the_host_resyncs() {
+    let session = session(true);
+
+    let session = receive(
+        &session,
+        Message::Move {
+            from: (2, 1),
+            to: (3, 0),
+        },
+    );
+
+    assert!(session.game.board.get_piece(3, 0).is_none());
+    assert!(remote(&session).notice.is_some());
+    assert!(matches!(
+        remote(&session).outbox.as_slice(),
+        [Message::Error { .. }, Message::Sync { .. }]
+    ));
+}
+
+#[test]
+fn test_guest_catches_up_with_the_hosts_game() {
+    let mut guest = session(false);
+    guest.remote = Some(RemotePlay::new(Color::White, false));
+
+    let guest = receive(
+        &guest,
+        Message::Sync {
+            moves: vec![((5, 0), (4, 1))],
+        },
+    );
+
+    assert!(guest.game.board.get_piece(4, 1).is_some());
+    assert_eq!(guest.game.current_player, Color::Black);
+    assert!(guest.is_local_turn());
+}
+
+#[test]
+fn test_host_ignores_sync() {
+    let host = receive(
+        &session(true),
+        Message::Sync {
+            moves: vec![((5, 0), (4, 1))],
+        },
+    );
+
+    assert!(host.game.board.get_piece(4, 1).is_none());
+}
+
+#[test]
+fn test_chat_goes_both_ways() {
+    let session = command(&session(true), "/say  good luck ").unwrap();
+    assert_eq!(
+        remote(&session).outbox,
+        vec![Message::Chat {
+            text: "good luck".to_string()
+        }]
+    );
+
+    let session = receive(
+        &session,
+        Message::Chat {
+            text: "you too".to_string(),
+        },
+    );
+    assert_eq!(
+        remote(&session).chat,
+        vec![
+            (Color::White, "good luck".to_string()),
+            (Color::Black, "you too".to_string())
+        ]
+    );
+    assert_eq!(remote(&session).notice.as_deref(), Some("Black: you too"));
+}
+
+#[test]
+fn test_draw_offer_accepted_ends_the_game() {
+    let offered = receive(&session(true), Message::DrawOffer);
+    assert_eq!(remote(&offered).draw_offer, Some(Color::Black));
+
+    let accepted = command(&offered, "/accept").unwrap();
+
+    assert!(accepted.game.is_game_over);
+    assert_eq!(remote(&accepted).result, Some(RemoteResult::DrawAgreed));
+    assert_eq!(remote(&accepted).outbox, vec![Message::DrawAccept]);
+    assert_eq!(outcome(&accepted), Some(None));
+}
+
+#[test]
+fn test_draw_needs_an_offer() {
+    let session = session(true);
+
+    assert!(command(&session, "/accept").is_err());
+    let ignored = receive(&session, Message::DrawAccept);
+    assert!(!ignored.game.is_game_over);
+
+    let offered = command(&session, "/draw").unwrap();
+    let accepted = receive(&offered, Message::DrawAccept);
+    assert_eq!(outcome(&accepted), Some(None));
+}
+
+#[test]
+fn test_resignation_gives_the_other_side_the_game() {
+    let resigned = command(&session(true), "/resign").unwrap();
+    assert_eq!(outcome(&resigned), Some(Some(Color::Black)));
+    assert_eq!(remote(&resigned).outbox, vec![Message::Resign]);
+
+    let they_resigned = receive(&session(true), Message::Resign);
+    assert_eq!(outcome(&they_resigned), Some(Some(Color::White)));
+    assert_eq!(outcome(&session(true)), None);
+}
+
+#[test]
+fn test_commands_need_a_network_game() {
+    assert!(command(&GameSession::new(), "/say hi").is_err());
+    assert!(command(&session(true), "/dance").is_err());
+    assert!(command(&session(true), "/say").is_err());
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "server")]
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use checkers_rs::server::api::Api;
+use checkers_rs::server::http::{read_request, HttpError};
+use checkers_rs::server::{bind, serve};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+async fn start() -> SocketAddr {
+    let listener = bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(serve(listener, Arc::new(Api::new())));
+    address
+}
+
+/// Sends one request on its own connection and returns the status and body
+async fn call(address: SocketAddr, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+    let body = body.map(|body| body.to_string()).unwrap_or_default();
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    let request = format!(
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").expect("headers end");
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let body = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(body).unwrap()
+    };
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_new_game_reports_state_and_legal_moves() {
+    let address = start().await;
+    let (status, game) = call(address, "POST", "/games", None).await;
+    assert_eq!(status, 201);
+    assert_eq!(
+        game["fen"],
+        "W:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12"
+    );
+    assert_eq!(game["to_move"], "white");
+    assert_eq!(game["history"], json!([]));
+    assert_eq!(game["result"], Value::Null);
+    assert_eq!(
+        game["board"][0][1],
+        json!({"color": "black", "king": false, "square": 1})
+    );
+
+    let id = game["id"].as_u64().unwrap();
+    let (status, moves) = call(address, "GET", &format!("/games/{id}/moves"), None).await;
+    assert_eq!(status, 200);
+    let moves = moves["moves"].as_array().unwrap();
+    assert_eq!(moves.len(), 7);
+    assert!(moves.contains(&json!({"move": "22-18", "path": [22, 18], "capture": false})));
+}
+
+#[tokio::test]
+async fn test_moves_are_applied_and_checked() {
+    let address = start().await;
+    let (_, game) = call(address, "POST", "/games", None).await;
+    let id = game["id"].as_u64().unwrap();
+    let moves = format!("/games/{id}/moves");
+
+    let (status, game) = call(address, "POST", &moves, Some(json!({"move": "22-18"}))).await;
+    assert_eq!(status, 200);
+    assert_eq!(game["to_move"], "black");
+    assert_eq!(game["history"], json!(["22-18"]));
+
+    let (status, error) = call(address, "POST", &moves, Some(json!({"move": "22-18"}))).await;
+    assert_eq!(status, 422);
+    assert!(error["error"].as_str().unwrap().contains("illegal move"));
+    let (status, _) = call(address, "POST", &moves, Some(json!({"move": "nowhere"}))).await;
+    assert_eq!(status, 400);
+    let (status, _) = call(address, "GET", "/games/99", None).await;
+    assert_eq!(status, 404);
+    let (status, _) = call(address, "PUT", &moves, None).await;
+    assert_eq!(status, 405);
+}
+
+#[tokio::test]
+async fn test_ai_moves_and_finished_games_refuse_moves() {
+    let address = start().await;
+    let (_, game) = call(address, "POST", "/games", None).await;
+    let id = game["id"].as_u64().unwrap();
+    let (status, reply) = call(
+        address,
+        "POST",
+        &format!("/games/{id}/ai"),
+        Some(json!({"depth": 2})),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(reply["game"]["history"], json!([reply["move"].clone()]));
+    assert_eq!(reply["game"]["to_move"], "black");
+
+    // White takes Black's last piece
+    let (_, game) = call(address, "POST", "/games", Some(json!({"fen": "W:W22:B18"}))).await;
+    let id = game["id"].as_u64().unwrap();
+    let (status, game) = call(address, "POST", &format!("/games/{id}/ai"), None).await;
+    assert_eq!(status, 200);
+    assert_eq!(game["move"], "22x15");
+    assert_eq!(game["game"]["result"], "1-0");
+    assert_eq!(game["game"]["winner"], "white");
+    let (status, _) = call(address, "POST", &format!("/games/{id}/ai"), None).await;
+    assert_eq!(status, 409);
+
+    let (status, _) = call(address, "POST", "/games", Some(json!({"fen": "W:W99:B1"}))).await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn test_sessions_are_kept_apart_and_deleted() {
+    let address = start().await;
+    let (_, first) = call(address, "POST", "/games", None).await;
+    let (_, second) = call(address, "POST", "/games", None).await;
+    let (first, second) = (
+        first["id"].as_u64().unwrap(),
+        second["id"].as_u64().unwrap(),
+    );
+    assert_ne!(first, second);
+
+    let ai = format!("/games/{first}/ai");
+    let playing = (0..4).map(|_| call(address, "POST", &ai, Some(json!({"depth": 1}))));
+    for (status, _) in futures::future::join_all(playing).await {
+        assert!(status == 200 || status == 409);
+    }
+    let (_, played) = call(address, "GET", &format!("/games/{first}"), None).await;
+    assert!(!played["history"].as_array().unwrap().is_empty());
+    let (_, untouched) = call(address, "GET", &format!("/games/{second}"), None).await;
+    assert_eq!(untouched["history"], json!([]));
+
+    let (status, _) = call(address, "DELETE", &format!("/games/{first}"), None).await;
+    assert_eq!(status, 204);
+    let (status, _) = call(address, "GET", &format!("/games/{first}"), None).await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_requests_are_read_and_bad_ones_rejected() {
+    let mut raw: &[u8] =
+        b"POST /games?x=1 HTTP/1.1\r\ncontent-length: 2\r\n\r\n{}GET /games/1 HTTP/1.1\r\n\r\n";
+    let request = read_request(&mut raw).await.unwrap().unwrap();
+    assert_eq!(
+        (request.method.as_str(), request.path.as_str()),
+        ("POST", "/games")
+    );
+    assert_eq!(request.body, b"{}");
+    let request = read_request(&mut raw).await.unwrap().unwrap();
+    assert_eq!(request.path, "/games/1");
+    assert!(read_request(&mut raw).await.unwrap().is_none());
+
+    let mut garbled: &[u8] = b"hello\r\n\r\n";
+    let error = read_request(&mut garbled).await.err().unwrap();
+    assert!(matches!(error, HttpError::Malformed(_)));
+    assert_eq!(error.status(), Some(400));
+    let mut huge: &[u8] = b"POST /games HTTP/1.1\r\nContent-Length: 10000000\r\n\r\n";
+    assert!(matches!(
+        read_request(&mut huge).await,
+        Err(HttpError::TooLarge)
+    ));
+}
@@ -1,7 +1,11 @@
-use checkers_rs::core::piece::Color;
+use std::time::Instant;
+
+use checkers_rs::config::Opponent;
+use checkers_rs::core::clock::{Clock, TimeControl};
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::interface::keymap::Input;
 use checkers_rs::state::states::AITurnState;
 use checkers_rs::state::{GameSession, State, StateTransition};
-use crossterm::event::{KeyCode, KeyEvent};
 
 #[test]
 fn test_ai_turn_state_shows_thinking_status() {
@@ -30,22 +34,16 @@ fn test_ai_turn_state_shows_ai_error_if_present() {
 
 #[tokio::test]
 async fn test_ai_turn_state_makes_ai_move() {
-    // Ensure AI_TEST_MODE is set to use deterministic/mocked AI logic if available,
-    // preventing actual LLM calls or sleeps.
-    std::env::set_var("AI_TEST_MODE", "1");
-
+    // The built-in engine moves at once, with no LLM calls or sleeps
     let mut initial_session = GameSession::new();
+    initial_session.opponent = Opponent::Engine;
     initial_session.game = initial_session.game.with_switched_player();
     assert_eq!(initial_session.game.current_player, Color::Black);
 
     let state = AITurnState::new();
 
     // Call handle_input which should make a move immediately in test mode
-    let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Char(' ')));
-
-    // It's good practice to clean up env vars set by tests, though test runners often isolate.
-    std::env::remove_var("AI_TEST_MODE");
+    let (new_session, transition) = state.handle_input(&initial_session, Input::Tick);
 
     match transition {
         StateTransition::To(next_state) => {
@@ -82,9 +80,8 @@ async fn test_ai_turn_state_makes_ai_move() {
 
 #[tokio::test]
 async fn test_ai_turn_state_transitions_to_game_over_if_no_moves() {
-    // Force test mode
-    std::env::set_var("AI_TEST_MODE", "1");
     let mut initial_session = GameSession::new();
+    initial_session.opponent = Opponent::Engine;
     initial_session.game = initial_session.game.with_switched_player();
 
     // Clear the board and set up a scenario with no valid moves for Black
@@ -109,8 +106,7 @@ async fn test_ai_turn_state_transitions_to_game_over_if_no_moves() {
     let state = AITurnState::new();
 
     // Should detect no valid moves and transition to GameOver
-    let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Char(' ')));
+    let (new_session, transition) = state.handle_input(&initial_session, Input::Tick);
 
     match transition {
         StateTransition::To(next_state) => {
@@ -128,8 +124,8 @@ async fn test_ai_turn_state_transitions_to_game_over_if_no_moves() {
 
 #[tokio::test]
 async fn test_ai_turn_state_simple_ai_makes_move_on_custom_board() {
-    std::env::set_var("AI_TEST_MODE", "1");
     let mut initial_session = GameSession::new();
+    initial_session.opponent = Opponent::Engine;
     initial_session.game = initial_session.game.with_switched_player();
 
     let mut cleared_board = initial_session.game.board.clone();
@@ -157,8 +153,7 @@ async fn test_ai_turn_state_simple_ai_makes_move_on_custom_board() {
 
     let state = AITurnState::new();
 
-    let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Char(' ')));
+    let (new_session, transition) = state.handle_input(&initial_session, Input::Tick);
 
     match transition {
         StateTransition::To(next_state) => {
@@ -172,3 +167,28 @@ async fn test_ai_turn_state_simple_ai_makes_move_on_custom_board() {
         _ => panic!("Expected transition to PlayingState"),
     }
 }
+
+#[tokio::test]
+async fn test_ai_turn_state_plays_within_its_clock() {
+    let mut initial_session = GameSession::new();
+    initial_session.opponent = Opponent::Engine;
+    initial_session.game = initial_session.game.with_switched_player();
+    initial_session.game.board.cells = vec![vec![None; 8]; 8];
+    initial_session.game.board.cells[1][2] = Some(Piece::new(Color::Black));
+    initial_session.game.board.cells[2][3] = Some(Piece::new(Color::White));
+    initial_session.game.board.cells[4][5] = Some(Piece::new(Color::White));
+    initial_session.game.board.cells[7][0] = Some(Piece::new(Color::White));
+
+    let mut clock = Clock::new(TimeControl::parse("0.5").unwrap());
+    clock.start(Color::Black, Instant::now());
+    initial_session.clock = Some(clock);
+
+    let (new_session, transition) = AITurnState::new().handle_input(&initial_session, Input::Tick);
+
+    // The whole double jump is played in one go
+    assert!(matches!(transition, StateTransition::To(_)));
+    assert!(new_session.game.board.get_piece(5, 6).is_some());
+    assert!(new_session.game.board.get_piece(2, 3).is_none());
+    assert!(new_session.game.board.get_piece(4, 5).is_none());
+    assert_eq!(new_session.game.current_player, Color::White);
+}
@@ -0,0 +1,119 @@
+use checkers_rs::config::Opponent;
+use checkers_rs::core::piece::Color;
+use checkers_rs::core::Turn;
+use checkers_rs::interface::keymap::{Action, Input};
+use checkers_rs::net::RemotePlay;
+use checkers_rs::state::states::{AnalysisState, GameOverState};
+use checkers_rs::state::{GameSession, State, StateTransition, StateType};
+
+use super::press;
+
+fn finished_session() -> GameSession {
+    let mut session = GameSession::new();
+    for path in [
+        vec![(5, 2), (4, 3)],
+        vec![(2, 1), (3, 2)],
+        vec![(4, 3), (2, 1)],
+    ] {
+        session.game = session.game.apply_turn(&Turn::new(path)).unwrap();
+    }
+    session.game.is_game_over = true;
+    session
+}
+
+#[test]
+fn test_game_over_opens_analysis() {
+    let session = finished_session();
+    let state = GameOverState::new(Some(Color::White));
+
+    let (session, next) = press(&state, &session, Input::Action(Action::Analyze));
+
+    // The review is worked out on the next frame, not in the key handler
+    assert_eq!(next.state_type(), StateType::Analysis);
+    assert!(session.analysis.is_none());
+    assert_eq!(
+        next.get_view_data(&session).status_message,
+        "Analyzing the game..."
+    );
+
+    let (session, transition) = next.handle_input(&session, Input::Tick);
+    assert_eq!(transition, StateTransition::None);
+    assert_eq!(session.analysis.as_ref().unwrap().moves.len(), 3);
+}
+
+#[test]
+fn test_analysis_steps_through_moves() {
+    let session = finished_session();
+    let (session, state) = press(
+        &GameOverState::new(Some(Color::White)),
+        &session,
+        Input::Action(Action::Analyze),
+    );
+    let (session, _) = state.handle_input(&session, Input::Tick);
+    let analysis = session.analysis.as_ref().unwrap();
+
+    let view = state.get_view_data(&session);
+    assert_eq!(view.analysis.as_ref().unwrap().selected, 0);
+    assert_eq!(view.board.cells, analysis.moves[0].board_after.cells);
+    assert_eq!(view.current_player, Color::Black);
+
+    let (_, state) = press(state.as_ref(), &session, Input::Action(Action::Right));
+    let (_, state) = press(state.as_ref(), &session, Input::Action(Action::Right));
+    let view = state.get_view_data(&session);
+    assert_eq!(view.analysis.as_ref().unwrap().selected, 2);
+    assert_eq!(view.board.cells, session.game.board.cells);
+
+    // Stepping past the last move does nothing
+    let (_, transition) = state.handle_input(&session, Input::Action(Action::Right));
+    assert_eq!(transition, StateTransition::None);
+
+    let (_, state) = press(state.as_ref(), &session, Input::Action(Action::Left));
+    let view = state.get_view_data(&session);
+    assert_eq!(view.analysis.as_ref().unwrap().selected, 1);
+}
+
+#[test]
+fn test_analysis_returns_to_game_over() {
+    let session = finished_session();
+    let state = AnalysisState::new(Some(Color::White));
+
+    let (_, next) = press(&state, &session, Input::Action(Action::Quit));
+
+    assert_eq!(next.state_type(), StateType::GameOver);
+}
+
+fn analyzed_tags(session: &GameSession) -> (String, String) {
+    let state = AnalysisState::new(Some(Color::White));
+    let (session, _) = state.handle_input(session, Input::Tick);
+    let tags = &session.analysis.unwrap().tags;
+    let tag = |wanted: &str| {
+        tags.iter()
+            .find(|(name, _)| name == wanted)
+            .map(|(_, value)| value.clone())
+            .unwrap()
+    };
+    (tag("White"), tag("Black"))
+}
+
+#[test]
+fn test_analysis_names_the_players_from_the_seats() {
+    let mut session = finished_session();
+    session.opponent = Opponent::Engine;
+    session.human_color = Color::Black;
+    assert_eq!(
+        analyzed_tags(&session),
+        ("Computer".to_string(), "Player".to_string())
+    );
+
+    session.opponent = Opponent::Human;
+    assert_eq!(
+        analyzed_tags(&session),
+        ("Player 1".to_string(), "Player 2".to_string())
+    );
+
+    session.remote = Some(RemotePlay::new(Color::White, true));
+    assert_eq!(
+        analyzed_tags(&session),
+        ("Remote player".to_string(), "Player".to_string())
+    );
+}
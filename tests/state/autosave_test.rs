@@ -0,0 +1,55 @@
+use checkers_rs::core::fen::{parse_fen, to_fen};
+use checkers_rs::core::game::CheckersGame;
+use checkers_rs::state::autosave::{game_pdn, load_game, remove_game, save_game};
+
+use crate::common::TempDir;
+
+fn play(game: &CheckersGame, turns: usize) -> CheckersGame {
+    (0..turns).fold(game.clone(), |game, _| {
+        let turn = game.legal_turns()[0].clone();
+        game.apply_turn(&turn).unwrap()
+    })
+}
+
+#[test]
+fn test_saved_game_comes_back_as_it_was() {
+    // Saving makes the directory it saves to
+    let dir = TempDir::new("autosave");
+    let path = dir.join("state").join("autosave.pdn");
+    let game = play(&CheckersGame::new(), 5);
+
+    save_game(&path, &game, None).unwrap();
+    let (start, loaded) = load_game(&path).unwrap();
+
+    assert!(start.is_none());
+    assert_eq!(to_fen(&loaded), to_fen(&game));
+    assert_eq!(loaded.move_history.turns(), game.move_history.turns());
+
+    remove_game(&path).unwrap();
+    assert!(!path.exists());
+    // Nothing left to remove is fine too
+    remove_game(&path).unwrap();
+    assert!(load_game(&path).is_err());
+}
+
+#[test]
+fn test_saved_game_keeps_its_starting_position() {
+    let start = parse_fen("B:W18,24,K27:B14,K5").unwrap();
+    let game = play(&start, 2);
+
+    let pdn = game_pdn(&game, Some(&start));
+    assert!(pdn.contains("[FEN \""));
+    assert!(pdn.trim_end().ends_with('*'));
+
+    let dir = TempDir::new("autosave-fen");
+    let path = dir.join("autosave.pdn");
+    save_game(&path, &game, Some(&start)).unwrap();
+    let (loaded_start, loaded) = load_game(&path).unwrap();
+    remove_game(&path).unwrap();
+
+    assert_eq!(
+        loaded_start.map(|start| to_fen(&start)),
+        Some(to_fen(&start))
+    );
+    assert_eq!(to_fen(&loaded), to_fen(&game));
+}
@@ -0,0 +1,106 @@
+use checkers_rs::ai::coach::CoachRole;
+use checkers_rs::interface::keymap::{Action, Input};
+use checkers_rs::state::states::{CoachState, PlayingState};
+use checkers_rs::state::{GameSession, State, StateTransition, StateType};
+
+fn type_text(
+    state: Box<dyn State>,
+    session: &GameSession,
+    text: &str,
+) -> (Box<dyn State>, GameSession) {
+    let mut state = state;
+    let mut session = session.clone();
+    for c in text.chars() {
+        let (new_session, transition) = state.handle_input(&session, Input::Char(c));
+        session = new_session;
+        if let StateTransition::To(next) = transition {
+            state = next;
+        }
+    }
+    (state, session)
+}
+
+#[test]
+fn test_playing_state_opens_coach() {
+    let session = GameSession::new();
+    let state = PlayingState::new();
+
+    let (_, transition) = state.handle_input(&session, Input::Action(Action::Coach));
+
+    match transition {
+        StateTransition::To(next_state) => assert_eq!(next_state.state_type(), StateType::Coach),
+        _ => panic!("Expected transition to CoachState"),
+    }
+}
+
+#[test]
+fn test_coach_state_collects_typed_text() {
+    let session = GameSession::new();
+    let (state, session) = type_text(Box::new(CoachState::new()), &session, "why not d4?");
+
+    let view = state.get_view_data(&session);
+    assert_eq!(view.coach.unwrap().input, "why not d4?");
+
+    let (state_after_backspace, session) = match state.handle_input(&session, Input::Backspace) {
+        (session, StateTransition::To(next)) => (next, session),
+        _ => panic!("Expected backspace to produce a new CoachState"),
+    };
+    let view = state_after_backspace.get_view_data(&session);
+    assert_eq!(view.coach.unwrap().input, "why not d4");
+}
+
+#[test]
+fn test_coach_state_keeps_q_as_text() {
+    let session = GameSession::new();
+    let state = CoachState::new();
+
+    let (_, transition) = state.handle_input(&session, Input::Char('q'));
+    assert_ne!(transition, StateTransition::Exit);
+}
+
+#[test]
+fn test_coach_state_submits_question() {
+    let session = GameSession::new();
+    let (state, session) = type_text(Box::new(CoachState::new()), &session, "what's the threat?");
+
+    let (new_session, transition) = state.handle_input(&session, Input::Action(Action::Select));
+
+    let messages = new_session.coach.messages();
+    assert_eq!(messages[0].role, CoachRole::Context);
+    assert_eq!(messages[1].text, "what's the threat?");
+    // Without an API key the answer is replaced by an explanation
+    assert!(messages[2].text.contains("Coach unavailable"));
+    assert!(!new_session.coach.is_awaiting_reply());
+
+    match transition {
+        StateTransition::To(next_state) => {
+            assert_eq!(
+                next_state.get_view_data(&new_session).coach.unwrap().input,
+                ""
+            );
+        }
+        _ => panic!("Expected a fresh CoachState after submitting"),
+    }
+}
+
+#[test]
+fn test_coach_state_ignores_empty_question() {
+    let session = GameSession::new();
+    let state = CoachState::new();
+
+    let (new_session, transition) = state.handle_input(&session, Input::Action(Action::Select));
+    assert_eq!(transition, StateTransition::None);
+    assert!(new_session.coach.messages().is_empty());
+}
+
+#[test]
+fn test_coach_state_escape_returns_to_board() {
+    let session = GameSession::new();
+    let state = CoachState::new();
+
+    let (_, transition) = state.handle_input(&session, Input::Action(Action::Quit));
+    match transition {
+        StateTransition::To(next_state) => assert_eq!(next_state.state_type(), StateType::Playing),
+        _ => panic!("Expected transition back to PlayingState"),
+    }
+}
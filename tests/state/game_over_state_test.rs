@@ -1,7 +1,10 @@
+use std::time::{Duration, Instant};
+
+use checkers_rs::core::clock::{Clock, TimeControl};
 use checkers_rs::core::piece::Color;
+use checkers_rs::interface::keymap::{Action, Input};
 use checkers_rs::state::states::GameOverState;
 use checkers_rs::state::{GameSession, State, StateTransition};
-use crossterm::event::{KeyCode, KeyEvent};
 
 #[test]
 fn test_game_over_state_displays_winner_message() {
@@ -15,6 +18,23 @@ fn test_game_over_state_displays_winner_message() {
     assert!(view_data.status_message.contains("White wins"));
 }
 
+#[test]
+fn test_game_over_state_says_when_the_loser_ran_out_of_time() {
+    let start = Instant::now();
+    let mut clock = Clock::new(TimeControl::parse("1").unwrap());
+    clock.start(Color::Black, start);
+    clock.stop(start + Duration::from_secs(61));
+
+    let mut session = GameSession::new();
+    session.game.is_game_over = true;
+    session.clock = Some(clock);
+
+    let view_data = GameOverState::new(Some(Color::White)).get_view_data(&session);
+
+    assert!(view_data.status_message.contains("White wins on time"));
+    assert!(view_data.clock.is_some());
+}
+
 #[test]
 fn test_game_over_state_displays_stalemate_message() {
     let mut initial_session = GameSession::new();
@@ -33,17 +53,17 @@ fn test_game_over_state_exits_only_on_esc() {
     let state = GameOverState::new(Some(Color::Black));
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
     assert_eq!(transition, StateTransition::None);
     assert_eq!(
         new_session.game.board.cells,
         initial_session.game.board.cells
     );
 
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Esc));
+    let (_, transition) = state.handle_input(&initial_session, Input::Action(Action::Quit));
     assert_eq!(transition, StateTransition::Exit);
 
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Char('a')));
+    let (_, transition) = state.handle_input(&initial_session, Input::Char('x'));
     assert_eq!(transition, StateTransition::None);
 }
 
@@ -68,3 +88,14 @@ fn test_game_over_state_shows_correct_winner_for_white() {
 
     assert!(view_data.status_message.contains("White wins"));
 }
+
+#[test]
+fn test_game_over_state_hides_usage_without_ai_calls() {
+    let mut initial_session = GameSession::new();
+    initial_session.game.is_game_over = true;
+
+    let state = GameOverState::new(Some(Color::White));
+    let view_data = state.get_view_data(&initial_session);
+
+    assert!(view_data.usage_summary.is_none());
+}
@@ -1,7 +1,28 @@
+use checkers_rs::interface::keymap::Input;
+use checkers_rs::state::{GameSession, State, StateTransition};
+
 pub mod ai_turn_state_test;
+pub mod analysis_state_test;
+pub mod autosave_test;
+pub mod coach_state_test;
 pub mod game_over_state_test;
 pub mod game_session_multicapture_test;
+pub mod move_entry_state_test;
 pub mod multi_capture_state_test;
 pub mod piece_selected_state_test;
 pub mod playing_state_test;
+pub mod replay_state_test;
+pub mod settings_state_test;
 pub mod welcome_state_test;
+
+/// Sends one input to `state`, which must move on to another state
+pub fn press(
+    state: &dyn State,
+    session: &GameSession,
+    input: Input,
+) -> (GameSession, Box<dyn State>) {
+    match state.handle_input(session, input) {
+        (session, StateTransition::To(next)) => (session, next),
+        (_, transition) => panic!("Expected a new state, got {transition:?}"),
+    }
+}
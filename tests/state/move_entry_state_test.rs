@@ -0,0 +1,187 @@
+use checkers_rs::core::board::Board;
+use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::interface::keymap::{Action, Input};
+use checkers_rs::state::states::{MoveEntryState, PlayingState};
+use checkers_rs::state::{GameSession, State, StateTransition, StateType};
+
+use super::press;
+
+fn type_text(
+    state: Box<dyn State>,
+    session: &GameSession,
+    text: &str,
+) -> (Box<dyn State>, GameSession) {
+    let mut state = state;
+    let mut session = session.clone();
+    for c in text.chars() {
+        let (new_session, transition) = state.handle_input(&session, Input::Char(c));
+        session = new_session;
+        if let StateTransition::To(next) = transition {
+            state = next;
+        }
+    }
+    (state, session)
+}
+
+#[test]
+fn test_playing_state_opens_move_entry() {
+    let session = GameSession::new();
+
+    let (_, next) = press(
+        &PlayingState::new(),
+        &session,
+        Input::Action(Action::Command),
+    );
+
+    assert_eq!(next.state_type(), StateType::MoveEntry);
+    let view = next.get_view_data(&session);
+    let entry = view.move_entry.expect("command line shown");
+    assert!(entry.input.is_empty());
+    assert_eq!(entry.completions.len(), 7);
+}
+
+#[test]
+fn test_typed_move_is_played() {
+    let session = GameSession::new();
+
+    let (state, session) = type_text(Box::new(MoveEntryState::new()), &session, "c3-d4");
+    let (session, next) = press(state.as_ref(), &session, Input::Action(Action::Select));
+
+    assert_eq!(next.state_type(), StateType::Playing);
+    assert_eq!(session.game.current_player, Color::Black);
+    assert!(session.game.board.get_piece(5, 2).is_none());
+    assert!(session.game.board.get_piece(4, 3).is_some());
+    assert_eq!(session.ui_state.cursor_pos, (4, 3));
+}
+
+#[test]
+fn test_numeric_move_is_played() {
+    let session = GameSession::new();
+
+    let (state, session) = type_text(Box::new(MoveEntryState::new()), &session, "22-18");
+    let (session, _) = press(state.as_ref(), &session, Input::Action(Action::Select));
+
+    assert!(session.game.board.get_piece(4, 3).is_some());
+}
+
+#[test]
+fn test_completions_follow_the_input() {
+    let session = GameSession::new();
+
+    let completions = MoveEntryState::with_input("C3").completions(&session.game);
+    assert_eq!(completions, vec!["c3-b4", "c3-d4"]);
+
+    // Typed numbers are completed with numbers
+    let completions = MoveEntryState::with_input("2").completions(&session.game);
+    assert_eq!(
+        completions,
+        vec!["21-17", "22-17", "22-18", "23-18", "23-19", "24-19", "24-20"]
+    );
+
+    assert!(MoveEntryState::with_input("d4")
+        .completions(&session.game)
+        .is_empty());
+}
+
+#[test]
+fn test_tab_completes_as_far_as_the_moves_agree() {
+    let session = GameSession::new();
+
+    let (session, next) = press(
+        &MoveEntryState::with_input("c"),
+        &session,
+        Input::Action(Action::Command),
+    );
+    assert_eq!(
+        next.get_view_data(&session).move_entry.unwrap().input,
+        "c3-"
+    );
+
+    let (session, next) = press(
+        &MoveEntryState::with_input("c3-d"),
+        &session,
+        Input::Action(Action::Command),
+    );
+    assert_eq!(
+        next.get_view_data(&session).move_entry.unwrap().input,
+        "c3-d4"
+    );
+}
+
+#[test]
+fn test_illegal_move_is_reported_and_kept() {
+    let session = GameSession::new();
+
+    let (session, next) = press(
+        &MoveEntryState::with_input("c3-d5"),
+        &session,
+        Input::Action(Action::Select),
+    );
+
+    assert_eq!(next.state_type(), StateType::MoveEntry);
+    assert_eq!(session.game.current_player, Color::White);
+    let view = next.get_view_data(&session);
+    assert!(view.status_message.contains("c3-d5"));
+    let entry = view.move_entry.unwrap();
+    assert!(entry.failed);
+    assert_eq!(entry.input, "c3-d5");
+
+    let (_, next) = press(
+        &MoveEntryState::with_input("hello"),
+        &session,
+        Input::Action(Action::Select),
+    );
+    assert!(next
+        .get_view_data(&session)
+        .status_message
+        .contains("Can't read"));
+}
+
+#[test]
+fn test_multi_jump_by_full_path_or_endpoints() {
+    // White man on c3 can jump d4 and then f6
+    let mut board = Board::new(8);
+    board.set_piece(5, 2, Some(Piece::new(Color::White)));
+    board.set_piece(4, 3, Some(Piece::new(Color::Black)));
+    board.set_piece(2, 5, Some(Piece::new(Color::Black)));
+    board.set_piece(0, 1, Some(Piece::new(Color::Black)));
+    let mut session = GameSession::new();
+    session.game.board = board;
+
+    let completions = MoveEntryState::new().completions(&session.game);
+    assert_eq!(completions, vec!["c3xe5xg7"]);
+
+    for text in ["c3xe5xg7", "c3xg7"] {
+        let (session, next) = press(
+            &MoveEntryState::with_input(text),
+            &session,
+            Input::Action(Action::Select),
+        );
+        assert_eq!(next.state_type(), StateType::Playing, "{text}");
+        assert!(session.game.board.get_piece(1, 6).is_some());
+        assert!(session.game.board.get_piece(4, 3).is_none());
+        assert!(session.game.board.get_piece(2, 5).is_none());
+    }
+
+    // Stopping halfway is not a complete turn
+    let (session, next) = press(
+        &MoveEntryState::with_input("c3xe5"),
+        &session,
+        Input::Action(Action::Select),
+    );
+    assert_eq!(next.state_type(), StateType::MoveEntry);
+    assert_eq!(session.game.current_player, Color::White);
+}
+
+#[test]
+fn test_escape_returns_to_the_board() {
+    let session = GameSession::new();
+
+    let (_, next) = press(
+        &MoveEntryState::with_input("c3"),
+        &session,
+        Input::Action(Action::Quit),
+    );
+
+    assert_eq!(next.state_type(), StateType::Playing);
+}
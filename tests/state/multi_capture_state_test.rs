@@ -1,7 +1,7 @@
 use checkers_rs::core::piece::{Color, Piece};
+use checkers_rs::interface::keymap::{Action, Input};
 use checkers_rs::state::states::MultiCaptureState;
 use checkers_rs::state::{GameSession, State, StateTransition};
-use crossterm::event::{KeyCode, KeyEvent};
 
 #[test]
 fn test_multi_capture_state_keeps_piece_selected() {
@@ -32,7 +32,7 @@ fn test_multi_capture_state_forces_capture_moves_only() {
     let state = MultiCaptureState::new((4, 3));
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     assert_eq!(transition, StateTransition::None);
     assert_eq!(
@@ -63,7 +63,7 @@ fn test_multi_capture_state_completes_capture_sequence() {
     let state = MultiCaptureState::new((2, 3));
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     // Since cursor is not in possible moves, should return None
     assert_eq!(transition, StateTransition::None);
@@ -91,7 +91,7 @@ fn test_multi_capture_state_continues_if_more_captures() {
     let state = MultiCaptureState::new((2, 1));
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     // If try_multicapture_move returns an error, we should get None
     // This happens when the move is in possible_moves but isn't actually valid
@@ -132,7 +132,7 @@ fn test_multi_capture_state_transitions_to_game_over() {
     let state = MultiCaptureState::new((2, 3));
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     // Should return None since cursor is not in possible moves
     assert_eq!(transition, StateTransition::None);
@@ -150,7 +150,7 @@ fn test_multi_capture_state_cursor_movement() {
     let initial_pos = initial_session.ui_state.cursor_pos;
 
     let (session_after_up, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Up));
+        state.handle_input(&initial_session, Input::Action(Action::Up));
     assert_eq!(
         session_after_up.ui_state.cursor_pos,
         (initial_pos.0.saturating_sub(1), initial_pos.1)
@@ -158,7 +158,7 @@ fn test_multi_capture_state_cursor_movement() {
     assert_eq!(transition, StateTransition::None);
 
     let (session_after_right, transition) =
-        state.handle_input(&session_after_up, KeyEvent::from(KeyCode::Right));
+        state.handle_input(&session_after_up, Input::Action(Action::Right));
     assert_eq!(
         session_after_right.ui_state.cursor_pos,
         (initial_pos.0.saturating_sub(1), (initial_pos.1 + 1).min(7))
@@ -181,7 +181,7 @@ fn test_multi_capture_state_ignores_non_capture_moves() {
     let state = MultiCaptureState::new((4, 3));
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     assert_eq!(transition, StateTransition::None);
     assert!(new_session.game.board.get_piece(4, 3).is_some());
@@ -196,7 +196,7 @@ fn test_multi_capture_state_no_exit_key() {
     let initial_session = GameSession::new();
     let state = MultiCaptureState::new((4, 3));
 
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Esc));
+    let (_, transition) = state.handle_input(&initial_session, Input::Action(Action::Quit));
     assert_eq!(transition, StateTransition::None);
 }
 
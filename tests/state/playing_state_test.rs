@@ -1,7 +1,10 @@
+use checkers_rs::core::board::Board;
 use checkers_rs::core::piece::Color;
+use checkers_rs::core::Turn;
+use checkers_rs::interface::keymap::{Action, Input};
 use checkers_rs::state::states::PlayingState;
+use checkers_rs::state::ui_state::{FlipMode, UIState};
 use checkers_rs::state::{GameSession, State, StateTransition};
-use crossterm::event::{KeyCode, KeyEvent};
 
 #[test]
 fn test_playing_state_handles_cursor_movement() {
@@ -11,7 +14,7 @@ fn test_playing_state_handles_cursor_movement() {
     let initial_pos = initial_session.ui_state.cursor_pos;
 
     let (session_after_right, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Right));
+        state.handle_input(&initial_session, Input::Action(Action::Right));
     assert_eq!(
         session_after_right.ui_state.cursor_pos,
         (initial_pos.0, initial_pos.1 + 1)
@@ -20,7 +23,7 @@ fn test_playing_state_handles_cursor_movement() {
     assert_eq!(initial_session.ui_state.cursor_pos, initial_pos);
 
     let (session_after_down, transition) =
-        state.handle_input(&session_after_right, KeyEvent::from(KeyCode::Down));
+        state.handle_input(&session_after_right, Input::Action(Action::Down));
     assert_eq!(
         session_after_down.ui_state.cursor_pos,
         (initial_pos.0 + 1, initial_pos.1 + 1)
@@ -32,7 +35,7 @@ fn test_playing_state_handles_cursor_movement() {
     );
 
     let (session_after_left, transition) =
-        state.handle_input(&session_after_down, KeyEvent::from(KeyCode::Left));
+        state.handle_input(&session_after_down, Input::Action(Action::Left));
     assert_eq!(
         session_after_left.ui_state.cursor_pos,
         (initial_pos.0 + 1, initial_pos.1)
@@ -40,7 +43,7 @@ fn test_playing_state_handles_cursor_movement() {
     assert_eq!(transition, StateTransition::None);
 
     let (session_after_up, transition) =
-        state.handle_input(&session_after_left, KeyEvent::from(KeyCode::Up));
+        state.handle_input(&session_after_left, Input::Action(Action::Up));
     assert_eq!(session_after_up.ui_state.cursor_pos, initial_pos);
     assert_eq!(transition, StateTransition::None);
 }
@@ -52,7 +55,7 @@ fn test_playing_state_transitions_to_piece_selected() {
     let state = PlayingState::new();
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     match transition {
         StateTransition::To(next_state) => {
@@ -77,7 +80,7 @@ fn test_playing_state_transitions_to_ai_turn() {
     let state = PlayingState::new();
 
     let (_new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Up));
+        state.handle_input(&initial_session, Input::Action(Action::Up));
 
     match transition {
         StateTransition::To(next_state) => {
@@ -99,7 +102,7 @@ fn test_playing_state_ignores_invalid_selection() {
     let state = PlayingState::new();
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     assert_eq!(transition, StateTransition::None);
     assert_eq!(
@@ -121,7 +124,7 @@ fn test_playing_state_cannot_select_opponent_piece() {
     let state = PlayingState::new();
 
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     assert_eq!(transition, StateTransition::None);
     assert_eq!(
@@ -135,13 +138,23 @@ fn test_playing_state_exit_on_quit() {
     let initial_session = GameSession::new();
     let state = PlayingState::new();
 
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Esc));
+    let (_, transition) = state.handle_input(&initial_session, Input::Action(Action::Quit));
     assert_eq!(transition, StateTransition::Exit);
 
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Char('q')));
+    let (_, transition) = state.handle_input(&initial_session, Input::Action(Action::Quit));
     assert_eq!(transition, StateTransition::Exit);
 }
 
+#[test]
+fn test_playing_state_hint_key_without_provider_keeps_playing() {
+    let initial_session = GameSession::new();
+    let state = PlayingState::new();
+
+    let (session, transition) = state.handle_input(&initial_session, Input::Action(Action::Hint));
+    assert_eq!(transition, StateTransition::None);
+    assert!(session.hint.is_none());
+}
+
 #[test]
 fn test_playing_state_view_data() {
     let session = GameSession::new();
@@ -155,27 +168,175 @@ fn test_playing_state_view_data() {
     assert_eq!(view_data.current_player, Color::White);
 }
 
+#[test]
+fn test_playing_state_acts_on_actions_not_letters() {
+    let session = GameSession::new();
+    let state = PlayingState::new();
+
+    // Letters only count through the actions they are bound to
+    for c in ['q', 'h', 'c', 'r'] {
+        let (new_session, transition) = state.handle_input(&session, Input::Char(c));
+        assert_eq!(transition, StateTransition::None);
+        assert!(new_session.hint.is_none());
+    }
+}
+
 #[test]
 fn test_playing_state_cursor_bounds() {
     let mut initial_session = GameSession::new();
     initial_session.ui_state.cursor_pos = (0, 0);
     let state = PlayingState::new();
 
-    let (session_after_up, _) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Up));
+    let (session_after_up, _) = state.handle_input(&initial_session, Input::Action(Action::Up));
     assert_eq!(session_after_up.ui_state.cursor_pos, (0, 0));
 
-    let (session_after_left, _) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Left));
+    let (session_after_left, _) = state.handle_input(&initial_session, Input::Action(Action::Left));
     assert_eq!(session_after_left.ui_state.cursor_pos, (0, 0));
 
     let mut corner_session = initial_session.clone();
     corner_session.ui_state.cursor_pos = (7, 7);
 
-    let (session_after_down, _) =
-        state.handle_input(&corner_session, KeyEvent::from(KeyCode::Down));
+    let (session_after_down, _) = state.handle_input(&corner_session, Input::Action(Action::Down));
     assert_eq!(session_after_down.ui_state.cursor_pos, (7, 7));
 
     let (session_after_right, _) =
-        state.handle_input(&corner_session, KeyEvent::from(KeyCode::Right));
+        state.handle_input(&corner_session, Input::Action(Action::Right));
     assert_eq!(session_after_right.ui_state.cursor_pos, (7, 7));
 }
+
+#[test]
+fn test_playing_state_cursor_bounds_follow_board_size() {
+    let mut session = GameSession::new();
+    session.game.board = Board::new(10);
+    session.ui_state.cursor_pos = (8, 8);
+    let state = PlayingState::new();
+
+    let (session, _) = state.handle_input(&session, Input::Action(Action::Down));
+    let (session, _) = state.handle_input(&session, Input::Action(Action::Right));
+    assert_eq!(session.ui_state.cursor_pos, (9, 9));
+
+    let (session, _) = state.handle_input(&session, Input::Action(Action::Down));
+    let (session, _) = state.handle_input(&session, Input::Action(Action::Right));
+    assert_eq!(session.ui_state.cursor_pos, (9, 9));
+}
+
+#[test]
+fn test_playing_state_cursor_follows_screen_when_flipped() {
+    let mut session = GameSession::new();
+    session.ui_state = session.ui_state.move_cursor_to((4, 4)).toggle_flip();
+    let state = PlayingState::new();
+
+    // Up on screen is toward White's side of the board
+    let (moved, _) = state.handle_input(&session, Input::Action(Action::Up));
+    assert_eq!(moved.ui_state.cursor_pos, (5, 4));
+    let (moved, _) = state.handle_input(&session, Input::Action(Action::Down));
+    assert_eq!(moved.ui_state.cursor_pos, (3, 4));
+    let (moved, _) = state.handle_input(&session, Input::Action(Action::Left));
+    assert_eq!(moved.ui_state.cursor_pos, (4, 5));
+    let (moved, _) = state.handle_input(&session, Input::Action(Action::Right));
+    assert_eq!(moved.ui_state.cursor_pos, (4, 3));
+
+    // The edges still stop the cursor
+    session.ui_state = session.ui_state.move_cursor_to((7, 0));
+    let (moved, _) = state.handle_input(&session, Input::Action(Action::Up));
+    assert_eq!(moved.ui_state.cursor_pos, (7, 0));
+    let (moved, _) = state.handle_input(&session, Input::Action(Action::Right));
+    assert_eq!(moved.ui_state.cursor_pos, (7, 0));
+
+    assert!(state.get_view_data(&session).flipped);
+}
+
+#[test]
+fn test_flip_modes() {
+    assert_eq!(FlipMode::parse("AUTO"), Some(FlipMode::Auto));
+    assert_eq!(FlipMode::parse("on"), Some(FlipMode::On));
+    assert_eq!(FlipMode::parse("sideways"), None);
+
+    let fixed = UIState::new().with_flip_mode(FlipMode::On);
+    assert!(fixed.flipped);
+    assert!(fixed.face(Color::White).flipped);
+
+    let auto = UIState::new().with_flip_mode(FlipMode::Auto);
+    assert!(!auto.flipped);
+    assert!(auto.face(Color::Black).flipped);
+    assert!(!auto.face(Color::Black).face(Color::White).flipped);
+}
+
+#[test]
+fn test_playing_state_pages_move_list() {
+    let mut session = GameSession::new();
+    for i in 0..24 {
+        let player = if i % 2 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        session
+            .game
+            .move_history
+            .add_move((5, 0), (4, 1), player, vec![], false);
+    }
+    let state = PlayingState::new();
+
+    let (session, _) = state.handle_input(&session, Input::Action(Action::ScrollBack));
+    assert_eq!(session.ui_state.move_list_scroll, 5);
+
+    let (session, _) = state.handle_input(&session, Input::Action(Action::ScrollBack));
+    let (session, _) = state.handle_input(&session, Input::Action(Action::ScrollBack));
+    assert_eq!(session.ui_state.move_list_scroll, 12);
+
+    let (session, transition) = state.handle_input(&session, Input::Action(Action::ScrollForward));
+    assert_eq!(session.ui_state.move_list_scroll, 7);
+    assert_eq!(transition, StateTransition::None);
+
+    let view = state.get_view_data(&session);
+    assert_eq!(view.move_list_scroll, 7);
+    assert!(view.move_history.is_some());
+}
+
+#[test]
+fn test_move_list_stops_when_the_first_move_shows() {
+    let mut session = GameSession::new();
+    for i in 0..24 {
+        let player = if i % 2 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        session
+            .game
+            .move_history
+            .add_move((5, 0), (4, 1), player, vec![], false);
+    }
+    // Ten of the twelve rows fit on screen
+    session.ui_state.move_list_rows = 10;
+    let state = PlayingState::new();
+
+    let (session, _) = state.handle_input(&session, Input::Action(Action::ScrollBack));
+    assert_eq!(session.ui_state.move_list_scroll, 2);
+
+    let (session, _) = state.handle_input(&session, Input::Action(Action::ScrollForward));
+    assert_eq!(session.ui_state.move_list_scroll, 0);
+}
+
+#[test]
+fn test_playing_state_shows_last_move_and_captures() {
+    let mut session = GameSession::new();
+    assert!(PlayingState::new()
+        .get_view_data(&session)
+        .last_move
+        .is_none());
+
+    for path in [
+        vec![(5, 2), (4, 3)],
+        vec![(2, 1), (3, 2)],
+        vec![(4, 3), (2, 1)],
+        vec![(1, 0), (3, 2)],
+    ] {
+        session.game = session.game.apply_turn(&Turn::new(path)).unwrap();
+    }
+
+    let view = PlayingState::new().get_view_data(&session);
+    assert_eq!(view.last_move, Some(Turn::new(vec![(1, 0), (3, 2)])));
+    assert_eq!(view.captured_squares, vec![(2, 1)]);
+}
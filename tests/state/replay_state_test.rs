@@ -0,0 +1,127 @@
+use checkers_rs::core::fen::parse_fen;
+use checkers_rs::core::piece::Color;
+use checkers_rs::core::Turn;
+use checkers_rs::interface::keymap::{Action, Input};
+use checkers_rs::state::states::{GameOverState, PlayingState, ReplayExit, ReplayState};
+use checkers_rs::state::{GameSession, State, StateTransition, StateType};
+
+use super::press;
+
+fn played_session() -> GameSession {
+    let mut session = GameSession::new();
+    for path in [
+        vec![(5, 2), (4, 3)],
+        vec![(2, 1), (3, 2)],
+        vec![(4, 3), (2, 1)],
+        vec![(1, 0), (3, 2)],
+    ] {
+        session.game = session.game.apply_turn(&Turn::new(path)).unwrap();
+    }
+    session
+}
+
+#[test]
+fn test_replay_opens_on_current_position() {
+    let session = played_session();
+
+    let (session, replay) = press(
+        &PlayingState::new(),
+        &session,
+        Input::Action(Action::Replay),
+    );
+
+    assert_eq!(replay.state_type(), StateType::Replay);
+    let view = replay.get_view_data(&session);
+    assert_eq!(view.board.cells, session.game.board.cells);
+    assert_eq!(view.status_message, "Move 4/4: Black a7xc5");
+    assert_eq!(view.last_move, Some(Turn::new(vec![(1, 0), (3, 2)])));
+    assert_eq!(view.captured_squares, vec![(2, 1)]);
+}
+
+#[test]
+fn test_replay_steps_and_jumps() {
+    let session = played_session();
+    let (session, replay) = press(
+        &PlayingState::new(),
+        &session,
+        Input::Action(Action::Replay),
+    );
+
+    let (session, back) = press(replay.as_ref(), &session, Input::Action(Action::Left));
+    let view = back.get_view_data(&session);
+    assert_eq!(view.status_message, "Move 3/4: White d4xb6");
+    assert_eq!(view.current_player, Color::Black);
+
+    let (session, start) = press(back.as_ref(), &session, Input::Action(Action::First));
+    let view = start.get_view_data(&session);
+    assert_eq!(view.status_message, "Move 0/4: starting position");
+    assert!(view.last_move.is_none());
+    assert_eq!(
+        view.board.cells,
+        checkers_rs::core::game::CheckersGame::new().board.cells
+    );
+
+    // Already at the start
+    let (_, transition) = start.handle_input(&session, Input::Action(Action::Left));
+    assert_eq!(transition, StateTransition::None);
+
+    let (session, end) = press(start.as_ref(), &session, Input::Action(Action::Last));
+    assert_eq!(
+        end.get_view_data(&session).status_message,
+        "Move 4/4: Black a7xc5"
+    );
+}
+
+#[test]
+fn test_replay_leaves_live_game_untouched() {
+    let session = played_session();
+    let (session, replay) = press(
+        &PlayingState::new(),
+        &session,
+        Input::Action(Action::Replay),
+    );
+    let (session, start) = press(replay.as_ref(), &session, Input::Action(Action::First));
+
+    let (session, next) = press(start.as_ref(), &session, Input::Action(Action::Quit));
+
+    assert_eq!(next.state_type(), StateType::Playing);
+    assert!(session.replay.is_none());
+    assert_eq!(session.game.move_history.moves().len(), 4);
+    assert_eq!(session.game.board.cells, played_session().game.board.cells);
+}
+
+#[test]
+fn test_replay_from_game_over_returns_there() {
+    let session = played_session();
+    let (session, replay) = press(
+        &GameOverState::new(Some(Color::White)),
+        &session,
+        Input::Action(Action::Replay),
+    );
+
+    let (_, next) = press(replay.as_ref(), &session, Input::Action(Action::Quit));
+
+    assert_eq!(next.state_type(), StateType::GameOver);
+    assert_eq!(
+        ReplayState::new(ReplayExit::Playing).state_type(),
+        StateType::Replay
+    );
+}
+
+#[test]
+fn test_replay_of_a_set_up_position_starts_with_its_side_to_move() {
+    let start = parse_fen("B:W18,24,K27:B14,K5").unwrap();
+    let turn = start.legal_turns()[0].clone();
+    let mut session = GameSession::new();
+    session.game = start.apply_turn(&turn).unwrap();
+    session.start = Some(start);
+
+    let (session, replay) = press(
+        &PlayingState::new(),
+        &session,
+        Input::Action(Action::Replay),
+    );
+    let (_, replay) = press(replay.as_ref(), &session, Input::Action(Action::First));
+
+    assert_eq!(replay.get_view_data(&session).current_player, Color::Black);
+}
@@ -0,0 +1,64 @@
+use checkers_rs::config::{Settings, SETTINGS};
+use checkers_rs::core::piece::Color;
+use checkers_rs::interface::keymap::{Action, Input};
+use checkers_rs::state::{states::SettingsState, GameSession, State, StateTransition, StateType};
+
+use super::press;
+use crate::common::TempDir;
+
+#[test]
+fn test_changing_a_setting_saves_it_and_updates_the_session() {
+    let dir = TempDir::new("settings-save");
+    let path = dir.join("config.txt");
+    std::fs::write(&path, "# mine\ndifficulty: hard\n").unwrap();
+    let state = SettingsState::new(Settings::default(), Some(path.clone()));
+    let session = GameSession::new();
+
+    let (session, state) = press(&state, &session, Input::Action(Action::Down));
+    let (session, state) = press(state.as_ref(), &session, Input::Action(Action::Right));
+
+    assert_eq!(session.human_color, Color::Black);
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "# mine\ndifficulty: hard\ncolor: black\n"
+    );
+    let view = state.get_view_data(&session);
+    let settings = view.settings.unwrap();
+    assert_eq!(settings.rows.len(), SETTINGS.len());
+    assert_eq!(settings.rows[settings.selected].name, "color");
+    assert_eq!(settings.rows[settings.selected].value, "black");
+    assert!(settings.message.unwrap().starts_with("Saved to"));
+
+    let (_, state) = press(state.as_ref(), &session, Input::Action(Action::Quit));
+    assert_eq!(state.state_type(), StateType::Welcome);
+}
+
+#[test]
+fn test_temperatures_step_by_a_tenth_and_stay_in_range() {
+    let settings = Settings {
+        temperature: 0.0,
+        ..Settings::default()
+    };
+    let state = SettingsState::new(settings, None);
+    let session = GameSession::new();
+    let row = SETTINGS
+        .iter()
+        .position(|name| *name == "temperature")
+        .unwrap();
+
+    let mut state: Box<dyn State> = Box::new(state);
+    for _ in 0..row {
+        state = press(state.as_ref(), &session, Input::Action(Action::Down)).1;
+    }
+    // Already at the lowest, so nothing changes
+    let (_, transition) = state.handle_input(&session, Input::Action(Action::Left));
+    assert_eq!(transition, StateTransition::None);
+
+    for _ in 0..3 {
+        state = press(state.as_ref(), &session, Input::Action(Action::Right)).1;
+    }
+    let view = state.get_view_data(&session);
+    let settings = view.settings.unwrap();
+    assert_eq!(settings.rows[row].value, "0.3");
+    assert!(settings.message.unwrap().contains("until you quit"));
+}
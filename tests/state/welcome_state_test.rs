@@ -1,8 +1,8 @@
+use checkers_rs::interface::keymap::{Action, Input};
 use checkers_rs::state::{
     states::{WelcomeContent, WelcomeState},
     GameSession, State, StateTransition,
 };
-use crossterm::event::{KeyCode, KeyEvent};
 
 #[test]
 fn test_welcome_state_transitions_to_playing_on_enter() {
@@ -19,7 +19,7 @@ fn test_welcome_state_transitions_to_playing_on_enter() {
 
     // Test Enter key transitions to PlayingState
     let (new_session, transition) =
-        state.handle_input(&initial_session, KeyEvent::from(KeyCode::Enter));
+        state.handle_input(&initial_session, Input::Action(Action::Select));
 
     match transition {
         StateTransition::To(next_state) => {
@@ -60,7 +60,7 @@ fn test_welcome_state_exits_on_esc() {
     let state = WelcomeState::new();
 
     // Test ESC key exits
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Esc));
+    let (_, transition) = state.handle_input(&initial_session, Input::Action(Action::Quit));
 
     match transition {
         StateTransition::Exit => {
@@ -84,7 +84,7 @@ fn test_welcome_state_exits_on_q() {
     let state = WelcomeState::new();
 
     // Test 'q' key exits
-    let (_, transition) = state.handle_input(&initial_session, KeyEvent::from(KeyCode::Char('q')));
+    let (_, transition) = state.handle_input(&initial_session, Input::Action(Action::Quit));
 
     match transition {
         StateTransition::Exit => {
@@ -109,16 +109,16 @@ fn test_welcome_state_ignores_other_keys() {
 
     // Test other keys do nothing
     let keys = vec![
-        KeyCode::Up,
-        KeyCode::Down,
-        KeyCode::Left,
-        KeyCode::Right,
-        KeyCode::Char('a'),
-        KeyCode::Char(' '),
+        Input::Action(Action::Up),
+        Input::Action(Action::Down),
+        Input::Action(Action::Left),
+        Input::Action(Action::Right),
+        Input::Action(Action::Analyze),
+        Input::Action(Action::Hint),
     ];
 
     for key in keys {
-        let (new_session, transition) = state.handle_input(&initial_session, KeyEvent::from(key));
+        let (new_session, transition) = state.handle_input(&initial_session, key);
         match transition {
             StateTransition::None => {
                 // Success - verify session unchanged
@@ -161,3 +161,20 @@ fn test_welcome_state_view_data() {
     assert!(view.error_message.is_none());
     assert!(view.hint.is_none());
 }
+
+#[test]
+fn test_welcome_state_arrows_cycle_persona() {
+    let session = GameSession::new();
+    let state = WelcomeState::new();
+    assert_eq!(session.persona().name, "classic");
+
+    let (next, transition) = state.handle_input(&session, Input::Action(Action::Right));
+    assert_eq!(transition, StateTransition::None);
+    assert_eq!(next.persona().name, "aggressive");
+
+    let (previous, _) = state.handle_input(&session, Input::Action(Action::Left));
+    assert_eq!(previous.persona().name, "trash-talker");
+
+    let view = state.get_view_data(&previous);
+    assert_eq!(view.persona.map(|p| p.name.as_str()), Some("trash-talker"));
+}
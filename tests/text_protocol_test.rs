@@ -0,0 +1,74 @@
+use checkers_rs::text_protocol::{run, TextProtocol};
+
+fn reply(protocol: &mut TextProtocol, line: &str) -> Vec<String> {
+    protocol.execute(line).expect("still running")
+}
+
+#[test]
+fn test_perft_matches_known_counts() {
+    let mut protocol = TextProtocol::new();
+    assert_eq!(reply(&mut protocol, "perft 1").last().unwrap(), "nodes 7");
+    assert_eq!(reply(&mut protocol, "perft 3").last().unwrap(), "nodes 302");
+    assert_eq!(
+        reply(&mut protocol, "perft 4").last().unwrap(),
+        "nodes 1469"
+    );
+}
+
+#[test]
+fn test_position_plays_moves_from_start_and_fen() {
+    let mut protocol = TextProtocol::new();
+    assert!(reply(&mut protocol, "position startpos moves 22-18 f6-e5").is_empty());
+    assert_eq!(
+        reply(&mut protocol, "fen"),
+        vec!["W:W18,21,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,12,15"]
+    );
+
+    reply(&mut protocol, "position fen W:WK18:B14");
+    assert_eq!(reply(&mut protocol, "legalmoves"), vec!["18x9"]);
+}
+
+#[test]
+fn test_mistakes_leave_the_position_alone() {
+    let mut protocol = TextProtocol::new();
+    reply(&mut protocol, "position startpos moves 22-18");
+    let before = reply(&mut protocol, "fen");
+
+    for line in [
+        "position startpos moves 22-18 22-17",
+        "position fen W:W33:B1",
+        "go depth zero",
+        "fly",
+    ] {
+        assert!(
+            reply(&mut protocol, line)[0].starts_with("error "),
+            "{line}"
+        );
+    }
+    assert_eq!(reply(&mut protocol, "fen"), before);
+}
+
+#[test]
+fn test_go_finds_the_winning_capture() {
+    let mut protocol = TextProtocol::new();
+    reply(&mut protocol, "position fen W:WK18:B14");
+    let lines = reply(&mut protocol, "go depth 3");
+    assert!(lines[0].starts_with("info depth 3 score "));
+    assert_eq!(lines[1], "bestmove 18x9");
+
+    reply(&mut protocol, "position fen B:W:B4");
+    assert_eq!(
+        reply(&mut protocol, "go movetime 50").last().unwrap(),
+        "bestmove 4-8"
+    );
+    reply(&mut protocol, "position fen W:W:B1");
+    assert_eq!(reply(&mut protocol, "go"), vec!["bestmove none"]);
+}
+
+#[test]
+fn test_run_answers_until_quit() {
+    let input = "eval\n\nquit\nlegalmoves\n";
+    let mut output = Vec::new();
+    run(input.as_bytes(), &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "eval 0\n");
+}
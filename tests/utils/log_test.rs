@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+use checkers_rs::state::states::{GameOverState, PlayingState};
+use checkers_rs::state::{StateMachine, StateTransition};
+use checkers_rs::utils::log::{
+    self, debug_bundle, entry, take_log_flags, timestamp, Level, LogConfig,
+};
+use serde_json::{json, Value};
+
+use crate::common::TempDir;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn test_verbosity_flags_are_taken_from_the_arguments() {
+    let (config, rest) = take_log_flags(args(&["-vv", "--host", "--seat", "black"])).unwrap();
+    assert_eq!(config.level, Some(Level::Debug));
+    assert_eq!(rest, args(&["--host", "--seat", "black"]));
+
+    let (config, _) = take_log_flags(args(&["-v", "-v", "-v", "-v"])).unwrap();
+    assert_eq!(config.level, Some(Level::Trace));
+    let (config, rest) =
+        take_log_flags(args(&["--log-level", "off", "--log-file", "/tmp/x.log"])).unwrap();
+    assert_eq!(
+        config,
+        LogConfig {
+            level: None,
+            path: Some(PathBuf::from("/tmp/x.log"))
+        }
+    );
+    assert!(rest.is_empty());
+
+    assert!(take_log_flags(args(&["--log-level", "loud"])).is_err());
+    assert!(take_log_flags(args(&["--log-file"])).is_err());
+    assert!(Level::Error < Level::Warn && Level::Debug < Level::Trace);
+}
+
+#[test]
+fn test_entries_are_one_json_object_per_line() {
+    let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_250);
+    assert_eq!(timestamp(time), "2023-11-14T22:13:20.250Z");
+    assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+
+    let line = entry(
+        Level::Info,
+        "move",
+        json!({ "from": "c3", "to": "d4\n" }),
+        time,
+    );
+    assert!(line.starts_with(r#"{"time":"2023-11-14T22:13:20.250Z","level":"info","event":"move""#));
+    assert!(!line.contains('\n'));
+    let parsed: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(parsed["to"], "d4\n");
+
+    let bare: Value = serde_json::from_str(&entry(Level::Warn, "note", json!(3), time)).unwrap();
+    assert_eq!(bare["value"], 3);
+}
+
+#[test]
+fn test_transitions_are_logged_at_the_chosen_level() {
+    let dir = TempDir::new("log-transitions");
+    let path = dir.join("transitions.log");
+    let config = LogConfig {
+        level: Some(Level::Info),
+        path: Some(path.clone()),
+    };
+    assert_eq!(log::init(&config), Some(path.clone()));
+    assert!(log::enabled(Level::Info) && !log::enabled(Level::Debug));
+
+    let mut machine = StateMachine::new(Box::new(PlayingState::new()));
+    machine.process_transition(StateTransition::To(Box::new(GameOverState::new(None))));
+    log::info("marker", json!({ "step": 1 }));
+
+    log::init(&LogConfig {
+        level: Some(Level::Debug),
+        path: Some(path.clone()),
+    });
+    machine.process_transition(StateTransition::To(Box::new(PlayingState::new())));
+    log::init(&LogConfig {
+        level: None,
+        path: None,
+    });
+    log::error("after", json!({}));
+
+    // Other tests may log through the same global logger meanwhile, but
+    // nothing below info level before the marker
+    let lines = fs::read_to_string(&path).unwrap();
+    let entries = lines
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let marker = entries
+        .iter()
+        .position(|entry| entry["event"] == "marker")
+        .unwrap();
+    assert!(!entries[..marker]
+        .iter()
+        .any(|entry| entry["event"] == "transition"));
+    assert!(entries[marker..].iter().any(|entry| {
+        entry["event"] == "transition"
+            && entry["from"] == "GameOver"
+            && entry["to"] == "Playing"
+            && entry["level"] == "debug"
+    }));
+    assert!(!entries.iter().any(|entry| entry["event"] == "after"));
+}
+
+#[test]
+fn test_debug_bundle_keeps_the_log_tail_but_not_the_api_key() {
+    let dir = TempDir::new("log-bundle");
+    let path = dir.join("bundle.log");
+    let key = "test-key-0123456789";
+    let env_var = |name: &str| (name == "GEMINI_API_KEY").then(|| key.to_string());
+    let lines = (0..600)
+        .map(|n| format!(r#"{{"event":"line","n":{n},"url":"https://example.org/?key={key}"}}"#))
+        .collect::<Vec<_>>();
+    fs::write(&path, lines.join("\n")).unwrap();
+
+    let bundle = debug_bundle(Some(&path), env_var);
+    assert!(bundle.starts_with("checkers-rs "));
+    assert!(bundle.contains("GEMINI_API_KEY=(set)"));
+    assert!(!bundle.contains(key));
+    assert!(bundle.contains("last 500 of 600 lines"));
+    assert!(bundle.contains(r#""n":599"#) && !bundle.contains(r#""n":99,"#));
+    assert!(bundle.contains("key=[redacted]"));
+
+    assert!(bundle.contains("CHECKERS_LOG=(unset)"));
+
+    assert!(debug_bundle(None, env_var).contains("[log: none kept]"));
+}
@@ -0,0 +1,2 @@
+mod log_test;
+mod prompts_test;
@@ -0,0 +1,77 @@
+use checkers_rs::utils::prompts::{
+    validate_template, PromptError, PromptSet, AI_MOVE, HINT, REVIEW, STORY,
+};
+
+use crate::common::TempDir;
+
+#[test]
+fn test_builtin_prompts_are_valid() {
+    for spec in [&HINT, &AI_MOVE, &STORY, &REVIEW] {
+        assert_eq!(validate_template(spec, spec.builtin), Ok(()));
+    }
+}
+
+#[test]
+fn test_missing_placeholder_is_rejected() {
+    let result = validate_template(&HINT, "Board: {board_state}\nMoves: {available_moves}");
+
+    assert_eq!(
+        result,
+        Err(PromptError::MissingPlaceholder {
+            file: "hint.txt".to_string(),
+            placeholder: "{move_history}".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_unknown_placeholder_is_rejected() {
+    let result = validate_template(&AI_MOVE, "{board_state} {available_moves} {boardstate}");
+
+    assert!(matches!(
+        result,
+        Err(PromptError::UnknownPlaceholder { placeholder, .. }) if placeholder == "{boardstate}"
+    ));
+}
+
+#[test]
+fn test_literal_braces_are_not_placeholders() {
+    let text = "{board_state} {available_moves}\nReply as JSON: {\"move\": 1}";
+
+    assert_eq!(validate_template(&AI_MOVE, text), Ok(()));
+}
+
+#[test]
+fn test_valid_override_replaces_builtin() {
+    let dir = TempDir::new("prompts-valid");
+    let custom = "Board:\n{board_state}\nPick one of:\n{available_moves}";
+    std::fs::write(dir.join("ai_move.txt"), custom).unwrap();
+
+    let prompts = PromptSet::load(Some(dir.path()));
+
+    assert_eq!(prompts.get(&AI_MOVE), custom);
+    assert_eq!(prompts.get(&HINT), HINT.builtin);
+    assert!(prompts.warnings().is_empty());
+}
+
+#[test]
+fn test_invalid_override_falls_back_with_warning() {
+    let dir = TempDir::new("prompts-invalid");
+    std::fs::write(dir.join("hint.txt"), "Give me a hint for {board_state}").unwrap();
+
+    let prompts = PromptSet::load(Some(dir.path()));
+
+    assert_eq!(prompts.get(&HINT), HINT.builtin);
+    assert_eq!(prompts.warnings().len(), 1);
+    assert!(prompts.warnings()[0]
+        .to_string()
+        .starts_with("hint.txt: missing placeholder"));
+}
+
+#[test]
+fn test_no_config_dir_uses_builtins() {
+    let prompts = PromptSet::load(None);
+
+    assert_eq!(prompts.get(&STORY), STORY.builtin);
+    assert!(prompts.warnings().is_empty());
+}